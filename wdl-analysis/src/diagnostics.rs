@@ -1,6 +1,7 @@
 //! Module for all diagnostic creation functions.
 
 use std::fmt;
+use std::fmt::Write;
 
 use wdl_ast::AstToken;
 use wdl_ast::Diagnostic;
@@ -14,6 +15,8 @@ use crate::UNUSED_CALL_RULE_ID;
 use crate::UNUSED_DECL_RULE_ID;
 use crate::UNUSED_IMPORT_RULE_ID;
 use crate::UNUSED_INPUT_RULE_ID;
+use crate::stdlib::FunctionBindError;
+use crate::stdlib::SignatureMismatch;
 use crate::types::CallKind;
 use crate::types::CallType;
 use crate::types::Type;
@@ -618,6 +621,50 @@ pub fn argument_type_mismatch(name: &str, expected: &str, actual: &Type, span: S
     .with_label(format!("this is type `{actual}`"), span)
 }
 
+/// Constructs a "no matching signature" diagnostic.
+///
+/// The diagnostic is an "argument type mismatch" diagnostic for the closest
+/// candidate signature with a fix that describes why each candidate signature
+/// failed to bind to the arguments.
+pub fn no_matching_signature(
+    name: &str,
+    expected: &str,
+    arguments: &[Type],
+    index: usize,
+    span: Span,
+    candidates: &[SignatureMismatch],
+) -> Diagnostic {
+    let mut fix = String::from("supply arguments that match one of the function's signatures:");
+    for candidate in candidates {
+        let sig = candidate.signature();
+        let reason = match candidate.error() {
+            FunctionBindError::RequiresVersion(minimum) => {
+                format!("requires a minimum WDL version of {minimum}")
+            }
+            FunctionBindError::TooFewArguments(minimum) => format!(
+                "requires at least {minimum} argument{s}",
+                s = if *minimum == 1 { "" } else { "s" }
+            ),
+            FunctionBindError::TooManyArguments(maximum) => format!(
+                "requires no more than {maximum} argument{s}",
+                s = if *maximum == 1 { "" } else { "s" }
+            ),
+            FunctionBindError::ArgumentTypeMismatch { index, expected } => format!(
+                "argument {n} expects type {expected}, but found type `{actual}`",
+                n = index + 1,
+                actual = arguments[*index]
+            ),
+            FunctionBindError::Ambiguous { first, second } => {
+                format!("conflicting signatures `{first}` and `{second}`")
+            }
+        };
+
+        write!(&mut fix, "\n  {name}{sig}: {reason}").unwrap();
+    }
+
+    argument_type_mismatch(name, expected, &arguments[index], span).with_fix(fix)
+}
+
 /// Constructs an "ambiguous argument" diagnostic.
 pub fn ambiguous_argument(name: &str, span: Span, first: &str, second: &str) -> Diagnostic {
     Diagnostic::error(format!(
@@ -720,11 +767,20 @@ pub fn unused_import(name: &str, span: Span) -> Diagnostic {
         .with_highlight(span)
 }
 
+/// Creates an "unused struct alias" diagnostic.
+pub fn unused_struct_alias(name: &str, span: Span) -> Diagnostic {
+    Diagnostic::warning(format!("unused imported struct alias `{name}`"))
+        .with_rule(UNUSED_IMPORT_RULE_ID)
+        .with_highlight(span)
+        .with_fix("remove the alias from the import statement")
+}
+
 /// Creates an "unused input" diagnostic.
 pub fn unused_input(name: &str, span: Span) -> Diagnostic {
     Diagnostic::warning(format!("unused input `{name}`"))
         .with_rule(UNUSED_INPUT_RULE_ID)
         .with_highlight(span)
+        .with_fix("remove the unused input")
 }
 
 /// Creates an "unused declaration" diagnostic.
@@ -732,13 +788,27 @@ pub fn unused_declaration(name: &str, span: Span) -> Diagnostic {
     Diagnostic::warning(format!("unused declaration `{name}`"))
         .with_rule(UNUSED_DECL_RULE_ID)
         .with_highlight(span)
+        .with_fix("remove the unused declaration; evaluating a declaration has no side effects")
 }
 
 /// Creates an "unused call" diagnostic.
-pub fn unused_call(name: &str, span: Span) -> Diagnostic {
+pub fn unused_call(name: &str, span: Span, outputs: &[&str]) -> Diagnostic {
+    let label = match outputs {
+        [output] => format!("the output `{output}` of this call is never used"),
+        _ => format!(
+            "none of the outputs of this call are used: {outputs}",
+            outputs = outputs
+                .iter()
+                .map(|o| format!("`{o}`"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    };
+
     Diagnostic::warning(format!("unused call `{name}`"))
         .with_rule(UNUSED_CALL_RULE_ID)
-        .with_highlight(span)
+        .with_label(label, span)
+        .with_fix("remove the call or use its outputs")
 }
 
 /// Creates an "unnecessary function call" diagnostic.
@@ -1,6 +1,5 @@
 //! Representation of analyzed WDL documents.
 
-use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::str::FromStr;
 use std::sync::Arc;
@@ -24,6 +23,7 @@ use wdl_ast::support::token;
 
 use crate::DiagnosticsConfig;
 use crate::diagnostics::unused_import;
+use crate::diagnostics::unused_struct_alias;
 use crate::graph::DocumentGraph;
 use crate::graph::ParseState;
 use crate::types::CallType;
@@ -131,6 +131,10 @@ pub struct Struct {
     ///
     /// Initially this is `None` until a type check occurs.
     ty: Option<Type>,
+    /// Whether or not the struct was introduced by an import's `alias` clause.
+    aliased: bool,
+    /// Whether or not the struct is used (i.e. referenced) in the document.
+    used: bool,
 }
 
 impl Struct {
@@ -529,6 +533,7 @@ impl Document {
         if let Some(severity) = config.unused_import {
             let Document {
                 namespaces,
+                structs,
                 diagnostics,
                 ..
             } = &mut document;
@@ -539,17 +544,24 @@ impl Document {
                     .filter(|(_, ns)| !ns.used && !ns.excepted)
                     .map(|(name, ns)| unused_import(name, ns.span()).with_severity(severity)),
             );
+
+            // Aliased structs are only reported for imports that are otherwise used
+            diagnostics.extend(
+                structs
+                    .iter()
+                    .filter(|(_, s)| {
+                        s.aliased
+                            && !s.used
+                            && s.namespace
+                                .as_ref()
+                                .is_some_and(|ns| namespaces[ns].used && !namespaces[ns].excepted)
+                    })
+                    .map(|(name, s)| unused_struct_alias(name, s.span).with_severity(severity)),
+            );
         }
 
-        // Sort the diagnostics by start
-        document
-            .diagnostics
-            .sort_by(|a, b| match (a.labels().next(), b.labels().next()) {
-                (None, None) => Ordering::Equal,
-                (None, Some(_)) => Ordering::Less,
-                (Some(_), None) => Ordering::Greater,
-                (Some(a), Some(b)) => a.span().start().cmp(&b.span().start()),
-            });
+        // Sort the diagnostics so that their order is deterministic
+        document.diagnostics.sort();
 
         document
     }
@@ -351,6 +351,8 @@ fn add_namespace(
                     node: s.node.clone(),
                     namespace: Some(ns.clone()),
                     ty: s.ty.clone(),
+                    aliased,
+                    used: false,
                 });
             }
         }
@@ -417,6 +419,8 @@ fn add_struct(document: &mut Document, definition: &StructDefinition) {
         offset: definition.span().start(),
         node: definition.syntax().green().into(),
         ty: None,
+        aliased: false,
+        used: false,
     });
 }
 
@@ -429,9 +433,10 @@ fn convert_ast_type(document: &mut Document, ty: &wdl_ast::v1::Type) -> Type {
         fn resolve(&mut self, name: &Ident) -> Result<Type, Diagnostic> {
             self.0
                 .structs
-                .get(name.as_str())
+                .get_mut(name.as_str())
                 .map(|s| {
-                    // Mark the struct's namespace as used
+                    // Mark the struct and its namespace as used
+                    s.used = true;
                     if let Some(ns) = &s.namespace {
                         self.0.namespaces[ns].used = true;
                     }
@@ -965,9 +970,22 @@ fn populate_workflow(
                             .map(|a| a.name())
                             .unwrap_or_else(|| target_name);
 
-                        document
-                            .diagnostics
-                            .push(unused_call(name.as_str(), name.span()).with_severity(severity));
+                        // Calls without outputs are run only for their side effects
+                        let diagnostic = document
+                            .workflow
+                            .as_ref()
+                            .and_then(|w| w.calls.get(name.as_str()))
+                            .filter(|ty| !ty.outputs().is_empty())
+                            .map(|ty| {
+                                let outputs: Vec<_> =
+                                    ty.outputs().keys().map(String::as_str).collect();
+                                unused_call(name.as_str(), name.span(), &outputs)
+                                    .with_severity(severity)
+                            });
+
+                        if let Some(diagnostic) = diagnostic {
+                            document.diagnostics.push(diagnostic);
+                        }
                     }
                 }
             }
@@ -1354,8 +1372,9 @@ fn set_struct_types(document: &mut Document) {
 
     impl TypeNameResolver for Resolver<'_> {
         fn resolve(&mut self, name: &Ident) -> Result<Type, Diagnostic> {
-            if let Some(s) = self.document.structs.get(name.as_str()) {
-                // Mark the struct's namespace as used
+            if let Some(s) = self.document.structs.get_mut(name.as_str()) {
+                // Mark the struct and its namespace as used
+                s.used = true;
                 if let Some(ns) = &s.namespace {
                     self.document.namespaces[ns].used = true;
                 }
@@ -1494,9 +1513,10 @@ impl crate::types::v1::EvaluationContext for EvaluationContext<'_> {
     fn resolve_type_name(&mut self, name: &Ident) -> Result<Type, Diagnostic> {
         self.document
             .structs
-            .get(name.as_str())
+            .get_mut(name.as_str())
             .map(|s| {
-                // Mark the struct's namespace as used
+                // Mark the struct and its namespace as used
+                s.used = true;
                 if let Some(ns) = &s.namespace {
                     self.document.namespaces[ns].used = true;
                 }
@@ -16,6 +16,7 @@ pub mod eval;
 mod graph;
 mod queue;
 mod rayon;
+pub mod report;
 mod rules;
 pub mod stdlib;
 pub mod types;
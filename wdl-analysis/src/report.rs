@@ -0,0 +1,3 @@
+//! Machine-readable reporting of diagnostics.
+
+pub mod json;
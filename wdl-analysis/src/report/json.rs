@@ -0,0 +1,337 @@
+//! Reporting of diagnostics as JSON.
+//!
+//! The reporter writes [JSON Lines](https://jsonlines.org/): one JSON object
+//! per diagnostic followed by a single summary object.
+//!
+//! Every record contains a `type` field (either `diagnostic` or `summary`) and
+//! a `schema_version` field (currently [`SCHEMA_VERSION`]); the schema version
+//! is incremented whenever a field is removed or its meaning changes.
+//!
+//! A diagnostic record has the following fields:
+//!
+//! * `file` - the file the diagnostic is for.
+//! * `rule` - the identifier of the rule that produced the diagnostic or
+//!   `null`.
+//! * `severity` - one of `error`, `warning`, or `note`.
+//! * `message` - the diagnostic message.
+//! * `fix` - the fix suggestion for the diagnostic or `null`.
+//! * `url` - the documentation URL of the rule that produced the diagnostic or
+//!   `null`; see [`JsonReporter::with_rule_urls`].
+//! * `labels` - the labels of the diagnostic; the first label is the primary
+//!   label. Each label has the following fields:
+//!   * `file` - the file the label is for.
+//!   * `message` - the label message (may be empty).
+//!   * `offset` - the byte offset of the start of the label.
+//!   * `length` - the length of the label, in bytes.
+//!   * `start` - the `line` and `column` of the start of the label.
+//!   * `end` - the `line` and `column` of the end of the label.
+//!
+//! Lines and columns are one-based; columns are counted in Unicode scalar
+//! values.
+//!
+//! A summary record has the following fields:
+//!
+//! * `errors` - the number of error diagnostics reported.
+//! * `warnings` - the number of warning diagnostics reported.
+//! * `notes` - the number of note diagnostics reported.
+//! * `total` - the total number of diagnostics reported.
+
+use std::io;
+use std::io::Write;
+
+pub use line_index::LineIndex;
+use line_index::TextSize;
+use line_index::WideEncoding;
+use serde::Serialize;
+use wdl_ast::Diagnostic;
+use wdl_ast::Label;
+use wdl_ast::Severity;
+
+/// The current version of the JSON diagnostics schema.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Represents a line and column position in a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct JsonPosition {
+    /// The one-based line number.
+    pub line: u32,
+    /// The one-based column number, counted in Unicode scalar values.
+    pub column: u32,
+}
+
+impl JsonPosition {
+    /// Resolves a position from a byte offset using the given line index.
+    fn new(lines: &LineIndex, offset: usize) -> Self {
+        let offset = u32::try_from(offset)
+            .unwrap_or(u32::MAX)
+            .min(lines.len().into());
+        let line_col = lines.line_col(TextSize::new(offset));
+        let column = lines
+            .to_wide(WideEncoding::Utf32, line_col)
+            .map(|wide| wide.col)
+            .unwrap_or(line_col.col);
+
+        Self {
+            line: line_col.line + 1,
+            column: column + 1,
+        }
+    }
+}
+
+/// The JSON representation of a diagnostic label.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct JsonLabel<'a> {
+    /// The file the label is for.
+    pub file: &'a str,
+    /// The label message.
+    pub message: &'a str,
+    /// The byte offset of the start of the label.
+    pub offset: usize,
+    /// The length of the label, in bytes.
+    pub length: usize,
+    /// The start position of the label.
+    pub start: JsonPosition,
+    /// The end position of the label.
+    pub end: JsonPosition,
+}
+
+impl<'a> JsonLabel<'a> {
+    /// Constructs a new JSON label from a diagnostic label.
+    pub fn new(file: &'a str, lines: &LineIndex, label: &'a Label) -> Self {
+        let span = label.span();
+        Self {
+            file,
+            message: label.message(),
+            offset: span.start(),
+            length: span.len(),
+            start: JsonPosition::new(lines, span.start()),
+            end: JsonPosition::new(lines, span.end()),
+        }
+    }
+}
+
+/// The JSON representation of a diagnostic.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct JsonDiagnostic<'a> {
+    /// The record type; always `diagnostic`.
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    /// The schema version of the record.
+    pub schema_version: u32,
+    /// The file the diagnostic is for.
+    pub file: &'a str,
+    /// The rule associated with the diagnostic.
+    pub rule: Option<&'a str>,
+    /// The severity of the diagnostic.
+    pub severity: &'static str,
+    /// The diagnostic message.
+    pub message: &'a str,
+    /// The fix suggestion for the diagnostic.
+    pub fix: Option<&'a str>,
+    /// The documentation URL of the rule associated with the diagnostic.
+    pub url: Option<&'a str>,
+    /// The labels of the diagnostic.
+    pub labels: Vec<JsonLabel<'a>>,
+}
+
+impl<'a> JsonDiagnostic<'a> {
+    /// Constructs a new JSON diagnostic.
+    ///
+    /// The line index is used to resolve the line and column of each label.
+    pub fn new(file: &'a str, lines: &LineIndex, diagnostic: &'a Diagnostic) -> Self {
+        Self {
+            kind: "diagnostic",
+            schema_version: SCHEMA_VERSION,
+            file,
+            rule: diagnostic.rule(),
+            severity: severity_name(diagnostic.severity()),
+            message: diagnostic.message(),
+            fix: diagnostic.fix(),
+            url: None,
+            labels: diagnostic
+                .labels()
+                .map(|label| JsonLabel::new(file, lines, label))
+                .collect(),
+        }
+    }
+}
+
+/// The JSON representation of a summary of reported diagnostics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct JsonSummary {
+    /// The record type; always `summary`.
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    /// The schema version of the record.
+    pub schema_version: u32,
+    /// The number of error diagnostics.
+    pub errors: usize,
+    /// The number of warning diagnostics.
+    pub warnings: usize,
+    /// The number of note diagnostics.
+    pub notes: usize,
+    /// The total number of diagnostics.
+    pub total: usize,
+}
+
+impl Default for JsonSummary {
+    fn default() -> Self {
+        Self {
+            kind: "summary",
+            schema_version: SCHEMA_VERSION,
+            errors: 0,
+            warnings: 0,
+            notes: 0,
+            total: 0,
+        }
+    }
+}
+
+/// Gets the name of a severity.
+fn severity_name(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Note => "note",
+    }
+}
+
+/// Reports diagnostics as JSON lines to a writer.
+#[derive(Debug)]
+pub struct JsonReporter<W> {
+    /// The writer to write records to.
+    writer: W,
+    /// The summary of the reported diagnostics.
+    summary: JsonSummary,
+    /// The function used to look up the documentation URL of a rule.
+    rule_url: fn(&str) -> Option<&'static str>,
+}
+
+impl<W: Write> JsonReporter<W> {
+    /// Constructs a new JSON reporter for the given writer.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            summary: JsonSummary::default(),
+            rule_url: |_| None,
+        }
+    }
+
+    /// Sets the function used to look up the documentation URL of the rule
+    /// that produced a diagnostic.
+    ///
+    /// By default, the `url` field of every diagnostic record is `null`.
+    pub fn with_rule_urls(mut self, rule_url: fn(&str) -> Option<&'static str>) -> Self {
+        self.rule_url = rule_url;
+        self
+    }
+
+    /// Reports the diagnostics of a file.
+    ///
+    /// The line index of the file is used to resolve the line and column of
+    /// each label.
+    pub fn report<'a>(
+        &mut self,
+        file: &str,
+        lines: &LineIndex,
+        diagnostics: impl IntoIterator<Item = &'a Diagnostic>,
+    ) -> io::Result<()> {
+        for diagnostic in diagnostics {
+            match diagnostic.severity() {
+                Severity::Error => self.summary.errors += 1,
+                Severity::Warning => self.summary.warnings += 1,
+                Severity::Note => self.summary.notes += 1,
+            }
+
+            self.summary.total += 1;
+
+            let mut record = JsonDiagnostic::new(file, lines, diagnostic);
+            record.url = diagnostic.rule().and_then(self.rule_url);
+            self.write_record(&record)?;
+        }
+
+        Ok(())
+    }
+
+    /// Gets the summary of the diagnostics reported so far.
+    pub fn summary(&self) -> &JsonSummary {
+        &self.summary
+    }
+
+    /// Finishes the report by writing the summary record.
+    ///
+    /// Returns the underlying writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        let summary = self.summary;
+        self.write_record(&summary)?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    /// Writes a single record followed by a newline.
+    fn write_record(&mut self, record: &impl Serialize) -> io::Result<()> {
+        serde_json::to_writer(&mut self.writer, record)?;
+        writeln!(&mut self.writer)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use wdl_ast::Span;
+
+    use super::*;
+
+    #[test]
+    fn it_reports_diagnostics() {
+        let source = r#"version 1.1
+
+task tëst {
+    command <<<>>>
+}
+
+workflow test {
+    call tëst
+}
+"#;
+
+        let lines = LineIndex::new(source);
+        let diagnostics = [
+            Diagnostic::error("conflicting workflow name `test`")
+                .with_label("this conflicts with a task named `test`", Span::new(57, 4))
+                .with_label(
+                    "the task with the conflicting name is here",
+                    Span::new(18, 5),
+                ),
+            Diagnostic::warning("unused call `tëst`")
+                .with_rule("UnusedCall")
+                .with_highlight(Span::new(73, 5))
+                .with_fix("remove the call"),
+            Diagnostic::note("a note without labels"),
+        ];
+
+        let mut reporter = JsonReporter::new(Vec::new()).with_rule_urls(|rule| {
+            (rule == "UnusedCall").then_some("https://example.com/rules#unusedcall")
+        });
+        reporter
+            .report("source.wdl", &lines, &diagnostics)
+            .expect("should report");
+        assert_eq!(reporter.summary().total, 3);
+
+        let output =
+            String::from_utf8(reporter.finish().expect("should finish")).expect("should be UTF-8");
+        assert_eq!(
+            output,
+            concat!(
+                r#"{"type":"diagnostic","schema_version":1,"file":"source.wdl","rule":null,"severity":"error","message":"conflicting workflow name `test`","fix":null,"url":null,"labels":[{"file":"source.wdl","message":"this conflicts with a task named `test`","offset":57,"length":4,"start":{"line":7,"column":10},"end":{"line":7,"column":14}},{"file":"source.wdl","message":"the task with the conflicting name is here","offset":18,"length":5,"start":{"line":3,"column":6},"end":{"line":3,"column":10}}]}"#,
+                "\n",
+                r#"{"type":"diagnostic","schema_version":1,"file":"source.wdl","rule":"UnusedCall","severity":"warning","message":"unused call `tëst`","fix":"remove the call","url":"https://example.com/rules#unusedcall","labels":[{"file":"source.wdl","message":"","offset":73,"length":5,"start":{"line":8,"column":10},"end":{"line":8,"column":14}}]}"#,
+                "\n",
+                r#"{"type":"diagnostic","schema_version":1,"file":"source.wdl","rule":null,"severity":"note","message":"a note without labels","fix":null,"url":null,"labels":[]}"#,
+                "\n",
+                r#"{"type":"summary","schema_version":1,"errors":1,"warnings":1,"notes":1,"total":3}"#,
+                "\n",
+            )
+        );
+    }
+}
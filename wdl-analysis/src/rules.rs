@@ -138,7 +138,9 @@ impl Rule for UnusedInputRule {
 
     fn explanation(&self) -> &'static str {
         "Unused inputs degrade evaluation performance and reduce the clarity of the code. Unused \
-         file inputs in tasks can also cause unnecessary file localizations."
+         file inputs in tasks can also cause unnecessary file localizations. An input is used if \
+         it is referenced by any expression in the task or workflow, including expressions within \
+         placeholders and calls to `defined`."
     }
 
     fn deny(&mut self) {
@@ -179,7 +181,9 @@ impl Rule for UnusedDeclarationRule {
 
     fn explanation(&self) -> &'static str {
         "Unused private declarations degrade evaluation performance and reduce the clarity of the \
-         code."
+         code. An unused declaration may also indicate a bug, such as a value that was meant to \
+         be referenced in the command section. As evaluating a declaration has no side effects, \
+         an unused declaration can always be safely removed."
     }
 
     fn deny(&mut self) {
@@ -218,7 +222,11 @@ impl Rule for UnusedCallRule {
     }
 
     fn explanation(&self) -> &'static str {
-        "Unused calls may cause unnecessary consumption of compute resources."
+        "Unused calls may cause unnecessary consumption of compute resources. A call is unused \
+         if none of its outputs are referenced by a later expression, call input, conditional, \
+         scatter, or the workflow's `output` section; this is commonly a leftover from \
+         refactoring. Calls to tasks or workflows without outputs are not reported, as they are \
+         run only for their side effects."
     }
 
     fn deny(&mut self) {
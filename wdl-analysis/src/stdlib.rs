@@ -109,6 +109,28 @@ pub enum FunctionBindError {
     },
 }
 
+/// Represents a function signature that failed to bind to the arguments of a
+/// call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatureMismatch {
+    /// The display of the signature (without the function name).
+    signature: String,
+    /// The error that occurred when binding the signature.
+    error: FunctionBindError,
+}
+
+impl SignatureMismatch {
+    /// Gets the display of the signature (without the function name).
+    pub fn signature(&self) -> &str {
+        &self.signature
+    }
+
+    /// Gets the error that occurred when binding the signature.
+    pub fn error(&self) -> &FunctionBindError {
+        &self.error
+    }
+}
+
 /// Represents a generic type to a standard library function.
 #[derive(Debug, Clone)]
 pub enum GenericType {
@@ -1497,6 +1519,31 @@ impl PolymorphicFunction {
             expected,
         })
     }
+
+    /// Gets the signatures supported by the given WDL version that fail to
+    /// bind to the given arguments.
+    ///
+    /// This is used to explain why overload resolution failed; the signatures
+    /// are returned in the order they were defined.
+    pub fn mismatches(
+        &self,
+        version: SupportedVersion,
+        arguments: &[Type],
+    ) -> Vec<SignatureMismatch> {
+        self.signatures
+            .iter()
+            .filter(|s| s.minimum_version() <= version)
+            .filter_map(|s| {
+                let error = s.bind(version, arguments).err()?;
+                Some(SignatureMismatch {
+                    signature: s
+                        .display(&TypeParameters::new(&s.type_parameters))
+                        .to_string(),
+                    error,
+                })
+            })
+            .collect()
+    }
 }
 
 impl From<PolymorphicFunction> for Function {
@@ -2775,6 +2822,14 @@ mod test {
 
     use super::*;
 
+    #[test]
+    fn verify_stdlib_function_names() {
+        assert_eq!(
+            STDLIB.functions().map(|(name, _)| name).collect::<Vec<_>>(),
+            wdl_ast::v1::STDLIB_FUNCTION_NAMES,
+        );
+    }
+
     #[test]
     fn verify_stdlib_signatures() {
         let mut signatures = Vec::new();
@@ -2938,6 +2993,52 @@ mod test {
         assert_eq!(binding.return_type().to_string(), "Int");
     }
 
+    #[test]
+    fn it_reports_signature_mismatches() {
+        let Some(Function::Polymorphic(f)) = STDLIB.function("size") else {
+            panic!("should have polymorphic function");
+        };
+
+        let ty: Type = MapType::new(PrimitiveType::String, PrimitiveType::Integer).into();
+        let mismatches = f.mismatches(SupportedVersion::V1(V1::One), &[ty]);
+        let mismatches: Vec<_> = mismatches
+            .iter()
+            .map(|m| (m.signature(), m.error().clone()))
+            .collect();
+        assert_eq!(mismatches, [
+            (
+                "(File?, <String>) -> Float",
+                FunctionBindError::ArgumentTypeMismatch {
+                    index: 0,
+                    expected: "`File?`".into()
+                }
+            ),
+            (
+                "(X, <String>) -> Float where `X`: any compound type that recursively contains \
+                 a `File` or `Directory`",
+                FunctionBindError::ArgumentTypeMismatch {
+                    index: 0,
+                    expected: "`X` where `X`: any compound type that recursively contains a \
+                               `File` or `Directory`"
+                        .into()
+                }
+            ),
+        ]);
+
+        // Every signature fails to bind with too many arguments
+        let mismatches = f.mismatches(SupportedVersion::V1(V1::Two), &[
+            PrimitiveType::File.into(),
+            PrimitiveType::String.into(),
+            PrimitiveType::String.into(),
+        ]);
+        assert_eq!(mismatches.len(), 5);
+        assert!(
+            mismatches
+                .iter()
+                .all(|m| m.error() == &FunctionBindError::TooManyArguments(2))
+        );
+    }
+
     #[test]
     fn it_binds_a_generic_function() {
         let f = STDLIB.function("values").expect("should have function");
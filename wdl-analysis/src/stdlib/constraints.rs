@@ -120,7 +120,10 @@ impl Constraint for JsonSerializableConstraint {
         fn compound_type_is_serializable(ty: &CompoundType) -> bool {
             match ty {
                 CompoundType::Array(ty) => type_is_serializable(ty.element_type()),
-                CompoundType::Pair(_) => false,
+                // Pairs are serialized as an object with `left` and `right` members
+                CompoundType::Pair(ty) => {
+                    type_is_serializable(ty.left_type()) && type_is_serializable(ty.right_type())
+                }
                 CompoundType::Map(ty) => {
                     ty.key_type().is_coercible_to(&PrimitiveType::String.into())
                         && type_is_serializable(ty.value_type())
@@ -311,9 +314,18 @@ mod test {
             constraint.satisfied(&Type::from(ArrayType::new(PrimitiveType::String)).optional())
         );
         assert!(
-            !constraint
+            constraint
                 .satisfied(&PairType::new(PrimitiveType::String, PrimitiveType::String,).into())
         );
+        assert!(
+            !constraint.satisfied(
+                &PairType::new(
+                    PrimitiveType::String,
+                    MapType::new(PrimitiveType::Integer, PrimitiveType::String),
+                )
+                .into()
+            )
+        );
         assert!(constraint.satisfied(
             &Type::from(MapType::new(PrimitiveType::String, PrimitiveType::String)).optional()
         ));
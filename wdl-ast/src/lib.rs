@@ -45,6 +45,7 @@ pub use rowan::ast::AstNode;
 pub use rowan::ast::support;
 pub use wdl_grammar::Diagnostic;
 pub use wdl_grammar::Label;
+pub use wdl_grammar::Replacement;
 pub use wdl_grammar::Severity;
 pub use wdl_grammar::Span;
 pub use wdl_grammar::SupportedVersion;
@@ -101,9 +102,40 @@ impl<T: AstNode> AstNodeExt for T {
     }
 }
 
+/// Gets the node trailed by the given comment.
+///
+/// A comment trails a node if it follows the last token of the node on the
+/// same line; if several nodes end with that token, the outermost is returned.
+///
+/// Returns `None` if the comment is on its own line or if the preceding token
+/// does not end a node.
+pub fn trailed_node(comment: &SyntaxToken) -> Option<SyntaxNode> {
+    let mut prev = comment.prev_token()?;
+    if prev.kind() == SyntaxKind::Whitespace {
+        if prev.text().contains('\n') {
+            return None;
+        }
+
+        prev = prev.prev_token()?;
+    }
+
+    if prev.kind().is_trivia() {
+        return None;
+    }
+
+    let end = prev.text_range().end();
+    prev.parent_ancestors()
+        .take_while(|node| node.text_range().end() == end)
+        .last()
+}
+
 /// An extension trait for syntax nodes.
 pub trait SyntaxNodeExt {
     /// Gets an iterator over the `@except` comments for a syntax node.
+    ///
+    /// The comments are those immediately preceding the node, excluding any
+    /// comment that trails a previous node, followed by any comment that
+    /// trails the node (see [`trailed_node`]).
     fn except_comments(&self) -> impl Iterator<Item = SyntaxToken> + '_;
 
     /// Gets the AST node's rule exceptions set.
@@ -114,10 +146,21 @@ pub trait SyntaxNodeExt {
 
     /// Determines if a given rule id is excepted for the syntax node.
     fn is_rule_excepted(&self, id: &str) -> bool;
+
+    /// Gets the `#@ except` comment that excepts the given rule id for the
+    /// syntax node.
+    ///
+    /// Returns `None` if the rule is not excepted for the syntax node.
+    fn rule_exception_comment(&self, id: &str) -> Option<SyntaxToken>;
 }
 
 impl SyntaxNodeExt for SyntaxNode {
     fn except_comments(&self) -> impl Iterator<Item = SyntaxToken> + '_ {
+        let trailing = self
+            .last_token()
+            .and_then(|t| t.inline_comment())
+            .filter(|c| trailed_node(c).as_ref() == Some(self));
+
         self.siblings_with_tokens(Direction::Prev)
             .skip(1)
             .map_while(|s| {
@@ -127,7 +170,8 @@ impl SyntaxNodeExt for SyntaxNode {
                     None
                 }
             })
-            .filter(|t| t.kind() == SyntaxKind::Comment)
+            .filter(|t| t.kind() == SyntaxKind::Comment && trailed_node(t).is_none())
+            .chain(trailing)
     }
 
     fn rule_exceptions(&self) -> HashSet<String> {
@@ -145,15 +189,16 @@ impl SyntaxNodeExt for SyntaxNode {
     }
 
     fn is_rule_excepted(&self, id: &str) -> bool {
-        for comment in self.except_comments() {
-            if let Some(ids) = comment.text().strip_prefix(EXCEPT_COMMENT_PREFIX) {
-                if ids.split(',').any(|i| i.trim() == id) {
-                    return true;
-                }
-            }
-        }
+        self.rule_exception_comment(id).is_some()
+    }
 
-        false
+    fn rule_exception_comment(&self, id: &str) -> Option<SyntaxToken> {
+        self.except_comments().find(|comment| {
+            comment
+                .text()
+                .strip_prefix(EXCEPT_COMMENT_PREFIX)
+                .is_some_and(|ids| ids.split(',').any(|i| i.trim() == id))
+        })
     }
 }
 
@@ -4,6 +4,7 @@ use crate::AstChildren;
 use crate::AstNode;
 use crate::SyntaxKind;
 use crate::SyntaxNode;
+use crate::ToSpan;
 use crate::WorkflowDescriptionLanguage;
 use crate::support::children;
 
@@ -64,6 +65,15 @@ impl Ast {
     pub fn workflows(&self) -> AstChildren<WorkflowDefinition> {
         children(&self.0)
     }
+
+    /// Gets the document item that contains the given offset.
+    ///
+    /// Returns `None` if the offset is not within a document item (e.g. it is
+    /// within the version statement or the trivia between items).
+    pub fn item_at(&self, offset: usize) -> Option<DocumentItem> {
+        self.items()
+            .find(|item| item.syntax().text_range().to_span().contains(offset))
+    }
 }
 
 impl AstNode for Ast {
@@ -18,6 +18,67 @@ use crate::support::children;
 use crate::token;
 use crate::token_child;
 
+/// The names of the functions of the WDL standard library.
+///
+/// The names are in the order in which the functions are defined by the
+/// standard library of `wdl-analysis`, which verifies that the two stay in
+/// sync.
+pub const STDLIB_FUNCTION_NAMES: &[&str] = &[
+    "floor",
+    "ceil",
+    "round",
+    "min",
+    "max",
+    "find",
+    "matches",
+    "sub",
+    "basename",
+    "join_paths",
+    "glob",
+    "size",
+    "stdout",
+    "stderr",
+    "read_string",
+    "read_int",
+    "read_float",
+    "read_boolean",
+    "read_lines",
+    "write_lines",
+    "read_tsv",
+    "write_tsv",
+    "read_map",
+    "write_map",
+    "read_json",
+    "write_json",
+    "read_object",
+    "read_objects",
+    "write_object",
+    "write_objects",
+    "prefix",
+    "suffix",
+    "quote",
+    "squote",
+    "sep",
+    "range",
+    "transpose",
+    "cross",
+    "zip",
+    "unzip",
+    "contains",
+    "chunk",
+    "flatten",
+    "select_first",
+    "select_all",
+    "as_pairs",
+    "as_map",
+    "keys",
+    "contains_key",
+    "values",
+    "collect_by_key",
+    "defined",
+    "length",
+];
+
 /// Represents an expression.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Expr {
@@ -1,3 +1,4 @@
 //! Common elements that span multiple task elements.
 
 pub mod container;
+pub mod units;
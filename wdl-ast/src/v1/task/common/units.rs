@@ -1,5 +1,7 @@
-//! Module for unit representations.
+//! Functionality related to the storage units used by the `memory` and `disks`
+//! keys within `runtime` and `requirements`.
 
+use std::fmt;
 use std::str::FromStr;
 
 /// Represents a storage unit.
@@ -80,6 +82,22 @@ impl FromStr for StorageUnit {
     }
 }
 
+impl fmt::Display for StorageUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bytes => write!(f, "B"),
+            Self::Kilobytes => write!(f, "KB"),
+            Self::Megabytes => write!(f, "MB"),
+            Self::Gigabytes => write!(f, "GB"),
+            Self::Terabytes => write!(f, "TB"),
+            Self::Kibibytes => write!(f, "KiB"),
+            Self::Mebibytes => write!(f, "MiB"),
+            Self::Gibibytes => write!(f, "GiB"),
+            Self::Tebibytes => write!(f, "TiB"),
+        }
+    }
+}
+
 /// Converts a unit string (e.g. `2 GiB`) to bytes.
 ///
 /// The string is expected to contain a single integer followed by the unit.
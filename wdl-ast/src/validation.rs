@@ -1,7 +1,14 @@
 //! Validator for WDL documents.
 
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use rowan::TextRange;
+use wdl_grammar::Severity;
+use wdl_grammar::Span;
 use wdl_grammar::SyntaxElement;
 use wdl_grammar::SyntaxKind;
+use wdl_grammar::ToSpan;
 
 use super::Comment;
 use super::Diagnostic;
@@ -30,19 +37,134 @@ pub const EXCEPT_COMMENT_PREFIX: &str = "#@ except:";
 ///
 /// Validation visitors receive a diagnostics collection during
 /// visitation of the AST.
+///
+/// The collection also records the number of diagnostics suppressed by each
+/// `#@ except` comment.
+///
+/// A rule diagnostic identical to one previously added, that is with the same
+/// rule, severity, message, and primary label span, is discarded unless its
+/// rule allows duplicates (see [`allow_duplicates`](Self::allow_duplicates)).
+/// Diagnostics without a rule are never deduplicated.
 #[allow(missing_debug_implementations)]
-#[derive(Default)]
-pub struct Diagnostics(Vec<Diagnostic>);
+#[derive(Clone, Default)]
+pub struct Diagnostics {
+    /// The collected diagnostics.
+    diagnostics: Vec<Diagnostic>,
+    /// The number of diagnostics suppressed, keyed by the span of the
+    /// `#@ except` comment and the rule identifier.
+    suppressions: BTreeMap<(Span, String), usize>,
+    /// The keys of the rule diagnostics seen so far, including those that were
+    /// suppressed.
+    seen: BTreeSet<DiagnosticKey>,
+    /// The identifiers of the rules whose diagnostics are not deduplicated.
+    duplicates_allowed: BTreeSet<String>,
+}
+
+/// The key used to deduplicate diagnostics.
+///
+/// This is the diagnostic's rule, severity, message, and primary label span.
+type DiagnosticKey = (String, Severity, String, Option<Span>);
 
 impl Diagnostics {
     /// Adds a diagnostic to the collection.
+    ///
+    /// A rule diagnostic is discarded if it is a duplicate of a diagnostic
+    /// previously added.
     pub fn add(&mut self, diagnostic: Diagnostic) {
-        self.0.push(diagnostic);
+        if self.is_new(&diagnostic) {
+            self.diagnostics.push(diagnostic);
+        }
+    }
+
+    /// Allows the diagnostics of the given rule to be repeated.
+    ///
+    /// Diagnostics for the rule are not deduplicated.
+    pub fn allow_duplicates(&mut self, rule: impl Into<String>) {
+        self.duplicates_allowed.insert(rule.into());
+    }
+
+    /// Determines if the given diagnostic has not been seen before and marks
+    /// it as seen.
+    ///
+    /// Diagnostics without a rule and diagnostics of rules that allow
+    /// duplicates are always new.
+    fn is_new(&mut self, diagnostic: &Diagnostic) -> bool {
+        let Some(rule) = diagnostic.rule() else {
+            return true;
+        };
+
+        if self.duplicates_allowed.contains(rule) {
+            return true;
+        }
+
+        self.seen.insert((
+            rule.to_string(),
+            diagnostic.severity(),
+            diagnostic.message().to_string(),
+            diagnostic.labels().next().map(|l| l.span()),
+        ))
+    }
+
+    /// Gets the collected diagnostics.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// Sorts the collected diagnostics.
+    ///
+    /// Diagnostics are sorted by the start of their primary label, then by
+    /// rule identifier, and then by message; see the [`Ord`] implementation of
+    /// [`Diagnostic`] for details. The resulting order does not depend on the
+    /// order in which the diagnostics were added.
+    pub fn sort(&mut self) {
+        self.diagnostics.sort();
+    }
+
+    /// Merges another collection into this collection.
+    ///
+    /// The diagnostics of the other collection are added (see
+    /// [`add`](Self::add)) and its recorded suppressions are added to those of
+    /// this collection.
+    pub fn merge(&mut self, other: Diagnostics) {
+        self.duplicates_allowed.extend(other.duplicates_allowed);
+        for diagnostic in other.diagnostics {
+            self.add(diagnostic);
+        }
+
+        for (key, count) in other.suppressions {
+            *self.suppressions.entry(key).or_default() += count;
+        }
+
+        self.seen.extend(other.seen);
+    }
+
+    /// Records that a diagnostic for the given rule was suppressed by the
+    /// `#@ except` comment with the given span.
+    pub fn record_suppression(&mut self, comment: Span, rule: &str) {
+        *self
+            .suppressions
+            .entry((comment, rule.to_string()))
+            .or_default() += 1;
+    }
+
+    /// Gets the number of diagnostics for the given rule that were suppressed
+    /// by the `#@ except` comment with the given span.
+    pub fn suppressed(&self, comment: Span, rule: &str) -> usize {
+        self.suppressions
+            .get(&(comment, rule.to_string()))
+            .copied()
+            .unwrap_or_default()
     }
 
     /// Adds a diagnostic to the collection, unless the diagnostic is for an
     /// element that has an exception for the given rule.
     ///
+    /// The nearest exceptable node enclosing the diagnostic's primary label
+    /// is checked first, so a rule that lists finer-grained node kinds (e.g.
+    /// declarations) as exceptable may be excepted for an individual node
+    /// even when the diagnostic is attached to an enclosing section; the
+    /// ancestors of the given element are checked after.
+    ///
     /// If the diagnostic does not have a rule, the diagnostic is always added.
     pub fn exceptable_add(
         &mut self,
@@ -50,23 +172,82 @@ impl Diagnostics {
         element: SyntaxElement,
         exceptable_nodes: &Option<&'static [SyntaxKind]>,
     ) {
+        // Duplicates are discarded before exceptions are checked so that a
+        // suppressed diagnostic is only counted once
+        if !self.is_new(&diagnostic) {
+            return;
+        }
+
         if let Some(rule) = diagnostic.rule() {
-            for node in element.ancestors().filter(|node| {
-                exceptable_nodes
-                    .as_ref()
-                    .map_or(true, |nodes| nodes.contains(&node.kind()))
-            }) {
-                if node.is_rule_excepted(rule) {
+            debug_assert!(
+                is_exceptable(&element, exceptable_nodes),
+                "rule `{rule}` attached a diagnostic to a `{kind:?}` element that cannot be \
+                 excepted by the rule's exceptable nodes",
+                kind = element.kind()
+            );
+
+            // Find the element covering the primary label within the document
+            let root = element.ancestors().last();
+            let covering = diagnostic
+                .labels()
+                .next()
+                .and_then(|label| TextRange::try_from(label.span()).ok())
+                .zip(root)
+                .filter(|(range, root)| root.text_range().contains_range(*range))
+                .map(|(range, root)| root.covering_element(range));
+
+            for node in covering
+                .iter()
+                .flat_map(|e| e.ancestors())
+                .chain(element.ancestors())
+                .filter(|node| {
+                    exceptable_nodes
+                        .as_ref()
+                        .map_or(true, |nodes| nodes.contains(&node.kind()))
+                })
+            {
+                if let Some(comment) = node.rule_exception_comment(rule) {
                     // Rule is currently excepted, don't add the diagnostic
+                    self.record_suppression(comment.text_range().to_span(), rule);
                     return;
                 }
             }
         }
 
-        self.add(diagnostic);
+        self.diagnostics.push(diagnostic);
     }
 }
 
+/// Determines if a diagnostic attached to the given element may be excepted
+/// by an `#@ except` comment preceding a node of one of the given exceptable
+/// node kinds.
+///
+/// An element that is not contained within any node other than the root node
+/// (e.g. whitespace or a comment at the top-level of a document) may only be
+/// excepted for the entire document, which requires that the version statement
+/// is an exceptable node.
+///
+/// If `exceptable_nodes` is `None`, all nodes are exceptable.
+pub fn is_exceptable(
+    element: &SyntaxElement,
+    exceptable_nodes: &Option<&'static [SyntaxKind]>,
+) -> bool {
+    let Some(nodes) = exceptable_nodes else {
+        return true;
+    };
+
+    let mut ancestors = element
+        .ancestors()
+        .filter(|node| node.kind() != SyntaxKind::RootNode)
+        .peekable();
+
+    if ancestors.peek().is_none() {
+        return nodes.contains(&SyntaxKind::VersionStatementNode);
+    }
+
+    ancestors.any(|node| nodes.contains(&node.kind()))
+}
+
 /// Implements an AST validator.
 ///
 /// A validator operates on a set of AST visitors, providing a collection
@@ -112,17 +293,31 @@ impl Validator {
 
     /// Validates the given document and returns the validation errors upon
     /// failure.
+    ///
+    /// The returned diagnostics are sorted (see [`Diagnostics::sort`]).
     pub fn validate(&mut self, document: &Document) -> Result<(), Vec<Diagnostic>> {
-        let mut diagnostics = Diagnostics::default();
-        document.visit(&mut diagnostics, self);
-
-        if diagnostics.0.is_empty() {
+        let diagnostics = self.run(document).diagnostics;
+        if diagnostics.is_empty() {
             Ok(())
         } else {
-            diagnostics.0.sort();
-            Err(diagnostics.0)
+            Err(diagnostics)
         }
     }
+
+    /// Validates the given document and returns the resulting diagnostics
+    /// collection.
+    ///
+    /// Unlike [`validate`](Self::validate), the returned collection includes
+    /// the suppressions recorded for `#@ except` comments.
+    ///
+    /// The diagnostics of the returned collection are sorted (see
+    /// [`Diagnostics::sort`]).
+    pub fn run(&mut self, document: &Document) -> Diagnostics {
+        let mut diagnostics = Diagnostics::default();
+        document.visit(&mut diagnostics, self);
+        diagnostics.sort();
+        diagnostics
+    }
 }
 
 impl Default for Validator {
@@ -447,3 +642,124 @@ impl Visitor for Validator {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::Ast;
+    use crate::AstNode;
+    use crate::AstToken;
+
+    #[test]
+    fn it_deduplicates_diagnostics() {
+        let diagnostic = |start| {
+            Diagnostic::warning("duplicate")
+                .with_rule("Foo")
+                .with_highlight(Span::new(start, 1))
+        };
+
+        let mut diagnostics = Diagnostics::default();
+        diagnostics.add(diagnostic(0));
+        diagnostics.add(diagnostic(0));
+        diagnostics.add(diagnostic(1));
+        assert_eq!(diagnostics.diagnostics(), [diagnostic(0), diagnostic(1)]);
+
+        // Rules that allow duplicates are not deduplicated
+        let mut diagnostics = Diagnostics::default();
+        diagnostics.allow_duplicates("Foo");
+        diagnostics.add(diagnostic(0));
+        diagnostics.add(diagnostic(0));
+        assert_eq!(diagnostics.diagnostics().len(), 2);
+
+        // Diagnostics without a rule are not deduplicated
+        let mut diagnostics = Diagnostics::default();
+        diagnostics.add(Diagnostic::error("duplicate").with_highlight(Span::new(0, 1)));
+        diagnostics.add(Diagnostic::error("duplicate").with_highlight(Span::new(0, 1)));
+        assert_eq!(diagnostics.diagnostics().len(), 2);
+    }
+
+    #[test]
+    fn it_counts_suppressed_duplicates_once() {
+        let (document, diagnostics) =
+            Document::parse("version 1.1\n\n#@ except: Foo\nworkflow test {}\n");
+        assert!(diagnostics.is_empty());
+
+        let Ast::V1(ast) = document.ast() else {
+            panic!("expected a V1 AST");
+        };
+
+        let workflow = ast.workflows().next().expect("should have a workflow");
+        let diagnostic = Diagnostic::warning("duplicate")
+            .with_rule("Foo")
+            .with_highlight(workflow.name().span());
+
+        let mut diagnostics = Diagnostics::default();
+        for _ in 0..2 {
+            diagnostics.exceptable_add(
+                diagnostic.clone(),
+                SyntaxElement::from(workflow.syntax().clone()),
+                &None,
+            );
+        }
+
+        let comment = Span::new(13, 14);
+        assert!(diagnostics.diagnostics().is_empty());
+        assert_eq!(diagnostics.suppressed(comment, "Foo"), 1);
+    }
+
+    #[test]
+    fn it_excepts_individual_declarations() {
+        let source = r#"version 1.1
+
+task test {
+    input {
+        #@ except: Foo
+        String a
+        String b  #@ except: Foo
+        String c
+    }
+
+    command <<<>>>
+}
+"#;
+        let (document, diagnostics) = Document::parse(source);
+        assert!(diagnostics.is_empty());
+
+        let Ast::V1(ast) = document.ast() else {
+            panic!("expected a V1 AST");
+        };
+
+        let task = ast.tasks().next().expect("should have a task");
+        let input = task.input().expect("should have an input section");
+
+        // Attach each diagnostic to the input section, highlighting a declaration
+        let mut diagnostics = Diagnostics::default();
+        for decl in input.declarations() {
+            diagnostics.exceptable_add(
+                Diagnostic::warning(format!("bad input `{name}`", name = decl.name().as_str()))
+                    .with_rule("Foo")
+                    .with_highlight(decl.name().span()),
+                SyntaxElement::from(input.syntax().clone()),
+                &Some(&[
+                    SyntaxKind::TaskDefinitionNode,
+                    SyntaxKind::BoundDeclNode,
+                    SyntaxKind::UnboundDeclNode,
+                ]),
+            );
+        }
+
+        let messages: Vec<_> = diagnostics
+            .diagnostics()
+            .iter()
+            .map(|d| d.message())
+            .collect();
+        assert_eq!(messages, ["bad input `c`"]);
+
+        let preceding = Span::new(source.find("#@").unwrap(), 14);
+        let trailing = Span::new(source.rfind("#@").unwrap(), 14);
+        assert_eq!(diagnostics.suppressed(preceding, "Foo"), 1);
+        assert_eq!(diagnostics.suppressed(trailing, "Foo"), 1);
+    }
+}
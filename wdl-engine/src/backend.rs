@@ -4,14 +4,30 @@ use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
 
+use anyhow::Context;
 use anyhow::Result;
 use futures::future::BoxFuture;
 use indexmap::IndexMap;
+use wdl_analysis::types::PrimitiveType;
+use wdl_ast::v1::TASK_REQUIREMENT_CPU;
+use wdl_ast::v1::TASK_REQUIREMENT_MEMORY;
 
+use crate::Coercible;
 use crate::Engine;
+use crate::Secret;
 use crate::Value;
+use crate::convert_unit_string;
 
 pub mod local;
+mod monitor;
+mod orphans;
+mod rerun;
+mod usage;
+
+pub use monitor::*;
+pub use orphans::*;
+pub use rerun::*;
+pub use usage::*;
 
 /// Represents constraints applied to a task's execution.
 pub struct TaskExecutionConstraints {
@@ -49,6 +65,56 @@ pub struct TaskExecutionConstraints {
     pub disks: IndexMap<String, i64>,
 }
 
+/// Gets the number of CPUs requested by a task's requirements.
+///
+/// The default for `cpu` is 1.
+pub(crate) fn requested_cpu(requirements: &HashMap<String, Value>) -> f64 {
+    requirements
+        .get(TASK_REQUIREMENT_CPU)
+        .map(|v| {
+            v.coerce(&PrimitiveType::Float.into())
+                .expect("type should coerce")
+                .unwrap_float()
+        })
+        .unwrap_or(1.0)
+}
+
+/// Gets the bytes of memory requested by a task's requirements.
+///
+/// The default for `memory` is 2 GiB.
+///
+/// Returns an error if the requirement is an invalid memory string.
+pub(crate) fn requested_memory(requirements: &HashMap<String, Value>) -> Result<i64> {
+    Ok(requirements
+        .get(TASK_REQUIREMENT_MEMORY)
+        .map(|v| {
+            if let Some(v) = v.as_integer() {
+                return Ok(v);
+            }
+
+            if let Some(s) = v.as_string() {
+                return convert_unit_string(s)
+                    .and_then(|v| v.try_into().ok())
+                    .with_context(|| {
+                        format!("task specifies an invalid `memory` requirement `{s}`")
+                    });
+            }
+
+            unreachable!("value should be an integer or string");
+        })
+        .transpose()?
+        .unwrap_or(2 * 1024 * 1024 * 1024))
+}
+
+/// Represents the result of a task's execution.
+#[derive(Debug, Clone, Copy)]
+pub struct TaskExecutionResult {
+    /// The exit status code of the task's process.
+    pub status_code: i32,
+    /// The resources used by the task's execution.
+    pub resource_usage: ResourceUsage,
+}
+
 /// Represents the execution of a particular task.
 pub trait TaskExecution: Send {
     /// Maps a host path to a guest path.
@@ -82,6 +148,19 @@ pub trait TaskExecution: Send {
     /// The stderr file is created upon spawning the task.
     fn stderr(&self) -> &Path;
 
+    /// Gets the invocation of the task's process.
+    ///
+    /// The invocation is used both to spawn the process and to generate its
+    /// rerun script.
+    fn invocation(&self) -> TaskInvocation;
+
+    /// Adds an environment variable holding a secret to the task's process.
+    ///
+    /// The variable must be part of the execution's invocation (see
+    /// [`TaskInvocation::with_secret_env`]) so that its value is not written
+    /// to the rerun script.
+    fn add_secret_env(&mut self, name: String, secret: Secret);
+
     /// Gets the execution constraints for the task given the task's
     /// requirements and hints.
     ///
@@ -99,13 +178,13 @@ pub trait TaskExecution: Send {
     ///
     /// Upon success, returns a future that will complete when the task's
     /// execution has finished; the future returns the exit status code of the
-    /// task's process.
+    /// task's process and the resources used by the execution.
     fn spawn(
         &self,
         command: String,
         requirements: &HashMap<String, Value>,
         hints: &HashMap<String, Value>,
-    ) -> Result<BoxFuture<'static, Result<i32>>>;
+    ) -> Result<BoxFuture<'static, Result<TaskExecutionResult>>>;
 }
 
 /// Represents a task execution backend.
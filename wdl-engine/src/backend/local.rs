@@ -3,29 +3,38 @@
 use std::collections::HashMap;
 use std::fs;
 use std::fs::File;
+use std::io;
 use std::path::Path;
 use std::path::PathBuf;
 use std::path::absolute;
 use std::process::Stdio;
+use std::time::Duration;
 
 use anyhow::Context;
 use anyhow::Result;
 use anyhow::bail;
 use futures::FutureExt;
 use futures::future::BoxFuture;
-use tokio::process::Command;
+use indexmap::IndexMap;
+use tokio::process::Child;
+use tokio::time::Interval;
 use tracing::info;
-use wdl_analysis::types::PrimitiveType;
-use wdl_ast::v1::TASK_REQUIREMENT_CPU;
-use wdl_ast::v1::TASK_REQUIREMENT_MEMORY;
 
 use super::TaskExecution;
 use super::TaskExecutionBackend;
 use super::TaskExecutionConstraints;
-use crate::Coercible;
+use super::TaskExecutionResult;
+use super::TaskInvocation;
+use super::monitor::StallDetection;
+use super::monitor::StallMonitor;
+use super::monitor::format_duration;
+use super::requested_cpu;
+use super::requested_memory;
+use super::usage::DEFAULT_USAGE_SAMPLE_INTERVAL;
+use super::usage::UsageSampler;
 use crate::Engine;
+use crate::Secret;
 use crate::Value;
-use crate::convert_unit_string;
 
 /// Represents a local task execution.
 ///
@@ -42,6 +51,12 @@ pub struct LocalTaskExecution {
     stdout: PathBuf,
     /// The path to the stderr file.
     stderr: PathBuf,
+    /// The stall detection settings for the execution.
+    stall_detection: Option<StallDetection>,
+    /// The interval at which the execution is sampled for resource usage.
+    usage_sample_interval: Duration,
+    /// The environment variables holding secrets for the execution.
+    secret_env: IndexMap<String, Secret>,
 }
 
 impl LocalTaskExecution {
@@ -70,8 +85,28 @@ impl LocalTaskExecution {
             command: root.join("command"),
             stdout: root.join("stdout"),
             stderr: root.join("stderr"),
+            stall_detection: None,
+            usage_sample_interval: DEFAULT_USAGE_SAMPLE_INTERVAL,
+            secret_env: Default::default(),
         })
     }
+
+    /// Sets the stall detection settings for the execution.
+    ///
+    /// Defaults to `None`, meaning the execution is not monitored for stalls.
+    pub fn with_stall_detection(mut self, detection: Option<StallDetection>) -> Self {
+        self.stall_detection = detection;
+        self
+    }
+
+    /// Sets the interval at which the execution is sampled for resource
+    /// usage.
+    ///
+    /// Defaults to [`DEFAULT_USAGE_SAMPLE_INTERVAL`].
+    pub fn with_usage_sample_interval(mut self, interval: Duration) -> Self {
+        self.usage_sample_interval = interval;
+        self
+    }
 }
 
 impl TaskExecution for LocalTaskExecution {
@@ -100,6 +135,25 @@ impl TaskExecution for LocalTaskExecution {
         &self.stderr
     }
 
+    fn invocation(&self) -> TaskInvocation {
+        let invocation = self.secret_env.iter().fold(
+            TaskInvocation::new(&self.work_dir, &self.command),
+            |invocation, (name, secret)| invocation.with_secret_env(name, secret.clone()),
+        );
+
+        // Set an environment variable on Windows to get consistent PATH searching
+        // See: https://github.com/rust-lang/rust/issues/122660
+        if cfg!(windows) {
+            invocation.with_env("WDL_TASK_EVALUATION", "1")
+        } else {
+            invocation
+        }
+    }
+
+    fn add_secret_env(&mut self, name: String, secret: Secret) {
+        self.secret_env.insert(name, secret);
+    }
+
     fn constraints(
         &self,
         engine: &Engine,
@@ -107,14 +161,7 @@ impl TaskExecution for LocalTaskExecution {
         _: &HashMap<String, Value>,
     ) -> Result<TaskExecutionConstraints> {
         let num_cpus: f64 = engine.system().cpus().len() as f64;
-        let min_cpu = requirements
-            .get(TASK_REQUIREMENT_CPU)
-            .map(|v| {
-                v.coerce(&PrimitiveType::Float.into())
-                    .expect("type should coerce")
-                    .unwrap_float()
-            })
-            .unwrap_or(1.0);
+        let min_cpu = requested_cpu(requirements);
         if num_cpus < min_cpu {
             bail!(
                 "task requires at least {min_cpu} CPU{s}, but the host only has {num_cpus} \
@@ -129,27 +176,7 @@ impl TaskExecution for LocalTaskExecution {
             .try_into()
             .context("system has too much memory to describe as a WDL value")?;
 
-        // The default value for `memory` is 2 GiB
-        let min_memory = requirements
-            .get(TASK_REQUIREMENT_MEMORY)
-            .map(|v| {
-                if let Some(v) = v.as_integer() {
-                    return Ok(v);
-                }
-
-                if let Some(s) = v.as_string() {
-                    return convert_unit_string(s)
-                        .and_then(|v| v.try_into().ok())
-                        .with_context(|| {
-                            format!("task specifies an invalid `memory` requirement `{s}`")
-                        });
-                }
-
-                unreachable!("value should be an integer or string");
-            })
-            .transpose()?
-            .unwrap_or(2 * 1024 * 1024 * 1024); // 2GiB is the default for `memory`
-
+        let min_memory = requested_memory(requirements)?;
         if memory < min_memory {
             // Display the error in GiB, as it is the most common unit for memory
             let memory = memory as f64 / (1024.0 * 1024.0 * 1024.0);
@@ -176,7 +203,7 @@ impl TaskExecution for LocalTaskExecution {
         command: String,
         _: &HashMap<String, Value>,
         _: &HashMap<String, Value>,
-    ) -> Result<BoxFuture<'static, Result<i32>>> {
+    ) -> Result<BoxFuture<'static, Result<TaskExecutionResult>>> {
         // Recreate the working directory
         if self.work_dir.exists() {
             fs::remove_dir_all(&self.work_dir).with_context(|| {
@@ -218,29 +245,63 @@ impl TaskExecution for LocalTaskExecution {
             )
         })?;
 
-        let mut command = Command::new("bash");
-        command
-            .current_dir(&self.work_dir)
-            .arg("-C")
-            .arg(&self.command)
-            .stdin(Stdio::null())
-            .stdout(stdout)
-            .stderr(stderr);
-
-        // Set an environment variable on Windows to get consistent PATH searching
-        // See: https://github.com/rust-lang/rust/issues/122660
-        #[cfg(windows)]
-        command.env("WDL_TASK_EVALUATION", "1");
+        let mut command = self.invocation().to_command();
+        command.stdin(Stdio::null()).stdout(stdout).stderr(stderr);
 
+        // Make the child the leader of a new process group so that a stalled task can
+        // be killed along with any processes it has spawned
         #[cfg(unix)]
+        command.process_group(0);
+
+        let stdout = self.stdout.clone();
         let stderr = self.stderr.clone();
+        let stall_detection = self.stall_detection;
+        let usage_sample_interval = self.usage_sample_interval;
 
         let mut child = command.spawn().context("failed to spawn `bash`")?;
         Ok(async move {
             let id = child.id().expect("should have id");
             info!("spawning local `bash` process {id} for task execution");
 
-            let status = child.wait().await.with_context(|| {
+            let mut sampler = UsageSampler::new(id);
+            let mut usage_interval = tokio::time::interval(usage_sample_interval);
+            let mut stall = stall_detection.map(|detection| {
+                (
+                    StallMonitor::new(detection, id, stdout.clone(), stderr.clone()),
+                    tokio::time::interval(detection.interval()),
+                )
+            });
+
+            let result = loop {
+                tokio::select! {
+                    status = child.wait() => break Ok(status),
+                    _ = usage_interval.tick() => sampler.sample(),
+                    stalled = next_stall(&mut stall) => {
+                        if let Some(stalled) = stalled {
+                            break Err(stalled);
+                        }
+                    }
+                }
+            };
+
+            let status = match result {
+                Ok(status) => status,
+                Err(kill_after) => {
+                    kill(&mut child).await.with_context(|| {
+                        format!("failed to kill stalled task child process {id}")
+                    })?;
+
+                    bail!(
+                        "task made no progress for {duration}; task child process {id} was \
+                         killed (see stdout file `{stdout}` and stderr file `{stderr}` for more \
+                         details)",
+                        duration = format_duration(kill_after),
+                        stdout = stdout.display(),
+                        stderr = stderr.display()
+                    );
+                }
+            }
+            .with_context(|| {
                 format!("failed to wait for termination of task child process {id}")
             })?;
 
@@ -256,28 +317,225 @@ impl TaskExecution for LocalTaskExecution {
                 }
             }
 
-            let status_code = status.code().expect("process should have exited");
-            Ok(status_code)
+            Ok(TaskExecutionResult {
+                status_code: status.code().expect("process should have exited"),
+                resource_usage: sampler.finish(),
+            })
         }
         .boxed())
     }
 }
 
+/// Kills a task's child process along with any processes it has spawned.
+///
+/// On Unix, the child process leads its own process group and the entire group
+/// is killed; on other platforms, only the child process is killed.
+async fn kill(child: &mut Child) -> io::Result<()> {
+    #[cfg(unix)]
+    if let Some(id) = child.id() {
+        // SAFETY: sending a signal has no memory safety requirements
+        if unsafe { libc::kill(-(id as libc::pid_t), libc::SIGKILL) } == 0 {
+            child.wait().await?;
+            return Ok(());
+        }
+    }
+
+    child.kill().await
+}
+
+/// Waits for the next stall sample of a running task.
+///
+/// Returns the configured kill duration if the task should be killed.
+///
+/// If the task is not monitored for stalls, this never completes.
+async fn next_stall(stall: &mut Option<(StallMonitor, Interval)>) -> Option<Duration> {
+    match stall {
+        Some((monitor, interval)) => {
+            interval.tick().await;
+            monitor.sample()
+        }
+        None => std::future::pending().await,
+    }
+}
+
 /// Represents a task execution backend that locally executes tasks.
 ///
 /// This backend will directly spawn processes without using a container.
 #[derive(Debug, Default, Clone, Copy)]
-pub struct LocalTaskExecutionBackend;
+pub struct LocalTaskExecutionBackend {
+    /// The stall detection settings for task executions.
+    stall_detection: Option<StallDetection>,
+    /// The interval at which task executions are sampled for resource usage.
+    usage_sample_interval: Option<Duration>,
+}
 
 impl LocalTaskExecutionBackend {
     /// Constructs a new local task execution backend.
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Sets the stall detection settings for task executions.
+    ///
+    /// Running tasks that make no progress for the configured duration are
+    /// reported with a warning and, optionally, killed.
+    ///
+    /// Defaults to `None`, meaning task executions are not monitored for
+    /// stalls.
+    pub fn with_stall_detection(mut self, detection: StallDetection) -> Self {
+        self.stall_detection = Some(detection);
+        self
+    }
+
+    /// Gets the stall detection settings for task executions.
+    pub fn stall_detection(&self) -> Option<StallDetection> {
+        self.stall_detection
+    }
+
+    /// Sets the interval at which task executions are sampled for resource
+    /// usage.
+    ///
+    /// Defaults to [`DEFAULT_USAGE_SAMPLE_INTERVAL`].
+    pub fn with_usage_sample_interval(mut self, interval: Duration) -> Self {
+        self.usage_sample_interval = Some(interval);
+        self
+    }
+
+    /// Gets the interval at which task executions are sampled for resource
+    /// usage.
+    pub fn usage_sample_interval(&self) -> Duration {
+        self.usage_sample_interval
+            .unwrap_or(DEFAULT_USAGE_SAMPLE_INTERVAL)
     }
 }
 
 impl TaskExecutionBackend for LocalTaskExecutionBackend {
     fn create_execution(&self, root: &Path) -> Result<Box<dyn TaskExecution>> {
-        Ok(Box::new(LocalTaskExecution::new(root)?))
+        Ok(Box::new(
+            LocalTaskExecution::new(root)?
+                .with_stall_detection(self.stall_detection)
+                .with_usage_sample_interval(self.usage_sample_interval()),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::DEFAULT_OVER_PROVISIONED_FRACTION;
+    use crate::ProvisionedResource;
+
+    /// Spawns the given command with the given stall detection settings.
+    async fn spawn(
+        command: &str,
+        detection: Option<StallDetection>,
+    ) -> Result<TaskExecutionResult> {
+        let dir = TempDir::new().expect("failed to create temporary directory");
+        let execution = LocalTaskExecution::new(dir.path())?
+            .with_stall_detection(detection)
+            .with_usage_sample_interval(Duration::from_millis(20));
+        execution
+            .spawn(
+                command.to_string(),
+                &Default::default(),
+                &Default::default(),
+            )?
+            .await
+    }
+
+    #[tokio::test]
+    async fn it_kills_stalled_tasks() {
+        let detection = StallDetection::new(Duration::from_millis(100))
+            .with_interval(Duration::from_millis(20))
+            .with_kill_after(Some(Duration::from_millis(300)));
+        let e = spawn("sleep 30", Some(detection))
+            .await
+            .expect_err("task should be killed");
+        assert!(
+            e.to_string()
+                .starts_with("task made no progress for 300 milliseconds;"),
+            "unexpected error `{e}`"
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn it_kills_the_processes_of_stalled_tasks() {
+        let dir = TempDir::new().expect("failed to create temporary directory");
+        let execution = LocalTaskExecution::new(dir.path())
+            .unwrap()
+            .with_stall_detection(Some(
+                StallDetection::new(Duration::from_millis(100))
+                    .with_interval(Duration::from_millis(20))
+                    .with_kill_after(Some(Duration::from_millis(300))),
+            ));
+
+        // Spawn a background process and write its identifier to stdout
+        execution
+            .spawn(
+                "sleep 30 & echo $!; wait".to_string(),
+                &Default::default(),
+                &Default::default(),
+            )
+            .unwrap()
+            .await
+            .expect_err("task should be killed");
+
+        let pid = fs::read_to_string(execution.stdout()).unwrap();
+        let stat = fs::read_to_string(format!("/proc/{pid}/stat", pid = pid.trim()));
+
+        // The background process should either be gone or a zombie awaiting reaping
+        assert!(
+            stat.map(|s| s[s.rfind(')').unwrap() + 1..].trim_start().starts_with('Z'))
+                .unwrap_or(true),
+            "background process of the stalled task is still running"
+        );
+    }
+
+    #[tokio::test]
+    async fn it_does_not_kill_progressing_tasks() {
+        let detection = StallDetection::new(Duration::from_secs(1))
+            .with_interval(Duration::from_millis(20))
+            .with_kill_after(Some(Duration::from_secs(2)));
+        let result = spawn(
+            "for i in 1 2 3 4 5; do echo $i; sleep 0.1; done",
+            Some(detection),
+        )
+        .await
+        .expect("task should not be killed");
+        assert_eq!(result.status_code, 0);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn it_records_resource_usage() {
+        const MIB: u64 = 1024 * 1024;
+
+        // Hold roughly 64 MiB in a shell variable for long enough to be sampled
+        let result = spawn(
+            "data=$(head -c 67108864 /dev/zero | tr '\\0' 'x'); sleep 0.5; echo ${#data}",
+            None,
+        )
+        .await
+        .expect("task should succeed");
+        assert_eq!(result.status_code, 0);
+
+        let usage = result.resource_usage;
+        let max_rss = usage.max_rss().expect("should have max RSS");
+        assert!(
+            (64 * MIB..1024 * MIB).contains(&max_rss),
+            "unexpected max RSS of {max_rss} bytes"
+        );
+        assert!(usage.cpu().is_some());
+        assert!(usage.elapsed() >= Duration::from_millis(500));
+
+        // A deliberately padded request should be reported as over-provisioned
+        let over = usage.over_provisioned(4.0, 64 * 1024 * MIB, DEFAULT_OVER_PROVISIONED_FRACTION);
+        assert_eq!(
+            over.iter().map(|o| o.resource).collect::<Vec<_>>(),
+            [ProvisionedResource::Cpu, ProvisionedResource::Memory]
+        );
     }
 }
@@ -0,0 +1,270 @@
+//! Implementation of stall detection for running tasks.
+
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::Instant;
+
+use tracing::warn;
+
+/// The default interval at which running tasks are sampled for progress.
+pub const DEFAULT_STALL_SAMPLE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Represents the settings for detecting tasks that have stalled.
+///
+/// A running task is considered to have made progress if the CPU time consumed
+/// by its processes or the size of its stdout or stderr files has changed since
+/// the previous sample.
+///
+/// CPU time is only sampled on Linux (from `/proc`); on other platforms only
+/// the stdout and stderr files are sampled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StallDetection {
+    /// The interval at which a running task is sampled.
+    interval: Duration,
+    /// The duration without progress after which a warning is emitted.
+    warn_after: Duration,
+    /// The duration without progress after which the task is killed.
+    kill_after: Option<Duration>,
+}
+
+impl StallDetection {
+    /// Constructs a new stall detection that warns when a task has made no
+    /// progress for the given duration.
+    ///
+    /// Tasks are sampled every [`DEFAULT_STALL_SAMPLE_INTERVAL`] and stalled
+    /// tasks are not killed.
+    pub fn new(warn_after: Duration) -> Self {
+        Self {
+            interval: DEFAULT_STALL_SAMPLE_INTERVAL,
+            warn_after,
+            kill_after: None,
+        }
+    }
+
+    /// Sets the interval at which a running task is sampled.
+    ///
+    /// Defaults to [`DEFAULT_STALL_SAMPLE_INTERVAL`].
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Sets the duration without progress after which the task is killed.
+    ///
+    /// Defaults to `None`, meaning stalled tasks are never killed.
+    pub fn with_kill_after(mut self, kill_after: Option<Duration>) -> Self {
+        self.kill_after = kill_after;
+        self
+    }
+
+    /// Gets the interval at which a running task is sampled.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Gets the duration without progress after which a warning is emitted.
+    pub fn warn_after(&self) -> Duration {
+        self.warn_after
+    }
+
+    /// Gets the duration without progress after which the task is killed.
+    pub fn kill_after(&self) -> Option<Duration> {
+        self.kill_after
+    }
+}
+
+/// Formats a duration for display in stall messages.
+pub(crate) fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (n, unit) = if secs >= 60 && secs % 60 == 0 {
+        (secs / 60, "minute")
+    } else if secs > 0 {
+        (secs, "second")
+    } else {
+        (duration.as_millis() as u64, "millisecond")
+    };
+
+    format!("{n} {unit}{s}", s = if n == 1 { "" } else { "s" })
+}
+
+/// Represents a sample of a running task's progress.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Sample {
+    /// The CPU time, in clock ticks, consumed by the task's processes.
+    ///
+    /// This is `None` if the CPU time could not be sampled.
+    cpu: Option<u64>,
+    /// The combined size, in bytes, of the task's stdout and stderr files.
+    output: u64,
+}
+
+/// Monitors a running task for stalls.
+#[derive(Debug)]
+pub(crate) struct StallMonitor {
+    /// The stall detection settings.
+    detection: StallDetection,
+    /// The identifier of the task's root process.
+    pid: u32,
+    /// The paths of the task's stdout and stderr files.
+    outputs: [PathBuf; 2],
+    /// The previous sample.
+    last: Sample,
+    /// The time at which progress was last observed.
+    progressed: Instant,
+    /// Whether or not a warning has been emitted for the current stall.
+    warned: bool,
+}
+
+impl StallMonitor {
+    /// Constructs a new stall monitor for the given process and output files.
+    pub fn new(detection: StallDetection, pid: u32, stdout: PathBuf, stderr: PathBuf) -> Self {
+        let outputs = [stdout, stderr];
+        let last = sample(pid, &outputs);
+        Self {
+            detection,
+            pid,
+            outputs,
+            last,
+            progressed: Instant::now(),
+            warned: false,
+        }
+    }
+
+    /// Samples the task's progress.
+    ///
+    /// A warning is emitted once per stall when the task has made no progress
+    /// for the configured duration.
+    ///
+    /// Returns the configured kill duration if the task should be killed.
+    pub fn sample(&mut self) -> Option<Duration> {
+        let current = sample(self.pid, &self.outputs);
+        if current != self.last {
+            self.last = current;
+            self.progressed = Instant::now();
+            self.warned = false;
+            return None;
+        }
+
+        let stalled = self.progressed.elapsed();
+        if let Some(kill_after) = self.detection.kill_after {
+            if stalled >= kill_after {
+                return Some(kill_after);
+            }
+        }
+
+        if !self.warned && stalled >= self.detection.warn_after {
+            self.warned = true;
+            warn!(
+                "task child process {pid} has stalled: no progress for {duration}",
+                pid = self.pid,
+                duration = format_duration(self.detection.warn_after)
+            );
+        }
+
+        None
+    }
+}
+
+/// Samples the progress of the given process and output files.
+fn sample(pid: u32, outputs: &[PathBuf; 2]) -> Sample {
+    Sample {
+        cpu: cpu_ticks(pid),
+        output: outputs
+            .iter()
+            .map(|p| fs::metadata(p).map(|m| m.len()).unwrap_or(0))
+            .sum(),
+    }
+}
+
+/// Gets the identifiers of a process and its descendants.
+///
+/// The given process is first in the returned list; the list is empty if the
+/// process no longer exists.
+#[cfg(target_os = "linux")]
+pub(crate) fn process_tree(pid: u32) -> Vec<u32> {
+    /// Gets the identifiers of the child processes of a process.
+    fn children(pid: u32) -> Vec<u32> {
+        let Ok(tasks) = fs::read_dir(format!("/proc/{pid}/task")) else {
+            return Vec::new();
+        };
+
+        tasks
+            .filter_map(|e| fs::read_to_string(e.ok()?.path().join("children")).ok())
+            .flat_map(|c| {
+                c.split_whitespace()
+                    .filter_map(|p| p.parse().ok())
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    if !fs::exists(format!("/proc/{pid}")).unwrap_or(false) {
+        return Vec::new();
+    }
+
+    let mut tree = vec![pid];
+    let mut current = 0;
+    while current < tree.len() {
+        tree.extend(children(tree[current]));
+        current += 1;
+    }
+
+    tree
+}
+
+/// Gets the CPU time, in clock ticks, consumed by a process and its
+/// descendants.
+///
+/// Returns `None` if the process no longer exists.
+#[cfg(target_os = "linux")]
+fn cpu_ticks(pid: u32) -> Option<u64> {
+    /// Gets the user and system CPU time of a process and its waited-for
+    /// children.
+    fn process_ticks(pid: u32) -> Option<u64> {
+        let stat = fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+
+        // The process name may contain spaces and parentheses, so the fields are read
+        // after the last closing parenthesis; the first field after it is the
+        // process state (field 3) and `utime`, `stime`, `cutime`, and `cstime` are
+        // fields 14 through 17
+        let fields: Vec<_> = stat[stat.rfind(')')? + 1..].split_whitespace().collect();
+        fields
+            .get(11..15)?
+            .iter()
+            .map(|f| f.parse::<u64>().ok())
+            .sum()
+    }
+
+    let mut tree = process_tree(pid).into_iter();
+    let mut total = process_ticks(tree.next()?)?;
+
+    // Descendants may exit at any time, so skip any that have gone away
+    total += tree.filter_map(process_ticks).sum::<u64>();
+    Some(total)
+}
+
+/// Gets the CPU time consumed by a process and its descendants.
+///
+/// CPU time is not sampled on this platform, so this always returns `None`.
+#[cfg(not(target_os = "linux"))]
+fn cpu_ticks(_: u32) -> Option<u64> {
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_formats_durations() {
+        assert_eq!(format_duration(Duration::from_secs(60)), "1 minute");
+        assert_eq!(format_duration(Duration::from_secs(600)), "10 minutes");
+        assert_eq!(format_duration(Duration::from_secs(90)), "90 seconds");
+        assert_eq!(format_duration(Duration::from_secs(1)), "1 second");
+        assert_eq!(
+            format_duration(Duration::from_millis(250)),
+            "250 milliseconds"
+        );
+    }
+}
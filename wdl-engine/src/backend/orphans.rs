@@ -0,0 +1,427 @@
+//! Implementation of the cleanup of containers orphaned by previous runs.
+//!
+//! Every container started for a task execution is labeled with the
+//! identifier of the engine that started it and the identifier of the run it
+//! belongs to (see [`container_labels`]).
+//!
+//! While a run is active, the engine periodically records a liveness
+//! timestamp in the run's state file (see [`record_liveness`]). If the engine
+//! process is killed, the timestamp is no longer updated and any containers
+//! still carrying the run's label are considered orphaned once the timestamp
+//! is older than the liveness timeout.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use anyhow::Context;
+use anyhow::Result;
+
+use crate::path_component;
+
+/// The label containing the identifier of the engine that started a
+/// container.
+pub const ENGINE_LABEL: &str = "org.wdl.engine";
+
+/// The label containing the identifier of the run a container belongs to.
+pub const RUN_ID_LABEL: &str = "org.wdl.run-id";
+
+/// The default duration after which a run that has not recorded its liveness
+/// is considered to have crashed.
+pub const DEFAULT_LIVENESS_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// The extension of run state files.
+const RUN_STATE_EXTENSION: &str = "run";
+
+/// Gets the labels to apply to a container started for the given engine and
+/// run.
+pub fn container_labels(engine: &str, run_id: &str) -> [(&'static str, String); 2] {
+    [
+        (ENGINE_LABEL, engine.to_string()),
+        (RUN_ID_LABEL, run_id.to_string()),
+    ]
+}
+
+/// Gets the command line arguments that apply the labels for the given engine
+/// and run to a container (e.g. `--label org.wdl.engine=wdl`).
+pub fn container_label_args(engine: &str, run_id: &str) -> Vec<String> {
+    container_labels(engine, run_id)
+        .into_iter()
+        .flat_map(|(k, v)| ["--label".to_string(), format!("{k}={v}")])
+        .collect()
+}
+
+/// Gets the path of the state file of the given run.
+pub fn run_state_path(dir: &Path, run_id: &str) -> PathBuf {
+    dir.join(path_component(run_id).as_ref())
+        .with_extension(RUN_STATE_EXTENSION)
+}
+
+/// Records the current time as the liveness timestamp of the given run.
+///
+/// An active run is expected to call this more frequently than the liveness
+/// timeout of any engine that may clean up its containers.
+pub fn record_liveness(dir: &Path, run_id: &str) -> Result<()> {
+    let path = run_state_path(dir, run_id);
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    fs::create_dir_all(dir)
+        .with_context(|| format!("failed to create directory `{path}`", path = dir.display()))?;
+    fs::write(&path, secs.to_string()).with_context(|| {
+        format!(
+            "failed to write run state file `{path}`",
+            path = path.display()
+        )
+    })
+}
+
+/// Reads the liveness timestamp of the given run.
+///
+/// Returns `None` if the run has no state file or the state file is invalid.
+pub fn read_liveness(dir: &Path, run_id: &str) -> Option<SystemTime> {
+    let secs = fs::read_to_string(run_state_path(dir, run_id))
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+    UNIX_EPOCH.checked_add(Duration::from_secs(secs))
+}
+
+/// Represents a container known to a container runtime.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Container {
+    /// The identifier of the container.
+    pub id: String,
+    /// The labels of the container.
+    pub labels: HashMap<String, String>,
+}
+
+impl Container {
+    /// Gets the identifier of the run the container belongs to.
+    pub fn run_id(&self) -> Option<&str> {
+        self.labels.get(RUN_ID_LABEL).map(String::as_str)
+    }
+}
+
+/// Represents a container runtime that can list and remove containers.
+///
+/// Implementations are expected to wrap the runtime's command line interface.
+pub trait ContainerRuntime {
+    /// Lists the containers (running or stopped) that carry the given label.
+    fn list(&self, label: &str) -> Result<Vec<Container>>;
+
+    /// Stops and removes the container with the given identifier.
+    fn remove(&self, id: &str) -> Result<()>;
+}
+
+/// Represents a filter of the containers that are considered for cleanup.
+#[derive(Debug, Clone)]
+pub struct OrphanFilter {
+    /// The identifier of the engine whose containers are considered.
+    engine: String,
+    /// The directory containing the state files of runs.
+    run_state_dir: PathBuf,
+    /// The identifiers of the runs whose containers are considered.
+    ///
+    /// If `None`, the containers of every run are considered.
+    runs: Option<Vec<String>>,
+    /// The duration after which a run that has not recorded its liveness is
+    /// considered to have crashed.
+    liveness_timeout: Duration,
+}
+
+impl OrphanFilter {
+    /// Constructs a new filter for the containers of the given engine whose
+    /// runs record their liveness in the given directory.
+    pub fn new(engine: impl Into<String>, run_state_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            engine: engine.into(),
+            run_state_dir: run_state_dir.into(),
+            runs: None,
+            liveness_timeout: DEFAULT_LIVENESS_TIMEOUT,
+        }
+    }
+
+    /// Restricts the filter to the containers of the given runs.
+    ///
+    /// Defaults to considering the containers of every run.
+    pub fn with_runs(mut self, runs: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.runs = Some(runs.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Sets the duration after which a run that has not recorded its liveness
+    /// is considered to have crashed.
+    ///
+    /// Defaults to [`DEFAULT_LIVENESS_TIMEOUT`].
+    pub fn with_liveness_timeout(mut self, timeout: Duration) -> Self {
+        self.liveness_timeout = timeout;
+        self
+    }
+
+    /// Gets the identifier of the engine whose containers are considered.
+    pub fn engine(&self) -> &str {
+        &self.engine
+    }
+
+    /// Gets the directory containing the state files of runs.
+    pub fn run_state_dir(&self) -> &Path {
+        &self.run_state_dir
+    }
+
+    /// Gets the duration after which a run that has not recorded its liveness
+    /// is considered to have crashed.
+    pub fn liveness_timeout(&self) -> Duration {
+        self.liveness_timeout
+    }
+
+    /// Determines if the given container is orphaned.
+    ///
+    /// A container is orphaned if it was started by the filter's engine and its
+    /// run has no liveness timestamp or last recorded its liveness more than
+    /// the liveness timeout before `now`. Containers without a run identifier
+    /// cannot belong to an active run and are always orphaned.
+    ///
+    /// The `liveness` callback returns the liveness timestamp of a run.
+    pub fn is_orphan(
+        &self,
+        container: &Container,
+        now: SystemTime,
+        liveness: impl Fn(&str) -> Option<SystemTime>,
+    ) -> bool {
+        if container.labels.get(ENGINE_LABEL) != Some(&self.engine) {
+            return false;
+        }
+
+        let Some(run_id) = container.run_id() else {
+            return true;
+        };
+
+        if let Some(runs) = &self.runs {
+            if !runs.iter().any(|r| r == run_id) {
+                return false;
+            }
+        }
+
+        match liveness(run_id) {
+            // A timestamp in the future (e.g. due to clock skew) is considered live
+            Some(timestamp) => now
+                .duration_since(timestamp)
+                .is_ok_and(|elapsed| elapsed > self.liveness_timeout),
+            None => true,
+        }
+    }
+}
+
+/// Removes the containers orphaned by previous runs.
+///
+/// Failures to remove individual containers are returned in the result so
+/// that the remaining containers are still removed.
+pub(crate) fn cleanup_orphans(
+    runtime: &dyn ContainerRuntime,
+    filter: &OrphanFilter,
+    now: SystemTime,
+    liveness: impl Fn(&str) -> Option<SystemTime>,
+) -> Result<CleanupReport> {
+    let mut report = CleanupReport::default();
+    for container in runtime
+        .list(ENGINE_LABEL)
+        .context("failed to list containers")?
+    {
+        if !filter.is_orphan(&container, now, &liveness) {
+            continue;
+        }
+
+        match runtime.remove(&container.id) {
+            Ok(()) => report.removed.push(container),
+            Err(e) => report.failed.push((container, e)),
+        }
+    }
+
+    Ok(report)
+}
+
+/// Represents the result of cleaning up orphaned containers.
+#[derive(Debug, Default)]
+pub struct CleanupReport {
+    /// The orphaned containers that were removed.
+    pub removed: Vec<Container>,
+    /// The orphaned containers that failed to be removed.
+    pub failed: Vec<(Container, anyhow::Error)>,
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::RefCell;
+
+    use anyhow::bail;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    use super::*;
+
+    /// A container runtime that records the containers it removes.
+    #[derive(Default)]
+    struct MockRuntime {
+        /// The containers known to the runtime.
+        containers: Vec<Container>,
+        /// The identifiers of the removed containers.
+        removed: RefCell<Vec<String>>,
+    }
+
+    impl MockRuntime {
+        /// Adds a container with the given labels to the runtime.
+        fn with(mut self, id: &str, labels: &[(&str, &str)]) -> Self {
+            self.containers.push(Container {
+                id: id.to_string(),
+                labels: labels
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect(),
+            });
+            self
+        }
+    }
+
+    impl ContainerRuntime for MockRuntime {
+        fn list(&self, label: &str) -> Result<Vec<Container>> {
+            Ok(self
+                .containers
+                .iter()
+                .filter(|c| c.labels.contains_key(label))
+                .cloned()
+                .collect())
+        }
+
+        fn remove(&self, id: &str) -> Result<()> {
+            if id == "busy" {
+                bail!("container `{id}` is in use");
+            }
+
+            self.removed.borrow_mut().push(id.to_string());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn it_constructs_labels() {
+        assert_eq!(
+            container_labels("wdl", "run-1"),
+            [
+                (ENGINE_LABEL, "wdl".to_string()),
+                (RUN_ID_LABEL, "run-1".to_string())
+            ]
+        );
+        assert_eq!(
+            container_label_args("wdl", "run-1"),
+            [
+                "--label",
+                "org.wdl.engine=wdl",
+                "--label",
+                "org.wdl.run-id=run-1"
+            ]
+        );
+    }
+
+    #[test]
+    fn it_reaps_only_orphans() {
+        let now = UNIX_EPOCH + Duration::from_secs(10_000);
+        let liveness = |run: &str| match run {
+            "live" => Some(now - Duration::from_secs(10)),
+            "stale" => Some(now - DEFAULT_LIVENESS_TIMEOUT - Duration::from_secs(1)),
+            "future" => Some(now + Duration::from_secs(60)),
+            _ => None,
+        };
+
+        let runtime = MockRuntime::default()
+            .with("a", &[(ENGINE_LABEL, "wdl"), (RUN_ID_LABEL, "live")])
+            .with("b", &[(ENGINE_LABEL, "wdl"), (RUN_ID_LABEL, "stale")])
+            .with("c", &[(ENGINE_LABEL, "wdl"), (RUN_ID_LABEL, "crashed")])
+            .with("d", &[(ENGINE_LABEL, "wdl"), (RUN_ID_LABEL, "future")])
+            .with("e", &[(ENGINE_LABEL, "wdl")])
+            .with("f", &[(ENGINE_LABEL, "other"), (RUN_ID_LABEL, "crashed")])
+            .with("g", &[(RUN_ID_LABEL, "crashed")])
+            .with("busy", &[(ENGINE_LABEL, "wdl"), (RUN_ID_LABEL, "crashed")]);
+
+        let filter = OrphanFilter::new("wdl", "unused");
+        let report = cleanup_orphans(&runtime, &filter, now, liveness).unwrap();
+        let ids = |c: &[Container]| c.iter().map(|c| c.id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(&report.removed), ["b", "c", "e"]);
+        assert_eq!(*runtime.removed.borrow(), ["b", "c", "e"]);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].0.id, "busy");
+
+        // Restricting the filter to specific runs leaves other orphans alone
+        let runtime = MockRuntime {
+            containers: runtime.containers,
+            ..Default::default()
+        };
+        let filter = OrphanFilter::new("wdl", "unused").with_runs(["stale"]);
+        let report = cleanup_orphans(&runtime, &filter, now, liveness).unwrap();
+        assert_eq!(ids(&report.removed), ["b"]);
+        assert!(report.failed.is_empty());
+    }
+
+    #[test]
+    fn it_records_liveness() {
+        let dir = TempDir::new().expect("failed to create temporary directory");
+        let runs = dir.path().join("runs");
+        assert_eq!(read_liveness(&runs, "My Run"), None);
+
+        record_liveness(&runs, "My Run").unwrap();
+        let timestamp = read_liveness(&runs, "My Run").expect("should have liveness");
+        assert!(SystemTime::now().duration_since(timestamp).unwrap() < Duration::from_secs(60));
+
+        let container = Container {
+            id: "a".to_string(),
+            labels: container_labels("wdl", "My Run")
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect(),
+        };
+        let filter = OrphanFilter::new("wdl", &runs);
+        let liveness = |run: &str| read_liveness(filter.run_state_dir(), run);
+        assert!(!filter.is_orphan(&container, SystemTime::now(), liveness));
+        assert!(filter.is_orphan(
+            &container,
+            SystemTime::now() + DEFAULT_LIVENESS_TIMEOUT * 2,
+            liveness
+        ));
+    }
+
+    #[test]
+    fn it_matches_prior_state_of_normalized_runs() {
+        let dir = TempDir::new().expect("failed to create temporary directory");
+        let runs = dir.path().join("runs");
+
+        // Runs that differ only by case or that are named after a reserved device
+        // have distinct state files
+        let paths: Vec<_> = ["run", "Run", "RUN", "con"]
+            .into_iter()
+            .map(|run| run_state_path(&runs, run))
+            .collect();
+        for (i, path) in paths.iter().enumerate() {
+            let name = path.file_name().unwrap().to_string_lossy().to_lowercase();
+            assert_ne!(name, "con.run");
+            for other in &paths[i + 1..] {
+                assert_ne!(
+                    name,
+                    other.file_name().unwrap().to_string_lossy().to_lowercase()
+                );
+            }
+        }
+
+        // State recorded by a previous run is found again under the same name only
+        record_liveness(&runs, "Run").unwrap();
+        assert!(read_liveness(&runs, "Run").is_some());
+        assert_eq!(read_liveness(&runs, "run"), None);
+        assert_eq!(read_liveness(&runs, "RUN"), None);
+    }
+}
@@ -0,0 +1,498 @@
+//! Implementation of rerun scripts for task executions.
+//!
+//! A rerun script reproduces the process of a task execution outside of the
+//! engine. It is generated from the same [`TaskInvocation`] that the backend
+//! uses to spawn the process, so that the script runs exactly what the engine
+//! ran.
+
+use std::ffi::OsStr;
+use std::ffi::OsString;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use indexmap::IndexMap;
+use tokio::process::Command;
+
+use crate::Secret;
+
+/// The name of the rerun script written to a task execution's directory.
+pub const RERUN_SCRIPT_FILE_NAME: &str = "rerun.sh";
+
+/// Represents when a rerun script is written for a task execution.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RerunScriptPolicy {
+    /// A rerun script is written for every task execution.
+    Always,
+    /// A rerun script is never written.
+    Never,
+    /// A rerun script is written only for task executions that failed.
+    #[default]
+    OnFailure,
+}
+
+impl RerunScriptPolicy {
+    /// Determines if a rerun script should be written for a task execution.
+    pub fn should_write(&self, failed: bool) -> bool {
+        match self {
+            Self::Always => true,
+            Self::Never => false,
+            Self::OnFailure => failed,
+        }
+    }
+}
+
+/// Quotes the given value for use as a word in a shell script.
+///
+/// Values consisting only of characters that have no special meaning to the
+/// shell are returned as-is; otherwise, the value is single quoted.
+pub fn shell_quote(value: &OsStr) -> String {
+    let value = value.to_string_lossy();
+    if !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "_-./:=@%+,".contains(c))
+    {
+        return value.into_owned();
+    }
+
+    format!("'{value}'", value = value.replace('\'', r"'\''"))
+}
+
+/// Represents a mount of a host path into a container.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mount {
+    /// The host path being mounted.
+    host: PathBuf,
+    /// The guest path the host path is mounted to.
+    guest: PathBuf,
+    /// Whether or not the mount is read-only.
+    read_only: bool,
+}
+
+impl Mount {
+    /// Constructs a new mount of the given host path to the given guest path.
+    pub fn new(host: impl Into<PathBuf>, guest: impl Into<PathBuf>, read_only: bool) -> Self {
+        Self {
+            host: host.into(),
+            guest: guest.into(),
+            read_only,
+        }
+    }
+
+    /// Gets the host path being mounted.
+    pub fn host(&self) -> &Path {
+        &self.host
+    }
+
+    /// Gets the guest path the host path is mounted to.
+    pub fn guest(&self) -> &Path {
+        &self.guest
+    }
+
+    /// Gets whether or not the mount is read-only.
+    pub fn read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Gets the argument to a container runtime's `--volume` option for the
+    /// mount.
+    fn volume_arg(&self) -> OsString {
+        let mut arg = OsString::from(&self.host);
+        arg.push(":");
+        arg.push(&self.guest);
+        if self.read_only {
+            arg.push(":ro");
+        }
+
+        arg
+    }
+}
+
+/// Represents the container a task's process is run in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContainerInvocation {
+    /// The container runtime program (e.g. `docker`).
+    program: String,
+    /// The image of the container.
+    image: String,
+    /// The guest path of the working directory.
+    work_dir: PathBuf,
+    /// The guest path of the command file.
+    command: PathBuf,
+    /// The mounts of the container.
+    mounts: Vec<Mount>,
+    /// Additional arguments to the runtime's `run` subcommand (e.g. labels).
+    args: Vec<String>,
+}
+
+impl ContainerInvocation {
+    /// Constructs a new container invocation.
+    ///
+    /// The working directory and command file paths are guest paths.
+    pub fn new(
+        program: impl Into<String>,
+        image: impl Into<String>,
+        work_dir: impl Into<PathBuf>,
+        command: impl Into<PathBuf>,
+    ) -> Self {
+        Self {
+            program: program.into(),
+            image: image.into(),
+            work_dir: work_dir.into(),
+            command: command.into(),
+            mounts: Vec::new(),
+            args: Vec::new(),
+        }
+    }
+
+    /// Adds a mount to the container.
+    pub fn with_mount(mut self, mount: Mount) -> Self {
+        self.mounts.push(mount);
+        self
+    }
+
+    /// Adds additional arguments to the runtime's `run` subcommand.
+    pub fn with_args(mut self, args: impl IntoIterator<Item = String>) -> Self {
+        self.args.extend(args);
+        self
+    }
+
+    /// Gets the image of the container.
+    pub fn image(&self) -> &str {
+        &self.image
+    }
+
+    /// Gets the mounts of the container.
+    pub fn mounts(&self) -> &[Mount] {
+        &self.mounts
+    }
+}
+
+/// Represents how the process of a task execution is invoked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskInvocation {
+    /// The host path of the working directory.
+    work_dir: PathBuf,
+    /// The host path of the command file.
+    command: PathBuf,
+    /// The environment variables of the process.
+    env: IndexMap<String, String>,
+    /// The environment variables of the process that hold secrets.
+    secret_env: IndexMap<String, Secret>,
+    /// The container the process runs in.
+    ///
+    /// A value of `None` indicates the process runs on the host.
+    container: Option<ContainerInvocation>,
+}
+
+impl TaskInvocation {
+    /// Constructs a new invocation of the given command file on the host.
+    pub fn new(work_dir: impl Into<PathBuf>, command: impl Into<PathBuf>) -> Self {
+        Self {
+            work_dir: work_dir.into(),
+            command: command.into(),
+            env: Default::default(),
+            secret_env: Default::default(),
+            container: None,
+        }
+    }
+
+    /// Sets an environment variable of the process.
+    pub fn with_env(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.insert(name.into(), value.into());
+        self
+    }
+
+    /// Sets an environment variable of the process that holds a secret.
+    ///
+    /// The variable is set when the process is spawned, but its value is not
+    /// written to the rerun script; the script instead requires the variable
+    /// to be set in its environment.
+    pub fn with_secret_env(mut self, name: impl Into<String>, secret: Secret) -> Self {
+        self.secret_env.insert(name.into(), secret);
+        self
+    }
+
+    /// Sets the container the process runs in.
+    pub fn with_container(mut self, container: ContainerInvocation) -> Self {
+        self.container = Some(container);
+        self
+    }
+
+    /// Gets the host path of the working directory.
+    pub fn work_dir(&self) -> &Path {
+        &self.work_dir
+    }
+
+    /// Gets the host path of the command file.
+    pub fn command(&self) -> &Path {
+        &self.command
+    }
+
+    /// Gets the environment variables of the process.
+    pub fn env(&self) -> &IndexMap<String, String> {
+        &self.env
+    }
+
+    /// Gets the environment variables of the process that hold secrets.
+    pub fn secret_env(&self) -> &IndexMap<String, Secret> {
+        &self.secret_env
+    }
+
+    /// Gets the container the process runs in.
+    pub fn container(&self) -> Option<&ContainerInvocation> {
+        self.container.as_ref()
+    }
+
+    /// Gets the program and arguments of the process.
+    ///
+    /// For a containerized process, this is the invocation of the container
+    /// runtime; environment variables are passed through to the container by
+    /// name.
+    pub fn args(&self) -> Vec<OsString> {
+        match &self.container {
+            Some(container) => {
+                let mut args: Vec<OsString> = vec![
+                    container.program.as_str().into(),
+                    "run".into(),
+                    "--rm".into(),
+                ];
+
+                for mount in &container.mounts {
+                    args.push("--volume".into());
+                    args.push(mount.volume_arg());
+                }
+
+                for name in self.env.keys().chain(self.secret_env.keys()) {
+                    args.push("--env".into());
+                    args.push(name.into());
+                }
+
+                args.push("--workdir".into());
+                args.push(container.work_dir.as_os_str().into());
+                args.extend(container.args.iter().map(Into::into));
+                args.push(container.image.as_str().into());
+                args.push("bash".into());
+                args.push("-C".into());
+                args.push(container.command.as_os_str().into());
+                args
+            }
+            None => vec!["bash".into(), "-C".into(), self.command.as_os_str().into()],
+        }
+    }
+
+    /// Creates the command that spawns the process.
+    ///
+    /// The standard streams of the command are not configured.
+    pub fn to_command(&self) -> Command {
+        let args = self.args();
+        let mut command = Command::new(&args[0]);
+        command
+            .args(&args[1..])
+            .envs(&self.env)
+            .envs(self.secret_env.iter().map(|(n, s)| (n, s.value())));
+
+        if self.container.is_none() {
+            command.current_dir(&self.work_dir);
+        }
+
+        command
+    }
+
+    /// Generates a script that reproduces the process of the task execution.
+    ///
+    /// The given input paths are listed in the script for reference.
+    ///
+    /// Unlike the engine, the script does not redirect the process' standard
+    /// streams and does not recreate the working directory.
+    pub fn rerun_script(&self, inputs: &[String]) -> String {
+        let mut script = String::new();
+        script.push_str("#!/usr/bin/env bash\n");
+        script.push_str("#\n# Reproduces the execution of a task outside of the engine.\n#\n");
+        writeln!(
+            script,
+            "# Command script: {command}",
+            command = self.command.display()
+        )
+        .unwrap();
+
+        if let Some(container) = &self.container {
+            writeln!(
+                script,
+                "# Container image: {image}",
+                image = container.image
+            )
+            .unwrap();
+        }
+
+        if !self.secret_env.is_empty() {
+            script.push_str("#\n# Secrets (must be set in the environment):\n");
+            for name in self.secret_env.keys() {
+                writeln!(script, "#   {name}").unwrap();
+            }
+        }
+
+        if !inputs.is_empty() {
+            script.push_str("#\n# Localized inputs:\n");
+            for input in inputs {
+                writeln!(script, "#   {input}").unwrap();
+            }
+        }
+
+        script.push_str("\nset -euo pipefail\n\n");
+
+        for (name, value) in &self.env {
+            writeln!(
+                script,
+                "export {name}={value}",
+                value = shell_quote(value.as_ref())
+            )
+            .unwrap();
+        }
+
+        for name in self.secret_env.keys() {
+            writeln!(script, ": \"${{{name}:?the secret {name} must be set}}\"").unwrap();
+        }
+
+        if self.container.is_none() {
+            writeln!(
+                script,
+                "cd {dir}",
+                dir = shell_quote(self.work_dir.as_os_str())
+            )
+            .unwrap();
+        }
+
+        let args: Vec<_> = self.args().iter().map(|a| shell_quote(a)).collect();
+        writeln!(script, "exec {args}", args = args.join(" ")).unwrap();
+        script
+    }
+
+    /// Writes a rerun script (see [`rerun_script`](Self::rerun_script)) to the
+    /// given path.
+    ///
+    /// On Unix, the script is made executable.
+    pub fn write_rerun_script(&self, path: &Path, inputs: &[String]) -> Result<()> {
+        fs::write(path, self.rerun_script(inputs)).with_context(|| {
+            format!(
+                "failed to write rerun script `{path}`",
+                path = path.display()
+            )
+        })?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(path, fs::Permissions::from_mode(0o755)).with_context(|| {
+                format!(
+                    "failed to set permissions of rerun script `{path}`",
+                    path = path.display()
+                )
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn it_quotes_shell_words() {
+        assert_eq!(shell_quote("/tmp/a-b_c.txt".as_ref()), "/tmp/a-b_c.txt");
+        assert_eq!(shell_quote("".as_ref()), "''");
+        assert_eq!(shell_quote("a b".as_ref()), "'a b'");
+        assert_eq!(shell_quote("it's $HOME".as_ref()), r"'it'\''s $HOME'");
+    }
+
+    #[test]
+    fn it_generates_containerized_rerun_scripts() {
+        let invocation = TaskInvocation::new("/runs/my task/work", "/runs/my task/command")
+            .with_env("FOO", "bar baz")
+            .with_env("QUOTED", "it's")
+            .with_container(
+                ContainerInvocation::new(
+                    "docker",
+                    "ubuntu@sha256:abc",
+                    "/mnt/task/work",
+                    "/mnt/task/command",
+                )
+                .with_mount(Mount::new("/runs/my task", "/mnt/task", false))
+                .with_mount(Mount::new("/data/in.txt", "/mnt/inputs/in.txt", true))
+                .with_args(["--label".to_string(), "org.wdl.run-id=1".to_string()]),
+            );
+
+        let script = invocation.rerun_script(&["/data/in.txt".to_string()]);
+        assert_eq!(
+            script,
+            r#"#!/usr/bin/env bash
+#
+# Reproduces the execution of a task outside of the engine.
+#
+# Command script: /runs/my task/command
+# Container image: ubuntu@sha256:abc
+#
+# Localized inputs:
+#   /data/in.txt
+
+set -euo pipefail
+
+export FOO='bar baz'
+export QUOTED='it'\''s'
+exec docker run --rm --volume '/runs/my task:/mnt/task' --volume /data/in.txt:/mnt/inputs/in.txt:ro --env FOO --env QUOTED --workdir /mnt/task/work --label org.wdl.run-id=1 ubuntu@sha256:abc bash -C /mnt/task/command
+"#
+        );
+    }
+
+    #[test]
+    fn it_generates_host_rerun_scripts() {
+        let invocation = TaskInvocation::new("/runs/task/work", "/runs/task/command");
+        let script = invocation.rerun_script(&[]);
+        assert!(script.contains("\ncd /runs/task/work\nexec bash -C /runs/task/command\n"));
+        assert!(!script.contains("Localized inputs"));
+    }
+
+    #[test]
+    fn it_omits_secret_values_from_rerun_scripts() {
+        let invocation = TaskInvocation::new("/runs/task/work", "/runs/task/command")
+            .with_secret_env("WDL_SECRET_TOKEN", Secret::new("hunter2", "abc123"));
+        let script = invocation.rerun_script(&[]);
+        assert!(!script.contains("hunter2"));
+        assert!(
+            script.contains("# Secrets (must be set in the environment):\n#   WDL_SECRET_TOKEN\n")
+        );
+        assert!(
+            script.contains(": \"${WDL_SECRET_TOKEN:?the secret WDL_SECRET_TOKEN must be set}\"\n")
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn it_reruns_failing_tasks() {
+        let dir = tempfile::TempDir::new().expect("failed to create temporary directory");
+        let work_dir = dir.path().join("work");
+        let command = dir.path().join("command");
+        fs::create_dir_all(&work_dir).unwrap();
+        fs::write(&command, "echo \"$GREETING\" > out.txt\nexit 3\n").unwrap();
+
+        let invocation = TaskInvocation::new(&work_dir, &command).with_env("GREETING", "hi there");
+        let script = dir.path().join(RERUN_SCRIPT_FILE_NAME);
+        invocation.write_rerun_script(&script, &[]).unwrap();
+
+        // The script reproduces the failure
+        let status = Command::new(&script).status().await.unwrap();
+        assert_eq!(status.code(), Some(3));
+        assert_eq!(
+            fs::read_to_string(work_dir.join("out.txt")).unwrap(),
+            "hi there\n"
+        );
+    }
+}
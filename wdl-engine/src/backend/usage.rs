@@ -0,0 +1,366 @@
+//! Implementation of resource usage collection for running tasks.
+
+use std::fmt;
+use std::time::Duration;
+use std::time::Instant;
+
+/// The default interval at which running tasks are sampled for resource usage.
+pub const DEFAULT_USAGE_SAMPLE_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The default fraction of a requested resource below which a task's usage is
+/// considered over-provisioned.
+pub const DEFAULT_OVER_PROVISIONED_FRACTION: f64 = 0.5;
+
+/// Represents the resources used by a task's execution.
+///
+/// Usage is collected on a best-effort basis: on Linux, the processes of a
+/// running task are periodically sampled from `/proc`, so short-lived spikes
+/// in memory usage between samples are not observed. On other platforms, only
+/// the elapsed time is collected.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResourceUsage {
+    /// The elapsed (wall clock) time of the execution.
+    elapsed: Duration,
+    /// The maximum resident set size, in bytes, of the task's processes.
+    max_rss: Option<u64>,
+    /// The user CPU time consumed by the task's processes.
+    user_cpu: Option<Duration>,
+    /// The system CPU time consumed by the task's processes.
+    system_cpu: Option<Duration>,
+    /// The number of bytes read from storage by the task's processes.
+    read_bytes: Option<u64>,
+    /// The number of bytes written to storage by the task's processes.
+    written_bytes: Option<u64>,
+}
+
+impl ResourceUsage {
+    /// Gets the elapsed (wall clock) time of the execution.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Gets the maximum resident set size, in bytes, of the task's processes.
+    ///
+    /// Returns `None` if memory usage was not collected.
+    pub fn max_rss(&self) -> Option<u64> {
+        self.max_rss
+    }
+
+    /// Gets the user CPU time consumed by the task's processes.
+    ///
+    /// Returns `None` if CPU usage was not collected.
+    pub fn user_cpu(&self) -> Option<Duration> {
+        self.user_cpu
+    }
+
+    /// Gets the system CPU time consumed by the task's processes.
+    ///
+    /// Returns `None` if CPU usage was not collected.
+    pub fn system_cpu(&self) -> Option<Duration> {
+        self.system_cpu
+    }
+
+    /// Gets the total (user and system) CPU time consumed by the task's
+    /// processes.
+    ///
+    /// Returns `None` if CPU usage was not collected.
+    pub fn cpu(&self) -> Option<Duration> {
+        Some(self.user_cpu? + self.system_cpu?)
+    }
+
+    /// Gets the number of bytes read from storage by the task's processes.
+    ///
+    /// Returns `None` if IO was not collected.
+    pub fn read_bytes(&self) -> Option<u64> {
+        self.read_bytes
+    }
+
+    /// Gets the number of bytes written to storage by the task's processes.
+    ///
+    /// Returns `None` if IO was not collected.
+    pub fn written_bytes(&self) -> Option<u64> {
+        self.written_bytes
+    }
+
+    /// Compares the usage against the requested number of CPUs and bytes of
+    /// memory.
+    ///
+    /// A resource is over-provisioned when its usage is below the given
+    /// fraction of the request; CPU usage is the average number of CPUs used
+    /// over the elapsed time.
+    ///
+    /// Resources whose usage was not collected are not reported.
+    pub fn over_provisioned(&self, cpu: f64, memory: u64, fraction: f64) -> Vec<OverProvisioned> {
+        let mut resources = Vec::new();
+
+        if let Some(used) = self.cpu() {
+            if !self.elapsed.is_zero() {
+                let used = used.as_secs_f64() / self.elapsed.as_secs_f64();
+                if used < cpu * fraction {
+                    resources.push(OverProvisioned {
+                        resource: ProvisionedResource::Cpu,
+                        requested: cpu,
+                        used,
+                    });
+                }
+            }
+        }
+
+        if let Some(used) = self.max_rss {
+            if (used as f64) < memory as f64 * fraction {
+                resources.push(OverProvisioned {
+                    resource: ProvisionedResource::Memory,
+                    requested: memory as f64,
+                    used: used as f64,
+                });
+            }
+        }
+
+        resources
+    }
+}
+
+/// Represents a resource that is requested by a task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProvisionedResource {
+    /// The resource is the number of CPUs.
+    Cpu,
+    /// The resource is the amount of memory, in bytes.
+    Memory,
+}
+
+impl fmt::Display for ProvisionedResource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Cpu => write!(f, "cpu"),
+            Self::Memory => write!(f, "memory"),
+        }
+    }
+}
+
+/// Represents a resource whose usage was below a fraction of its request.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OverProvisioned {
+    /// The over-provisioned resource.
+    pub resource: ProvisionedResource,
+    /// The requested amount of the resource.
+    pub requested: f64,
+    /// The used amount of the resource.
+    pub used: f64,
+}
+
+/// Samples the resource usage of a running task.
+#[derive(Debug)]
+pub(crate) struct UsageSampler {
+    /// The identifier of the task's root process.
+    pid: u32,
+    /// The time at which the task started.
+    started: Instant,
+    /// The usage collected so far.
+    usage: ResourceUsage,
+}
+
+impl UsageSampler {
+    /// Constructs a new usage sampler for the given process.
+    pub fn new(pid: u32) -> Self {
+        let mut sampler = Self {
+            pid,
+            started: Instant::now(),
+            usage: Default::default(),
+        };
+        sampler.sample();
+        sampler
+    }
+
+    /// Samples the task's resource usage.
+    ///
+    /// Memory usage is the maximum observed across samples; CPU time and IO
+    /// are cumulative, so the latest observation is kept.
+    pub fn sample(&mut self) {
+        let Some(sample) = sample(self.pid) else {
+            return;
+        };
+
+        let usage = &mut self.usage;
+        usage.max_rss = Some(usage.max_rss.unwrap_or(0).max(sample.rss));
+        usage.user_cpu = Some(usage.user_cpu.unwrap_or_default().max(sample.user_cpu));
+        usage.system_cpu = Some(usage.system_cpu.unwrap_or_default().max(sample.system_cpu));
+
+        if let Some((read, written)) = sample.io {
+            usage.read_bytes = Some(usage.read_bytes.unwrap_or(0).max(read));
+            usage.written_bytes = Some(usage.written_bytes.unwrap_or(0).max(written));
+        }
+    }
+
+    /// Finishes sampling, returning the collected usage.
+    pub fn finish(mut self) -> ResourceUsage {
+        self.usage.elapsed = self.started.elapsed();
+        self.usage
+    }
+}
+
+/// Represents a sample of a running task's resource usage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+struct Sample {
+    /// The resident set size, in bytes, of the task's processes.
+    rss: u64,
+    /// The user CPU time consumed by the task's processes.
+    user_cpu: Duration,
+    /// The system CPU time consumed by the task's processes.
+    system_cpu: Duration,
+    /// The bytes read from and written to storage by the task's processes.
+    ///
+    /// This is `None` if IO could not be sampled.
+    io: Option<(u64, u64)>,
+}
+
+/// Samples the resource usage of a process and its descendants.
+///
+/// Returns `None` if the process no longer exists.
+#[cfg(target_os = "linux")]
+fn sample(pid: u32) -> Option<Sample> {
+    use std::fs;
+
+    use super::monitor::process_tree;
+
+    /// The number of clock ticks per second reported in `/proc/<pid>/stat`.
+    ///
+    /// This is `USER_HZ`, which is 100 on all supported architectures.
+    const TICKS_PER_SECOND: u64 = 100;
+
+    /// Gets the user and system CPU time, in clock ticks, of a process and
+    /// its waited-for children.
+    fn ticks(pid: u32) -> Option<(u64, u64)> {
+        let stat = fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+
+        // See `cpu_ticks` in the stall monitor for the layout of the fields
+        let fields: Vec<_> = stat[stat.rfind(')')? + 1..].split_whitespace().collect();
+        let field = |i: usize| fields.get(i)?.parse::<u64>().ok();
+        Some((field(11)? + field(13)?, field(12)? + field(14)?))
+    }
+
+    /// Gets the resident set size, in bytes, of a process.
+    ///
+    /// The peak resident set size (`VmHWM`) is also returned so that peaks of
+    /// a process between samples are observed.
+    fn rss(pid: u32) -> Option<(u64, u64)> {
+        let status = fs::read_to_string(format!("/proc/{pid}/status")).ok()?;
+        let kib = |key: &str| -> Option<u64> {
+            let line = status.lines().find(|l| l.starts_with(key))?;
+            line[key.len()..]
+                .trim()
+                .trim_end_matches("kB")
+                .trim()
+                .parse()
+                .ok()
+        };
+
+        Some((kib("VmRSS:")? * 1024, kib("VmHWM:")? * 1024))
+    }
+
+    /// Gets the bytes read from and written to storage by a process and its
+    /// waited-for children.
+    fn io(pid: u32) -> Option<(u64, u64)> {
+        let io = fs::read_to_string(format!("/proc/{pid}/io")).ok()?;
+        let field = |key: &str| -> Option<u64> {
+            io.lines()
+                .find_map(|l| l.strip_prefix(key))?
+                .trim()
+                .parse()
+                .ok()
+        };
+
+        Some((field("read_bytes:")?, field("write_bytes:")?))
+    }
+
+    let tree = process_tree(pid);
+    if tree.is_empty() {
+        return None;
+    }
+
+    let mut rss_total = 0;
+    let mut peak = 0;
+    let mut user = 0;
+    let mut system = 0;
+    let mut io_total = Some((0, 0));
+    for pid in tree {
+        // Descendants may exit at any time, so skip any that have gone away
+        let Some((u, s)) = ticks(pid) else {
+            continue;
+        };
+
+        user += u;
+        system += s;
+
+        if let Some((current, hwm)) = rss(pid) {
+            rss_total += current;
+            peak = peak.max(hwm);
+        }
+
+        io_total = match (io_total, io(pid)) {
+            (Some((r, w)), Some((pr, pw))) => Some((r + pr, w + pw)),
+            _ => None,
+        };
+    }
+
+    let to_duration = |ticks: u64| Duration::from_millis(ticks * 1000 / TICKS_PER_SECOND);
+    Some(Sample {
+        rss: rss_total.max(peak),
+        user_cpu: to_duration(user),
+        system_cpu: to_duration(system),
+        io: io_total,
+    })
+}
+
+/// Samples the resource usage of a process and its descendants.
+///
+/// Resource usage is not sampled on this platform, so this always returns
+/// `None`.
+#[cfg(not(target_os = "linux"))]
+fn sample(_: u32) -> Option<Sample> {
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_reports_over_provisioned_resources() {
+        let usage = ResourceUsage {
+            elapsed: Duration::from_secs(10),
+            max_rss: Some(512 * 1024 * 1024),
+            user_cpu: Some(Duration::from_secs(8)),
+            system_cpu: Some(Duration::from_secs(2)),
+            read_bytes: None,
+            written_bytes: None,
+        };
+
+        // One CPU was used on average and 512 MiB of memory
+        let gib = 1024 * 1024 * 1024;
+        assert!(usage.over_provisioned(1.0, gib, 0.5).is_empty());
+        assert_eq!(
+            usage.over_provisioned(4.0, 8 * gib, 0.5),
+            [
+                OverProvisioned {
+                    resource: ProvisionedResource::Cpu,
+                    requested: 4.0,
+                    used: 1.0,
+                },
+                OverProvisioned {
+                    resource: ProvisionedResource::Memory,
+                    requested: (8 * gib) as f64,
+                    used: (512 * 1024 * 1024) as f64,
+                }
+            ]
+        );
+
+        // Usage that was not collected is not reported
+        assert!(
+            ResourceUsage::default()
+                .over_provisioned(4.0, 8 * gib, 0.5)
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,306 @@
+//! Implementation of the engine's conformance mode.
+//!
+//! Every engine extension that is not part of the WDL specification is
+//! identified by a [`Capability`]. Extension points check whether they are
+//! allowed through [`Engine::allows`](crate::Engine::allows) rather than
+//! through their individual settings, so that conformance mode can disable
+//! every extension in one place.
+//!
+//! In conformance mode, the engine refuses to evaluate a task if its
+//! configuration enables any extension or if the task relies on one (e.g. an
+//! engine-specific hint the engine would otherwise honor); the error lists
+//! every such extension so that the configuration and the task can be
+//! corrected at once.
+
+use std::fmt;
+
+/// Represents an engine extension that is not part of the WDL specification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Capability {
+    /// Floats with a zero fractional part are accepted where an `Int` is
+    /// expected.
+    ///
+    /// See [`Engine::with_lenient_float_to_int`](crate::Engine::with_lenient_float_to_int).
+    LenientFloatToInt,
+    /// The values of task inputs marked as secrets are sourced from a secret
+    /// provider.
+    ///
+    /// A task relies on this extension if it marks an input with the
+    /// engine-specific [`SECRET_HINT`](crate::SECRET_HINT).
+    ///
+    /// See [`Engine::with_secret_provider`](crate::Engine::with_secret_provider).
+    SecretInputs,
+}
+
+impl Capability {
+    /// Every capability known to the engine.
+    pub const ALL: &[Self] = &[Self::LenientFloatToInt, Self::SecretInputs];
+
+    /// Gets the identifier of the capability.
+    pub fn id(&self) -> &'static str {
+        match self {
+            Self::LenientFloatToInt => "lenient-float-to-int",
+            Self::SecretInputs => "secret-inputs",
+        }
+    }
+
+    /// Gets a description of the capability.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::LenientFloatToInt => {
+                "floats with a zero fractional part are accepted where an `Int` is expected"
+            }
+            Self::SecretInputs => {
+                "the values of task inputs marked as secrets are sourced from a secret provider"
+            }
+        }
+    }
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{id}", id = self.id())
+    }
+}
+
+/// Represents a report of the non-portable extensions relied upon by an
+/// engine configuration.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConformanceReport {
+    /// The extensions that are relied upon, in order of capability.
+    capabilities: Vec<Capability>,
+}
+
+impl ConformanceReport {
+    /// Constructs a new report of the given capabilities.
+    pub(crate) fn new(capabilities: impl IntoIterator<Item = Capability>) -> Self {
+        let mut capabilities: Vec<_> = capabilities.into_iter().collect();
+        capabilities.sort();
+        capabilities.dedup();
+        Self { capabilities }
+    }
+
+    /// Determines if no extensions are relied upon.
+    pub fn is_empty(&self) -> bool {
+        self.capabilities.is_empty()
+    }
+
+    /// Gets the extensions that are relied upon.
+    pub fn capabilities(&self) -> &[Capability] {
+        &self.capabilities
+    }
+}
+
+impl fmt::Display for ConformanceReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, capability) in self.capabilities.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+
+            write!(
+                f,
+                "`{id}`: {description}",
+                id = capability.id(),
+                description = capability.description()
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::Engine;
+    use crate::EvaluationError;
+    use crate::Secret;
+    use crate::SecretProvider;
+    use crate::TaskInputs;
+    use crate::local::LocalTaskExecutionBackend;
+    use crate::testing::analyze;
+    use crate::v1::TaskEvaluator;
+
+    /// A secret provider that returns the same secret for every key.
+    struct TestProvider;
+
+    impl SecretProvider for TestProvider {
+        fn secret(&self, key: &str) -> anyhow::Result<Secret> {
+            Ok(Secret::new("secret", format!("fingerprint-of-{key}")))
+        }
+    }
+
+    #[test]
+    fn it_reports_enabled_extensions() {
+        let engine = Engine::new(LocalTaskExecutionBackend::new());
+        assert!(engine.conformance_report().is_empty());
+        assert!(!engine.allows(Capability::LenientFloatToInt));
+
+        let engine = engine.with_lenient_float_to_int(true);
+        assert_eq!(
+            engine.conformance_report().capabilities(),
+            [Capability::LenientFloatToInt]
+        );
+        assert!(engine.allows(Capability::LenientFloatToInt));
+
+        // Conformance mode disables every extension but still reports them
+        let engine = engine.with_conformance_mode(true);
+        assert_eq!(
+            engine.conformance_report().capabilities(),
+            [Capability::LenientFloatToInt]
+        );
+        assert!(!engine.allows(Capability::LenientFloatToInt));
+    }
+
+    #[tokio::test]
+    async fn it_rejects_extensions_in_conformance_mode() {
+        let dir = TempDir::new().expect("failed to create temporary directory");
+        let document = analyze(
+            &dir,
+            r#"version 1.1
+
+task test {
+    input {
+        Int n
+    }
+
+    command <<<>>>
+
+    output {
+        Int out = n + 1
+    }
+}
+"#,
+        )
+        .await;
+
+        let task = document.task_by_name("test").expect("should have task");
+        let mut inputs = TaskInputs::default();
+        inputs.set("n", 3.0);
+
+        // The task runs normally with the extension enabled
+        let mut engine =
+            Engine::new(LocalTaskExecutionBackend::new()).with_lenient_float_to_int(true);
+        let evaluated = TaskEvaluator::new(&mut engine)
+            .evaluate(&document, task, &inputs, &dir.path().join("normal"), "test")
+            .await
+            .unwrap();
+        let outputs = evaluated.into_result().unwrap();
+        assert_eq!(outputs.get("out").unwrap().as_integer(), Some(4));
+
+        // The same task is rejected in conformance mode with a report of exactly the
+        // extensions the configuration relies on
+        let mut engine = engine.with_conformance_mode(true);
+        let e = TaskEvaluator::new(&mut engine)
+            .evaluate(
+                &document,
+                task,
+                &inputs,
+                &dir.path().join("conformance"),
+                "test",
+            )
+            .await
+            .unwrap_err();
+        match e {
+            EvaluationError::Source(diagnostic) => assert_eq!(
+                diagnostic.message(),
+                "task `test` relies on extensions that are not allowed in conformance \
+                 mode:\n`lenient-float-to-int`: floats with a zero fractional part are accepted \
+                 where an `Int` is expected"
+            ),
+            e => panic!("unexpected error `{e:?}`"),
+        }
+    }
+
+    #[tokio::test]
+    async fn it_reports_extensions_relied_upon_by_a_task() {
+        let dir = TempDir::new().expect("failed to create temporary directory");
+        let document = analyze(
+            &dir,
+            r#"version 1.2
+
+task test {
+    input {
+        Int n
+        String token
+    }
+
+    command <<<>>>
+
+    output {
+        Int out = n + 1
+    }
+
+    hints {
+        inputs: input {
+            token: hints {
+                secret: true
+            }
+        }
+    }
+}
+"#,
+        )
+        .await;
+
+        let task = document.task_by_name("test").expect("should have task");
+        let mut inputs = TaskInputs::default();
+        inputs.set("n", 3.0);
+
+        // The secret hint is reported even if no secret provider is configured
+        let engine = Engine::new(LocalTaskExecutionBackend::new());
+        assert!(engine.conformance_report().is_empty());
+        assert_eq!(
+            engine
+                .task_conformance_report(&document, task)
+                .capabilities(),
+            [Capability::SecretInputs]
+        );
+
+        // The task runs normally with the extensions it relies on enabled
+        let mut engine = engine
+            .with_lenient_float_to_int(true)
+            .with_secret_provider(TestProvider);
+        let report = engine.task_conformance_report(&document, task);
+        assert_eq!(
+            report.capabilities(),
+            [Capability::LenientFloatToInt, Capability::SecretInputs]
+        );
+
+        let evaluated = TaskEvaluator::new(&mut engine)
+            .evaluate(&document, task, &inputs, &dir.path().join("normal"), "test")
+            .await
+            .unwrap();
+        let outputs = evaluated.into_result().unwrap();
+        assert_eq!(outputs.get("out").unwrap().as_integer(), Some(4));
+
+        // The same task is rejected in conformance mode with a report of exactly the
+        // extensions it relies on
+        let mut engine = engine.with_conformance_mode(true);
+        assert_eq!(engine.task_conformance_report(&document, task), report);
+        let e = TaskEvaluator::new(&mut engine)
+            .evaluate(
+                &document,
+                task,
+                &inputs,
+                &dir.path().join("conformance"),
+                "test",
+            )
+            .await
+            .unwrap_err();
+        match e {
+            EvaluationError::Source(diagnostic) => assert_eq!(
+                diagnostic.message(),
+                format!(
+                    "task `test` relies on extensions that are not allowed in conformance \
+                     mode:\n{report}"
+                )
+            ),
+            e => panic!("unexpected error `{e:?}`"),
+        }
+    }
+}
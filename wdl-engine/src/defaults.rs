@@ -0,0 +1,375 @@
+//! Evaluation of input default expressions without running a workflow.
+//!
+//! This is intended for documentation and user interfaces that want to show
+//! the default value of an input; evaluation is pure and never touches the
+//! file system.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::anyhow;
+use anyhow::bail;
+use wdl_analysis::diagnostics::unknown_name;
+use wdl_analysis::diagnostics::unknown_type;
+use wdl_analysis::document::Document;
+use wdl_analysis::document::Task;
+use wdl_analysis::types::Type;
+use wdl_analysis::types::TypeNameResolver;
+use wdl_analysis::types::v1::AstTypeConverter;
+use wdl_ast::Ast;
+use wdl_ast::AstNode;
+use wdl_ast::AstNodeExt;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Ident;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::ToSpan;
+use wdl_ast::v1::CallExpr;
+use wdl_ast::v1::Decl;
+use wdl_ast::v1::Expr;
+use wdl_ast::v1::NameRef;
+
+use crate::Coercible;
+use crate::EvaluationContext;
+use crate::Value;
+use crate::diagnostics::runtime_type_mismatch;
+use crate::v1::ExprEvaluator;
+
+/// The standard library functions that access the file system or the output
+/// of a task.
+///
+/// A default expression that calls any of these functions is not evaluated.
+const IMPURE_FUNCTIONS: &[&str] = &[
+    "glob",
+    "read_boolean",
+    "read_float",
+    "read_int",
+    "read_json",
+    "read_lines",
+    "read_map",
+    "read_object",
+    "read_objects",
+    "read_string",
+    "read_tsv",
+    "size",
+    "stderr",
+    "stdout",
+    "write_json",
+    "write_lines",
+    "write_map",
+    "write_object",
+    "write_objects",
+    "write_tsv",
+];
+
+/// Represents the default value of an input.
+#[derive(Debug, Clone)]
+pub enum DefaultValue {
+    /// The default expression evaluated to a constant value.
+    Constant(Value),
+    /// The default expression could not be evaluated without running the
+    /// workflow.
+    ///
+    /// This is the case when the expression accesses the file system or
+    /// references an input whose default value is not constant.
+    ///
+    /// Contains the text of the default expression.
+    Dynamic(String),
+    /// The default expression failed to evaluate.
+    Error {
+        /// The span of the default expression.
+        span: Span,
+        /// The diagnostic describing the failure.
+        diagnostic: Diagnostic,
+    },
+}
+
+impl DefaultValue {
+    /// Gets the constant value of the default.
+    ///
+    /// Returns `None` if the default value is not constant.
+    pub fn as_constant(&self) -> Option<&Value> {
+        match self {
+            Self::Constant(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// Evaluates the default expressions of the inputs of the given workflow.
+///
+/// The returned map contains an entry for each input that has a default
+/// expression.
+///
+/// Returns an error if the document is not a 1.x document or does not contain
+/// the given workflow.
+pub fn evaluate(
+    document: &Document,
+    workflow: &str,
+) -> anyhow::Result<HashMap<String, DefaultValue>> {
+    let Ast::V1(ast) = document.node().ast() else {
+        bail!("document is not a 1.x document");
+    };
+
+    let definition = ast
+        .workflows()
+        .find(|w| w.name().as_str() == workflow)
+        .ok_or_else(|| anyhow!("document does not contain a workflow named `{workflow}`"))?;
+
+    let version = document.version().expect("document should have a version");
+
+    // Evaluate the defaults in dependency order; a default is evaluated once the
+    // defaults of every input it references have been evaluated
+    let mut pending: Vec<_> = definition
+        .input()
+        .into_iter()
+        .flat_map(|s| s.declarations())
+        .filter_map(|decl| decl.expr().map(|expr| (decl, expr)))
+        .collect();
+    let inputs: Vec<_> = pending
+        .iter()
+        .map(|(decl, _)| decl.name().as_str().to_string())
+        .collect();
+
+    let mut defaults = HashMap::new();
+    while !pending.is_empty() {
+        let len = pending.len();
+        pending.retain(|(decl, expr)| {
+            let references: Vec<_> = expr
+                .syntax()
+                .descendants()
+                .filter_map(NameRef::cast)
+                .map(|r| r.name().as_str().to_string())
+                .filter(|n| inputs.contains(n))
+                .collect();
+
+            if references.iter().any(|n| !defaults.contains_key(n)) {
+                return true;
+            }
+
+            let value = if references
+                .iter()
+                .any(|n| defaults[n.as_str()].as_constant().is_none())
+                || is_impure(expr)
+            {
+                DefaultValue::Dynamic(expr.syntax().text().to_string())
+            } else {
+                evaluate_default(document, version, &defaults, decl, expr)
+            };
+
+            defaults.insert(decl.name().as_str().to_string(), value);
+            false
+        });
+
+        // Any remaining defaults reference each other and cannot be evaluated
+        if pending.len() == len {
+            for (decl, expr) in pending.drain(..) {
+                defaults.insert(
+                    decl.name().as_str().to_string(),
+                    DefaultValue::Dynamic(expr.syntax().text().to_string()),
+                );
+            }
+        }
+    }
+
+    Ok(defaults)
+}
+
+/// Determines if the given expression calls a function that accesses the file
+/// system.
+fn is_impure(expr: &Expr) -> bool {
+    expr.syntax()
+        .descendants()
+        .filter_map(CallExpr::cast)
+        .any(|c| IMPURE_FUNCTIONS.contains(&c.target().as_str()))
+}
+
+/// Evaluates a single default expression.
+fn evaluate_default(
+    document: &Document,
+    version: SupportedVersion,
+    defaults: &HashMap<String, DefaultValue>,
+    decl: &Decl,
+    expr: &Expr,
+) -> DefaultValue {
+    let error = |diagnostic| DefaultValue::Error {
+        span: expr.span(),
+        diagnostic,
+    };
+
+    let decl_ty = decl.ty();
+    let ty = match AstTypeConverter::new(Resolver(document)).convert_type(&decl_ty) {
+        Ok(ty) => ty,
+        Err(diagnostic) => return error(diagnostic),
+    };
+
+    let mut evaluator = ExprEvaluator::new(DefaultsEvaluationContext {
+        document,
+        version,
+        defaults,
+    });
+
+    let value = match evaluator.evaluate_expr(expr) {
+        Ok(value) => value,
+        Err(diagnostic) => return error(diagnostic),
+    };
+
+    match value.coerce(&ty) {
+        Ok(value) => DefaultValue::Constant(value),
+        Err(e) => error(runtime_type_mismatch(
+            e,
+            &ty,
+            decl_ty.syntax().text_range().to_span(),
+            &value.ty(),
+            expr.span(),
+        )),
+    }
+}
+
+/// Used to resolve a type name from a document.
+struct Resolver<'a>(&'a Document);
+
+impl TypeNameResolver for Resolver<'_> {
+    fn resolve(&mut self, name: &Ident) -> Result<Type, Diagnostic> {
+        resolve_type_name(self.0, name)
+    }
+}
+
+/// Resolves a type name from a document.
+fn resolve_type_name(document: &Document, name: &Ident) -> Result<Type, Diagnostic> {
+    document
+        .struct_by_name(name.as_str())
+        .map(|s| s.ty().expect("struct should have type").clone())
+        .ok_or_else(|| unknown_type(name.as_str(), name.span()))
+}
+
+/// Represents the context for evaluating default expressions.
+///
+/// Only the constant defaults of other inputs are in scope.
+struct DefaultsEvaluationContext<'a> {
+    /// The document being evaluated.
+    document: &'a Document,
+    /// The version of the document being evaluated.
+    version: SupportedVersion,
+    /// The evaluated defaults of the inputs.
+    defaults: &'a HashMap<String, DefaultValue>,
+}
+
+impl EvaluationContext for DefaultsEvaluationContext<'_> {
+    fn version(&self) -> SupportedVersion {
+        self.version
+    }
+
+    fn resolve_name(&self, name: &Ident) -> Result<Value, Diagnostic> {
+        self.defaults
+            .get(name.as_str())
+            .and_then(DefaultValue::as_constant)
+            .cloned()
+            .ok_or_else(|| unknown_name(name.as_str(), name.span()))
+    }
+
+    fn resolve_type_name(&mut self, name: &Ident) -> Result<Type, Diagnostic> {
+        resolve_type_name(self.document, name)
+    }
+
+    fn work_dir(&self) -> &Path {
+        // Functions that access the file system are never called
+        Path::new("")
+    }
+
+    fn temp_dir(&self) -> &Path {
+        // Functions that access the file system are never called
+        Path::new("")
+    }
+
+    fn stdout(&self) -> Option<&Value> {
+        None
+    }
+
+    fn stderr(&self) -> Option<&Value> {
+        None
+    }
+
+    fn task(&self) -> Option<&Task> {
+        None
+    }
+
+    fn array_spill_threshold(&self) -> Option<usize> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::testing::analyze;
+
+    #[tokio::test]
+    async fn it_evaluates_defaults() {
+        let dir = TempDir::new().expect("failed to create temporary directory");
+        let source = r#"version 1.1
+
+workflow test {
+    input {
+        Int a = 1 + 2 * 3
+        String greeting = "hello" + " " + "world"
+        Int b = a * 2
+        Float f = b
+        Array[String] lines = read_lines("foo.txt")
+        Int count = length(lines)
+        Int d = 1 / 0
+        Int? none
+    }
+}
+"#;
+
+        let document = analyze(&dir, source).await;
+        let defaults = evaluate(&document, "test").unwrap();
+        assert_eq!(defaults.len(), 7);
+
+        // Constant defaults are evaluated
+        let constant = |name: &str| {
+            defaults[name]
+                .as_constant()
+                .unwrap_or_else(|| panic!("default of `{name}` should be constant"))
+                .clone()
+        };
+        assert_eq!(constant("a").as_integer(), Some(7));
+        assert_eq!(
+            constant("greeting").as_string().unwrap().as_str(),
+            "hello world"
+        );
+
+        // References to constant defaults are resolved
+        assert_eq!(constant("b").as_integer(), Some(14));
+        assert_eq!(constant("f").as_float(), Some(14.0));
+
+        // Defaults that read files, or reference such defaults, are dynamic
+        match &defaults["lines"] {
+            DefaultValue::Dynamic(text) => assert_eq!(text, r#"read_lines("foo.txt")"#),
+            v => panic!("unexpected default value `{v:?}`"),
+        }
+        match &defaults["count"] {
+            DefaultValue::Dynamic(text) => assert_eq!(text, "length(lines)"),
+            v => panic!("unexpected default value `{v:?}`"),
+        }
+
+        // Failures are reported with the span of the expression
+        match &defaults["d"] {
+            DefaultValue::Error { span, diagnostic } => {
+                assert_eq!(&source[span.start()..span.end()], "1 / 0");
+                assert_eq!(diagnostic.message(), "attempt to divide by zero");
+            }
+            v => panic!("unexpected default value `{v:?}`"),
+        }
+
+        assert_eq!(
+            evaluate(&document, "missing").unwrap_err().to_string(),
+            "document does not contain a workflow named `missing`"
+        );
+    }
+}
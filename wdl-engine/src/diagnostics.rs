@@ -2,12 +2,16 @@
 
 use std::fmt;
 
+use itertools::Itertools;
+use url::Url;
 use wdl_analysis::types::Type;
 use wdl_ast::AstToken;
 use wdl_ast::Diagnostic;
 use wdl_ast::Ident;
 use wdl_ast::Span;
 
+use crate::ConformanceReport;
+
 /// Creates an "integer not in range" diagnostic.
 pub fn integer_not_in_range(span: Span) -> Diagnostic {
     Diagnostic::error(format!(
@@ -119,6 +123,14 @@ pub fn array_index_out_of_range(
         )
 }
 
+/// Creates an "array read failed" diagnostic.
+pub fn array_read_failed(error: &anyhow::Error, span: Span) -> Diagnostic {
+    Diagnostic::error(format!(
+        "failed to read the elements of an array: {error:#}"
+    ))
+    .with_highlight(span)
+}
+
 /// Creates a "map key not found" diagnostic.
 pub fn map_key_not_found(span: Span) -> Diagnostic {
     Diagnostic::error("the map does not contain an entry for the specified key")
@@ -197,3 +209,49 @@ pub fn missing_task_output(e: anyhow::Error, task: &str, output: &Ident) -> Diag
 
     Diagnostic::error(format!("{e:?}")).with_highlight(output.span())
 }
+
+/// Creates an "import cycle" diagnostic.
+///
+/// The span at each index of `spans` is of the import statement in the
+/// document at the same index of `path` that imports the next document of the
+/// path; each is labeled, starting with the import in the first document.
+pub fn import_cycle(path: &[Url], spans: &[Span]) -> Diagnostic {
+    let mut diagnostic = Diagnostic::error(format!(
+        "import cycle detected: {path}",
+        path = path.iter().map(Url::as_str).join(" -> ")
+    ));
+
+    for (i, span) in spans.iter().enumerate() {
+        diagnostic = if i == 0 {
+            diagnostic.with_label("this import introduces a cycle", *span)
+        } else {
+            diagnostic.with_label(
+                format!(
+                    "`{importer}` imports `{import}`",
+                    importer = path[i],
+                    import = path[i + 1]
+                ),
+                *span,
+            )
+        };
+    }
+
+    diagnostic
+}
+
+/// Creates an "import depth exceeded" diagnostic.
+pub fn import_depth_exceeded(max: usize, path: &[Url], span: Span) -> Diagnostic {
+    Diagnostic::error(format!(
+        "imports are nested more than {max} level{s} deep: {path}",
+        s = if max == 1 { "" } else { "s" },
+        path = path.iter().map(Url::as_str).join(" -> ")
+    ))
+    .with_label("this import exceeds the maximum import depth", span)
+}
+
+/// Creates a "non-conformant task" diagnostic.
+pub fn nonconformant_task(task: &str, report: &ConformanceReport) -> Diagnostic {
+    Diagnostic::error(format!(
+        "task `{task}` relies on extensions that are not allowed in conformance mode:\n{report}"
+    ))
+}
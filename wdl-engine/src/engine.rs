@@ -1,16 +1,39 @@
 //! Implementation of the WDL evaluation engine.
 
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+use anyhow::Result;
 use sysinfo::CpuRefreshKind;
 use sysinfo::MemoryRefreshKind;
 use sysinfo::System;
+use url::Url;
 use wdl_analysis::diagnostics::unknown_type;
 use wdl_analysis::document::Document;
+use wdl_analysis::document::Task;
 use wdl_analysis::types::Type;
+use wdl_ast::Ast;
 use wdl_ast::AstToken;
 use wdl_ast::Diagnostic;
 use wdl_ast::Ident;
+use wdl_ast::Span;
 
+use crate::Capability;
+use crate::CleanupReport;
+use crate::ConformanceReport;
+use crate::ContainerRuntime;
+use crate::OrphanFilter;
+use crate::RerunScriptPolicy;
+use crate::SecretInjection;
+use crate::SecretProvider;
 use crate::TaskExecutionBackend;
+use crate::backend;
+use crate::diagnostics::import_cycle;
+use crate::diagnostics::import_depth_exceeded;
+use crate::secrets::secret_inputs;
+
+/// The default maximum depth of nested imports allowed for evaluation.
+pub const DEFAULT_MAX_IMPORT_DEPTH: usize = 32;
 
 /// Represents an evaluation engine.
 pub struct Engine {
@@ -18,6 +41,21 @@ pub struct Engine {
     backend: Box<dyn TaskExecutionBackend>,
     /// Information about the current system.
     system: System,
+    /// The maximum depth of nested imports allowed for evaluation.
+    max_import_depth: usize,
+    /// Whether or not floats with a zero fractional part are accepted where an
+    /// `Int` is expected.
+    lenient_float_to_int: bool,
+    /// The number of elements above which arrays are spilled to disk.
+    array_spill_threshold: Option<usize>,
+    /// Whether or not every extension to the WDL specification is rejected.
+    conformance_mode: bool,
+    /// When rerun scripts are written for task executions.
+    rerun_scripts: RerunScriptPolicy,
+    /// The provider of the values of secret task inputs.
+    secret_provider: Option<Box<dyn SecretProvider>>,
+    /// How secrets are injected into task processes.
+    secret_injection: SecretInjection,
 }
 
 impl Engine {
@@ -30,9 +68,207 @@ impl Engine {
         Self {
             backend: Box::new(backend),
             system,
+            max_import_depth: DEFAULT_MAX_IMPORT_DEPTH,
+            lenient_float_to_int: false,
+            array_spill_threshold: None,
+            conformance_mode: false,
+            rerun_scripts: Default::default(),
+            secret_provider: None,
+            secret_injection: Default::default(),
         }
     }
 
+    /// Sets the maximum depth of nested imports allowed for evaluation.
+    ///
+    /// Defaults to [`DEFAULT_MAX_IMPORT_DEPTH`].
+    pub fn with_max_import_depth(mut self, depth: usize) -> Self {
+        self.max_import_depth = depth;
+        self
+    }
+
+    /// Gets the maximum depth of nested imports allowed for evaluation.
+    pub fn max_import_depth(&self) -> usize {
+        self.max_import_depth
+    }
+
+    /// Sets whether or not floats with a zero fractional part (e.g. `3.0`) are
+    /// accepted where an `Int` is expected.
+    ///
+    /// This applies to task inputs and to values read with `read_json`; floats
+    /// with a fractional part or that are outside the range of a 64-bit signed
+    /// integer are always rejected.
+    ///
+    /// A warning is emitted for each converted float.
+    ///
+    /// Defaults to `false`.
+    pub fn with_lenient_float_to_int(mut self, lenient: bool) -> Self {
+        self.lenient_float_to_int = lenient;
+        self
+    }
+
+    /// Gets whether or not floats with a zero fractional part are accepted
+    /// where an `Int` is expected.
+    pub fn lenient_float_to_int(&self) -> bool {
+        self.lenient_float_to_int
+    }
+
+    /// Sets the number of elements above which an array of primitive values
+    /// created by a standard library function (e.g. `read_lines`, `range`,
+    /// `chunk`, or `flatten`) is spilled to disk.
+    ///
+    /// The elements of a spilled array are written to a file in the
+    /// evaluation's temp directory and read back as they are accessed, so
+    /// that very large arrays are not held in memory.
+    ///
+    /// Defaults to `None`, meaning arrays are never spilled.
+    pub fn with_array_spill_threshold(mut self, threshold: Option<usize>) -> Self {
+        self.array_spill_threshold = threshold;
+        self
+    }
+
+    /// Gets the number of elements above which arrays are spilled to disk.
+    pub fn array_spill_threshold(&self) -> Option<usize> {
+        self.array_spill_threshold
+    }
+
+    /// Sets when a rerun script is written for a task execution.
+    ///
+    /// A rerun script (see [`RERUN_SCRIPT_FILE_NAME`](crate::RERUN_SCRIPT_FILE_NAME))
+    /// is written next to the execution's command file and reproduces the
+    /// execution's process outside of the engine.
+    ///
+    /// Defaults to [`RerunScriptPolicy::OnFailure`].
+    pub fn with_rerun_scripts(mut self, policy: RerunScriptPolicy) -> Self {
+        self.rerun_scripts = policy;
+        self
+    }
+
+    /// Gets when a rerun script is written for a task execution.
+    pub fn rerun_scripts(&self) -> RerunScriptPolicy {
+        self.rerun_scripts
+    }
+
+    /// Sets the provider of the values of secret task inputs.
+    ///
+    /// A task input is a secret if the task's `inputs` hint marks it with
+    /// `secret: true`; its value is sourced from the provider when the task is
+    /// evaluated rather than from the task's inputs (see
+    /// [`SecretInjection`]).
+    ///
+    /// Defaults to `None`, meaning tasks with secret inputs cannot be
+    /// evaluated.
+    pub fn with_secret_provider(mut self, provider: impl SecretProvider + 'static) -> Self {
+        self.secret_provider = Some(Box::new(provider));
+        self
+    }
+
+    /// Gets the provider of the values of secret task inputs.
+    pub fn secret_provider(&self) -> Option<&dyn SecretProvider> {
+        self.secret_provider.as_deref()
+    }
+
+    /// Sets how secrets are injected into task processes.
+    ///
+    /// Defaults to [`SecretInjection::Environment`].
+    pub fn with_secret_injection(mut self, injection: SecretInjection) -> Self {
+        self.secret_injection = injection;
+        self
+    }
+
+    /// Gets how secrets are injected into task processes.
+    pub fn secret_injection(&self) -> SecretInjection {
+        self.secret_injection
+    }
+
+    /// Sets whether or not the engine runs in conformance mode.
+    ///
+    /// In conformance mode, every extension to the WDL specification is
+    /// disabled and the engine refuses to evaluate a task if its configuration
+    /// enables any extension; see
+    /// [`conformance_report`](Self::conformance_report).
+    ///
+    /// Defaults to `false`.
+    pub fn with_conformance_mode(mut self, conformance_mode: bool) -> Self {
+        self.conformance_mode = conformance_mode;
+        self
+    }
+
+    /// Gets whether or not the engine runs in conformance mode.
+    pub fn conformance_mode(&self) -> bool {
+        self.conformance_mode
+    }
+
+    /// Determines if the given extension is allowed to be used.
+    ///
+    /// An extension is allowed if it is enabled by the engine's configuration
+    /// and the engine is not in conformance mode.
+    pub fn allows(&self, capability: Capability) -> bool {
+        !self.conformance_mode && self.enables(capability)
+    }
+
+    /// Gets a report of every extension enabled by the engine's
+    /// configuration.
+    ///
+    /// The report is independent of whether or not the engine is in
+    /// conformance mode.
+    pub fn conformance_report(&self) -> ConformanceReport {
+        ConformanceReport::new(Capability::ALL.iter().copied().filter(|c| self.enables(*c)))
+    }
+
+    /// Gets a report of every extension enabled by the engine's configuration
+    /// or relied upon by the given task.
+    ///
+    /// A task relies on [`Capability::SecretInputs`] if the `inputs` hint of
+    /// the task marks any input as a secret.
+    pub fn task_conformance_report(&self, document: &Document, task: &Task) -> ConformanceReport {
+        let secrets = match document.node().ast() {
+            Ast::V1(ast) => ast
+                .tasks()
+                .find(|t| t.name().as_str() == task.name())
+                .is_some_and(|t| !secret_inputs(&t).is_empty()),
+            _ => false,
+        };
+
+        ConformanceReport::new(
+            Capability::ALL
+                .iter()
+                .copied()
+                .filter(|c| self.enables(*c) || (*c == Capability::SecretInputs && secrets)),
+        )
+    }
+
+    /// Determines if the given extension is enabled by the engine's
+    /// configuration.
+    fn enables(&self, capability: Capability) -> bool {
+        match capability {
+            Capability::LenientFloatToInt => self.lenient_float_to_int,
+            Capability::SecretInputs => self.secret_provider.is_some(),
+        }
+    }
+
+    /// Removes the containers started by the filter's engine that do not
+    /// belong to an active run.
+    ///
+    /// The engine never calls this itself, as it must otherwise never touch
+    /// containers it did not start in the current run; callers opt in by
+    /// calling it, typically before evaluating any tasks.
+    ///
+    /// A run is active if its state file in the filter's run state directory
+    /// contains a liveness timestamp that is more recent than the filter's
+    /// liveness timeout.
+    ///
+    /// Returns an error if the containers could not be listed; failures to
+    /// remove individual containers are returned in the report.
+    pub fn cleanup_orphans(
+        &self,
+        runtime: &dyn ContainerRuntime,
+        filter: &OrphanFilter,
+    ) -> Result<CleanupReport> {
+        backend::cleanup_orphans(runtime, filter, SystemTime::now(), |run| {
+            backend::read_liveness(filter.run_state_dir(), run)
+        })
+    }
+
     /// Gets a reference to the task execution backend.
     pub fn backend(&self) -> &dyn TaskExecutionBackend {
         self.backend.as_ref()
@@ -57,4 +293,271 @@ impl Engine {
             .map(|s| s.ty().expect("struct should have type").clone())
             .ok_or_else(|| unknown_type(name.as_str(), name.span()))
     }
+
+    /// Checks the imports of the given document for cycles and for exceeding
+    /// the maximum import depth.
+    ///
+    /// Returns a diagnostic for the first problem found; the diagnostic's
+    /// primary label is attached to the import statement in the given document
+    /// that leads to the problem.
+    pub(crate) fn check_imports(&self, document: &Document) -> Result<(), Diagnostic> {
+        ImportChecker::new(self.max_import_depth, document.uri().as_ref().clone()).check(&document)
+    }
+}
+
+/// Represents a document of an import graph checked by an [`ImportChecker`].
+///
+/// Analysis breaks import cycles, but the checker does not rely on it as
+/// embedders may provide documents directly.
+trait ImportNode: Sized {
+    /// Gets the URI of the document.
+    fn uri(&self) -> &Url;
+
+    /// Gets the documents imported by the document along with the span of
+    /// each import statement.
+    fn imports(&self) -> Vec<(Self, Span)>;
+}
+
+impl<'a> ImportNode for &'a Document {
+    fn uri(&self) -> &Url {
+        Document::uri(self).as_ref()
+    }
+
+    fn imports(&self) -> Vec<(Self, Span)> {
+        let document: &'a Document = self;
+        document
+            .namespaces()
+            .map(|(_, ns)| (ns.document().as_ref(), ns.span()))
+            .collect()
+    }
+}
+
+/// Used to check the import graph of a document.
+struct ImportChecker {
+    /// The maximum depth of nested imports.
+    max_depth: usize,
+    /// The URIs of the documents on the current import path.
+    path: Vec<Url>,
+    /// The spans of the import statements on the current import path.
+    ///
+    /// Each span is of the statement in the document at the same index of
+    /// `path` that imports the next document of the path.
+    spans: Vec<Span>,
+    /// The documents whose imports have been checked, mapped to the deepest
+    /// depth they were checked at.
+    ///
+    /// A document checked at a given depth need not be checked again at the
+    /// same or a shallower depth.
+    checked: HashMap<Url, usize>,
+}
+
+impl ImportChecker {
+    /// Constructs a new import checker for the document with the given URI.
+    fn new(max_depth: usize, uri: Url) -> Self {
+        Self {
+            max_depth,
+            path: vec![uri],
+            spans: Vec::new(),
+            checked: Default::default(),
+        }
+    }
+
+    /// Checks the imports of the given document.
+    ///
+    /// The document is expected to be the last entry in the current path.
+    fn check<N: ImportNode>(&mut self, document: &N) -> Result<(), Diagnostic> {
+        for (import, span) in document.imports() {
+            let uri = import.uri();
+            if self.path.contains(uri) {
+                self.path.push(uri.clone());
+                self.spans.push(span);
+                return Err(import_cycle(&self.path, &self.spans));
+            }
+
+            let depth = self.path.len();
+            if depth > self.max_depth {
+                let span = self.spans.first().copied().unwrap_or(span);
+                self.path.push(uri.clone());
+                return Err(import_depth_exceeded(self.max_depth, &self.path, span));
+            }
+
+            if self
+                .checked
+                .get(uri)
+                .map(|checked| *checked >= depth)
+                .unwrap_or(false)
+            {
+                continue;
+            }
+
+            self.path.push(uri.clone());
+            self.spans.push(span);
+            self.check(&import)?;
+            self.spans.pop();
+            self.path.pop();
+
+            self.checked
+                .entry(uri.clone())
+                .and_modify(|checked| *checked = (*checked).max(depth))
+                .or_insert(depth);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+    use std::sync::Arc;
+
+    use tempfile::TempDir;
+    use wdl_analysis::Analyzer;
+    use wdl_analysis::DiagnosticsConfig;
+    use wdl_analysis::path_to_uri;
+    use wdl_analysis::rules;
+
+    use super::*;
+    use crate::LocalTaskExecutionBackend;
+
+    /// Represents a document of an in-memory import graph.
+    #[derive(Clone, Copy)]
+    struct InMemoryDocument<'a> {
+        /// The URI of the document.
+        uri: &'a Url,
+        /// The imports of every document in the graph, keyed by URI.
+        graph: &'a HashMap<Url, Vec<(Url, Span)>>,
+    }
+
+    impl ImportNode for InMemoryDocument<'_> {
+        fn uri(&self) -> &Url {
+            self.uri
+        }
+
+        fn imports(&self) -> Vec<(Self, Span)> {
+            let graph = self.graph;
+            graph[self.uri]
+                .iter()
+                .map(|(uri, span)| (Self { uri, graph }, *span))
+                .collect()
+        }
+    }
+
+    /// Analyzes a chain of documents where each document imports the next.
+    async fn analyze_import_chain(dir: &TempDir, len: usize) -> Arc<Document> {
+        for i in 0..len {
+            let import = if i + 1 < len {
+                format!("import \"{next}.wdl\"\n\n", next = i + 1)
+            } else {
+                String::new()
+            };
+
+            fs::write(
+                dir.path().join(format!("{i}.wdl")),
+                format!("version 1.1\n\n{import}task t{i} {{\n    command <<<>>>\n}}\n"),
+            )
+            .expect("failed to create test file");
+        }
+
+        let uri = path_to_uri(dir.path().join("0.wdl")).expect("should convert to URI");
+        let analyzer = Analyzer::new(DiagnosticsConfig::new(rules()), |_: (), _, _, _| async {});
+        analyzer
+            .add_document(uri.clone())
+            .await
+            .expect("should add document");
+
+        let results = analyzer.analyze(()).await.unwrap();
+        results
+            .into_iter()
+            .find(|r| r.document().uri().as_ref() == &uri)
+            .expect("should have a result for the root document")
+            .document()
+            .clone()
+    }
+
+    #[test]
+    fn it_rejects_import_cycles() {
+        let a = Url::parse("file:///pipelines/a.wdl").unwrap();
+        let b = Url::parse("file:///pipelines/b.wdl").unwrap();
+        let graph = HashMap::from([
+            (a.clone(), vec![(b.clone(), Span::new(20, 14))]),
+            (b.clone(), vec![(a.clone(), Span::new(42, 14))]),
+        ]);
+
+        let diagnostic = ImportChecker::new(DEFAULT_MAX_IMPORT_DEPTH, a.clone())
+            .check(&InMemoryDocument {
+                uri: &a,
+                graph: &graph,
+            })
+            .expect_err("import cycle should be detected");
+        assert_eq!(
+            diagnostic.message(),
+            "import cycle detected: file:///pipelines/a.wdl -> file:///pipelines/b.wdl -> \
+             file:///pipelines/a.wdl"
+        );
+
+        // Every import statement of the cycle is labeled
+        let labels: Vec<_> = diagnostic
+            .labels()
+            .map(|l| (l.message(), l.span()))
+            .collect();
+        assert_eq!(
+            labels,
+            [
+                ("this import introduces a cycle", Span::new(20, 14)),
+                (
+                    "`file:///pipelines/b.wdl` imports `file:///pipelines/a.wdl`",
+                    Span::new(42, 14)
+                ),
+            ]
+        );
+
+        // A document that is imported twice without a cycle is accepted
+        let c = Url::parse("file:///pipelines/c.wdl").unwrap();
+        let graph = HashMap::from([
+            (
+                a.clone(),
+                vec![
+                    (b.clone(), Span::new(20, 14)),
+                    (c.clone(), Span::new(35, 14)),
+                ],
+            ),
+            (b.clone(), vec![(c.clone(), Span::new(20, 14))]),
+            (c.clone(), Vec::new()),
+        ]);
+        ImportChecker::new(DEFAULT_MAX_IMPORT_DEPTH, a.clone())
+            .check(&InMemoryDocument {
+                uri: &a,
+                graph: &graph,
+            })
+            .expect("imports should not contain a cycle");
+    }
+
+    #[tokio::test]
+    async fn it_rejects_deeply_nested_imports() {
+        let dir = TempDir::new().expect("failed to create temporary directory");
+        let document = analyze_import_chain(&dir, 50).await;
+
+        let engine = Engine::new(LocalTaskExecutionBackend::new());
+        let diagnostic = engine
+            .check_imports(&document)
+            .expect_err("import depth should be exceeded");
+        assert!(
+            diagnostic
+                .message()
+                .starts_with("imports are nested more than 32 levels deep"),
+            "unexpected diagnostic message `{message}`",
+            message = diagnostic.message()
+        );
+
+        let engine = Engine::new(LocalTaskExecutionBackend::new()).with_max_import_depth(49);
+        engine
+            .check_imports(&document)
+            .expect("import depth should not be exceeded");
+
+        let engine = Engine::new(LocalTaskExecutionBackend::new()).with_max_import_depth(48);
+        engine
+            .check_imports(&document)
+            .expect_err("import depth should be exceeded");
+    }
 }
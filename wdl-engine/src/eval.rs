@@ -18,9 +18,14 @@ use wdl_ast::v1::TASK_REQUIREMENT_RETURN_CODES_ALIAS;
 
 use crate::CompoundValue;
 use crate::Outputs;
+use crate::OverProvisioned;
 use crate::PrimitiveValue;
+use crate::ResourceUsage;
 use crate::TaskExecution;
+use crate::TaskExecutionResult;
 use crate::Value;
+use crate::backend::requested_cpu;
+use crate::backend::requested_memory;
 
 pub mod v1;
 
@@ -79,6 +84,12 @@ pub trait EvaluationContext {
     ///
     /// This is only `Some` when evaluating task hints sections.
     fn task(&self) -> Option<&Task>;
+
+    /// Gets the number of elements above which an array of primitive values
+    /// created by a standard library function is spilled to disk.
+    ///
+    /// Returns `None` if arrays are never spilled.
+    fn array_spill_threshold(&self) -> Option<usize>;
 }
 
 /// Represents an index of a scope in a collection of scopes.
@@ -217,13 +228,26 @@ pub struct EvaluatedTask {
     /// Otherwise, this contains the error that occurred while attempting to
     /// evaluate the task's outputs.
     outputs: EvaluationResult<Outputs>,
+    /// The fingerprints of the secrets injected into the task, keyed by input
+    /// name.
+    secret_fingerprints: IndexMap<String, String>,
+    /// The resources used by the task's execution.
+    resource_usage: ResourceUsage,
+    /// The number of CPUs requested by the task.
+    requested_cpu: f64,
+    /// The bytes of memory requested by the task.
+    requested_memory: u64,
 }
 
 impl EvaluatedTask {
     /// Constructs a new evaluated task.
     ///
     /// Returns an error if the stdout or stderr paths are not UTF-8.
-    fn new(execution: &dyn TaskExecution, status_code: i32) -> anyhow::Result<Self> {
+    fn new(
+        execution: &dyn TaskExecution,
+        result: TaskExecutionResult,
+        requirements: &HashMap<String, Value>,
+    ) -> anyhow::Result<Self> {
         let stdout = PrimitiveValue::new_file(execution.stdout().to_str().with_context(|| {
             format!(
                 "path to stdout file `{path}` is not UTF-8",
@@ -240,13 +264,17 @@ impl EvaluatedTask {
         .into();
 
         Ok(Self {
-            status_code,
+            status_code: result.status_code,
             work_dir: execution.work_dir().into(),
             temp_dir: execution.temp_dir().into(),
             command: execution.command().into(),
             stdout,
             stderr,
             outputs: Ok(Default::default()),
+            secret_fingerprints: Default::default(),
+            resource_usage: result.resource_usage,
+            requested_cpu: requested_cpu(requirements),
+            requested_memory: requested_memory(requirements)?.try_into().unwrap_or(0),
         })
     }
 
@@ -291,6 +319,33 @@ impl EvaluatedTask {
         &self.outputs
     }
 
+    /// Gets the fingerprints of the secrets injected into the evaluated task,
+    /// keyed by input name.
+    ///
+    /// The fingerprints identify the secrets' values without revealing them
+    /// and are suitable for use in place of the values (e.g. in a call cache
+    /// key).
+    pub fn secret_fingerprints(&self) -> &IndexMap<String, String> {
+        &self.secret_fingerprints
+    }
+
+    /// Gets the resources used by the evaluated task's execution.
+    pub fn resource_usage(&self) -> &ResourceUsage {
+        &self.resource_usage
+    }
+
+    /// Compares the resources used by the evaluated task against its `cpu`
+    /// and `memory` requirements.
+    ///
+    /// Returns the resources whose usage was below the given fraction of the
+    /// requirement (see [`DEFAULT_OVER_PROVISIONED_FRACTION`]).
+    ///
+    /// [`DEFAULT_OVER_PROVISIONED_FRACTION`]: crate::DEFAULT_OVER_PROVISIONED_FRACTION
+    pub fn over_provisioned(&self, fraction: f64) -> Vec<OverProvisioned> {
+        self.resource_usage
+            .over_provisioned(self.requested_cpu, self.requested_memory, fraction)
+    }
+
     /// Converts the evaluated task into an evaluation result.
     ///
     /// Returns `Ok(_)` if the task outputs were evaluated.
@@ -325,11 +380,16 @@ impl EvaluatedTask {
                     }
                 }
                 Value::Compound(CompoundValue::Array(codes)) => {
-                    error = !codes.as_slice().iter().any(|v| {
-                        v.as_integer()
+                    for v in codes.iter() {
+                        if v?
+                            .as_integer()
                             .map(|i| i32::try_from(i).unwrap_or_default() == self.status_code)
                             .unwrap_or(false)
-                    });
+                        {
+                            error = false;
+                            break;
+                        }
+                    }
                 }
                 _ => unreachable!("unexpected return codes value"),
             }
@@ -25,6 +25,7 @@ use wdl_analysis::diagnostics::map_key_not_primitive;
 use wdl_analysis::diagnostics::missing_struct_members;
 use wdl_analysis::diagnostics::multiple_type_mismatch;
 use wdl_analysis::diagnostics::no_common_type;
+use wdl_analysis::diagnostics::no_matching_signature;
 use wdl_analysis::diagnostics::not_a_pair_accessor;
 use wdl_analysis::diagnostics::not_a_struct;
 use wdl_analysis::diagnostics::not_a_struct_member;
@@ -37,6 +38,7 @@ use wdl_analysis::diagnostics::unknown_function;
 use wdl_analysis::diagnostics::unknown_task_io;
 use wdl_analysis::diagnostics::unsupported_function;
 use wdl_analysis::document::Task;
+use wdl_analysis::stdlib::Function;
 use wdl_analysis::stdlib::FunctionBindError;
 use wdl_analysis::stdlib::MAX_PARAMETERS;
 use wdl_analysis::types::ArrayType;
@@ -97,10 +99,12 @@ use crate::PrimitiveValue;
 use crate::Struct;
 use crate::Value;
 use crate::diagnostics::array_index_out_of_range;
+use crate::diagnostics::array_read_failed;
 use crate::diagnostics::division_by_zero;
 use crate::diagnostics::exponent_not_in_range;
 use crate::diagnostics::exponentiation_requirement;
 use crate::diagnostics::float_not_in_range;
+use crate::diagnostics::function_call_failed;
 use crate::diagnostics::integer_negation_not_in_range;
 use crate::diagnostics::integer_not_in_range;
 use crate::diagnostics::map_key_not_found;
@@ -111,6 +115,7 @@ use crate::diagnostics::runtime_type_mismatch;
 use crate::stdlib::CallArgument;
 use crate::stdlib::CallContext;
 use crate::stdlib::STDLIB;
+use crate::stdlib::read_lines::LineReader;
 
 /// Represents a WDL V1 expression evaluator.
 #[derive(Debug)]
@@ -323,23 +328,28 @@ impl<C: EvaluationContext> ExprEvaluator<C> {
                 }
                 Value::Primitive(v) => write!(buffer, "{v}", v = v.raw()).unwrap(),
                 Value::Compound(CompoundValue::Array(v))
-                    if matches!(placeholder.option(), Some(PlaceholderOption::Sep(_)))
-                        && v.as_slice()
-                            .first()
-                            .map(|e| !matches!(e, Value::None | Value::Compound(_)))
-                            .unwrap_or(false) =>
+                    if matches!(placeholder.option(), Some(PlaceholderOption::Sep(_))) =>
                 {
+                    let first = v.get(0).map_err(|e| array_read_failed(&e, expr.span()))?;
+                    if first
+                        .map(|e| matches!(*e, Value::None | Value::Compound(_)))
+                        .unwrap_or(true)
+                    {
+                        return Err(cannot_coerce_to_string(&v.ty(), expr.span()));
+                    }
+
                     let option = placeholder.option().unwrap().unwrap_sep();
 
                     let sep = evaluator
                         .evaluate_literal_string(&option.separator())?
                         .unwrap_string();
-                    for (i, e) in v.as_slice().iter().enumerate() {
+                    for (i, e) in v.iter().enumerate() {
                         if i > 0 {
                             buffer.push_str(&sep);
                         }
 
-                        match e {
+                        let e = e.map_err(|e| array_read_failed(&e, expr.span()))?;
+                        match &*e {
                             Value::None => {}
                             Value::Primitive(v) => write!(buffer, "{v}", v = v.raw()).unwrap(),
                             _ => {
@@ -1123,9 +1133,68 @@ impl<C: EvaluationContext> ExprEvaluator<C> {
         .ok_or_else(|| numeric_mismatch(op, span, &left.ty(), lhs.span(), &right.ty(), rhs.span()))
     }
 
+    /// Opens the file of a direct call to `read_lines` for reading its lines.
+    ///
+    /// This allows `length(read_lines(f))` and `read_lines(f)[i]` to be
+    /// evaluated without reading every line of the file into an array.
+    ///
+    /// Only direct composition is supported; an array of lines bound to a name
+    /// may be used elsewhere and is always read in full.
+    ///
+    /// Returns `None` if the expression is not a call to `read_lines` with a
+    /// single argument coercible to `File`; the expression should then be
+    /// evaluated normally.
+    fn open_read_lines(&mut self, expr: &Expr) -> Option<Result<LineReader, Diagnostic>> {
+        let mut expr = expr.clone();
+        while let Expr::Parenthesized(inner) = expr {
+            expr = inner.inner();
+        }
+
+        let Expr::Call(call) = expr else {
+            return None;
+        };
+
+        let target = call.target();
+        if target.as_str() != "read_lines" {
+            return None;
+        }
+
+        let mut arguments = call.arguments();
+        let (Some(arg), None) = (arguments.next(), arguments.next()) else {
+            return None;
+        };
+
+        let file = match self.evaluate_expr(&arg) {
+            Ok(value) => value.coerce(&PrimitiveType::File.into()).ok()?,
+            Err(e) => return Some(Err(e)),
+        };
+
+        let path = self.context.work_dir().join(file.unwrap_file().as_str());
+        Some(LineReader::open(&path, target.span()))
+    }
+
     /// Evaluates a call expression.
     fn evaluate_call_expr(&mut self, expr: &CallExpr) -> Result<Value, Diagnostic> {
         let target = expr.target();
+
+        // Count the lines of `length(read_lines(f))` without reading them into an array
+        if target.as_str() == "length" {
+            let mut arguments = expr.arguments();
+            if let (Some(arg), None) = (arguments.next(), arguments.next()) {
+                if let Some(lines) = self.open_read_lines(&arg) {
+                    return Ok(i64::try_from(lines?.count()?)
+                        .map_err(|_| {
+                            function_call_failed(
+                                "length",
+                                "array length exceeds a signed 64-bit integer",
+                                target.span(),
+                            )
+                        })?
+                        .into());
+                }
+            }
+        }
+
         match wdl_analysis::stdlib::STDLIB.function(target.as_str()) {
             Some(f) => {
                 // Evaluate the argument expressions
@@ -1179,15 +1248,27 @@ impl<C: EvaluationContext> ExprEvaluator<C> {
                             ))
                         }
                         Err(FunctionBindError::ArgumentTypeMismatch { index, expected }) => {
-                            Err(argument_type_mismatch(
-                                target.as_str(),
-                                &expected,
-                                &types[index],
-                                expr.arguments()
-                                    .nth(index)
-                                    .map(|e| e.span())
-                                    .expect("should have span"),
-                            ))
+                            let span = expr
+                                .arguments()
+                                .nth(index)
+                                .map(|e| e.span())
+                                .expect("should have span");
+                            Err(match f {
+                                Function::Polymorphic(f) => no_matching_signature(
+                                    target.as_str(),
+                                    &expected,
+                                    types,
+                                    index,
+                                    span,
+                                    &f.mismatches(self.context.version(), types),
+                                ),
+                                Function::Monomorphic(_) => argument_type_mismatch(
+                                    target.as_str(),
+                                    &expected,
+                                    &types[index],
+                                    span,
+                                ),
+                            })
                         }
                         Err(FunctionBindError::Ambiguous { first, second }) => Err(
                             ambiguous_argument(target.as_str(), target.span(), &first, &second),
@@ -1221,11 +1302,34 @@ impl<C: EvaluationContext> ExprEvaluator<C> {
     /// Evaluates the type of an index expression.
     fn evaluate_index_expr(&mut self, expr: &IndexExpr) -> Result<Value, Diagnostic> {
         let (target, index) = expr.operands();
+
+        // Read only up to the requested line of `read_lines(f)[i]`
+        if let Some(lines) = self.open_read_lines(&target) {
+            let lines = lines?;
+            return match self.evaluate_expr(&index)? {
+                Value::Primitive(PrimitiveValue::Integer(i)) => match lines.nth(i)? {
+                    Ok(line) => Ok(PrimitiveValue::new_string(line).into()),
+                    Err(count) => Err(array_index_out_of_range(
+                        i,
+                        count,
+                        index.span(),
+                        target.span(),
+                    )),
+                },
+                value => Err(index_type_mismatch(
+                    &PrimitiveType::Integer.into(),
+                    &value.ty(),
+                    index.span(),
+                )),
+            };
+        }
+
         match self.evaluate_expr(&target)? {
             Value::Compound(CompoundValue::Array(array)) => match self.evaluate_expr(&index)? {
                 Value::Primitive(PrimitiveValue::Integer(i)) => {
-                    match i.try_into().map(|i: usize| array.as_slice().get(i)) {
-                        Ok(Some(value)) => Ok(value.clone()),
+                    match i.try_into().map(|i: usize| array.get(i)) {
+                        Ok(Ok(Some(value))) => Ok(value.into_owned()),
+                        Ok(Err(e)) => Err(array_read_failed(&e, target.span())),
                         _ => Err(array_index_out_of_range(
                             i,
                             array.len(),
@@ -1332,6 +1436,8 @@ pub(crate) mod test {
         work_dir: TempDir,
         /// The current directory.
         temp_dir: TempDir,
+        /// The number of elements above which arrays are spilled to disk.
+        array_spill_threshold: Option<usize>,
     }
 
     impl TestEnv {
@@ -1355,6 +1461,10 @@ pub(crate) mod test {
             self.temp_dir.path()
         }
 
+        pub fn set_array_spill_threshold(&mut self, threshold: usize) {
+            self.array_spill_threshold = Some(threshold);
+        }
+
         pub fn write_file(&self, name: &str, bytes: impl AsRef<[u8]>) {
             fs::write(self.work_dir().join(name), bytes).expect("failed to create temp file");
         }
@@ -1367,6 +1477,7 @@ pub(crate) mod test {
                 structs: Default::default(),
                 temp_dir: TempDir::new().expect("failed to create temp directory"),
                 work_dir: TempDir::new().expect("failed to create work directory"),
+                array_spill_threshold: None,
             }
         }
     }
@@ -1445,6 +1556,10 @@ pub(crate) mod test {
         fn task(&self) -> Option<&Task> {
             None
         }
+
+        fn array_spill_threshold(&self) -> Option<usize> {
+            self.env.array_spill_threshold
+        }
     }
 
     pub fn eval_v1_expr(env: &mut TestEnv, version: V1, source: &str) -> Result<Value, Diagnostic> {
@@ -2775,6 +2890,30 @@ pub(crate) mod test {
             "type mismatch: argument to function `min` expects type `Int` or `Float`, but found \
              type `String`"
         );
+
+        let diagnostic = eval_v1_expr(&mut env, V1::One, "min(1, '2')").unwrap_err();
+        assert_eq!(
+            diagnostic.message(),
+            "type mismatch: argument to function `min` expects type `Int` or `Float`, but found \
+             type `String`"
+        );
+        assert_eq!(
+            diagnostic
+                .labels()
+                .map(|l| (l.message(), l.span().start(), l.span().end()))
+                .collect::<Vec<_>>(),
+            [("this is type `String`", 7, 10)]
+        );
+        assert_eq!(
+            diagnostic.fix(),
+            Some(
+                "supply arguments that match one of the function's signatures:
+  min(Int, Int) -> Int: argument 2 expects type `Int`, but found type `String`
+  min(Int, Float) -> Float: argument 2 expects type `Float`, but found type `String`
+  min(Float, Int) -> Float: argument 2 expects type `Int`, but found type `String`
+  min(Float, Float) -> Float: argument 2 expects type `Float`, but found type `String`"
+            )
+        );
     }
 
     #[test]
@@ -6,6 +6,8 @@ use std::path::Path;
 
 use anyhow::Context;
 use anyhow::anyhow;
+use anyhow::bail;
+use indexmap::IndexMap;
 use petgraph::Direction;
 use petgraph::Graph;
 use petgraph::algo::toposort;
@@ -22,6 +24,7 @@ use wdl_analysis::document::Task;
 use wdl_analysis::eval::v1::TaskGraphBuilder;
 use wdl_analysis::eval::v1::TaskGraphNode;
 use wdl_analysis::types::Optional;
+use wdl_analysis::types::PrimitiveType;
 use wdl_analysis::types::Type;
 use wdl_analysis::types::TypeNameResolver;
 use wdl_analysis::types::v1::AstTypeConverter;
@@ -45,20 +48,29 @@ use wdl_ast::v1::StrippedCommandPart;
 use wdl_ast::v1::TaskHintsSection;
 use wdl_ast::version::V1;
 
+use crate::Capability;
 use crate::Coercible;
 use crate::Engine;
 use crate::EvaluationContext;
 use crate::EvaluationResult;
 use crate::Outputs;
+use crate::RERUN_SCRIPT_FILE_NAME;
+use crate::SECRETS_DIR_NAME;
 use crate::Scope;
 use crate::ScopeRef;
+use crate::SecretInjection;
 use crate::TaskExecution;
 use crate::TaskInputs;
 use crate::TaskValue;
 use crate::Value;
 use crate::diagnostics::missing_task_output;
+use crate::diagnostics::nonconformant_task;
 use crate::diagnostics::runtime_type_mismatch;
 use crate::eval::EvaluatedTask;
+use crate::path_component;
+use crate::secret_env_name;
+use crate::secrets::secret_inputs;
+use crate::secrets::write_secret_file;
 use crate::v1::ExprEvaluator;
 
 /// The index of a task's root scope.
@@ -128,6 +140,10 @@ impl EvaluationContext for TaskEvaluationContext<'_> {
     fn task(&self) -> Option<&Task> {
         self.task
     }
+
+    fn array_spill_threshold(&self) -> Option<usize> {
+        self.engine.array_spill_threshold()
+    }
 }
 
 impl<'a> TaskEvaluationContext<'a> {
@@ -206,6 +222,43 @@ impl<'a> TaskEvaluator<'a> {
             return Err(diagnostic.clone().into());
         }
 
+        self.engine.check_imports(document)?;
+
+        if self.engine.conformance_mode() {
+            let report = self.engine.task_conformance_report(document, task);
+            if !report.is_empty() {
+                return Err(nonconformant_task(task.name(), &report).into());
+            }
+        }
+
+        // Convert any floats written for integer inputs if the engine allows it
+        let converted;
+        let inputs = if self.engine.allows(Capability::LenientFloatToInt) {
+            let mut inputs = inputs.clone();
+            inputs.convert_lenient_floats(task);
+            converted = inputs;
+            &converted
+        } else {
+            inputs
+        };
+
+        let mut execution = self.engine.backend().create_execution(root)?;
+
+        // Source the values of any secret inputs from the secret provider
+        let (secrets, fingerprints) =
+            self.inject_secrets(document, task, execution.as_mut(), inputs)?;
+        let injected;
+        let inputs = if secrets.is_empty() {
+            inputs
+        } else {
+            let mut inputs = inputs.clone();
+            for (name, value) in secrets {
+                inputs.set(name, value);
+            }
+            injected = inputs;
+            &injected
+        };
+
         inputs.validate(document, task).with_context(|| {
             format!(
                 "failed to validate the inputs to task `{task}`",
@@ -213,7 +266,6 @@ impl<'a> TaskEvaluator<'a> {
             )
         })?;
 
-        let mut execution = self.engine.backend().create_execution(root)?;
         match document.node().ast() {
             Ast::V1(ast) => {
                 // Find the task in the AST
@@ -312,7 +364,10 @@ impl<'a> TaskEvaluator<'a> {
                                 nodes[current],
                                 ScopeRef::new(&scopes, TASK_SCOPE_INDEX),
                                 &mut execution,
-                            );
+                            )
+                            .with_context(|| {
+                                format!("failed to execute task `{task}`", task = task.name())
+                            })?;
 
                             command = self.evaluate_command(
                                 document,
@@ -378,11 +433,23 @@ impl<'a> TaskEvaluator<'a> {
                 // TODO: check call cache for a hit. if so, skip task execution and use cache
                 // paths for output evaluation
 
-                let status_code = execution.spawn(command, &requirements, &hints)?.await?;
+                let result = match execution.spawn(command, &requirements, &hints)?.await {
+                    Ok(result) => result,
+                    Err(e) => {
+                        self.write_rerun_script(execution.as_ref(), inputs, true);
+                        return Err(e.into());
+                    }
+                };
 
                 // TODO: support retrying the task if it fails
 
-                let mut evaluated = EvaluatedTask::new(execution.as_ref(), status_code)?;
+                let mut evaluated = EvaluatedTask::new(execution.as_ref(), result, &requirements)?;
+                evaluated.secret_fingerprints = fingerprints;
+                self.write_rerun_script(
+                    execution.as_ref(),
+                    inputs,
+                    evaluated.handle_exit(&requirements).is_err(),
+                );
 
                 // Update the task variable's return code
                 if version >= SupportedVersion::V1(V1::Two) {
@@ -456,6 +523,127 @@ impl<'a> TaskEvaluator<'a> {
         }
     }
 
+    /// Injects the secret inputs of a task into the given execution.
+    ///
+    /// Returns the values of the secret inputs, which reference the injected
+    /// secrets rather than contain them, and the fingerprints of the secrets.
+    fn inject_secrets(
+        &self,
+        document: &Document,
+        task: &Task,
+        execution: &mut dyn TaskExecution,
+        inputs: &TaskInputs,
+    ) -> anyhow::Result<(Vec<(String, String)>, IndexMap<String, String>)> {
+        let Ast::V1(ast) = document.node().ast() else {
+            return Ok(Default::default());
+        };
+
+        let definition = ast
+            .tasks()
+            .find(|t| t.name().as_str() == task.name())
+            .expect("task should exist in the AST");
+
+        let mut values = Vec::new();
+        let mut fingerprints = IndexMap::new();
+        for name in secret_inputs(&definition) {
+            let Some(provider) = self.engine.secret_provider() else {
+                bail!(
+                    "secret input `{name}` of task `{task}` requires a secret provider",
+                    task = task.name()
+                );
+            };
+
+            if inputs.get(&name).is_some() {
+                bail!(
+                    "secret input `{name}` of task `{task}` cannot be specified as an input",
+                    task = task.name()
+                );
+            }
+
+            match task.inputs().get(&name).map(|i| i.ty().as_primitive()) {
+                Some(Some(PrimitiveType::String)) => {}
+                Some(_) => bail!(
+                    "secret input `{name}` of task `{task}` must be of type `String`",
+                    task = task.name()
+                ),
+                None => bail!(
+                    "secret input `{name}` is not an input of task `{task}`",
+                    task = task.name()
+                ),
+            }
+
+            let secret = provider.secret(&name).with_context(|| {
+                format!(
+                    "failed to get the secret for input `{name}` of task `{task}`",
+                    task = task.name()
+                )
+            })?;
+
+            let value = match self.engine.secret_injection() {
+                SecretInjection::Environment => {
+                    let var = secret_env_name(&name);
+                    let value = format!("${{{var}}}");
+                    execution.add_secret_env(var, secret.clone());
+                    value
+                }
+                SecretInjection::File => {
+                    let path = execution
+                        .temp_dir()
+                        .join(SECRETS_DIR_NAME)
+                        .join(path_component(&name).as_ref());
+                    write_secret_file(&path, &secret)?;
+                    let path = execution.map_path(&path).unwrap_or(path);
+                    path.to_str()
+                        .with_context(|| {
+                            format!(
+                                "path to secret file `{path}` is not UTF-8",
+                                path = path.display()
+                            )
+                        })?
+                        .to_string()
+                }
+            };
+
+            info!(
+                "injected secret input `{name}` of task `{task}` (fingerprint `{fingerprint}`)",
+                task = task.name(),
+                fingerprint = secret.fingerprint()
+            );
+            fingerprints.insert(name.clone(), secret.fingerprint().to_string());
+            values.push((name, value));
+        }
+
+        Ok((values, fingerprints))
+    }
+
+    /// Writes a rerun script for a task execution if the engine's policy
+    /// requires it.
+    ///
+    /// A failure to write the script is logged rather than returned so that it
+    /// does not mask the result of the task.
+    fn write_rerun_script(&self, execution: &dyn TaskExecution, inputs: &TaskInputs, failed: bool) {
+        if !self.engine.rerun_scripts().should_write(failed) {
+            return;
+        }
+
+        let mut paths = Vec::new();
+        for (_, value) in inputs.iter() {
+            if let Err(e) = value.visit_paths(&mut |path| paths.push(path.to_string())) {
+                warn!("{e:#}");
+                return;
+            }
+        }
+
+        let path = execution.command().with_file_name(RERUN_SCRIPT_FILE_NAME);
+        match execution.invocation().write_rerun_script(&path, &paths) {
+            Ok(()) => info!(
+                "wrote rerun script `{path}` for task execution",
+                path = path.display()
+            ),
+            Err(e) => warn!("{e:#}"),
+        }
+    }
+
     /// Evaluates a task input.
     fn evaluate_input(
         &mut self,
@@ -497,7 +685,7 @@ impl<'a> TaskEvaluator<'a> {
             }
         };
 
-        let value = value.coerce(&ty).map_err(|e| {
+        let value = self.coerce_decl_value(&value, &ty, &name).map_err(|e| {
             runtime_type_mismatch(
                 e,
                 &ty,
@@ -540,7 +728,7 @@ impl<'a> TaskEvaluator<'a> {
 
         let expr = decl.expr().expect("private decls should have expressions");
         let value = evaluator.evaluate_expr(&expr)?;
-        let value = value.coerce(&ty).map_err(|e| {
+        let value = self.coerce_decl_value(&value, &ty, &name).map_err(|e| {
             runtime_type_mismatch(
                 e,
                 &ty,
@@ -808,7 +996,7 @@ impl<'a> TaskEvaluator<'a> {
         let value = evaluator.evaluate_expr(&expr)?;
 
         // First coerce the output value to the expected type
-        let mut value = value.coerce(&ty).map_err(|e| {
+        let mut value = self.coerce_decl_value(&value, &ty, &name).map_err(|e| {
             runtime_type_mismatch(
                 e,
                 &ty,
@@ -827,6 +1015,21 @@ impl<'a> TaskEvaluator<'a> {
         Ok(())
     }
 
+    /// Coerces the value of a declaration to the declaration's type.
+    ///
+    /// If the engine allows the `lenient_float_to_int` extension, floats with
+    /// a zero fractional part are first converted to integers where the type
+    /// expects an `Int`.
+    fn coerce_decl_value(&self, value: &Value, ty: &Type, name: &Ident) -> anyhow::Result<Value> {
+        if self.engine.allows(Capability::LenientFloatToInt) {
+            if let Some(value) = value.lenient_float_to_int(ty, name.as_str()) {
+                return value.coerce(ty);
+            }
+        }
+
+        value.coerce(ty)
+    }
+
     /// Converts an AST type to an analysis type.
     fn convert_ast_type(
         &mut self,
@@ -856,12 +1059,14 @@ impl<'a> TaskEvaluator<'a> {
 
     /// Maps any host paths referenced by a command to a corresponding guest
     /// path.
+    ///
+    /// Returns an error if the elements of a spilled array could not be read.
     fn map_command_paths(
         graph: &Graph<TaskGraphNode, ()>,
         index: NodeIndex,
         scope: ScopeRef<'_>,
         execution: &mut Box<dyn TaskExecution>,
-    ) -> HashMap<String, String> {
+    ) -> anyhow::Result<HashMap<String, String>> {
         let mut mapped_paths = HashMap::new();
         for edge in graph.edges_directed(index, Direction::Incoming) {
             match &graph[edge.source()] {
@@ -886,12 +1091,12 @@ impl<'a> TaskEvaluator<'a> {
                                     );
                                 }
                             }
-                        });
+                        })?;
                 }
                 _ => continue,
             }
         }
 
-        mapped_paths
+        Ok(mapped_paths)
     }
 }
@@ -10,22 +10,185 @@ use anyhow::Context;
 use anyhow::Result;
 use anyhow::bail;
 use serde_json::Value as JsonValue;
+use wdl_analysis::diagnostics::unknown_name;
+use wdl_analysis::diagnostics::unknown_type;
 use wdl_analysis::document::Document;
 use wdl_analysis::document::Task;
 use wdl_analysis::document::Workflow;
 use wdl_analysis::types::CallKind;
 use wdl_analysis::types::Coercible as _;
+use wdl_analysis::types::CompoundType;
+use wdl_analysis::types::Optional;
 use wdl_analysis::types::Type;
 use wdl_analysis::types::display_types;
 use wdl_analysis::types::v1::task_hint_types;
 use wdl_analysis::types::v1::task_requirement_types;
+use wdl_ast::Ast;
+use wdl_ast::AstNode;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Ident;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxKind;
+use wdl_ast::v1::Expr;
+use wdl_ast::v1::MetadataValue;
+use wdl_ast::v1::ParameterMetadataSection;
 
 use crate::Coercible;
+use crate::EvaluationContext;
 use crate::Value;
+use crate::v1::ExprEvaluator;
 
 /// A type alias to a JSON map (object).
 type JsonMap = serde_json::Map<String, JsonValue>;
 
+/// The key of the object containing input descriptions in an inputs template.
+const TEMPLATE_DESCRIPTIONS_KEY: &str = "_descriptions";
+
+/// Determines if the given key of an inputs file is ignored.
+///
+/// Keys beginning with an underscore can never name an input and are ignored,
+/// which allows an inputs template to carry input descriptions.
+fn is_ignored_key(key: &str) -> bool {
+    key.starts_with('_')
+}
+
+/// Represents options for generating an inputs template (see
+/// [`Inputs::template`]).
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TemplateOptions {
+    /// Whether or not optional inputs are included in the template.
+    ///
+    /// An input is optional if it has an optional type or a default value.
+    pub optional: bool,
+
+    /// Whether or not the descriptions of inputs from `parameter_meta` are
+    /// included in the template.
+    pub descriptions: bool,
+}
+
+/// An evaluation context for constant expressions.
+///
+/// Constant expressions do not reference names or call functions, so name
+/// resolution always fails and the directories of the context are never used.
+struct ConstantEvaluationContext<'a> {
+    /// The document containing the expression.
+    document: &'a Document,
+}
+
+impl EvaluationContext for ConstantEvaluationContext<'_> {
+    fn version(&self) -> SupportedVersion {
+        self.document
+            .version()
+            .expect("document should have a version")
+    }
+
+    fn resolve_name(&self, name: &Ident) -> Result<Value, Diagnostic> {
+        Err(unknown_name(name.as_str(), name.span()))
+    }
+
+    fn resolve_type_name(&mut self, name: &Ident) -> Result<Type, Diagnostic> {
+        self.document
+            .struct_by_name(name.as_str())
+            .map(|s| s.ty().expect("struct should have type").clone())
+            .ok_or_else(|| unknown_type(name.as_str(), name.span()))
+    }
+
+    fn work_dir(&self) -> &Path {
+        Path::new("")
+    }
+
+    fn temp_dir(&self) -> &Path {
+        Path::new("")
+    }
+
+    fn stdout(&self) -> Option<&Value> {
+        None
+    }
+
+    fn stderr(&self) -> Option<&Value> {
+        None
+    }
+
+    fn task(&self) -> Option<&Task> {
+        None
+    }
+
+    fn array_spill_threshold(&self) -> Option<usize> {
+        None
+    }
+}
+
+/// Evaluates the given expression if it is a constant expression.
+///
+/// An expression is constant if it does not reference any names (including
+/// within placeholders) and does not call any functions.
+///
+/// Returns `None` if the expression is not constant or if it fails to evaluate
+/// to the given type.
+fn evaluate_constant(document: &Document, expr: &Expr, ty: &Type) -> Option<Value> {
+    if expr
+        .syntax()
+        .descendants()
+        .any(|n| matches!(n.kind(), SyntaxKind::NameRefNode | SyntaxKind::CallExprNode))
+    {
+        return None;
+    }
+
+    ExprEvaluator::new(ConstantEvaluationContext { document })
+        .evaluate_expr(expr)
+        .ok()?
+        .coerce(ty)
+        .ok()
+}
+
+/// Gets the placeholder for a value of the given type in an inputs template.
+///
+/// A placeholder for a struct is an object containing placeholders for its
+/// members; otherwise, the placeholder is the name of the type.
+fn type_placeholder(ty: &Type) -> JsonValue {
+    match ty {
+        Type::Compound(CompoundType::Struct(s), false) => JsonValue::Object(
+            s.members()
+                .iter()
+                .map(|(name, ty)| (name.clone(), type_placeholder(ty)))
+                .collect(),
+        ),
+        _ => JsonValue::String(ty.to_string()),
+    }
+}
+
+/// Gets the input descriptions from a `parameter_meta` section.
+///
+/// The description of an input is either its string value or the
+/// `description` member of its object value.
+fn input_descriptions(section: Option<ParameterMetadataSection>) -> HashMap<String, String> {
+    section
+        .iter()
+        .flat_map(|s| s.items())
+        .filter_map(|item| {
+            let value = match item.value() {
+                MetadataValue::String(s) => s,
+                MetadataValue::Object(o) => {
+                    match o
+                        .items()
+                        .find(|i| i.name().as_str() == "description")?
+                        .value()
+                    {
+                        MetadataValue::String(s) => s,
+                        _ => return None,
+                    }
+                }
+                _ => return None,
+            };
+
+            let mut description = String::new();
+            value.text()?.unescape_to(&mut description);
+            Some((item.name().as_str().to_string(), description))
+        })
+        .collect()
+}
+
 /// Helper for replacing input paths with a path derived from joining the
 /// specified path with the input path.
 fn join_paths(inputs: &mut HashMap<String, Value>, path: &Path, ty: impl Fn(&str) -> Option<Type>) {
@@ -109,6 +272,22 @@ impl TaskInputs {
         });
     }
 
+    /// Converts floats with a zero fractional part to integers for inputs
+    /// that expect an `Int`.
+    ///
+    /// This is used when the engine's `lenient_float_to_int` option is
+    /// enabled.
+    pub(crate) fn convert_lenient_floats(&mut self, task: &Task) {
+        for (name, value) in self.inputs.iter_mut() {
+            if let Some(converted) = task.inputs().get(name).and_then(|input| {
+                value
+                    .lenient_float_to_int(input.ty(), &format!("{task}.{name}", task = task.name()))
+            }) {
+                *value = converted;
+            }
+        }
+    }
+
     /// Validates the inputs for the given task.
     ///
     /// Note that this alters the inputs
@@ -595,6 +774,103 @@ impl Inputs {
             .with_context(|| format!("failed to parse input file `{path}`", path = path.display()))
     }
 
+    /// Generates an inputs template for the task or workflow with the given
+    /// name.
+    ///
+    /// The template is a JSON object with a key for each required input; the
+    /// value of each key is a placeholder naming the input's type, with struct
+    /// inputs expanded into objects of their members.
+    ///
+    /// If [`TemplateOptions::optional`] is set, optional inputs are also
+    /// included. The value of an optional input is its default value if the
+    /// default is a constant expression, a type placeholder if the default is
+    /// not constant, or `null` if the input has no default.
+    ///
+    /// If [`TemplateOptions::descriptions`] is set, the `parameter_meta`
+    /// descriptions of the included inputs are emitted in a `_descriptions`
+    /// object; as keys beginning with an underscore are ignored when parsing
+    /// inputs, the object need not be removed from a completed template.
+    pub fn template(
+        document: &Document,
+        name: &str,
+        options: TemplateOptions,
+    ) -> Result<JsonValue> {
+        let Ast::V1(ast) = document.node().ast() else {
+            bail!(
+                "document `{uri}` is not a supported WDL document",
+                uri = document.uri()
+            );
+        };
+
+        let (inputs, section, metadata) = match (document.task_by_name(name), document.workflow()) {
+            (Some(task), _) => {
+                let definition = ast
+                    .tasks()
+                    .find(|t| t.name().as_str() == name)
+                    .expect("task should be present");
+                (
+                    task.inputs(),
+                    definition.input(),
+                    definition.parameter_metadata(),
+                )
+            }
+            (None, Some(workflow)) if workflow.name() == name => {
+                let definition = ast.workflows().next().expect("workflow should be present");
+                (
+                    workflow.inputs(),
+                    definition.input(),
+                    definition.parameter_metadata(),
+                )
+            }
+            _ => bail!("a task or workflow named `{name}` does not exist in the document"),
+        };
+
+        let defaults: HashMap<_, _> = section
+            .iter()
+            .flat_map(|s| s.declarations())
+            .filter_map(|decl| Some((decl.name().as_str().to_string(), decl.expr()?)))
+            .collect();
+        let descriptions = if options.descriptions {
+            input_descriptions(metadata)
+        } else {
+            Default::default()
+        };
+
+        let mut template = JsonMap::new();
+        let mut described = JsonMap::new();
+        for (input, decl) in inputs {
+            let ty = decl.ty();
+            let value = if decl.required() {
+                type_placeholder(ty)
+            } else if options.optional {
+                match defaults.get(input) {
+                    Some(expr) => match evaluate_constant(document, expr, ty) {
+                        Some(value) => serde_json::to_value(&value).with_context(|| {
+                            format!("failed to serialize the default value of input `{input}`")
+                        })?,
+                        None => type_placeholder(&ty.require()),
+                    },
+                    None => JsonValue::Null,
+                }
+            } else {
+                continue;
+            };
+
+            let key = format!("{name}.{input}");
+            if let Some(description) = descriptions.get(input) {
+                described.insert(key.clone(), description.clone().into());
+            }
+
+            template.insert(key, value);
+        }
+
+        if !described.is_empty() {
+            template.insert(TEMPLATE_DESCRIPTIONS_KEY.to_string(), described.into());
+        }
+
+        Ok(JsonValue::Object(template))
+    }
+
     /// Gets an input value.
     pub fn get(&self, name: &str) -> Option<&Value> {
         match self {
@@ -646,8 +922,8 @@ impl Inputs {
     /// Parses the root object in an input file.
     fn parse_object(document: &Document, object: JsonMap) -> Result<Option<(String, Self)>> {
         // Determine the root workflow or task name
-        let (key, name) = match object.iter().next() {
-            Some((key, _)) => match key.split_once('.') {
+        let (key, name) = match object.keys().find(|k| !is_ignored_key(k)) {
+            Some(key) => match key.split_once('.') {
                 Some((name, _)) => (key, name),
                 None => {
                     bail!(
@@ -656,7 +932,8 @@ impl Inputs {
                     )
                 }
             },
-            // If the object is empty, treat it as a workflow evaluation without any inputs
+            // If the object has no input keys, treat it as a workflow evaluation without any
+            // inputs
             None => {
                 return Ok(None);
             }
@@ -682,6 +959,10 @@ impl Inputs {
     ) -> Result<(String, Self)> {
         let mut inputs = TaskInputs::default();
         for (key, value) in object {
+            if is_ignored_key(&key) {
+                continue;
+            }
+
             let value = serde_json::from_value(value)
                 .with_context(|| format!("invalid input key `{key}`"))?;
             match key.split_once(".") {
@@ -710,6 +991,10 @@ impl Inputs {
     ) -> Result<(String, Self)> {
         let mut inputs = WorkflowInputs::default();
         for (key, value) in object {
+            if is_ignored_key(&key) {
+                continue;
+            }
+
             let value = serde_json::from_value(value)
                 .with_context(|| format!("invalid input key `{key}`"))?;
             match key.split_once(".") {
@@ -1,19 +1,29 @@
 //! Execution engine for Workflow Description Language (WDL) documents.
 
 mod backend;
+mod conformance;
+pub mod defaults;
 pub mod diagnostics;
 mod engine;
 mod eval;
 mod inputs;
+mod names;
 mod outputs;
+mod secrets;
+mod spill;
 mod stdlib;
-mod units;
+#[cfg(test)]
+mod testing;
 mod value;
 
 pub use backend::*;
+pub use conformance::*;
 pub use engine::*;
 pub use eval::*;
 pub use inputs::*;
+pub use names::*;
 pub use outputs::*;
-pub use units::*;
+pub use secrets::*;
 pub use value::*;
+pub use wdl_ast::v1::common::units::StorageUnit;
+pub use wdl_ast::v1::common::units::convert_unit_string;
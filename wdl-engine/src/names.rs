@@ -0,0 +1,165 @@
+//! Implementation of filesystem-safe names for task and workflow executions.
+//!
+//! Names of tasks, workflows, and calls are used as path components of
+//! execution directories. A name is normalized so that:
+//!
+//! * it only contains characters that are safe in a path component on every
+//!   supported platform.
+//! * names that differ only by case do not collide on case-insensitive file
+//!   systems.
+//! * it is not a device name reserved by Windows (e.g. `con` or `nul`).
+//! * it does not exceed [`MAX_PATH_COMPONENT_LEN`] bytes.
+//!
+//! Whenever a name is changed by normalization or contains an uppercase
+//! character, a short hash of the original name is appended so that distinct
+//! names always produce distinct path components.
+
+use std::borrow::Cow;
+use std::path::PathBuf;
+
+/// The maximum length, in bytes, of a normalized path component.
+pub const MAX_PATH_COMPONENT_LEN: usize = 128;
+
+/// The number of hexadecimal digits of a path component's hash suffix.
+const HASH_LEN: usize = 8;
+
+/// The device names that Windows reserves regardless of case or extension.
+const WINDOWS_RESERVED_NAMES: &[&str] = &[
+    "con", "prn", "aux", "nul", "com1", "com2", "com3", "com4", "com5", "com6", "com7", "com8",
+    "com9", "lpt1", "lpt2", "lpt3", "lpt4", "lpt5", "lpt6", "lpt7", "lpt8", "lpt9",
+];
+
+/// Calculates a stable 64-bit FNV-1a hash of the given string.
+///
+/// A hash that is stable across platforms and compiler versions is required as
+/// normalized names must match those of previous runs.
+fn hash(s: &str) -> u64 {
+    const OFFSET: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    s.bytes()
+        .fold(OFFSET, |h, b| (h ^ u64::from(b)).wrapping_mul(PRIME))
+}
+
+/// Normalizes a task, workflow, or call name for use as a path component.
+///
+/// Names consisting only of lowercase ASCII letters, digits, `_`, and `-`
+/// that do not exceed [`MAX_PATH_COMPONENT_LEN`] bytes and are not reserved by
+/// Windows are returned unchanged.
+///
+/// Otherwise, unsafe characters are replaced with `_`, the name is truncated
+/// if necessary, and a `-` followed by a hash of the original name is
+/// appended.
+pub fn path_component(name: &str) -> Cow<'_, str> {
+    let safe = |c: char| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-';
+    if !name.is_empty()
+        && name.len() <= MAX_PATH_COMPONENT_LEN
+        && name.chars().all(safe)
+        && !WINDOWS_RESERVED_NAMES.contains(&name)
+    {
+        return Cow::Borrowed(name);
+    }
+
+    let prefix: String = name
+        .chars()
+        .map(|c| {
+            if safe(c) || c.is_ascii_uppercase() {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(MAX_PATH_COMPONENT_LEN - HASH_LEN - 1)
+        .collect();
+
+    Cow::Owned(format!(
+        "{prefix}-{hash:0width$x}",
+        hash = hash(name) & 0xffffffff,
+        width = HASH_LEN
+    ))
+}
+
+/// Converts a call path (e.g. `workflow.call`) into a relative path of
+/// normalized path components.
+pub fn call_dir(path: &str) -> PathBuf {
+    path.split('.')
+        .map(|c| path_component(c).into_owned())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn it_leaves_safe_names_unchanged() {
+        assert_eq!(path_component("my_task"), "my_task");
+        assert_eq!(path_component("task-2"), "task-2");
+        assert!(matches!(path_component("my_task"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn it_distinguishes_names_differing_by_case() {
+        let lower = path_component("mytask");
+        let upper = path_component("MyTask");
+        let all = path_component("MYTASK");
+        assert_eq!(lower, "mytask");
+        assert!(upper.starts_with("MyTask-"));
+        assert!(all.starts_with("MYTASK-"));
+
+        // The components must differ even on a case-insensitive file system
+        let lower = lower.to_lowercase();
+        let upper = upper.to_lowercase();
+        let all = all.to_lowercase();
+        assert_ne!(lower, upper);
+        assert_ne!(lower, all);
+        assert_ne!(upper, all);
+    }
+
+    #[test]
+    fn it_truncates_long_names() {
+        let name = "a".repeat(300);
+        let component = path_component(&name);
+        assert_eq!(component.len(), MAX_PATH_COMPONENT_LEN);
+        assert!(component.starts_with(&"a".repeat(MAX_PATH_COMPONENT_LEN - HASH_LEN - 1)));
+
+        // Truncation is deterministic and distinguishes names with a common prefix
+        assert_eq!(path_component(&name), component);
+        assert_ne!(path_component(&"a".repeat(301)), component);
+    }
+
+    #[test]
+    fn it_avoids_windows_reserved_names() {
+        for name in ["con", "nul", "aux", "com1", "lpt9"] {
+            let component = path_component(name);
+            assert!(component.starts_with(&format!("{name}-")));
+            assert_ne!(component, name);
+        }
+
+        // Reserved names in other cases are already distinguished by their hash
+        assert!(path_component("CON").starts_with("CON-"));
+        assert_eq!(path_component("console"), "console");
+        assert_eq!(path_component("com10"), "com10");
+    }
+
+    #[test]
+    fn it_is_stable() {
+        // Normalized names must match those of previous runs, so the hash must
+        // never change
+        assert_eq!(path_component("MyTask"), "MyTask-9e65d2b2");
+        assert_eq!(path_component("my task"), "my_task-c5edf468");
+    }
+
+    #[test]
+    fn it_normalizes_call_dirs() {
+        assert_eq!(call_dir("wf.my_call"), Path::new("wf").join("my_call"));
+        assert_eq!(
+            call_dir("wf.MyCall"),
+            Path::new("wf").join(path_component("MyCall").as_ref())
+        );
+    }
+}
@@ -0,0 +1,467 @@
+//! Implementation of secret task inputs.
+//!
+//! A task input is a secret if the task's `inputs` hint marks it with a
+//! `secret` hint:
+//!
+//! ```wdl
+//! hints {
+//!     inputs: input {
+//!         token: hints {
+//!             secret: true
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! The value of a secret input is never taken from the task's inputs; it is
+//! sourced from the engine's [`SecretProvider`] when the task is evaluated and
+//! injected into the task's process as an environment variable or a file (see
+//! [`SecretInjection`]). The input itself evaluates to a reference to the
+//! injected secret, so the secret's value never appears in the command file,
+//! the rerun script, the task's outputs, or the engine's events.
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use anyhow::Context;
+use anyhow::Result;
+use wdl_ast::AstToken;
+use wdl_ast::v1::Expr;
+use wdl_ast::v1::LiteralExpr;
+use wdl_ast::v1::TASK_HINT_INPUTS;
+use wdl_ast::v1::TaskDefinition;
+
+/// The name of the hint that marks a task input as a secret.
+pub const SECRET_HINT: &str = "secret";
+
+/// The prefix of the environment variables that secrets are injected as.
+pub const SECRET_ENV_PREFIX: &str = "WDL_SECRET_";
+
+/// The name of the directory, within a task execution's temp directory, that
+/// secrets are injected into as files.
+pub const SECRETS_DIR_NAME: &str = "secrets";
+
+/// Represents the value of a secret.
+///
+/// The value of a secret is not displayed by its [`Debug`] implementation.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Secret {
+    /// The value of the secret.
+    value: String,
+    /// The fingerprint of the secret.
+    fingerprint: String,
+}
+
+impl Secret {
+    /// Constructs a new secret with the given value and fingerprint.
+    ///
+    /// The fingerprint identifies the value of the secret without revealing
+    /// it; it must remain the same for as long as the value does, so that it
+    /// may be used in place of the value (e.g. in a call cache key).
+    pub fn new(value: impl Into<String>, fingerprint: impl Into<String>) -> Self {
+        Self {
+            value: value.into(),
+            fingerprint: fingerprint.into(),
+        }
+    }
+
+    /// Gets the value of the secret.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Gets the fingerprint of the secret.
+    pub fn fingerprint(&self) -> &str {
+        &self.fingerprint
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Secret")
+            .field("value", &"<redacted>")
+            .field("fingerprint", &self.fingerprint)
+            .finish()
+    }
+}
+
+/// Represents a provider of the values of secret task inputs.
+pub trait SecretProvider: Send + Sync {
+    /// Gets the secret with the given key.
+    ///
+    /// The key of a secret input is the name of the input.
+    fn secret(&self, key: &str) -> Result<Secret>;
+}
+
+/// Represents how secrets are injected into a task's process.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SecretInjection {
+    /// A secret is injected as an environment variable named after the input
+    /// (see [`secret_env_name`]).
+    ///
+    /// The input evaluates to a reference to the variable (e.g.
+    /// `${WDL_SECRET_TOKEN}`) that the shell expands when the command runs.
+    #[default]
+    Environment,
+    /// A secret is injected as a file named after the input (see
+    /// [`path_component`](crate::path_component)) in the [`SECRETS_DIR_NAME`]
+    /// directory of the execution's temp directory.
+    ///
+    /// The input evaluates to the path of the file; on Unix, the file is
+    /// readable only by its owner.
+    File,
+}
+
+/// Gets the name of the environment variable a secret input is injected as.
+pub fn secret_env_name(input: &str) -> String {
+    format!(
+        "{SECRET_ENV_PREFIX}{input}",
+        input = input.to_ascii_uppercase()
+    )
+}
+
+/// Gets the names of the inputs of the given task that are marked as secrets.
+///
+/// Only a literal `true` value for the [`SECRET_HINT`] marks an input as a
+/// secret, as secrets must be known before any expression is evaluated.
+pub(crate) fn secret_inputs(definition: &TaskDefinition) -> Vec<String> {
+    let Some(section) = definition.hints() else {
+        return Vec::new();
+    };
+
+    section
+        .items()
+        .filter(|item| item.name().as_str() == TASK_HINT_INPUTS)
+        .filter_map(|item| match item.expr() {
+            Expr::Literal(LiteralExpr::Input(input)) => Some(input),
+            _ => None,
+        })
+        .flat_map(|input| input.items())
+        .filter_map(|item| {
+            // Struct members cannot be secrets
+            let mut names = item.names();
+            let name = names.next()?;
+            if names.next().is_some() {
+                return None;
+            }
+
+            let Expr::Literal(LiteralExpr::Hints(hints)) = item.expr() else {
+                return None;
+            };
+
+            hints
+                .items()
+                .any(|hint| {
+                    hint.name().as_str() == SECRET_HINT
+                        && matches!(
+                            hint.expr(),
+                            Expr::Literal(LiteralExpr::Boolean(b)) if b.value()
+                        )
+                })
+                .then(|| name.as_str().to_string())
+        })
+        .collect()
+}
+
+/// Writes a secret to the given file.
+///
+/// On Unix, the file is created readable only by its owner.
+pub(crate) fn write_secret_file(path: &Path, secret: &Secret) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).with_context(|| {
+            format!(
+                "failed to create directory `{path}`",
+                path = parent.display()
+            )
+        })?;
+    }
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let mut file = options.open(path).with_context(|| {
+        format!(
+            "failed to create secret file `{path}`",
+            path = path.display()
+        )
+    })?;
+
+    std::io::Write::write_all(&mut file, secret.value().as_bytes()).with_context(|| {
+        format!(
+            "failed to write secret file `{path}`",
+            path = path.display()
+        )
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+    use wdl_analysis::document::Document;
+    use wdl_ast::Ast;
+
+    use super::*;
+    use crate::Engine;
+    use crate::EvaluatedTask;
+    use crate::EvaluationError;
+    use crate::EvaluationResult;
+    use crate::RERUN_SCRIPT_FILE_NAME;
+    use crate::RerunScriptPolicy;
+    use crate::TaskInputs;
+    use crate::local::LocalTaskExecutionBackend;
+    use crate::testing::analyze;
+    use crate::v1::TaskEvaluator;
+
+    /// The value of the secret returned by the test provider.
+    const SECRET: &str = "hunter2-very-secret";
+
+    /// A secret provider that returns the same secret for every key.
+    struct TestProvider;
+
+    impl SecretProvider for TestProvider {
+        fn secret(&self, key: &str) -> Result<Secret> {
+            Ok(Secret::new(SECRET, format!("fingerprint-of-{key}")))
+        }
+    }
+
+    /// The source of a task with a secret input.
+    const SOURCE: &str = r#"version 1.2
+
+task test {
+    input {
+        String token
+        String name
+    }
+
+    command <<<
+        printf '%s' "~{token}" > token.txt
+        echo "hello ~{name}"
+        exit ~{if name == "fail" then 1 else 0}
+    >>>
+
+    output {
+        String reference = token
+        String seen = read_string("token.txt")
+    }
+
+    hints {
+        inputs: input {
+            token: hints {
+                secret: true
+            }
+        }
+    }
+}
+"#;
+
+    /// Evaluates the test task with the given engine and name input.
+    async fn evaluate(
+        engine: &mut Engine,
+        document: &Document,
+        root: &Path,
+        name: &str,
+    ) -> EvaluationResult<EvaluatedTask> {
+        let task = document.task_by_name("test").expect("should have task");
+        let mut inputs = TaskInputs::default();
+        inputs.set("name", name.to_string());
+        TaskEvaluator::new(engine)
+            .evaluate(document, task, &inputs, root, "test")
+            .await
+    }
+
+    /// Gets the message of the given evaluation error.
+    fn message(e: EvaluationError) -> String {
+        match e {
+            EvaluationError::Source(diagnostic) => diagnostic.message().to_string(),
+            EvaluationError::Other(e) => format!("{e:#}"),
+        }
+    }
+
+    /// Reads every file under the given directory.
+    fn read_all(dir: &Path) -> Vec<(String, String)> {
+        let mut files = Vec::new();
+        for entry in walkdir::WalkDir::new(dir) {
+            let entry = entry.unwrap();
+            if entry.file_type().is_file() {
+                let contents = fs::read(entry.path()).unwrap();
+                files.push((
+                    entry.path().display().to_string(),
+                    String::from_utf8_lossy(&contents).into_owned(),
+                ));
+            }
+        }
+
+        files
+    }
+
+    #[test]
+    fn it_finds_secret_inputs() {
+        let (document, diagnostics) = wdl_ast::Document::parse(SOURCE);
+        assert!(diagnostics.is_empty());
+
+        let Ast::V1(ast) = document.ast() else {
+            panic!("should be a 1.x document");
+        };
+
+        let definition = ast.tasks().next().expect("should have a task");
+        assert_eq!(secret_inputs(&definition), ["token"]);
+    }
+
+    #[test]
+    fn it_redacts_debug_output() {
+        let secret = Secret::new(SECRET, "abc");
+        let debug = format!("{secret:?}");
+        assert!(!debug.contains(SECRET));
+        assert!(debug.contains("abc"));
+        assert_eq!(secret_env_name("token"), "WDL_SECRET_TOKEN");
+    }
+
+    #[tokio::test]
+    async fn it_injects_secrets_as_environment_variables() {
+        let dir = TempDir::new().expect("failed to create temporary directory");
+        let document = analyze(&dir, SOURCE).await;
+
+        let mut engine = Engine::new(LocalTaskExecutionBackend::new())
+            .with_secret_provider(TestProvider)
+            .with_rerun_scripts(RerunScriptPolicy::Always);
+
+        let root = dir.path().join("first");
+        let evaluated = evaluate(&mut engine, &document, &root, "world")
+            .await
+            .unwrap();
+        let fingerprints = evaluated.secret_fingerprints().clone();
+        assert_eq!(
+            fingerprints.get("token").map(String::as_str),
+            Some("fingerprint-of-token")
+        );
+
+        let outputs = evaluated.into_result().unwrap();
+        assert_eq!(
+            outputs
+                .get("reference")
+                .unwrap()
+                .as_string()
+                .unwrap()
+                .as_str(),
+            "${WDL_SECRET_TOKEN}"
+        );
+        assert_eq!(
+            outputs.get("seen").unwrap().as_string().unwrap().as_str(),
+            SECRET
+        );
+
+        // The serialized outputs only contain the reference to the secret; the
+        // `seen` output is excluded as the task itself wrote the secret out
+        let reference = serde_json::to_string(outputs.get("reference").unwrap()).unwrap();
+        assert!(!reference.contains(SECRET));
+
+        // Nothing the engine wrote contains the secret; only the file the task
+        // itself wrote does
+        let rerun = root.join(RERUN_SCRIPT_FILE_NAME);
+        assert!(rerun.is_file(), "rerun script should be written");
+        assert!(
+            fs::read_to_string(&rerun)
+                .unwrap()
+                .contains("WDL_SECRET_TOKEN")
+        );
+        for (path, contents) in read_all(&root) {
+            if path.ends_with("token.txt") {
+                continue;
+            }
+
+            assert!(!contents.contains(SECRET), "`{path}` contains the secret");
+        }
+
+        // A second run with an unchanged secret has the same fingerprints
+        let evaluated = evaluate(&mut engine, &document, &dir.path().join("second"), "world")
+            .await
+            .unwrap();
+        assert_eq!(evaluated.secret_fingerprints(), &fingerprints);
+    }
+
+    #[tokio::test]
+    async fn it_injects_secrets_as_files() {
+        let dir = TempDir::new().expect("failed to create temporary directory");
+        let document = analyze(&dir, SOURCE).await;
+
+        let mut engine = Engine::new(LocalTaskExecutionBackend::new())
+            .with_secret_provider(TestProvider)
+            .with_secret_injection(SecretInjection::File)
+            .with_rerun_scripts(RerunScriptPolicy::OnFailure);
+
+        let root = dir.path().join("run");
+        let evaluated = evaluate(&mut engine, &document, &root, "fail")
+            .await
+            .unwrap();
+        assert_eq!(evaluated.status_code(), 1);
+
+        let path = root.join("tmp").join(SECRETS_DIR_NAME).join("token");
+        assert_eq!(fs::read_to_string(&path).unwrap(), SECRET);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        // The failed execution's rerun script and command reference the file
+        assert!(root.join(RERUN_SCRIPT_FILE_NAME).is_file());
+        assert!(
+            fs::read_to_string(evaluated.command())
+                .unwrap()
+                .contains(&path.display().to_string())
+        );
+        for (file, contents) in read_all(&root) {
+            if file.ends_with("token.txt") || Path::new(&file) == path {
+                continue;
+            }
+
+            assert!(!contents.contains(SECRET), "`{file}` contains the secret");
+        }
+    }
+
+    #[tokio::test]
+    async fn it_rejects_misconfigured_secrets() {
+        let dir = TempDir::new().expect("failed to create temporary directory");
+        let document = analyze(&dir, SOURCE).await;
+
+        // No secret provider
+        let mut engine = Engine::new(LocalTaskExecutionBackend::new());
+        let e = evaluate(&mut engine, &document, &dir.path().join("a"), "world")
+            .await
+            .map(|_| ())
+            .expect_err("evaluation should fail");
+        assert_eq!(
+            message(e),
+            "secret input `token` of task `test` requires a secret provider"
+        );
+
+        // The secret is specified as an input
+        let mut engine =
+            Engine::new(LocalTaskExecutionBackend::new()).with_secret_provider(TestProvider);
+        let task = document.task_by_name("test").expect("should have task");
+        let mut inputs = TaskInputs::default();
+        inputs.set("name", "world".to_string());
+        inputs.set("token", SECRET.to_string());
+        let e = TaskEvaluator::new(&mut engine)
+            .evaluate(&document, task, &inputs, &dir.path().join("b"), "test")
+            .await
+            .map(|_| ())
+            .expect_err("evaluation should fail");
+        assert_eq!(
+            message(e),
+            "secret input `token` of task `test` cannot be specified as an input"
+        );
+    }
+}
@@ -0,0 +1,387 @@
+//! Implementation of array elements spilled to disk.
+//!
+//! Very large arrays of primitive values may be spilled to a file in the
+//! evaluation's temp directory rather than held in memory (see
+//! [`Engine::with_array_spill_threshold`](crate::Engine::with_array_spill_threshold)).
+//!
+//! The elements of a spilled array are written sequentially to the file and
+//! read back a page at a time; only the offset of each page is held in memory.
+
+use std::fs::File;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use ordered_float::OrderedFloat;
+use tempfile::NamedTempFile;
+
+use crate::PrimitiveValue;
+use crate::Value;
+
+/// The maximum number of elements in a page of a spilled array.
+const MAX_PAGE_LEN: usize = 4096;
+
+/// The tag of a spilled `None` value.
+const NONE_TAG: u8 = 0;
+/// The tag of a spilled `Boolean` value.
+const BOOLEAN_TAG: u8 = 1;
+/// The tag of a spilled `Int` value.
+const INTEGER_TAG: u8 = 2;
+/// The tag of a spilled `Float` value.
+const FLOAT_TAG: u8 = 3;
+/// The tag of a spilled `String` value.
+const STRING_TAG: u8 = 4;
+/// The tag of a spilled `File` value.
+const FILE_TAG: u8 = 5;
+/// The tag of a spilled `Directory` value.
+const DIRECTORY_TAG: u8 = 6;
+
+/// Tracks the number of spilled elements resident in memory.
+///
+/// This is used by tests to assert that operating on spilled arrays does not
+/// load every element into memory.
+#[cfg(test)]
+pub(crate) mod resident {
+    use std::cell::Cell;
+
+    thread_local! {
+        /// The number of spilled elements currently resident in memory.
+        static CURRENT: Cell<usize> = const { Cell::new(0) };
+        /// The peak number of spilled elements resident in memory.
+        static PEAK: Cell<usize> = const { Cell::new(0) };
+    }
+
+    /// Records that the given number of elements were loaded into memory.
+    pub(crate) fn acquire(count: usize) {
+        let current = CURRENT.get() + count;
+        CURRENT.set(current);
+        PEAK.set(PEAK.get().max(current));
+    }
+
+    /// Records that the given number of elements were released from memory.
+    pub(crate) fn release(count: usize) {
+        CURRENT.set(CURRENT.get() - count);
+    }
+
+    /// Resets the peak number of resident elements to the current number.
+    pub(crate) fn reset_peak() {
+        PEAK.set(CURRENT.get());
+    }
+
+    /// Gets the peak number of resident elements since the last reset.
+    pub(crate) fn peak() -> usize {
+        PEAK.get()
+    }
+}
+
+/// Writes a spilled value.
+///
+/// Returns the number of bytes written.
+///
+/// Returns an error if the value is not a primitive value or `None`.
+fn write_value(writer: &mut impl Write, value: &Value) -> Result<u64> {
+    let (tag, bytes): (u8, Vec<u8>) = match value {
+        Value::None => (NONE_TAG, Vec::new()),
+        Value::Primitive(PrimitiveValue::Boolean(v)) => (BOOLEAN_TAG, vec![u8::from(*v)]),
+        Value::Primitive(PrimitiveValue::Integer(v)) => (INTEGER_TAG, v.to_le_bytes().to_vec()),
+        Value::Primitive(PrimitiveValue::Float(v)) => (FLOAT_TAG, v.0.to_le_bytes().to_vec()),
+        Value::Primitive(PrimitiveValue::String(v)) => (STRING_TAG, string_bytes(v)),
+        Value::Primitive(PrimitiveValue::File(v)) => (FILE_TAG, string_bytes(v)),
+        Value::Primitive(PrimitiveValue::Directory(v)) => (DIRECTORY_TAG, string_bytes(v)),
+        _ => bail!("cannot spill a value of type `{ty}`", ty = value.ty()),
+    };
+
+    writer.write_all(&[tag])?;
+    writer.write_all(&bytes)?;
+    Ok(1 + bytes.len() as u64)
+}
+
+/// Gets the spilled bytes of a string: its length followed by its UTF-8
+/// bytes.
+fn string_bytes(s: &str) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(8 + s.len());
+    bytes.extend((s.len() as u64).to_le_bytes());
+    bytes.extend(s.as_bytes());
+    bytes
+}
+
+/// Reads a spilled value.
+fn read_value(reader: &mut impl Read) -> Result<Value> {
+    /// Reads a fixed number of bytes.
+    fn read_bytes<const N: usize>(reader: &mut impl Read) -> Result<[u8; N]> {
+        let mut bytes = [0; N];
+        reader.read_exact(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// Reads a spilled string.
+    fn read_string(reader: &mut impl Read) -> Result<String> {
+        let len = u64::from_le_bytes(read_bytes(reader)?);
+        let mut bytes = vec![0; len.try_into()?];
+        reader.read_exact(&mut bytes)?;
+        Ok(String::from_utf8(bytes)?)
+    }
+
+    let [tag] = read_bytes(reader)?;
+    Ok(match tag {
+        NONE_TAG => Value::None,
+        BOOLEAN_TAG => PrimitiveValue::Boolean(read_bytes::<1>(reader)?[0] != 0).into(),
+        INTEGER_TAG => PrimitiveValue::Integer(i64::from_le_bytes(read_bytes(reader)?)).into(),
+        FLOAT_TAG => {
+            PrimitiveValue::Float(OrderedFloat(f64::from_le_bytes(read_bytes(reader)?))).into()
+        }
+        STRING_TAG => PrimitiveValue::new_string(read_string(reader)?).into(),
+        FILE_TAG => PrimitiveValue::new_file(read_string(reader)?).into(),
+        DIRECTORY_TAG => PrimitiveValue::new_directory(read_string(reader)?).into(),
+        _ => bail!("invalid spilled value tag `{tag}`"),
+    })
+}
+
+/// Represents a page of spilled elements loaded into memory.
+#[derive(Debug)]
+struct Page(Vec<Value>);
+
+impl Page {
+    /// Constructs a new page from the given elements.
+    fn new(elements: Vec<Value>) -> Self {
+        #[cfg(test)]
+        resident::acquire(elements.len());
+        Self(elements)
+    }
+}
+
+impl Drop for Page {
+    fn drop(&mut self) {
+        #[cfg(test)]
+        resident::release(self.0.len());
+    }
+}
+
+/// Writes the elements of an array to a spill file.
+#[derive(Debug)]
+pub(crate) struct SpillWriter {
+    /// The spill file being written.
+    file: NamedTempFile,
+    /// The buffered writer of the spill file.
+    writer: BufWriter<File>,
+    /// The number of elements in a page.
+    page_len: usize,
+    /// The file offset of each page.
+    pages: Vec<u64>,
+    /// The current file offset.
+    offset: u64,
+    /// The number of elements written.
+    len: usize,
+}
+
+impl SpillWriter {
+    /// Creates a new spill writer for a spill file in the given directory.
+    ///
+    /// The given page length is clamped to a maximum of 4096 elements.
+    pub(crate) fn new(dir: &Path, page_len: usize) -> Result<Self> {
+        let file = NamedTempFile::new_in(dir).with_context(|| {
+            format!(
+                "failed to create array spill file in directory `{dir}`",
+                dir = dir.display()
+            )
+        })?;
+        let writer = BufWriter::new(file.reopen().context("failed to open array spill file")?);
+
+        Ok(Self {
+            file,
+            writer,
+            page_len: page_len.clamp(1, MAX_PAGE_LEN),
+            pages: Vec::new(),
+            offset: 0,
+            len: 0,
+        })
+    }
+
+    /// Writes an element to the spill file.
+    pub(crate) fn push(&mut self, value: &Value) -> Result<()> {
+        if self.len % self.page_len == 0 {
+            self.pages.push(self.offset);
+        }
+
+        self.offset +=
+            write_value(&mut self.writer, value).context("failed to write to array spill file")?;
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Finishes writing the spill file.
+    pub(crate) fn finish(mut self) -> Result<SpilledElements> {
+        self.writer
+            .flush()
+            .context("failed to write to array spill file")?;
+
+        Ok(SpilledElements {
+            file: self.file,
+            page_len: self.page_len,
+            pages: self.pages,
+            len: self.len,
+            cache: Default::default(),
+        })
+    }
+}
+
+/// Represents the elements of an array spilled to disk.
+///
+/// The spill file is deleted when the elements are dropped.
+#[derive(Debug)]
+pub(crate) struct SpilledElements {
+    /// The spill file.
+    file: NamedTempFile,
+    /// The number of elements in a page.
+    page_len: usize,
+    /// The file offset of each page.
+    pages: Vec<u64>,
+    /// The number of elements.
+    len: usize,
+    /// The most recently accessed page, used for element access.
+    cache: Mutex<Option<(usize, Arc<Page>)>>,
+}
+
+impl SpilledElements {
+    /// Gets the number of elements.
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Reads the page with the given index.
+    fn read_page(&self, index: usize) -> Result<Page> {
+        let read = || -> Result<Page> {
+            let count = self.page_len.min(self.len - index * self.page_len);
+            let mut file = self.file.reopen()?;
+            file.seek(SeekFrom::Start(self.pages[index]))?;
+            let mut reader = BufReader::new(file);
+            Ok(Page::new(
+                (0..count)
+                    .map(|_| read_value(&mut reader))
+                    .collect::<Result<_>>()?,
+            ))
+        };
+
+        read().with_context(|| {
+            format!(
+                "failed to read array spill file `{path}`",
+                path = self.file.path().display()
+            )
+        })
+    }
+
+    /// Gets the element at the given index.
+    ///
+    /// Returns `Ok(None)` if the index is out of bounds.
+    ///
+    /// Returns an error if the spill file could not be read.
+    pub(crate) fn get(&self, index: usize) -> Result<Option<Value>> {
+        if index >= self.len {
+            return Ok(None);
+        }
+
+        let page = index / self.page_len;
+        let mut cache = self.cache.lock().expect("failed to lock page cache");
+        let elements = match &*cache {
+            Some((i, elements)) if *i == page => elements.clone(),
+            _ => {
+                let elements = Arc::new(self.read_page(page)?);
+                *cache = Some((page, elements.clone()));
+                elements
+            }
+        };
+
+        Ok(Some(elements.0[index % self.page_len].clone()))
+    }
+
+    /// Iterates the elements.
+    ///
+    /// Only one page of elements is held in memory by the iterator at a time.
+    ///
+    /// The iterator yields an error if the spill file could not be read, after
+    /// which it yields no more elements.
+    pub(crate) fn iter(self: &Arc<Self>) -> SpilledIter {
+        SpilledIter {
+            elements: self.clone(),
+            page: None,
+            index: 0,
+        }
+    }
+
+    /// Creates new spilled elements by mapping each element with the given
+    /// function.
+    ///
+    /// The new spill file is created in the same directory as this one.
+    pub(crate) fn try_map(
+        self: &Arc<Self>,
+        mut f: impl FnMut(Value) -> Result<Value>,
+    ) -> Result<Self> {
+        let dir = self
+            .file
+            .path()
+            .parent()
+            .expect("spill file should have a parent directory");
+
+        let mut writer = SpillWriter::new(dir, self.page_len)?;
+        for element in self.iter() {
+            writer.push(&f(element?)?)?;
+        }
+
+        writer.finish()
+    }
+}
+
+/// An iterator over spilled elements.
+#[derive(Debug)]
+pub(crate) struct SpilledIter {
+    /// The spilled elements being iterated.
+    elements: Arc<SpilledElements>,
+    /// The current page.
+    page: Option<Page>,
+    /// The index of the next element.
+    index: usize,
+}
+
+impl Iterator for SpilledIter {
+    type Item = Result<Value>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.elements.len {
+            self.page = None;
+            return None;
+        }
+
+        let page_len = self.elements.page_len;
+        if self.index % page_len == 0 {
+            // Drop the previous page before reading the next one
+            self.page = None;
+            match self.elements.read_page(self.index / page_len) {
+                Ok(page) => self.page = Some(page),
+                Err(e) => {
+                    // Stop iterating after an error
+                    self.index = self.elements.len;
+                    return Some(Err(e));
+                }
+            }
+        }
+
+        let element =
+            self.page.as_ref().expect("should have a page").0[self.index % page_len].clone();
+        self.index += 1;
+        Some(Ok(element))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // An error ends the iteration early
+        (0, Some(self.elements.len - self.index))
+    }
+}
@@ -4,11 +4,13 @@ use std::collections::HashMap;
 use std::path::Path;
 use std::sync::LazyLock;
 
+use itertools::process_results;
 use wdl_analysis::stdlib::Binding;
 use wdl_analysis::types::Type;
 use wdl_ast::Diagnostic;
 use wdl_ast::Span;
 
+use crate::Array;
 use crate::Coercible;
 use crate::EvaluationContext;
 use crate::Value;
@@ -40,7 +42,7 @@ mod read_boolean;
 mod read_float;
 mod read_int;
 mod read_json;
-mod read_lines;
+pub(crate) mod read_lines;
 mod read_map;
 mod read_object;
 mod read_objects;
@@ -138,6 +140,47 @@ impl<'a> CallContext<'a> {
         self.context.stderr()
     }
 
+    /// Creates a new array of the given type.
+    ///
+    /// The array's elements are spilled to disk if there are more than the
+    /// evaluation's array spill threshold (see
+    /// [`EvaluationContext::array_spill_threshold`]).
+    ///
+    /// Returns an error if an element did not coerce to the array's element
+    /// type or if the elements could not be spilled.
+    fn new_array<V>(
+        &self,
+        ty: impl Into<Type>,
+        elements: impl IntoIterator<Item = V>,
+    ) -> anyhow::Result<Array>
+    where
+        V: Into<Value>,
+    {
+        Array::new_spillable(
+            ty,
+            elements,
+            self.context
+                .array_spill_threshold()
+                .map(|threshold| (threshold, self.temp_dir())),
+        )
+    }
+
+    /// Creates a new array of the given type from elements that may have
+    /// failed to be produced, such as the elements of another array.
+    ///
+    /// Returns the first error of the given elements or an error if the array
+    /// could not be created (see [`CallContext::new_array`]).
+    fn try_new_array<V>(
+        &self,
+        ty: impl Into<Type>,
+        elements: impl IntoIterator<Item = anyhow::Result<V>>,
+    ) -> anyhow::Result<Array>
+    where
+        V: Into<Value>,
+    {
+        process_results(elements, |elements| self.new_array(ty, elements)).and_then(|array| array)
+    }
+
     /// Coerces an argument to the given type.
     ///
     /// # Panics
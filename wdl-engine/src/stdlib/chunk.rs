@@ -1,5 +1,8 @@
 //! Implements the `chunk` function from the WDL standard library.
 
+use std::borrow::Cow;
+
+use itertools::Itertools;
 use wdl_ast::Diagnostic;
 
 use super::CallContext;
@@ -43,13 +46,16 @@ fn chunk(context: CallContext<'_>) -> Result<Value, Diagnostic> {
         .expect("type should be an array")
         .element_type();
 
-    let elements = array
-        .as_slice()
-        .chunks(size as usize)
+    let chunks = array.iter().chunks(size as usize);
+    let elements = chunks
+        .into_iter()
         .map(|chunk| {
-            Array::new_unchecked(element_ty.clone(), Vec::from_iter(chunk.iter().cloned())).into()
+            context
+                .try_new_array(element_ty.clone(), chunk.map(|v| v.map(Cow::into_owned)))
+                .map(Value::from)
         })
-        .collect();
+        .collect::<anyhow::Result<_>>()
+        .map_err(|e| function_call_failed("chunk", format!("{e:?}"), context.call_site))?;
 
     Ok(Array::new_unchecked(context.return_type, elements).into())
 }
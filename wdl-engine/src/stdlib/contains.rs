@@ -1,5 +1,6 @@
 //! Implements the `contains` function from the WDL standard library.
 
+use itertools::process_results;
 use wdl_analysis::types::PrimitiveType;
 use wdl_ast::Diagnostic;
 
@@ -7,6 +8,7 @@ use super::CallContext;
 use super::Function;
 use super::Signature;
 use crate::Value;
+use crate::diagnostics::function_call_failed;
 
 /// Tests whether the given array contains at least one occurrence of the given
 /// value.
@@ -23,11 +25,11 @@ fn contains(context: CallContext<'_>) -> Result<Value, Diagnostic> {
 
     let item = &context.arguments[1].value;
 
-    Ok(array
-        .as_slice()
-        .iter()
-        .any(|e| Value::equals(e, item).unwrap_or(false))
-        .into())
+    process_results(array.iter(), |mut elements| {
+        elements.any(|e| Value::equals(&e, item).unwrap_or(false))
+    })
+    .map(Into::into)
+    .map_err(|e| function_call_failed("contains", format!("{e:?}"), context.call_site))
 }
 
 /// Gets the function describing `contains`.
@@ -14,6 +14,7 @@ use crate::CompoundValue;
 use crate::PrimitiveValue;
 use crate::Struct;
 use crate::Value;
+use crate::diagnostics::function_call_failed;
 
 /// Given a Map and a key, tests whether the collection contains an entry with
 /// the given key.
@@ -94,12 +95,11 @@ fn contains_key_recursive(context: CallContext<'_>) -> Result<Value, Diagnostic>
         .coerce_argument(1, ANALYSIS_STDLIB.array_string_type().clone())
         .unwrap_array();
 
-    for key in keys
-        .as_slice()
-        .iter()
-        .map(|v| v.as_string().expect("element should be a string"))
-    {
-        match get(&value, key) {
+    for key in keys.iter() {
+        let key = key.map_err(|e| {
+            function_call_failed("contains_key", format!("{e:?}"), context.call_site)
+        })?;
+        match get(&value, key.as_string().expect("key should be a string")) {
             Some(v) => value = v,
             None => return Ok(false.into()),
         }
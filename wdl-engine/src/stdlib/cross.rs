@@ -1,6 +1,7 @@
 //! Implements the `cross` function from the WDL standard library.
 
 use itertools::Itertools;
+use itertools::process_results;
 use wdl_ast::Diagnostic;
 
 use super::CallContext;
@@ -9,6 +10,7 @@ use super::Signature;
 use crate::Array;
 use crate::Pair;
 use crate::Value;
+use crate::diagnostics::function_call_failed;
 
 /// Creates an array of Pairs containing the cross product of two input arrays,
 /// i.e., each element in the first array is paired with each element in the
@@ -40,12 +42,20 @@ fn cross(context: CallContext<'_>) -> Result<Value, Diagnostic> {
 
     let element_ty = context.return_type.as_array().unwrap().element_type();
 
-    let elements = left
-        .as_slice()
+    let right = right
         .iter()
-        .cartesian_product(right.as_slice().iter())
-        .map(|(l, r)| Pair::new_unchecked(element_ty.clone(), l.clone(), r.clone()).into())
-        .collect();
+        .collect::<anyhow::Result<Vec<_>>>()
+        .map_err(|e| function_call_failed("cross", format!("{e:?}"), context.call_site))?;
+
+    let elements = process_results(left.iter(), |left| {
+        left.cartesian_product(&right)
+            .map(|(l, r)| {
+                Pair::new_unchecked(element_ty.clone(), l.into_owned(), r.clone().into_owned())
+                    .into()
+            })
+            .collect()
+    })
+    .map_err(|e| function_call_failed("cross", format!("{e:?}"), context.call_site))?;
     Ok(Array::new_unchecked(context.return_type, elements).into())
 }
 
@@ -1,5 +1,7 @@
 //! Implements the `flatten` function from the WDL standard library.
 
+use std::borrow::Cow;
+
 use wdl_ast::Diagnostic;
 
 use super::CallContext;
@@ -7,6 +9,7 @@ use super::Function;
 use super::Signature;
 use crate::Array;
 use crate::Value;
+use crate::diagnostics::function_call_failed;
 
 /// Flattens a nested Array[Array[X]] by concatenating all of the element
 /// arrays, in order, into a single array.
@@ -29,19 +32,27 @@ fn flatten(context: CallContext<'_>) -> Result<Value, Diagnostic> {
         .as_array()
         .expect("argument should be an array");
 
-    let elements = array
-        .as_slice()
+    // Arrays are cheap to clone, so clone the inner arrays to iterate them
+    let arrays = array
         .iter()
-        .flat_map(|v| {
-            v.as_array()
-                .expect("array element should be an array")
-                .as_slice()
-                .iter()
-                .cloned()
+        .map(|v| {
+            v.map(|v| {
+                v.as_array()
+                    .expect("array element should be an array")
+                    .clone()
+            })
         })
-        .collect();
+        .collect::<anyhow::Result<Vec<_>>>()
+        .map_err(|e| function_call_failed("flatten", format!("{e:?}"), context.call_site))?;
+
+    let elements = arrays
+        .iter()
+        .flat_map(|a| a.iter().map(|v| v.map(Cow::into_owned)));
 
-    Ok(Array::new_unchecked(context.return_type, elements).into())
+    context
+        .try_new_array(context.return_type.clone(), elements)
+        .map(Into::into)
+        .map_err(|e| function_call_failed("flatten", format!("{e:?}"), context.call_site))
 }
 
 /// Gets the function describing `flatten`.
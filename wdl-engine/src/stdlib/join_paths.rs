@@ -14,6 +14,7 @@ use super::Signature;
 use crate::PrimitiveValue;
 use crate::Value;
 use crate::diagnostics::array_path_not_relative;
+use crate::diagnostics::function_call_failed;
 use crate::diagnostics::path_not_relative;
 
 /// Joins together two paths into an absolute path in the host
@@ -80,7 +81,14 @@ fn join_paths(context: CallContext<'_>) -> Result<Value, Diagnostic> {
             .unwrap_array();
 
         (
-            array.as_slice()[0].clone().unwrap_string(),
+            array
+                .get(0)
+                .map_err(|e| {
+                    function_call_failed("join_paths", format!("{e:?}"), context.call_site)
+                })?
+                .expect("array should not be empty")
+                .into_owned()
+                .unwrap_string(),
             array,
             true,
             context.arguments[0].span,
@@ -99,13 +107,11 @@ fn join_paths(context: CallContext<'_>) -> Result<Value, Diagnostic> {
 
     let mut path = PathBuf::from(Arc::unwrap_or_clone(first));
 
-    for (i, element) in array
-        .as_slice()
-        .iter()
-        .enumerate()
-        .skip(if skip { 1 } else { 0 })
-    {
-        let next = element.as_string().expect("element should be string");
+    for (i, element) in array.iter().enumerate().skip(if skip { 1 } else { 0 }) {
+        let next = element
+            .map_err(|e| function_call_failed("join_paths", format!("{e:?}"), context.call_site))?
+            .into_owned()
+            .unwrap_string();
 
         let next = Path::new(next.as_str());
         if !next.is_relative() {
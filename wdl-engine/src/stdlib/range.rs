@@ -7,7 +7,6 @@ use wdl_ast::Diagnostic;
 use super::CallContext;
 use super::Function;
 use super::Signature;
-use crate::Array;
 use crate::Value;
 use crate::diagnostics::function_call_failed;
 
@@ -33,7 +32,10 @@ fn range(context: CallContext<'_>) -> Result<Value, Diagnostic> {
         ));
     }
 
-    Ok(Array::new_unchecked(context.return_type, (0..n).map(Into::into).collect()).into())
+    context
+        .new_array(context.return_type.clone(), 0..n)
+        .map(Into::into)
+        .map_err(|e| function_call_failed("range", format!("{e:?}"), context.call_site))
 }
 
 /// Gets the function describing `range`.
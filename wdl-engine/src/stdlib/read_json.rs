@@ -18,6 +18,11 @@ use crate::diagnostics::function_call_failed;
 /// Reads a JSON file into a WDL value whose type depends on the file's
 /// contents.
 ///
+/// JSON numbers without a fractional part or exponent are read as `Int`
+/// values when they are within the range of a 64-bit signed integer; all other
+/// numbers are read as `Float` values. Object members are read in order; an
+/// object with only `left` and `right` members may be coerced to a `Pair`.
+///
 /// https://github.com/openwdl/wdl/blob/wdl-1.2/SPEC.md#read_json
 fn read_json(context: CallContext<'_>) -> Result<Value, Diagnostic> {
     debug_assert!(context.arguments.len() == 1);
@@ -1,22 +1,132 @@
 //! Implements the `read_lines` function from the WDL standard library.
 
+#[cfg(test)]
+use std::cell::Cell;
 use std::fs;
+use std::io;
 use std::io::BufRead;
 use std::io::BufReader;
+use std::io::Lines;
+use std::io::Read;
+use std::path::Path;
+use std::path::PathBuf;
 
 use anyhow::Context;
+use itertools::process_results;
 use wdl_analysis::stdlib::STDLIB as ANALYSIS_STDLIB;
 use wdl_analysis::types::PrimitiveType;
 use wdl_ast::Diagnostic;
+use wdl_ast::Span;
 
 use super::CallContext;
 use super::Function;
 use super::Signature;
-use crate::Array;
 use crate::PrimitiveValue;
 use crate::Value;
 use crate::diagnostics::function_call_failed;
 
+#[cfg(test)]
+thread_local! {
+    /// The number of bytes read from files by [`LineReader`] on the current
+    /// thread.
+    pub(crate) static BYTES_READ: Cell<u64> = const { Cell::new(0) };
+}
+
+/// A file reader that records the number of bytes read in tests.
+struct FileReader(fs::File);
+
+impl Read for FileReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.0.read(buf)?;
+
+        #[cfg(test)]
+        BYTES_READ.with(|b| b.set(b.get() + read as u64));
+
+        Ok(read)
+    }
+}
+
+/// Reads the lines of a file for `read_lines`.
+///
+/// This is used both by `read_lines` itself and by the evaluation of
+/// expressions that only need the number of lines (`length(read_lines(f))`)
+/// or a single line (`read_lines(f)[i]`) of a file; such expressions are
+/// evaluated without reading the lines into an array.
+///
+/// Errors are reported identically for each use.
+pub(crate) struct LineReader {
+    /// The path of the file being read.
+    path: PathBuf,
+    /// The lines of the file.
+    lines: Lines<BufReader<FileReader>>,
+    /// The call site of `read_lines`.
+    call_site: Span,
+}
+
+impl LineReader {
+    /// Opens a file for reading its lines.
+    pub fn open(path: &Path, call_site: Span) -> Result<Self, Diagnostic> {
+        let file = fs::File::open(path)
+            .with_context(|| format!("failed to open file `{path}`", path = path.display()))
+            .map_err(|e| function_call_failed("read_lines", format!("{e:?}"), call_site))?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            lines: BufReader::new(FileReader(file)).lines(),
+            call_site,
+        })
+    }
+
+    /// Counts the remaining lines of the file without storing them.
+    pub fn count(mut self) -> Result<usize, Diagnostic> {
+        let mut count = 0;
+        while self.next_line()?.is_some() {
+            count += 1;
+        }
+
+        Ok(count)
+    }
+
+    /// Reads the line at the given index.
+    ///
+    /// The file is only read up to the requested line.
+    ///
+    /// Returns `Ok(Err(count))` with the number of lines in the file if the
+    /// index is out of range.
+    pub fn nth(mut self, index: i64) -> Result<Result<String, usize>, Diagnostic> {
+        let Ok(index) = usize::try_from(index) else {
+            return self.count().map(Err);
+        };
+
+        for i in 0..=index {
+            match self.next_line()? {
+                Some(line) if i == index => return Ok(Ok(line)),
+                Some(_) => continue,
+                None => return Ok(Err(i)),
+            }
+        }
+
+        unreachable!("the line should have been returned")
+    }
+
+    /// Reads the next line of the file.
+    fn next_line(&mut self) -> Result<Option<String>, Diagnostic> {
+        self.lines
+            .next()
+            .transpose()
+            .with_context(|| format!("failed to read file `{path}`", path = self.path.display()))
+            .map_err(|e| function_call_failed("read_lines", format!("{e:?}"), self.call_site))
+    }
+}
+
+impl Iterator for LineReader {
+    type Item = Result<String, Diagnostic>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_line().transpose()
+    }
+}
+
 /// Reads each line of a file as a String, and returns all lines in the file as
 /// an Array[String].
 ///
@@ -39,26 +149,18 @@ fn read_lines(context: CallContext<'_>) -> Result<Value, Diagnostic> {
             .as_str(),
     );
 
-    let file = fs::File::open(&path)
-        .with_context(|| format!("failed to open file `{path}`", path = path.display()))
-        .map_err(|e| function_call_failed("read_lines", format!("{e:?}"), context.call_site))?;
-
-    let elements = BufReader::new(file)
-        .lines()
-        .map(|line| {
-            Ok(PrimitiveValue::new_string(
-                line.with_context(|| {
-                    format!("failed to read file `{path}`", path = path.display())
-                })
-                .map_err(|e| {
-                    function_call_failed("read_lines", format!("{e:?}"), context.call_site)
-                })?,
-            )
-            .into())
-        })
-        .collect::<Result<Vec<Value>, _>>()?;
+    let lines = LineReader::open(&path, context.call_site)?
+        .map(|line| line.map(PrimitiveValue::new_string));
 
-    Ok(Array::new_unchecked(context.return_type, elements).into())
+    // Stream the lines into the array so that a spilled array is never held in
+    // memory
+    process_results(lines, |lines| {
+        context
+            .new_array(context.return_type.clone(), lines)
+            .map_err(|e| function_call_failed("read_lines", format!("{e:?}"), context.call_site))
+    })
+    .and_then(|array| array)
+    .map(Into::into)
 }
 
 /// Gets the function describing `read_lines`.
@@ -71,6 +173,7 @@ mod test {
     use pretty_assertions::assert_eq;
     use wdl_ast::version::V1;
 
+    use super::BYTES_READ;
     use crate::PrimitiveValue;
     use crate::v1::test::TestEnv;
     use crate::v1::test::eval_v1_expr;
@@ -113,4 +216,69 @@ mod test {
         let value = eval_v1_expr(&mut env, V1::Two, "read_lines('empty')").unwrap();
         assert!(value.unwrap_array().is_empty());
     }
+
+    #[test]
+    fn read_lines_fused() {
+        let mut env = TestEnv::default();
+        env.write_file("foo", "\nhello!\nworld!\n\r\nhi!\r\nthere!");
+        env.write_file("empty", "");
+        env.insert_name("file", PrimitiveValue::new_file("foo"));
+
+        // The fused expressions should evaluate the same as the unfused ones
+        for (fused, unfused) in [
+            (
+                "length(read_lines('foo'))",
+                "length(flatten([read_lines('foo')]))",
+            ),
+            (
+                "length((read_lines(file)))",
+                "length(flatten([read_lines(file)]))",
+            ),
+            (
+                "length(read_lines('empty'))",
+                "length(flatten([read_lines('empty')]))",
+            ),
+            ("read_lines('foo')[1]", "flatten([read_lines('foo')])[1]"),
+            ("(read_lines(file))[5]", "flatten([read_lines(file)])[5]"),
+        ] {
+            let fused = eval_v1_expr(&mut env, V1::Two, fused).unwrap();
+            let unfused = eval_v1_expr(&mut env, V1::Two, unfused).unwrap();
+            assert_eq!(fused.to_string(), unfused.to_string());
+        }
+
+        // The fused expressions should fail the same as the unfused ones
+        for (fused, unfused) in [
+            (
+                "length(read_lines('does-not-exist'))",
+                "length(flatten([read_lines('does-not-exist')]))",
+            ),
+            (
+                "read_lines('does-not-exist')[0]",
+                "flatten([read_lines('does-not-exist')])[0]",
+            ),
+            ("read_lines('foo')[6]", "flatten([read_lines('foo')])[6]"),
+            ("read_lines('foo')[-1]", "flatten([read_lines('foo')])[-1]"),
+            (
+                "read_lines('empty')[0]",
+                "flatten([read_lines('empty')])[0]",
+            ),
+        ] {
+            let fused = eval_v1_expr(&mut env, V1::Two, fused).unwrap_err();
+            let unfused = eval_v1_expr(&mut env, V1::Two, unfused).unwrap_err();
+            assert_eq!(fused.message(), unfused.message());
+        }
+
+        // Indexing should only read the file up to the requested line
+        env.write_file("big", "line\n".repeat(100_000));
+        BYTES_READ.with(|b| b.set(0));
+        let value = eval_v1_expr(&mut env, V1::Two, "read_lines('big')[0]").unwrap();
+        assert_eq!(value.unwrap_string().as_str(), "line");
+        let fused = BYTES_READ.with(|b| b.get());
+
+        BYTES_READ.with(|b| b.set(0));
+        eval_v1_expr(&mut env, V1::Two, "read_lines('big')").unwrap();
+        let unfused = BYTES_READ.with(|b| b.get());
+        assert!(fused < unfused, "{fused} bytes read when indexing");
+        assert_eq!(unfused, 500_000);
+    }
 }
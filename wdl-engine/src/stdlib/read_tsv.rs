@@ -3,6 +3,7 @@
 use std::fs;
 use std::io::BufRead;
 use std::io::BufReader;
+use std::sync::Arc;
 
 use anyhow::Context;
 use indexmap::IndexMap;
@@ -26,24 +27,16 @@ use crate::diagnostics::function_call_failed;
 /// Represents a header in a TSV (tab-separated value) file.
 enum TsvHeader {
     /// The header was explicitly specified as an `Array[String]`.
-    Specified(Array),
+    Specified(Vec<Arc<String>>),
     /// The header was read from the file.
     File(String),
 }
 
 impl TsvHeader {
     /// Gets the column names in the header.
-    ///
-    /// # Panics
-    ///
-    /// Panics if a specified header contains a value that is not a string.
     pub fn columns(&self) -> impl Iterator<Item = &str> {
         match self {
-            Self::Specified(array) => Either::Left(array.as_slice().iter().map(|v| {
-                v.as_string()
-                    .expect("header value must be a string")
-                    .as_str()
-            })),
+            Self::Specified(columns) => Either::Left(columns.iter().map(|c| c.as_str())),
             Self::File(s) => Either::Right(s.split('\t')),
         }
     }
@@ -139,7 +132,13 @@ fn read_tsv(context: CallContext<'_>) -> Result<Value, Diagnostic> {
         TsvHeader::Specified(
             context
                 .coerce_argument(2, ANALYSIS_STDLIB.array_string_type().clone())
-                .unwrap_array(),
+                .unwrap_array()
+                .iter()
+                .map(|v| v.map(|v| v.into_owned().unwrap_string()))
+                .collect::<anyhow::Result<_>>()
+                .map_err(|e| {
+                    function_call_failed("read_tsv", format!("{e:?}"), context.call_site)
+                })?,
         )
     } else if !file_has_header {
         return Err(function_call_failed(
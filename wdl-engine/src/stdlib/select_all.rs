@@ -1,12 +1,14 @@
 //! Implements the `select_all` function from the WDL standard library.
 
+use std::borrow::Cow;
+
 use wdl_ast::Diagnostic;
 
 use super::CallContext;
 use super::Function;
 use super::Signature;
-use crate::Array;
 use crate::Value;
+use crate::diagnostics::function_call_failed;
 
 /// Filters the input Array of optional values by removing all None values.
 ///
@@ -28,12 +30,14 @@ fn select_all(context: CallContext<'_>) -> Result<Value, Diagnostic> {
         .expect("argument should be an array");
 
     let elements = array
-        .as_slice()
         .iter()
-        .filter(|v| !v.is_none())
-        .cloned()
-        .collect();
-    Ok(Array::new_unchecked(context.return_type, elements).into())
+        .filter(|v| !matches!(v, Ok(v) if v.is_none()))
+        .map(|v| v.map(Cow::into_owned));
+
+    context
+        .try_new_array(context.return_type.clone(), elements)
+        .map(Into::into)
+        .map_err(|e| function_call_failed("select_all", format!("{e:?}"), context.call_site))
 }
 
 /// Gets the function describing `select_all`.
@@ -1,5 +1,6 @@
 //! Implements the `select_first` function from the WDL standard library.
 
+use itertools::process_results;
 use wdl_ast::Diagnostic;
 
 use super::CallContext;
@@ -34,7 +35,12 @@ fn select_first(context: CallContext<'_>) -> Result<Value, Diagnostic> {
         ));
     }
 
-    match array.as_slice().iter().find(|v| !v.is_none()) {
+    let first = process_results(array.iter(), |mut elements| elements.find(|v| !v.is_none()))
+        .map_err(|e| {
+            function_call_failed("select_first", format!("{e:?}"), context.arguments[0].span)
+        })?;
+
+    match first {
         Some(v) => Ok(v.clone_as_required()),
         None => {
             if context.arguments.len() < 2 {
@@ -10,6 +10,7 @@ use super::Function;
 use super::Signature;
 use crate::PrimitiveValue;
 use crate::Value;
+use crate::diagnostics::function_call_failed;
 
 /// Concatenates the elements of an array together into a string with the given
 /// separator between consecutive elements.
@@ -36,15 +37,14 @@ fn sep(context: CallContext<'_>) -> Result<Value, Diagnostic> {
         .expect("value should be an array");
 
     let s = array
-        .as_slice()
         .iter()
         .enumerate()
-        .fold(String::new(), |mut s, (i, v)| {
+        .try_fold(String::new(), |mut s, (i, v)| {
             if i > 0 {
                 s.push_str(&sep);
             }
 
-            match v {
+            match &*v? {
                 Value::None => {}
                 Value::Primitive(v) => {
                     write!(&mut s, "{v}", v = v.raw()).expect("failed to write to a string")
@@ -52,8 +52,9 @@ fn sep(context: CallContext<'_>) -> Result<Value, Diagnostic> {
                 _ => panic!("expected an array of primitive values"),
             }
 
-            s
-        });
+            anyhow::Ok(s)
+        })
+        .map_err(|e| function_call_failed("sep", format!("{e:?}"), context.call_site))?;
 
     Ok(PrimitiveValue::new_string(s).into())
 }
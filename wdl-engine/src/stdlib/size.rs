@@ -116,8 +116,8 @@ fn compound_disk_size(value: &CompoundValue, unit: StorageUnit, cwd: &Path) -> R
     match value {
         CompoundValue::Pair(pair) => Ok(calculate_disk_size(pair.left(), unit, cwd)?
             + calculate_disk_size(pair.right(), unit, cwd)?),
-        CompoundValue::Array(array) => Ok(array.as_slice().iter().try_fold(0.0, |t, e| {
-            anyhow::Ok(t + calculate_disk_size(e, unit, cwd)?)
+        CompoundValue::Array(array) => Ok(array.iter().try_fold(0.0, |t, e| {
+            anyhow::Ok(t + calculate_disk_size(&e?, unit, cwd)?)
         })?),
         CompoundValue::Map(map) => Ok(map.iter().try_fold(0.0, |t, (k, v)| {
             anyhow::Ok(
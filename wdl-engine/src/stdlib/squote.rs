@@ -6,9 +6,9 @@ use wdl_ast::Diagnostic;
 use super::CallContext;
 use super::Function;
 use super::Signature;
-use crate::Array;
 use crate::PrimitiveValue;
 use crate::Value;
+use crate::diagnostics::function_call_failed;
 
 /// Adds single-quotes (') around each element of the input array of primitive
 /// values.
@@ -25,17 +25,18 @@ fn squote(context: CallContext<'_>) -> Result<Value, Diagnostic> {
         .as_array()
         .expect("value should be an array");
 
-    let elements = array
-        .as_slice()
-        .iter()
-        .map(|v| match v {
-            Value::None => PrimitiveValue::new_string("''").into(),
-            Value::Primitive(v) => PrimitiveValue::new_string(format!("'{v}'", v = v.raw())).into(),
+    let elements = array.iter().map(|v| {
+        v.map(|v| match &*v {
+            Value::None => PrimitiveValue::new_string("''"),
+            Value::Primitive(v) => PrimitiveValue::new_string(format!("'{v}'", v = v.raw())),
             _ => panic!("expected an array of primitive values"),
         })
-        .collect();
+    });
 
-    Ok(Array::new_unchecked(context.return_type, elements).into())
+    context
+        .try_new_array(context.return_type.clone(), elements)
+        .map(Into::into)
+        .map_err(|e| function_call_failed("squote", format!("{e:?}"), context.call_site))
 }
 
 /// Gets the function describing `squote`.
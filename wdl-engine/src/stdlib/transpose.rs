@@ -38,10 +38,7 @@ fn transpose(context: CallContext<'_>) -> Result<Value, Diagnostic> {
         .first()
         .map(|v| {
             (
-                v.as_array()
-                    .expect("element should be an array")
-                    .as_slice()
-                    .len(),
+                v.as_array().expect("element should be an array").len(),
                 v.ty(),
             )
         })
@@ -62,7 +59,15 @@ fn transpose(context: CallContext<'_>) -> Result<Value, Diagnostic> {
                 ));
             }
 
-            transposed_inner.push(inner.as_slice()[i].clone())
+            transposed_inner.push(
+                inner
+                    .get(i)
+                    .map_err(|e| {
+                        function_call_failed("transpose", format!("{e:?}"), context.call_site)
+                    })?
+                    .expect("index should be in bounds")
+                    .into_owned(),
+            )
         }
 
         transposed_outer.push(Array::new_unchecked(ty.clone(), transposed_inner).into());
@@ -17,6 +17,14 @@ use crate::diagnostics::function_call_failed;
 
 /// Writes a JSON file with the serialized form of a WDL value.
 ///
+/// `Int` values are written without a decimal point and `Float` values are
+/// written with a decimal point (or an exponent); non-finite `Float` values
+/// cannot be written. The members of objects, structs, and maps are written in
+/// order and pairs are written as an object with `left` and `right` members.
+///
+/// A value written by this function is equal to the value read back by
+/// `read_json` once it is coerced to the original type.
+///
 /// https://github.com/openwdl/wdl/blob/wdl-1.2/SPEC.md#write_json
 fn write_json(context: CallContext<'_>) -> Result<Value, Diagnostic> {
     debug_assert!(context.arguments.len() == 1);
@@ -244,5 +252,12 @@ mod test {
             fs::read_to_string(value.unwrap_file().as_str()).expect("failed to read file"),
             "{\n  \"foo\": 1,\n  \"bar\": \"baz\",\n  \"baz\": 1.9\n}",
         );
+
+        let value = eval_v1_expr(&mut env, V1::Two, "write_json((1, 2.0))").unwrap();
+        assert_file_in_temp(&env, &value);
+        assert_eq!(
+            fs::read_to_string(value.unwrap_file().as_str()).expect("failed to read file"),
+            "{\n  \"left\": 1,\n  \"right\": 2.0\n}",
+        );
     }
 }
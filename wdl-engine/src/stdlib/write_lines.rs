@@ -5,7 +5,6 @@ use std::io::Write;
 use std::path::Path;
 
 use tempfile::NamedTempFile;
-use wdl_analysis::stdlib::STDLIB as ANALYSIS_STDLIB;
 use wdl_analysis::types::PrimitiveType;
 use wdl_ast::Diagnostic;
 
@@ -37,9 +36,13 @@ fn write_lines(context: CallContext<'_>) -> Result<Value, Diagnostic> {
         )
     };
 
-    let lines = context
-        .coerce_argument(0, ANALYSIS_STDLIB.array_string_type().clone())
-        .unwrap_array();
+    // Stream the elements of the argument directly rather than coercing the
+    // entire array to `Array[String]`, which would allocate a copy of every
+    // element up front
+    let lines = context.arguments[0]
+        .value
+        .as_array()
+        .expect("argument should be an array");
 
     // Create a temporary file that will be persisted after writing the lines
     let mut file = NamedTempFile::with_prefix_in("tmp", context.temp_dir()).map_err(|e| {
@@ -52,11 +55,15 @@ fn write_lines(context: CallContext<'_>) -> Result<Value, Diagnostic> {
 
     // Write the lines
     let mut writer = BufWriter::new(file.as_file_mut());
-    for line in lines.as_slice() {
-        writer
-            .write(line.as_string().unwrap().as_bytes())
-            .map_err(write_error)?;
-        writeln!(&mut writer).map_err(write_error)?;
+    for line in lines.iter() {
+        let line = line.map_err(|e| {
+            function_call_failed("write_lines", format!("{e:?}"), context.call_site)
+        })?;
+        let line = line
+            .as_primitive()
+            .expect("analysis should ensure the argument is an array of primitive values");
+
+        writeln!(&mut writer, "{line}", line = line.raw()).map_err(write_error)?;
     }
 
     // Consume the writer, flushing the buffer to disk.
@@ -99,6 +106,7 @@ mod test {
     use pretty_assertions::assert_eq;
     use wdl_ast::version::V1;
 
+    use crate::spill::resident;
     use crate::v1::test::TestEnv;
     use crate::v1::test::eval_v1_expr;
 
@@ -139,4 +147,53 @@ mod test {
             "hello\nworld\n!\n\n!\n"
         );
     }
+
+    #[test]
+    fn write_lines_spilled() {
+        const COUNT: usize = 100;
+        const THRESHOLD: usize = 10;
+
+        let mut env = TestEnv::default();
+        env.set_array_spill_threshold(THRESHOLD);
+
+        let contents: String = (0..COUNT).map(|i| format!("line {i}\n")).collect();
+        env.write_file("lines.txt", &contents);
+
+        let lines = eval_v1_expr(&mut env, V1::Two, "read_lines('lines.txt')").unwrap();
+        assert!(
+            lines.as_array().unwrap().is_spilled(),
+            "array should be spilled"
+        );
+        env.insert_name("lines", lines);
+
+        let chunks = eval_v1_expr(&mut env, V1::Two, "chunk(lines, 25)").unwrap();
+        let chunks = chunks.unwrap_array();
+        assert_eq!(chunks.len(), 4);
+        assert!(
+            chunks
+                .as_slice()
+                .iter()
+                .all(|c| c.unwrap_array().is_spilled()),
+            "every chunk should be spilled"
+        );
+
+        resident::reset_peak();
+        let value = eval_v1_expr(
+            &mut env,
+            V1::Two,
+            "write_lines(flatten(chunk(read_lines('lines.txt'), 25)))",
+        )
+        .unwrap();
+        assert_eq!(
+            fs::read_to_string(value.unwrap_file().as_str()).expect("failed to read file"),
+            contents
+        );
+
+        let peak = resident::peak();
+        assert!(
+            peak <= 3 * THRESHOLD,
+            "expected at most {max} resident elements, but the peak was {peak}",
+            max = 3 * THRESHOLD
+        );
+    }
 }
@@ -43,9 +43,12 @@ pub(crate) fn write_tsv_value<W: Write>(
 }
 
 /// Helper for writing a `Array[Array[String]]` to a TSV file.
+///
+/// The rows are streamed to the file without first coercing them to
+/// `Array[Array[String]]`.
 fn write_array_tsv_file(
     tmp: &Path,
-    rows: Array,
+    rows: &Array,
     header: Option<Array>,
     call_site: Span,
 ) -> Result<Value, Diagnostic> {
@@ -58,6 +61,10 @@ fn write_array_tsv_file(
         )
     };
 
+    // Helper for handling errors while reading the elements of an array.
+    let read_error =
+        |e: anyhow::Error| function_call_failed("write_tsv", format!("{e:?}"), call_site);
+
     // Create a temporary file that will be persisted after writing
     let mut file = NamedTempFile::with_prefix_in("tmp", tmp).map_err(|e| {
         function_call_failed(
@@ -71,8 +78,8 @@ fn write_array_tsv_file(
 
     // Start by writing the header, if one was provided
     let column_count = if let Some(header) = header {
-        for (i, name) in header.as_slice().iter().enumerate() {
-            let name = name.as_string().unwrap();
+        for (i, name) in header.iter().enumerate() {
+            let name = name.map_err(read_error)?.into_owned().unwrap_string();
             if name.contains('\t') {
                 return Err(function_call_failed(
                     "write_tsv",
@@ -112,21 +119,23 @@ fn write_array_tsv_file(
             }
         }
 
-        for (i, column) in row.as_slice().iter().enumerate() {
-            let column = column.as_string().unwrap();
-            if column.contains('\t') {
+        for (i, column) in row.iter().enumerate() {
+            let column = column.map_err(read_error)?;
+            let column = column
+                .as_primitive()
+                .expect("analysis should ensure the rows are arrays of primitive values");
+
+            if i > 0 {
+                writer.write(b"\t").map_err(write_error)?;
+            }
+
+            if !write_tsv_value(&mut writer, column).map_err(write_error)? {
                 return Err(function_call_failed(
                     "write_tsv",
                     format!("element of array at index {index} contains a tab character"),
                     call_site,
                 ));
             }
-
-            if i > 0 {
-                writer.write(b"\t").map_err(write_error)?;
-            }
-
-            writer.write(column.as_bytes()).map_err(write_error)?;
         }
 
         writeln!(&mut writer).map_err(write_error)?;
@@ -172,9 +181,10 @@ fn write_tsv(context: CallContext<'_>) -> Result<Value, Diagnostic> {
     debug_assert!(context.arguments.len() == 1);
     debug_assert!(context.return_type_eq(PrimitiveType::File));
 
-    let rows = context
-        .coerce_argument(0, ANALYSIS_STDLIB.array_array_string_type().clone())
-        .unwrap_array();
+    let rows = context.arguments[0]
+        .value
+        .as_array()
+        .expect("argument should be an array");
 
     write_array_tsv_file(context.temp_dir(), rows, None, context.call_site)
 }
@@ -193,9 +203,10 @@ fn write_tsv_with_header(context: CallContext<'_>) -> Result<Value, Diagnostic>
     debug_assert!(context.arguments.len() == 3);
     debug_assert!(context.return_type_eq(PrimitiveType::File));
 
-    let rows = context
-        .coerce_argument(0, ANALYSIS_STDLIB.array_array_string_type().clone())
-        .unwrap_array();
+    let rows = context.arguments[0]
+        .value
+        .as_array()
+        .expect("argument should be an array");
     let write_header = context
         .coerce_argument(1, PrimitiveType::Boolean)
         .unwrap_boolean();
@@ -235,6 +246,10 @@ fn write_tsv_struct(context: CallContext<'_>) -> Result<Value, Diagnostic> {
         )
     };
 
+    // Helper for handling errors while reading the elements of an array.
+    let read_error =
+        |e: anyhow::Error| function_call_failed("write_tsv", format!("{e:?}"), context.call_site);
+
     let rows = context.arguments[0].value.as_array().unwrap();
     let write_header = if context.arguments.len() >= 2 {
         context
@@ -295,8 +310,8 @@ fn write_tsv_struct(context: CallContext<'_>) -> Result<Value, Diagnostic> {
             }
 
             // Header was explicitly specified, write out the values
-            for (i, name) in header.as_slice().iter().enumerate() {
-                let name = name.as_string().unwrap();
+            for (i, name) in header.iter().enumerate() {
+                let name = name.map_err(read_error)?.into_owned().unwrap_string();
                 if name.contains('\t') {
                     return Err(function_call_failed(
                         "write_tsv",
@@ -56,11 +56,13 @@ fn zip(context: CallContext<'_>) -> Result<Value, Diagnostic> {
     );
 
     let elements = left
-        .as_slice()
         .iter()
-        .zip(right.as_slice())
-        .map(|(l, r)| Pair::new_unchecked(element_ty.clone(), l.clone(), r.clone()).into())
-        .collect();
+        .zip(right.iter())
+        .map(|(l, r)| {
+            Ok(Pair::new_unchecked(element_ty.clone(), l?.into_owned(), r?.into_owned()).into())
+        })
+        .collect::<anyhow::Result<_>>()
+        .map_err(|e| function_call_failed("zip", format!("{e:?}"), context.call_site))?;
 
     Ok(Array::new_unchecked(context.return_type, elements).into())
 }
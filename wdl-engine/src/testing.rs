@@ -0,0 +1,32 @@
+//! Utilities for the unit tests of the engine.
+
+use std::fs;
+use std::sync::Arc;
+
+use tempfile::TempDir;
+use wdl_analysis::Analyzer;
+use wdl_analysis::DiagnosticsConfig;
+use wdl_analysis::document::Document;
+use wdl_analysis::path_to_uri;
+use wdl_analysis::rules;
+
+/// Analyzes the given source as a document in the given directory.
+pub(crate) async fn analyze(dir: &TempDir, source: &str) -> Arc<Document> {
+    let path = dir.path().join("source.wdl");
+    fs::write(&path, source).expect("failed to create test file");
+
+    let uri = path_to_uri(&path).expect("should convert to URI");
+    let analyzer = Analyzer::new(DiagnosticsConfig::new(rules()), |_: (), _, _, _| async {});
+    analyzer
+        .add_document(uri.clone())
+        .await
+        .expect("should add document");
+
+    let results = analyzer.analyze(()).await.unwrap();
+    results
+        .into_iter()
+        .find(|r| r.document().uri().as_ref() == &uri)
+        .expect("should have a result for the document")
+        .document()
+        .clone()
+}
@@ -1,5 +1,6 @@
 //! Implementation of the WDL runtime and values.
 
+use std::borrow::Cow;
 use std::cmp::Ordering;
 use std::fmt;
 use std::hash::Hash;
@@ -16,6 +17,7 @@ use itertools::Either;
 use ordered_float::OrderedFloat;
 use serde::ser::SerializeMap;
 use serde::ser::SerializeSeq;
+use tracing::warn;
 use wdl_analysis::stdlib::STDLIB as ANALYSIS_STDLIB;
 use wdl_analysis::types::ArrayType;
 use wdl_analysis::types::Coercible as _;
@@ -42,6 +44,8 @@ use wdl_ast::v1::TASK_FIELD_RETURN_CODE;
 use wdl_grammar::lexer::v1::is_ident;
 
 use crate::TaskExecutionConstraints;
+use crate::spill::SpillWriter;
+use crate::spill::SpilledElements;
 
 /// Implemented on coercible values.
 pub trait Coercible: Sized {
@@ -126,6 +130,16 @@ impl Value {
         matches!(self, Self::None)
     }
 
+    /// Gets the value as a primitive value.
+    ///
+    /// Returns `None` if the value is not a primitive value.
+    pub fn as_primitive(&self) -> Option<&PrimitiveValue> {
+        match self {
+            Self::Primitive(v) => Some(v),
+            _ => None,
+        }
+    }
+
     /// Gets the value as a `Boolean`.
     ///
     /// Returns `None` if the value is not a `Boolean`.
@@ -423,11 +437,16 @@ impl Value {
     }
 
     /// Visits each file or directory path contained in the value.
-    pub(crate) fn visit_paths(&self, cb: &mut impl FnMut(&str)) {
+    ///
+    /// Returns an error if the elements of a spilled array could not be read.
+    pub(crate) fn visit_paths(&self, cb: &mut impl FnMut(&str)) -> Result<()> {
         match self {
-            Self::Primitive(v) => v.visit_paths(cb),
+            Self::Primitive(v) => {
+                v.visit_paths(cb);
+                Ok(())
+            }
             Self::Compound(v) => v.visit_paths(cb),
-            _ => {}
+            _ => Ok(()),
         }
     }
 
@@ -466,6 +485,94 @@ impl Value {
         }
     }
 
+    /// Converts floats with a zero fractional part to integers where the given
+    /// type expects an `Int`.
+    ///
+    /// This is used to accept values from tooling that writes integers as
+    /// floats (e.g. `3.0`); floats with a fractional part or that are outside
+    /// the range of a 64-bit signed integer are not converted.
+    ///
+    /// A warning is emitted for each converted float, identified by the given
+    /// path.
+    ///
+    /// Returns `None` if no floats were converted.
+    pub(crate) fn lenient_float_to_int(&self, target: &Type, path: &str) -> Option<Self> {
+        match self {
+            Self::Primitive(PrimitiveValue::Float(v)) => {
+                if target.as_primitive() != Some(PrimitiveType::Integer) {
+                    return None;
+                }
+
+                // Note: `i64::MAX as f64` rounds up to 2^63, which is out of range
+                let v = v.into_inner();
+                if v.fract() != 0.0 || v < i64::MIN as f64 || v >= i64::MAX as f64 {
+                    return None;
+                }
+
+                warn!("converting float value `{v}` at `{path}` to an integer");
+                Some(Self::Primitive(PrimitiveValue::Integer(v as i64)))
+            }
+            Self::Compound(CompoundValue::Array(array)) => {
+                let element_type = target.as_array()?.element_type();
+                let mut converted = false;
+                // Elements that cannot be read are left for coercion to report
+                let elements: Vec<_> = array
+                    .iter()
+                    .enumerate()
+                    .map(|(i, v)| {
+                        let v = v?;
+                        Ok(
+                            match v.lenient_float_to_int(element_type, &format!("{path}[{i}]")) {
+                                Some(v) => {
+                                    converted = true;
+                                    v
+                                }
+                                None => v.into_owned(),
+                            },
+                        )
+                    })
+                    .collect::<Result<_>>()
+                    .ok()?;
+
+                if !converted {
+                    return None;
+                }
+
+                Array::new(target.clone(), elements).ok().map(Into::into)
+            }
+            Self::Compound(CompoundValue::Object(object)) => {
+                let mut converted = false;
+                let members: Vec<_> = object
+                    .iter()
+                    .map(|(name, v)| {
+                        let ty = match (target.as_map(), target.as_struct()) {
+                            (Some(ty), _) => Some(ty.value_type()),
+                            (_, Some(ty)) => ty.members().get(name),
+                            _ => None,
+                        };
+
+                        match ty
+                            .and_then(|ty| v.lenient_float_to_int(ty, &format!("{path}.{name}")))
+                        {
+                            Some(v) => {
+                                converted = true;
+                                (name, v)
+                            }
+                            None => (name, v.clone()),
+                        }
+                    })
+                    .collect();
+
+                if !converted {
+                    return None;
+                }
+
+                Some(Object::new(members).into())
+            }
+            _ => None,
+        }
+    }
+
     /// Determines if two values have equality according to the WDL
     /// specification.
     ///
@@ -1233,15 +1340,33 @@ impl serde::Serialize for PrimitiveValue {
     where
         S: serde::Serializer,
     {
+        use serde::ser::Error;
+
         match self {
             Self::Boolean(v) => v.serialize(serializer),
             Self::Integer(v) => v.serialize(serializer),
-            Self::Float(v) => v.serialize(serializer),
+            Self::Float(v) => {
+                if !v.0.is_finite() {
+                    return Err(S::Error::custom(format!(
+                        "float value `{v}` cannot be serialized"
+                    )));
+                }
+
+                v.serialize(serializer)
+            }
             Self::String(s) | Self::File(s) | Self::Directory(s) => s.serialize(serializer),
         }
     }
 }
 
+/// The name of the member holding the left value of a pair serialized as an
+/// object.
+const PAIR_LEFT_MEMBER: &str = "left";
+
+/// The name of the member holding the right value of a pair serialized as an
+/// object.
+const PAIR_RIGHT_MEMBER: &str = "right";
+
 /// Represents a `Pair` value.
 ///
 /// Pairs are cheap to clone.
@@ -1323,9 +1448,24 @@ impl fmt::Display for Pair {
     }
 }
 
+/// Represents the storage of an array's elements.
+#[derive(Debug, Clone)]
+enum ArrayElements {
+    /// The elements are held in memory.
+    Memory(Arc<Vec<Value>>),
+    /// The elements are spilled to disk.
+    Spilled(Arc<SpilledElements>),
+}
+
 /// Represents an `Array` value.
 ///
 /// Arrays are cheap to clone.
+///
+/// The elements of a very large array of primitive values may be spilled to
+/// disk rather than held in memory (see
+/// [`Engine::with_array_spill_threshold`](crate::Engine::with_array_spill_threshold));
+/// the elements of any array may be accessed with [`Array::get`] and
+/// [`Array::iter`].
 #[derive(Debug, Clone)]
 pub struct Array {
     /// The type of the array.
@@ -1333,7 +1473,7 @@ pub struct Array {
     /// The array's elements.
     ///
     /// A value of `None` indicates an empty array.
-    elements: Option<Arc<Vec<Value>>>,
+    elements: Option<ArrayElements>,
 }
 
 impl Array {
@@ -1346,26 +1486,77 @@ impl Array {
     ///
     /// Panics if the given type is not an array type.
     pub fn new<V>(ty: impl Into<Type>, elements: impl IntoIterator<Item = V>) -> Result<Self>
+    where
+        V: Into<Value>,
+    {
+        Self::new_spillable(ty, elements, None)
+    }
+
+    /// Creates a new `Array` value for the given array type, spilling the
+    /// elements to disk if there are more than the given threshold.
+    ///
+    /// Only arrays of primitive values are spilled; the spill file is created
+    /// in the given directory.
+    ///
+    /// Returns an error if an element did not coerce to the array's element
+    /// type or if the elements could not be spilled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given type is not an array type.
+    pub(crate) fn new_spillable<V>(
+        ty: impl Into<Type>,
+        elements: impl IntoIterator<Item = V>,
+        spill: Option<(usize, &Path)>,
+    ) -> Result<Self>
     where
         V: Into<Value>,
     {
         let ty = ty.into();
         if let Type::Compound(CompoundType::Array(ty), optional) = ty {
-            let element_type = ty.element_type();
-            let elements = elements
-                .into_iter()
-                .enumerate()
-                .map(|(i, v)| {
-                    let v = v.into();
-                    v.coerce(element_type)
-                        .with_context(|| format!("failed to coerce array element at index {i}"))
-                })
-                .collect::<Result<Vec<_>>>()?;
+            let element_type = ty.element_type().clone();
+            let spill = spill.filter(|_| matches!(element_type, Type::Primitive(..)));
+            let mut elements = elements.into_iter().enumerate().map(|(i, v)| {
+                let v = v.into();
+                v.coerce(&element_type)
+                    .with_context(|| format!("failed to coerce array element at index {i}"))
+            });
 
-            return Ok(Self::new_unchecked(
-                Type::Compound(CompoundType::Array(ty), optional),
-                elements,
-            ));
+            let ty = Type::Compound(CompoundType::Array(ty), optional);
+            let Some((threshold, dir)) = spill else {
+                return Ok(Self::new_unchecked(ty, elements.collect::<Result<_>>()?));
+            };
+
+            // Buffer the elements until the threshold is exceeded
+            let mut buffer = Vec::new();
+            for element in elements.by_ref() {
+                buffer.push(element?);
+                if buffer.len() > threshold {
+                    break;
+                }
+            }
+
+            if buffer.len() <= threshold {
+                return Ok(Self::new_unchecked(ty, buffer));
+            }
+
+            #[cfg(test)]
+            crate::spill::resident::acquire(buffer.len());
+            let mut writer = SpillWriter::new(dir, threshold)?;
+            let result = buffer.iter().try_for_each(|e| writer.push(e));
+            #[cfg(test)]
+            crate::spill::resident::release(buffer.len());
+            drop(buffer);
+            result?;
+
+            for element in elements {
+                writer.push(&element?)?;
+            }
+
+            return Ok(Self {
+                ty,
+                elements: Some(ArrayElements::Spilled(Arc::new(writer.finish()?))),
+            });
         }
 
         panic!("type `{ty}` is not an array type");
@@ -1379,7 +1570,7 @@ impl Array {
             elements: if elements.is_empty() {
                 None
             } else {
-                Some(Arc::new(elements))
+                Some(ArrayElements::Memory(Arc::new(elements)))
             },
         }
     }
@@ -1390,33 +1581,115 @@ impl Array {
     }
 
     /// Converts the array value to a slice of values.
+    ///
+    /// Only arrays of primitive values are spilled to disk, so the elements of
+    /// any other array may always be accessed as a slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the array's elements are spilled to disk (see
+    /// [`Array::is_spilled`]); use [`Array::try_as_slice`] for arrays that may
+    /// be spilled.
     pub fn as_slice(&self) -> &[Value] {
-        self.elements.as_ref().map(|v| v.as_slice()).unwrap_or(&[])
+        self.try_as_slice()
+            .expect("the elements of a spilled array cannot be accessed as a slice")
+    }
+
+    /// Converts the array value to a slice of values.
+    ///
+    /// Returns `None` if the array's elements are spilled to disk (see
+    /// [`Array::is_spilled`]); use [`Array::iter`] or [`Array::get`] to
+    /// access the elements of any array.
+    pub fn try_as_slice(&self) -> Option<&[Value]> {
+        match &self.elements {
+            None => Some(&[]),
+            Some(ArrayElements::Memory(elements)) => Some(elements.as_slice()),
+            Some(ArrayElements::Spilled(_)) => None,
+        }
+    }
+
+    /// Gets the element at the given index.
+    ///
+    /// Returns `Ok(None)` if the index is out of bounds.
+    ///
+    /// Returns an error if the array's elements are spilled to disk and could
+    /// not be read.
+    pub fn get(&self, index: usize) -> Result<Option<Cow<'_, Value>>> {
+        match &self.elements {
+            None => Ok(None),
+            Some(ArrayElements::Memory(elements)) => Ok(elements.get(index).map(Cow::Borrowed)),
+            Some(ArrayElements::Spilled(elements)) => Ok(elements.get(index)?.map(Cow::Owned)),
+        }
+    }
+
+    /// Iterates the elements of the array.
+    ///
+    /// Elements held in memory are borrowed; if the array's elements are
+    /// spilled to disk, they are read from disk as the iterator advances.
+    ///
+    /// The iterator yields an error if the spilled elements could not be read.
+    pub fn iter(&self) -> impl Iterator<Item = Result<Cow<'_, Value>>> {
+        let elements = match &self.elements {
+            Some(ArrayElements::Spilled(elements)) => {
+                return Either::Right(elements.iter().map(|e| e.map(Cow::Owned)));
+            }
+            Some(ArrayElements::Memory(elements)) => elements.as_slice(),
+            None => &[],
+        };
+
+        Either::Left(elements.iter().map(|e| Ok(Cow::Borrowed(e))))
     }
 
     /// Returns the number of elements in the array.
     pub fn len(&self) -> usize {
-        self.elements.as_ref().map(|v| v.len()).unwrap_or(0)
+        match &self.elements {
+            None => 0,
+            Some(ArrayElements::Memory(elements)) => elements.len(),
+            Some(ArrayElements::Spilled(elements)) => elements.len(),
+        }
     }
 
     /// Returns `true` if the array has no elements.
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Returns `true` if the array's elements are spilled to disk.
+    pub fn is_spilled(&self) -> bool {
+        matches!(self.elements, Some(ArrayElements::Spilled(_)))
+    }
+
+    /// Maps each element of the array with the given function.
+    ///
+    /// If the array's elements are spilled to disk, the mapped elements are
+    /// spilled to a new file.
+    fn try_map(&self, ty: Type, f: impl FnMut(Value) -> Result<Value>) -> Result<Self> {
+        let elements = match &self.elements {
+            None => None,
+            Some(ArrayElements::Memory(elements)) => Some(ArrayElements::Memory(Arc::new(
+                elements.iter().cloned().map(f).collect::<Result<_>>()?,
+            ))),
+            Some(ArrayElements::Spilled(elements)) => {
+                Some(ArrayElements::Spilled(Arc::new(elements.try_map(f)?)))
+            }
+        };
+
+        Ok(Self { ty, elements })
+    }
 }
 
 impl fmt::Display for Array {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "[")?;
 
-        if let Some(elements) = &self.elements {
-            for (i, element) in elements.iter().enumerate() {
-                if i > 0 {
-                    write!(f, ", ")?;
-                }
-
-                write!(f, "{element}")?;
+        for (i, element) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
             }
+
+            // A spilled element that could not be read cannot be displayed
+            let element = element.map_err(|_| fmt::Error)?;
+            write!(f, "{element}")?;
         }
 
         write!(f, "]")
@@ -1998,11 +2271,11 @@ impl CompoundValue {
             ),
             (CompoundValue::Array(left), CompoundValue::Array(right)) => Some(
                 left.len() == right.len()
-                    && left
-                        .as_slice()
-                        .iter()
-                        .zip(right.as_slice())
-                        .all(|(l, r)| Value::equals(l, r).unwrap_or(false)),
+                    && left.iter().zip(right.iter()).all(|(l, r)| match (l, r) {
+                        (Ok(l), Ok(r)) => Value::equals(&l, &r).unwrap_or(false),
+                        // Spilled elements that cannot be read are never equal
+                        _ => false,
+                    }),
             ),
             (CompoundValue::Map(left), CompoundValue::Map(right)) => Some(
                 left.len() == right.len()
@@ -2039,15 +2312,17 @@ impl CompoundValue {
     }
 
     /// Visits each file or directory path contained in the value.
-    fn visit_paths(&self, cb: &mut impl FnMut(&str)) {
+    ///
+    /// Returns an error if the elements of a spilled array could not be read.
+    fn visit_paths(&self, cb: &mut impl FnMut(&str)) -> Result<()> {
         match self {
             Self::Pair(pair) => {
-                pair.left().visit_paths(cb);
-                pair.right().visit_paths(cb);
+                pair.left().visit_paths(cb)?;
+                pair.right().visit_paths(cb)?;
             }
             Self::Array(array) => {
-                for v in array.as_slice() {
-                    v.visit_paths(cb);
+                for v in array.iter() {
+                    v?.visit_paths(cb)?;
                 }
             }
             Self::Map(map) => {
@@ -2056,20 +2331,22 @@ impl CompoundValue {
                         k.visit_paths(cb);
                     }
 
-                    v.visit_paths(cb);
+                    v.visit_paths(cb)?;
                 }
             }
             Self::Object(object) => {
                 for v in object.values() {
-                    v.visit_paths(cb);
+                    v.visit_paths(cb)?;
                 }
             }
             Self::Struct(Struct { members, .. }) => {
                 for v in members.values() {
-                    v.visit_paths(cb);
+                    v.visit_paths(cb)?;
                 }
             }
         }
+
+        Ok(())
     }
 
     /// Replaces any inner path values by joining the specified path with the
@@ -2091,7 +2368,12 @@ impl CompoundValue {
             Self::Array(array) => {
                 let ty = array.ty.as_array().expect("should be an array type");
                 let optional = ty.element_type().is_optional();
-                if let Some(elements) = &mut array.elements {
+                if array.is_spilled() {
+                    *array = array.try_map(array.ty(), |mut v| {
+                        v.join_paths(path, check_existence, optional)?;
+                        Ok(v)
+                    })?;
+                } else if let Some(ArrayElements::Memory(elements)) = &mut array.elements {
                     for v in Arc::make_mut(elements) {
                         v.join_paths(path, check_existence, optional)?;
                     }
@@ -2207,9 +2489,19 @@ impl Coercible for CompoundValue {
                         bail!("cannot coerce empty array value to non-empty array type `{target}`",);
                     }
 
+                    // Spilled elements are coerced into a new spill file
+                    if v.is_spilled() {
+                        let element_type = target_ty.element_type();
+                        return Ok(Self::Array(
+                            v.try_map(target.clone(), |e| e.coerce(element_type))?,
+                        ));
+                    }
+
                     return Ok(Self::Array(Array::new(
                         target.clone(),
-                        v.as_slice().iter().cloned(),
+                        v.iter()
+                            .map(|e| e.map(Cow::into_owned))
+                            .collect::<Result<Vec<_>>>()?,
                     )?));
                 }
                 // Map[W, Y] -> Map[X, Z] where W -> X and Y -> Z
@@ -2229,6 +2521,23 @@ impl Coercible for CompoundValue {
                         v.values.1.clone(),
                     )?));
                 }
+                // Object -> Pair[X, Y] where the object has only `left` and `right` members
+                (Self::Object(v), CompoundType::Pair(_)) => {
+                    match (v.len(), v.get(PAIR_LEFT_MEMBER), v.get(PAIR_RIGHT_MEMBER)) {
+                        (2, Some(left), Some(right)) => {
+                            return Ok(Self::Pair(Pair::new(
+                                target.clone(),
+                                left.clone(),
+                                right.clone(),
+                            )?));
+                        }
+                        _ => bail!(
+                            "cannot coerce an object to type `{target}` as the object does not \
+                             have exactly the members `{PAIR_LEFT_MEMBER}` and \
+                             `{PAIR_RIGHT_MEMBER}`"
+                        ),
+                    }
+                }
                 // Map[String, Y] -> Struct
                 (Self::Map(v), CompoundType::Struct(target_ty)) => {
                     let len = v.len();
@@ -2445,11 +2754,16 @@ impl serde::Serialize for CompoundValue {
         use serde::ser::Error;
 
         match self {
-            Self::Pair(_) => Err(S::Error::custom("a pair cannot be serialized")),
+            Self::Pair(pair) => {
+                let mut s = serializer.serialize_map(Some(2))?;
+                s.serialize_entry(PAIR_LEFT_MEMBER, pair.left())?;
+                s.serialize_entry(PAIR_RIGHT_MEMBER, pair.right())?;
+                s.end()
+            }
             Self::Array(v) => {
                 let mut s = serializer.serialize_seq(Some(v.len()))?;
-                for v in v.as_slice() {
-                    s.serialize_element(v)?;
+                for v in v.iter() {
+                    s.serialize_element(&*v.map_err(|e| S::Error::custom(format!("{e:#}")))?)?;
                 }
 
                 s.end()
@@ -3308,4 +3622,320 @@ Caused by:
             r#"Foo {foo: 1.101000, bar: "foo", baz: 1234}"#
         );
     }
+
+    #[test]
+    fn lenient_float_to_int() {
+        let int: Type = PrimitiveType::Integer.into();
+
+        // Floats without a fractional part are converted
+        let value = Value::from(3.0)
+            .lenient_float_to_int(&int, "x")
+            .expect("float should convert");
+        assert_eq!(value.unwrap_integer(), 3);
+        assert_eq!(
+            Value::from(-3.0)
+                .lenient_float_to_int(&int.optional(), "x")
+                .expect("float should convert")
+                .unwrap_integer(),
+            -3
+        );
+
+        // Without conversion, the float does not coerce
+        assert_eq!(
+            format!("{e:?}", e = Value::from(3.0).coerce(&int).unwrap_err()),
+            "cannot coerce type `Float` to type `Int`"
+        );
+
+        // Floats with a fractional part or out of range are not converted
+        assert!(Value::from(3.5).lenient_float_to_int(&int, "x").is_none());
+        assert!(Value::from(1e19).lenient_float_to_int(&int, "x").is_none());
+        assert!(
+            Value::from(f64::NAN)
+                .lenient_float_to_int(&int, "x")
+                .is_none()
+        );
+        assert!(
+            Value::from(f64::INFINITY)
+                .lenient_float_to_int(&int, "x")
+                .is_none()
+        );
+
+        // Floats are only converted where an `Int` is expected
+        assert!(
+            Value::from(3.0)
+                .lenient_float_to_int(&PrimitiveType::Float.into(), "x")
+                .is_none()
+        );
+        assert!(Value::from(3).lenient_float_to_int(&int, "x").is_none());
+
+        // Array elements are converted
+        let value: Value = Array::new(ArrayType::new(PrimitiveType::Float), [1.0, 2.0])
+            .expect("should create array value")
+            .into();
+        let converted = value
+            .lenient_float_to_int(&ArrayType::new(PrimitiveType::Integer).into(), "x")
+            .expect("array should convert");
+        assert_eq!(converted.ty().to_string(), "Array[Int]");
+        assert_eq!(converted.to_string(), "[1, 2]");
+
+        let value: Value = Array::new(ArrayType::new(PrimitiveType::Float), [1.0, 2.5])
+            .expect("should create array value")
+            .into();
+        assert!(
+            value
+                .lenient_float_to_int(&ArrayType::new(PrimitiveType::Integer).into(), "x")
+                .is_none()
+        );
+
+        // Object members are converted for maps and structs
+        let value: Value = Object::new([("foo", 1.0), ("bar", 2.5)]).into();
+        let converted = value
+            .lenient_float_to_int(
+                &StructType::new("Foo", [
+                    ("foo", PrimitiveType::Integer),
+                    ("bar", PrimitiveType::Float),
+                ])
+                .into(),
+                "x",
+            )
+            .expect("object should convert");
+        assert_eq!(converted.to_string(), "object {foo: 1, bar: 2.500000}");
+
+        let value: Value = Object::new([("foo", 1.0), ("bar", 2.5)]).into();
+        assert!(
+            value
+                .lenient_float_to_int(
+                    &MapType::new(PrimitiveType::String, PrimitiveType::Float).into(),
+                    "x"
+                )
+                .is_none()
+        );
+    }
+
+    /// A simple pseudo-random number generator for generating test values.
+    struct Rng(u64);
+
+    impl Rng {
+        /// Gets the next random number.
+        fn next(&mut self) -> u64 {
+            // xorshift64*
+            self.0 ^= self.0 >> 12;
+            self.0 ^= self.0 << 25;
+            self.0 ^= self.0 >> 27;
+            self.0.wrapping_mul(0x2545F4914F6CDD1D)
+        }
+
+        /// Gets a random number less than `n`.
+        fn below(&mut self, n: u64) -> u64 {
+            self.next() % n
+        }
+    }
+
+    /// Generates a random type with the given maximum depth.
+    ///
+    /// If `typed` is `false`, only types that JSON natively represents are
+    /// generated (i.e. no pairs or maps).
+    fn gen_type(rng: &mut Rng, depth: usize, typed: bool) -> Type {
+        let kinds = match (depth, typed) {
+            (0, _) => 4,
+            (_, false) => 6,
+            (_, true) => 8,
+        };
+
+        match rng.below(kinds) {
+            0 => PrimitiveType::Boolean.into(),
+            1 => PrimitiveType::Integer.into(),
+            2 => PrimitiveType::Float.into(),
+            3 => PrimitiveType::String.into(),
+            4 => Type::Object,
+            5 => ArrayType::new(gen_type(rng, depth - 1, typed)).into(),
+            6 => PairType::new(
+                gen_type(rng, depth - 1, typed),
+                gen_type(rng, depth - 1, typed),
+            )
+            .into(),
+            _ => MapType::new(PrimitiveType::String, gen_type(rng, depth - 1, typed)).into(),
+        }
+    }
+
+    /// Generates a random value of the given type.
+    fn gen_value(rng: &mut Rng, ty: &Type, depth: usize) -> Value {
+        const CHARS: &[char] = &['a', 'Z', '0', ' ', '_', '"', '\\', '\n', '\t', 'é', '🦀'];
+
+        match ty {
+            Type::Primitive(PrimitiveType::Boolean, _) => (rng.below(2) == 1).into(),
+            Type::Primitive(PrimitiveType::Integer, _) => match rng.below(4) {
+                0 => i64::MIN.into(),
+                1 => i64::MAX.into(),
+                2 => (rng.below(100) as i64 - 50).into(),
+                _ => (rng.next() as i64).into(),
+            },
+            Type::Primitive(PrimitiveType::Float, _) => match rng.below(5) {
+                0 => (rng.below(1000) as f64 - 500.0).into(),
+                1 => f64::MAX.into(),
+                2 => f64::MIN_POSITIVE.into(),
+                3 => 1e16.into(),
+                _ => {
+                    let v = f64::from_bits(rng.next());
+                    Value::from(if v.is_finite() { v } else { 0.5 })
+                }
+            },
+            Type::Primitive(PrimitiveType::String, _) => PrimitiveValue::new_string(
+                (0..rng.below(8))
+                    .map(|_| CHARS[rng.below(CHARS.len() as u64) as usize])
+                    .collect::<String>(),
+            )
+            .into(),
+            Type::Object => {
+                const NAMES: &[&str] = &["zeta", "alpha", "mid", "beta"];
+
+                let len = rng.below(4);
+                Object::new(
+                    (0..len)
+                        .map(|i| {
+                            let name =
+                                format!("{}{i}", NAMES[rng.below(NAMES.len() as u64) as usize]);
+                            let value = if rng.below(6) == 0 {
+                                Value::None
+                            } else {
+                                let ty = gen_type(rng, depth.saturating_sub(1), false);
+                                gen_value(rng, &ty, depth.saturating_sub(1))
+                            };
+                            (name, value)
+                        })
+                        .collect::<Vec<_>>(),
+                )
+                .into()
+            }
+            Type::Compound(CompoundType::Array(array_ty), _) => {
+                // Arrays are never empty as the element type of an empty array is unknown
+                let len = rng.below(3) + 1;
+                Array::new(
+                    ty.clone(),
+                    (0..len)
+                        .map(|_| gen_value(rng, array_ty.element_type(), depth.saturating_sub(1)))
+                        .collect::<Vec<_>>(),
+                )
+                .expect("should create array")
+                .into()
+            }
+            Type::Compound(CompoundType::Pair(pair_ty), _) => Pair::new(
+                ty.clone(),
+                gen_value(rng, pair_ty.left_type(), depth.saturating_sub(1)),
+                gen_value(rng, pair_ty.right_type(), depth.saturating_sub(1)),
+            )
+            .expect("should create pair")
+            .into(),
+            Type::Compound(CompoundType::Map(map_ty), _) => {
+                let len = rng.below(3);
+                Map::new(
+                    ty.clone(),
+                    (0..len)
+                        .map(|i| {
+                            (
+                                PrimitiveValue::new_string(format!("k{i}")),
+                                gen_value(rng, map_ty.value_type(), depth.saturating_sub(1)),
+                            )
+                        })
+                        .collect::<Vec<_>>(),
+                )
+                .expect("should create map")
+                .into()
+            }
+            _ => unreachable!("unexpected type `{ty}`"),
+        }
+    }
+
+    /// Asserts that a value round-trips through JSON.
+    fn assert_json_round_trip(value: &Value) {
+        let json = serde_json::to_string(value).expect("should serialize");
+        let read: Value = serde_json::from_str(&json).expect("should deserialize");
+        let read = read.coerce(&value.ty()).expect("should coerce");
+        assert_eq!(
+            Value::equals(value, &read),
+            Some(true),
+            "value `{value}` did not round-trip through `{json}`"
+        );
+
+        // Serializing again checks member order and numeric type fidelity
+        assert_eq!(
+            serde_json::to_string(&read).expect("should serialize"),
+            json
+        );
+    }
+
+    #[test]
+    fn json_round_trip() {
+        for seed in 1..=500 {
+            let mut rng = Rng(seed);
+            let ty = gen_type(&mut rng, 3, true);
+            let value = gen_value(&mut rng, &ty, 3);
+            assert_json_round_trip(&value);
+        }
+    }
+
+    #[test]
+    fn json_object_round_trip() {
+        // Objects round-trip without coercion
+        for seed in 1..=500 {
+            let mut rng = Rng(seed);
+            let value = gen_value(&mut rng, &Type::Object, 3);
+            let json = serde_json::to_string(&value).expect("should serialize");
+            let read: Value = serde_json::from_str(&json).expect("should deserialize");
+            assert_eq!(
+                Value::equals(&value, &read),
+                Some(true),
+                "value `{value}` did not round-trip through `{json}`"
+            );
+            assert_eq!(
+                serde_json::to_string(&read).expect("should serialize"),
+                json
+            );
+        }
+    }
+
+    #[test]
+    fn json_numbers() {
+        assert_eq!(serde_json::to_string(&Value::from(1)).unwrap(), "1");
+        assert_eq!(serde_json::to_string(&Value::from(1.0)).unwrap(), "1.0");
+        assert!(
+            serde_json::from_str::<Value>("1")
+                .unwrap()
+                .as_integer()
+                .is_some()
+        );
+        assert!(
+            serde_json::from_str::<Value>("1.0")
+                .unwrap()
+                .as_float()
+                .is_some()
+        );
+        assert!(
+            serde_json::from_str::<Value>("1e2")
+                .unwrap()
+                .as_float()
+                .is_some()
+        );
+
+        let e = serde_json::to_string(&Value::from(f64::NAN)).unwrap_err();
+        assert_eq!(e.to_string(), "float value `NaN` cannot be serialized");
+    }
+
+    #[test]
+    fn json_pair() {
+        let ty: Type = PairType::new(PrimitiveType::Integer, PrimitiveType::String).into();
+        let value: Value = Pair::new(ty.clone(), 1, PrimitiveValue::new_string("foo"))
+            .unwrap()
+            .into();
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(json, r#"{"left":1,"right":"foo"}"#);
+        assert_json_round_trip(&value);
+
+        let read: Value = serde_json::from_str(r#"{"left":1,"middle":2}"#).unwrap();
+        assert_eq!(
+            read.coerce(&ty).unwrap_err().to_string(),
+            "cannot coerce an object to type `Pair[Int, String]` as the object does not have \
+             exactly the members `left` and `right`"
+        );
+    }
 }
@@ -0,0 +1,277 @@
+//! The memory allocation tests.
+//!
+//! These tests check that standard library functions that write large arrays
+//! stream their elements rather than building their output in memory.
+//!
+//! The tests are in their own test binary as they install a global allocator
+//! that counts the bytes allocated by each thread; the allocator does not
+//! affect any other tests.
+
+use std::alloc::GlobalAlloc;
+use std::alloc::Layout;
+use std::alloc::System;
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use tempfile::TempDir;
+use wdl_analysis::diagnostics::unknown_name;
+use wdl_analysis::diagnostics::unknown_type;
+use wdl_analysis::document::Task;
+use wdl_analysis::stdlib::STDLIB as ANALYSIS_STDLIB;
+use wdl_analysis::types::ArrayType;
+use wdl_analysis::types::PrimitiveType;
+use wdl_analysis::types::Type;
+use wdl_ast::AstNode;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Ident;
+use wdl_ast::SupportedVersion;
+use wdl_ast::v1::Expr;
+use wdl_ast::version::V1;
+use wdl_engine::Array;
+use wdl_engine::EvaluationContext;
+use wdl_engine::PrimitiveValue;
+use wdl_engine::Value;
+use wdl_engine::v1::ExprEvaluator;
+use wdl_grammar::construct_tree;
+use wdl_grammar::grammar::v1;
+use wdl_grammar::lexer::Lexer;
+
+/// The maximum number of bytes an expression is expected to allocate.
+const MAX_ALLOCATED: usize = 1024 * 1024;
+
+thread_local! {
+    /// The number of bytes currently allocated by the thread.
+    static CURRENT: Cell<isize> = const { Cell::new(0) };
+    /// The peak number of bytes allocated by the thread.
+    static PEAK: Cell<isize> = const { Cell::new(0) };
+    /// The number of bytes allocated by the thread when the peak was last
+    /// reset.
+    static BASE: Cell<isize> = const { Cell::new(0) };
+}
+
+/// Records a change in the number of bytes allocated by the thread.
+fn record(delta: isize) {
+    // The thread's counts may be inaccessible while the thread exits
+    let _ = CURRENT.try_with(|current| {
+        let value = current.get() + delta;
+        current.set(value);
+        let _ = PEAK.try_with(|peak| peak.set(peak.get().max(value)));
+    });
+}
+
+/// A global allocator that counts the bytes allocated by each thread.
+///
+/// The counts are per thread so that tests running in parallel do not affect
+/// one another.
+struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            record(layout.size() as isize);
+        }
+
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        record(-(layout.size() as isize));
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new.is_null() {
+            record(new_size as isize - layout.size() as isize);
+        }
+
+        new
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Resets the peak number of bytes allocated by the current thread.
+fn reset_peak() {
+    let current = CURRENT.get();
+    BASE.set(current);
+    PEAK.set(current);
+}
+
+/// Gets the peak number of bytes allocated by the current thread since the
+/// last reset.
+fn peak() -> usize {
+    (PEAK.get() - BASE.get()).max(0) as usize
+}
+
+/// Represents the context of an expression evaluated by a test.
+struct TestContext {
+    /// The values of the names in scope.
+    names: HashMap<&'static str, Value>,
+    /// The working directory.
+    work_dir: TempDir,
+    /// The temp directory.
+    temp_dir: TempDir,
+}
+
+impl TestContext {
+    /// Constructs a new context with the given names in scope.
+    fn new(names: impl IntoIterator<Item = (&'static str, Value)>) -> Self {
+        Self {
+            names: names.into_iter().collect(),
+            work_dir: TempDir::new().expect("failed to create work directory"),
+            temp_dir: TempDir::new().expect("failed to create temp directory"),
+        }
+    }
+}
+
+impl EvaluationContext for &TestContext {
+    fn version(&self) -> SupportedVersion {
+        SupportedVersion::V1(V1::Two)
+    }
+
+    fn resolve_name(&self, name: &Ident) -> Result<Value, Diagnostic> {
+        self.names
+            .get(name.as_str())
+            .cloned()
+            .ok_or_else(|| unknown_name(name.as_str(), name.span()))
+    }
+
+    fn resolve_type_name(&mut self, name: &Ident) -> Result<Type, Diagnostic> {
+        Err(unknown_type(name.as_str(), name.span()))
+    }
+
+    fn work_dir(&self) -> &Path {
+        self.work_dir.path()
+    }
+
+    fn temp_dir(&self) -> &Path {
+        self.temp_dir.path()
+    }
+
+    fn stdout(&self) -> Option<&Value> {
+        None
+    }
+
+    fn stderr(&self) -> Option<&Value> {
+        None
+    }
+
+    fn task(&self) -> Option<&Task> {
+        None
+    }
+
+    fn array_spill_threshold(&self) -> Option<usize> {
+        None
+    }
+}
+
+/// Parses the given expression.
+fn parse(source: &str) -> Expr {
+    let mut parser = v1::Parser::new(Lexer::new(source));
+    let marker = parser.start();
+    if let Err((marker, diagnostic)) = v1::expr(&mut parser, marker) {
+        marker.abandon(&mut parser);
+        panic!("failed to parse expression `{source}`: {diagnostic:?}");
+    }
+
+    // This call to `next` is important as `next` adds any remaining buffered events
+    assert!(parser.next().is_none(), "expected a single expression");
+    let output = parser.finish();
+    assert!(
+        output.diagnostics.is_empty(),
+        "failed to parse expression `{source}`"
+    );
+    Expr::cast(construct_tree(source, output.events)).expect("should be an expression")
+}
+
+/// Evaluates the given expression and asserts that it allocates less than
+/// [`MAX_ALLOCATED`] bytes.
+fn evaluate(context: &TestContext, source: &str) -> Value {
+    let expr = parse(source);
+
+    reset_peak();
+    let value = ExprEvaluator::new(context)
+        .evaluate_expr(&expr)
+        .expect("expression should evaluate");
+    let peak = peak();
+    assert!(
+        peak < MAX_ALLOCATED,
+        "expected `{source}` to allocate less than {MAX_ALLOCATED} bytes, but the peak was {peak}"
+    );
+    value
+}
+
+#[test]
+fn write_lines_large() {
+    const COUNT: usize = 500_000;
+
+    let context = TestContext::new([
+        (
+            "lines",
+            Array::new(
+                ANALYSIS_STDLIB.array_string_type().clone(),
+                (0..COUNT).map(|i| PrimitiveValue::new_string(format!("line {i}"))),
+            )
+            .unwrap()
+            .into(),
+        ),
+        (
+            "files",
+            Array::new(
+                ArrayType::new(PrimitiveType::File),
+                (0..COUNT).map(|i| PrimitiveValue::new_file(format!("line {i}"))),
+            )
+            .unwrap()
+            .into(),
+        ),
+    ]);
+
+    // The elements are streamed, so the memory allocated while writing is a
+    // fraction of the size of the output
+    let strings = evaluate(&context, "write_lines(lines)");
+    let files = evaluate(&context, "write_lines(files)");
+
+    let strings = fs::read_to_string(strings.unwrap_file().as_str()).expect("failed to read file");
+    let files = fs::read_to_string(files.unwrap_file().as_str()).expect("failed to read file");
+    assert_eq!(strings.lines().count(), COUNT);
+    assert!(strings.starts_with("line 0\nline 1\n"));
+    assert!(strings.ends_with(&format!("line {last}\n", last = COUNT - 1)));
+    assert!(strings == files, "streamed contents should be identical");
+}
+
+#[test]
+fn write_tsv_large() {
+    const COUNT: usize = 200_000;
+
+    let row_ty = ANALYSIS_STDLIB.array_string_type().clone();
+    let context = TestContext::new([(
+        "rows",
+        Array::new(
+            ANALYSIS_STDLIB.array_array_string_type().clone(),
+            (0..COUNT).map(|i| {
+                Array::new(row_ty.clone(), [
+                    PrimitiveValue::new_string(format!("{i}")),
+                    PrimitiveValue::new_string("foo"),
+                ])
+                .unwrap()
+            }),
+        )
+        .unwrap()
+        .into(),
+    )]);
+
+    // The rows are streamed, so the memory allocated while writing is a
+    // fraction of the size of the output
+    let value = evaluate(&context, "write_tsv(rows)");
+
+    let contents = fs::read_to_string(value.unwrap_file().as_str()).expect("failed to read file");
+    assert_eq!(contents.lines().count(), COUNT);
+    assert!(contents.starts_with("0\tfoo\n1\tfoo\n"));
+    assert!(contents.ends_with(&format!("{last}\tfoo\n", last = COUNT - 1)));
+}
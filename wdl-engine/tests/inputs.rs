@@ -9,8 +9,14 @@
 //! * `inputs.json` - the inputs to the workflow or task.
 //! * `error.txt` - the expected error message (if there is one).
 //!
-//! The `error.txt` file may be automatically generated or updated by setting
-//! the `BLESS` environment variable when running this test.
+//! A directory may also contain:
+//!
+//! * `template.json` - the expected inputs template, including optional inputs
+//!   and descriptions, for the document's workflow (or its first task if there
+//!   is no workflow).
+//!
+//! The `error.txt` and `template.json` files may be automatically generated or
+//! updated by setting the `BLESS` environment variable when running this test.
 
 use std::borrow::Cow;
 use std::collections::HashSet;
@@ -42,6 +48,7 @@ use wdl_analysis::rules;
 use wdl_ast::Diagnostic;
 use wdl_ast::Severity;
 use wdl_engine::Inputs;
+use wdl_engine::TemplateOptions;
 
 /// Finds tests to run as part of the analysis test suite.
 fn find_tests() -> Vec<PathBuf> {
@@ -183,6 +190,30 @@ fn run_test(test: &Path, result: AnalysisResult, ntests: &AtomicUsize) -> Result
     let output = test.join("error.txt");
     compare_result(&output, &result)?;
 
+    let output = test.join("template.json");
+    if output.is_file() {
+        let name = document
+            .workflow()
+            .map(|w| w.name())
+            .or_else(|| document.tasks().next().map(|t| t.name()))
+            .expect("document should have a workflow or task");
+        let template = Inputs::template(
+            document,
+            name,
+            TemplateOptions {
+                optional: true,
+                descriptions: true,
+            },
+        )?;
+        compare_result(
+            &output,
+            &format!(
+                "{template}\n",
+                template = serde_json::to_string_pretty(&template)?
+            ),
+        )?;
+    }
+
     ntests.fetch_add(1, Ordering::SeqCst);
     Ok(())
 }
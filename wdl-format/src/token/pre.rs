@@ -1,6 +1,7 @@
 //! Tokens emitted during the formatting of particular elements.
 
 use wdl_ast::SyntaxKind;
+use wdl_ast::SyntaxToken;
 use wdl_ast::SyntaxTokenExt;
 
 use crate::Comment;
@@ -50,6 +51,27 @@ pub enum PreToken {
     TempIndentEnd,
 }
 
+/// Splits the preceding trivia of a `version` keyword into the document's
+/// header and the comments attached to the version statement.
+///
+/// The header is every comment that is separated from the version statement
+/// by a blank line (e.g. a license header); the remaining comments directly
+/// precede the version statement and are attached to it.
+fn split_header(keyword: &SyntaxToken) -> (Vec<SyntaxToken>, Vec<SyntaxToken>) {
+    let mut header: Vec<_> = keyword.preceding_trivia().collect();
+    match header
+        .iter()
+        .rposition(|t| t.kind() == SyntaxKind::Whitespace)
+    {
+        Some(index) => {
+            let attached = header.split_off(index + 1);
+            header.pop();
+            (header, attached)
+        }
+        None => (Vec::new(), header),
+    }
+}
+
 /// The line length to use when displaying pretokens.
 const DISPLAY_LINE_LENGTH: usize = 90;
 
@@ -161,10 +183,52 @@ impl TokenStream<PreToken> {
         ));
     }
 
+    /// Inserts the header of a document into the stream given the document's
+    /// `version` keyword.
+    ///
+    /// The header comments are written verbatim (including any blank lines
+    /// between them) and are always followed by exactly one blank line; the
+    /// comments attached to the version statement are written with the
+    /// `version` keyword itself.
+    pub fn push_header(&mut self, keyword: &SyntaxToken) {
+        assert!(keyword.kind() == SyntaxKind::VersionKeyword);
+        let (header, _) = split_header(keyword);
+        let mut header = header
+            .into_iter()
+            .skip_while(|t| t.kind() == SyntaxKind::Whitespace)
+            .peekable();
+        if header.peek().is_none() {
+            return;
+        }
+
+        for token in header {
+            match token.kind() {
+                SyntaxKind::Whitespace => self.blank_line(),
+                SyntaxKind::Comment => {
+                    self.0
+                        .push(PreToken::Trivia(Trivia::Comment(Comment::Preceding(
+                            token.text().trim_end().to_owned(),
+                        ))));
+                }
+                _ => unreachable!("unexpected trivia: {:?}", token),
+            }
+        }
+
+        self.blank_line();
+    }
+
     /// Inserts any preceding trivia into the stream.
+    ///
+    /// The header of the document is not included in the preceding trivia of
+    /// the `version` keyword (see [`push_header`](Self::push_header)).
     fn push_preceding_trivia(&mut self, token: &wdl_ast::Token) {
         assert!(!token.syntax().kind().is_trivia());
-        let preceding_trivia = token.syntax().preceding_trivia();
+        let preceding_trivia: Vec<_> = if token.syntax().kind() == SyntaxKind::VersionKeyword {
+            split_header(token.syntax()).1
+        } else {
+            token.syntax().preceding_trivia().collect()
+        };
+
         for token in preceding_trivia {
             match token.kind() {
                 SyntaxKind::Whitespace => {
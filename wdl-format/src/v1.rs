@@ -1,6 +1,7 @@
 //! Formatting of WDL v1.x elements.
 
 use wdl_ast::AstToken;
+use wdl_ast::SyntaxElement;
 use wdl_ast::SyntaxKind;
 
 pub mod decl;
@@ -22,6 +23,19 @@ pub fn format_ast(element: &FormatElement, stream: &mut TokenStream<PreToken>) {
 
     let version_statement = children.next().expect("version statement");
     assert!(version_statement.element().kind() == SyntaxKind::VersionStatementNode);
+
+    // Write the header (e.g. a license header) separately from the comments
+    // attached to the version statement
+    let keyword = version_statement
+        .element()
+        .syntax()
+        .into_node()
+        .expect("version statement node")
+        .children_with_tokens()
+        .filter_map(SyntaxElement::into_token)
+        .find(|t| t.kind() == SyntaxKind::VersionKeyword)
+        .expect("version keyword");
+    stream.push_header(&keyword);
     (&version_statement).write(stream);
 
     stream.blank_line();
@@ -99,11 +99,21 @@ pub struct Diagnostic {
     ///
     /// The first label in the collection is considered the primary label.
     labels: Vec<Label>,
+    /// The replacements of source text that fix the diagnostic.
+    replacements: Vec<Replacement>,
 }
 
+/// Diagnostics are ordered by the start of their primary label (diagnostics
+/// without a label come first), then by rule identifier (diagnostics without a
+/// rule come first), and then by message.
+///
+/// Any remaining ties are broken by severity, fix, labels, and replacements so
+/// that the ordering is total; sorting a collection of diagnostics is therefore
+/// deterministic regardless of the order in which they were reported.
 impl Ord for Diagnostic {
     fn cmp(&self, other: &Self) -> Ordering {
-        match self.labels.cmp(&other.labels) {
+        let start = |d: &Self| d.labels.first().map(|l| l.span.start());
+        match start(self).cmp(&start(other)) {
             Ordering::Equal => {}
             ord => return ord,
         }
@@ -113,17 +123,27 @@ impl Ord for Diagnostic {
             ord => return ord,
         }
 
+        match self.message.cmp(&other.message) {
+            Ordering::Equal => {}
+            ord => return ord,
+        }
+
         match self.severity.cmp(&other.severity) {
             Ordering::Equal => {}
             ord => return ord,
         }
 
-        match self.message.cmp(&other.message) {
+        match self.fix.cmp(&other.fix) {
             Ordering::Equal => {}
             ord => return ord,
         }
 
-        self.fix.cmp(&other.fix)
+        match self.labels.cmp(&other.labels) {
+            Ordering::Equal => {}
+            ord => return ord,
+        }
+
+        self.replacements.cmp(&other.replacements)
     }
 }
 
@@ -142,6 +162,7 @@ impl Diagnostic {
             message: message.into(),
             fix: None,
             labels: Default::default(),
+            replacements: Default::default(),
         }
     }
 
@@ -153,6 +174,7 @@ impl Diagnostic {
             message: message.into(),
             fix: None,
             labels: Default::default(),
+            replacements: Default::default(),
         }
     }
 
@@ -164,6 +186,7 @@ impl Diagnostic {
             message: message.into(),
             fix: None,
             labels: Default::default(),
+            replacements: Default::default(),
         }
     }
 
@@ -195,6 +218,15 @@ impl Diagnostic {
         self
     }
 
+    /// Adds a replacement of source text that fixes the diagnostic.
+    ///
+    /// The replacements of a diagnostic must not overlap; together they apply
+    /// the diagnostic's fix.
+    pub fn with_replacement(mut self, span: impl ToSpan, text: impl Into<String>) -> Self {
+        self.replacements.push(Replacement::new(span, text));
+        self
+    }
+
     /// Sets the severity of the diagnostic.
     pub fn with_severity(mut self, severity: Severity) -> Self {
         self.severity = severity;
@@ -233,6 +265,14 @@ impl Diagnostic {
         self.labels.iter_mut()
     }
 
+    /// Gets the replacements of source text that fix the diagnostic.
+    ///
+    /// Returns an empty iterator if the diagnostic's fix cannot be applied
+    /// automatically.
+    pub fn replacements(&self) -> impl Iterator<Item = &Replacement> {
+        self.replacements.iter()
+    }
+
     /// Converts this diagnostic to a `codespan` [Diagnostic].
     ///
     /// [Diagnostic]: codespan_reporting::diagnostic::Diagnostic
@@ -337,6 +377,35 @@ impl Label {
     }
 }
 
+/// Represents a replacement of a span of source text.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Replacement {
+    /// The span of the source text to replace.
+    span: Span,
+    /// The replacement text.
+    text: String,
+}
+
+impl Replacement {
+    /// Creates a new replacement of the given span with the given text.
+    pub fn new(span: impl ToSpan, text: impl Into<String>) -> Self {
+        Self {
+            span: span.to_span(),
+            text: text.into(),
+        }
+    }
+
+    /// Gets the span of the source text to replace.
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    /// Gets the replacement text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
 /// A trait implemented on types that convert to spans.
 pub trait ToSpan {
     /// Converts the type to a span.
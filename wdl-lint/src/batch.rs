@@ -0,0 +1,106 @@
+//! Linting of multiple documents in parallel.
+
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::thread;
+
+use wdl_ast::Diagnostic;
+use wdl_ast::Document;
+use wdl_ast::SyntaxNode;
+use wdl_ast::Validator;
+
+use crate::LintVisitor;
+
+/// Lints the given documents with the default lint rules, using up to
+/// `parallelism` worker threads.
+///
+/// See [`lint_documents_with`] for details.
+pub fn lint_documents(
+    parallelism: usize,
+    documents: impl IntoIterator<Item = Document>,
+) -> Vec<(Arc<Document>, Vec<Diagnostic>)> {
+    lint_documents_with(parallelism, documents, LintVisitor::default)
+}
+
+/// Lints the given documents using up to `parallelism` worker threads.
+///
+/// A `parallelism` of zero uses the available parallelism of the system.
+///
+/// Each worker thread lints documents with its own [`LintVisitor`] created by
+/// the given function, so rules that hold per-document state are never shared
+/// between threads.
+///
+/// Only lint diagnostics are returned; the documents are not otherwise
+/// validated.
+///
+/// The returned diagnostics are grouped by document and sorted, and the
+/// documents are returned in the order they were given; the result is the same
+/// regardless of the number of worker threads.
+// The documents never leave the calling thread; only their green trees are
+// shared with the worker threads
+#[allow(clippy::arc_with_non_send_sync)]
+pub fn lint_documents_with<F>(
+    parallelism: usize,
+    documents: impl IntoIterator<Item = Document>,
+    visitor: F,
+) -> Vec<(Arc<Document>, Vec<Diagnostic>)>
+where
+    F: Fn() -> LintVisitor + Sync,
+{
+    let documents: Vec<_> = documents.into_iter().collect();
+    let parallelism = match parallelism {
+        0 => thread::available_parallelism().map_or(1, NonZeroUsize::get),
+        n => n,
+    };
+
+    // Syntax trees cannot be sent between threads, so each worker lints a
+    // document recreated from the (thread-safe) green tree of the original
+    let trees: Vec<_> = documents
+        .iter()
+        .map(|d| d.syntax().green().into_owned())
+        .collect();
+    let next = AtomicUsize::new(0);
+    let mut diagnostics: Vec<Vec<Diagnostic>> = vec![Vec::new(); documents.len()];
+
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..parallelism.min(documents.len()))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut validator = Validator::empty();
+                    validator.add_visitor(visitor());
+
+                    let mut results = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(tree) = trees.get(index) else {
+                            break;
+                        };
+
+                        let document = Document::cast(SyntaxNode::new_root(tree.clone()))
+                            .expect("document should cast");
+                        results.push((
+                            index,
+                            validator.validate(&document).err().unwrap_or_default(),
+                        ));
+                    }
+
+                    results
+                })
+            })
+            .collect();
+
+        for worker in workers {
+            for (index, result) in worker.join().expect("lint worker thread panicked") {
+                diagnostics[index] = result;
+            }
+        }
+    });
+
+    documents
+        .into_iter()
+        .map(Arc::new)
+        .zip(diagnostics)
+        .collect()
+}
@@ -0,0 +1,854 @@
+//! Implementation of lint configuration files.
+//!
+//! Lint configuration is read from `wdl.toml` or `.wdl-lint.toml` files. A
+//! configuration file applies to every document in the directory containing
+//! it, including the documents of any subdirectories.
+//!
+//! An example configuration file:
+//!
+//! ```toml
+//! [lint]
+//! profile = "recommended"
+//! enable = ["ShellCheck"]
+//! disable = ["LineWidth"]
+//!
+//! [lint.tags]
+//! exclude = ["Sorting"]
+//!
+//! [lint.rules.SnakeCase]
+//! enabled = false
+//!
+//! [lint.rules.LineWidth]
+//! severity = "note"
+//! max_width = 120
+//! ```
+//!
+//! Keys of a per-rule table other than `enabled` and `severity` are options of
+//! the rule; an option that the rule does not have is an error.
+//!
+//! When the configuration for a document is discovered, every configuration
+//! file from the root of the file system to the document's directory is
+//! merged, with files nearer to the document taking precedence. Tables (such
+//! as the per-rule tables) are merged key by key while any other value,
+//! including arrays, replaces the value of a more distant file. If a directory
+//! contains both files, `.wdl-lint.toml` takes precedence over `wdl.toml`.
+//!
+//! A configuration file with a top-level `root = true` key stops the
+//! discovery: configuration files of the directories above it are not read.
+//!
+//! Use [`ConfigDiscovery::effective_config_for`] to see which file set each
+//! value of a document's configuration.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::path::absolute;
+use std::sync::Arc;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::anyhow;
+use indexmap::IndexMap;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use wdl_ast::Diagnostic;
+use wdl_ast::Severity;
+
+use crate::Profile;
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+use crate::rules_with_tags;
+
+/// The name of a configuration file.
+pub const CONFIG_FILE_NAME: &str = "wdl.toml";
+
+/// The name of a lint-specific configuration file.
+///
+/// If a directory contains both a [`CONFIG_FILE_NAME`] file and a lint
+/// configuration file, the lint configuration file takes precedence.
+pub const LINT_CONFIG_FILE_NAME: &str = ".wdl-lint.toml";
+
+/// Represents the contents of a configuration file.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct Config {
+    /// Whether or not the configuration file is the root configuration.
+    ///
+    /// Configuration files of the directories above a root configuration file
+    /// are not read.
+    #[serde(default)]
+    pub root: bool,
+    /// The lint configuration.
+    #[serde(default)]
+    pub lint: LintConfig,
+}
+
+impl Config {
+    /// Parses a configuration from a TOML string.
+    pub fn parse(s: &str) -> Result<Self> {
+        Ok(toml::from_str(s)?)
+    }
+
+    /// Reads a configuration from the given file.
+    ///
+    /// Unlike [`ConfigDiscovery`], only the given file is read.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path).with_context(|| {
+            format!(
+                "failed to read configuration file `{path}`",
+                path = path.display()
+            )
+        })?;
+
+        Self::parse(&contents).with_context(|| {
+            format!(
+                "failed to parse configuration file `{path}`",
+                path = path.display()
+            )
+        })
+    }
+}
+
+/// Represents the lint configuration.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LintConfig {
+    /// The name of the lint rule profile to use.
+    ///
+    /// Defaults to the `recommended` profile.
+    pub profile: Option<String>,
+    /// The rules to enable in addition to the rules of the profile.
+    pub enable: Vec<String>,
+    /// The rules of the profile to disable.
+    pub disable: Vec<String>,
+    /// The tag filter of the rules.
+    pub tags: TagsConfig,
+    /// The per-rule configuration, keyed by rule identifier.
+    pub rules: IndexMap<String, RuleConfig>,
+}
+
+impl LintConfig {
+    /// Gets the rules to run for the configuration.
+    ///
+    /// Each rule is configured with the options of its per-rule table (see
+    /// [`Rule::configure`]).
+    ///
+    /// Returns an error if the profile or a tag is unknown, if a tag is both
+    /// included and excluded, or if the options of a rule are invalid.
+    pub fn rules(&self) -> Result<Vec<Box<dyn Rule>>> {
+        let profile = match &self.profile {
+            Some(name) => {
+                Profile::from_name(name).ok_or_else(|| anyhow!("unknown lint profile `{name}`"))?
+            }
+            None => Profile::default(),
+        };
+
+        let overrides = |enabled| {
+            self.rules
+                .iter()
+                .filter(move |(_, r)| r.enabled == Some(enabled))
+                .map(|(id, _)| id.as_str())
+        };
+
+        let mut rules = profile.rules_with_overrides(
+            self.enable
+                .iter()
+                .map(String::as_str)
+                .chain(overrides(true)),
+            self.disable
+                .iter()
+                .map(String::as_str)
+                .chain(overrides(false)),
+        );
+
+        if !self.tags.include.is_empty() || !self.tags.exclude.is_empty() {
+            let ids: Vec<_> = rules_with_tags(self.tags.include()?, self.tags.exclude()?)?
+                .iter()
+                .map(|r| r.id())
+                .collect();
+            rules.retain(|r| ids.contains(&r.id()));
+        }
+
+        for rule in &mut rules {
+            if let Some(config) = self.rules.get(rule.id()) {
+                rule.configure(&config.options).with_context(|| {
+                    format!("invalid configuration of lint rule `{id}`", id = rule.id())
+                })?;
+            }
+        }
+
+        Ok(rules)
+    }
+
+    /// Gets the configured severity of the given rule.
+    ///
+    /// Returns `None` if the severity of the rule is not configured.
+    pub fn severity(&self, rule: &str) -> Option<Severity> {
+        self.rules
+            .get(rule)
+            .and_then(|r| r.severity)
+            .map(Into::into)
+    }
+
+    /// Applies the configured rule severities to the given diagnostics.
+    pub fn apply_severities(&self, diagnostics: &mut [Diagnostic]) {
+        for diagnostic in diagnostics {
+            if let Some(severity) = diagnostic.rule().and_then(|r| self.severity(r)) {
+                *diagnostic = diagnostic.clone().with_severity(severity);
+            }
+        }
+    }
+}
+
+/// Represents the tag filter of the lint configuration.
+///
+/// See [`rules_with_tags`] for how the rules are filtered.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TagsConfig {
+    /// The tags of the rules to include.
+    pub include: Vec<String>,
+    /// The tags of the rules to exclude.
+    pub exclude: Vec<String>,
+}
+
+impl TagsConfig {
+    /// Gets the set of included tags.
+    pub fn include(&self) -> Result<TagSet> {
+        self.include.iter().map(|t| t.parse::<Tag>()).collect()
+    }
+
+    /// Gets the set of excluded tags.
+    pub fn exclude(&self) -> Result<TagSet> {
+        self.exclude.iter().map(|t| t.parse::<Tag>()).collect()
+    }
+}
+
+/// Represents a configured severity of a rule's diagnostics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleSeverity {
+    /// The rule's diagnostics are errors.
+    Error,
+    /// The rule's diagnostics are warnings.
+    Warning,
+    /// The rule's diagnostics are notes.
+    Note,
+}
+
+impl From<RuleSeverity> for Severity {
+    fn from(severity: RuleSeverity) -> Self {
+        match severity {
+            RuleSeverity::Error => Self::Error,
+            RuleSeverity::Warning => Self::Warning,
+            RuleSeverity::Note => Self::Note,
+        }
+    }
+}
+
+/// Represents the configuration of a single rule.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct RuleConfig {
+    /// Whether or not the rule is enabled.
+    ///
+    /// If `None`, the rule is enabled only if it is part of the profile.
+    pub enabled: Option<bool>,
+    /// The severity of the rule's diagnostics.
+    ///
+    /// If `None`, the rule's diagnostics keep their severity.
+    pub severity: Option<RuleSeverity>,
+    /// Any additional rule options.
+    ///
+    /// The options are applied to the rule with [`Rule::configure`].
+    #[serde(flatten)]
+    pub options: toml::Table,
+}
+
+/// Deserializes the options of a rule from its configuration table.
+///
+/// This is a helper for implementing [`Rule::configure`]; the options type
+/// is expected to deny unknown fields so that unknown options are rejected.
+pub(crate) fn rule_options<T: DeserializeOwned>(options: &toml::Table) -> Result<T> {
+    Ok(toml::Value::Table(options.clone()).try_into()?)
+}
+
+/// Merges the `overlay` table into the `base` table.
+///
+/// Tables are merged recursively; any other value replaces the base value.
+///
+/// The given file is recorded in `sources` as the source of each merged value,
+/// keyed by the dotted path of the value prefixed with `prefix`.
+fn merge(
+    base: &mut toml::Table,
+    overlay: toml::Table,
+    prefix: &str,
+    file: &Path,
+    sources: &mut IndexMap<String, PathBuf>,
+) {
+    for (key, value) in overlay {
+        let path = format!("{prefix}{key}");
+        match value {
+            toml::Value::Table(overlay) => {
+                if !matches!(base.get(&key), Some(toml::Value::Table(_))) {
+                    sources.shift_remove(&path);
+                    base.insert(key.clone(), toml::Value::Table(Default::default()));
+                }
+
+                let Some(toml::Value::Table(base)) = base.get_mut(&key) else {
+                    unreachable!("value should be a table");
+                };
+
+                merge(base, overlay, &format!("{path}."), file, sources);
+            }
+            value => {
+                let nested = format!("{path}.");
+                sources.retain(|k, _| !k.starts_with(&nested));
+                sources.insert(path, file.to_path_buf());
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Represents the merged configuration table of a directory.
+#[derive(Debug, Clone, Default)]
+struct Layer {
+    /// The merged configuration table.
+    table: toml::Table,
+    /// The configuration files that were merged, from the most distant to the
+    /// nearest.
+    files: Vec<PathBuf>,
+    /// The file that set each value of the table, keyed by dotted path.
+    sources: IndexMap<String, PathBuf>,
+}
+
+/// Represents the effective configuration of a document.
+///
+/// In addition to the merged configuration, this records which configuration
+/// file set each value so that the configuration may be debugged.
+#[derive(Debug, Clone)]
+pub struct EffectiveConfig {
+    /// The merged configuration.
+    config: Arc<Config>,
+    /// The configuration files that were merged, from the most distant to the
+    /// nearest.
+    files: Vec<PathBuf>,
+    /// The file that set each value of the configuration, keyed by dotted path.
+    sources: IndexMap<String, PathBuf>,
+}
+
+impl EffectiveConfig {
+    /// Gets the merged configuration.
+    pub fn config(&self) -> &Arc<Config> {
+        &self.config
+    }
+
+    /// Gets the configuration files that were merged.
+    ///
+    /// The files are ordered from the most distant to the nearest to the
+    /// document.
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    /// Gets the configuration file that set the value with the given dotted
+    /// path (e.g. `lint.rules.LineWidth.max_width`).
+    ///
+    /// Returns `None` if the value was not set by a configuration file.
+    pub fn source(&self, key: &str) -> Option<&Path> {
+        self.sources.get(key).map(PathBuf::as_path)
+    }
+
+    /// Gets the configuration file that set each value of the configuration.
+    ///
+    /// The values are keyed by dotted path.
+    pub fn sources(&self) -> impl Iterator<Item = (&str, &Path)> {
+        self.sources.iter().map(|(k, v)| (k.as_str(), v.as_path()))
+    }
+}
+
+/// Represents the source of configuration for a [`ConfigDiscovery`].
+#[derive(Debug)]
+enum Source {
+    /// Configuration is discovered from configuration files.
+    Discover {
+        /// The cache of merged configuration tables, keyed by directory.
+        layers: HashMap<PathBuf, Arc<Layer>>,
+        /// The cache of merged configuration, keyed by directory.
+        configs: HashMap<PathBuf, Arc<Config>>,
+    },
+    /// An explicit configuration is used for every document.
+    Explicit(Arc<Config>),
+}
+
+/// Discovers the configuration for documents.
+///
+/// The merged configuration of each directory is cached so that the
+/// configuration files of a directory are read only once.
+#[derive(Debug)]
+pub struct ConfigDiscovery(Source);
+
+impl ConfigDiscovery {
+    /// Constructs a new configuration discovery that reads configuration
+    /// files.
+    pub fn new() -> Self {
+        Self(Source::Discover {
+            layers: Default::default(),
+            configs: Default::default(),
+        })
+    }
+
+    /// Constructs a new configuration discovery that uses the given
+    /// configuration for every document.
+    ///
+    /// No configuration files are read.
+    pub fn with_config(config: Config) -> Self {
+        Self(Source::Explicit(Arc::new(config)))
+    }
+
+    /// Constructs a new configuration discovery that uses the default
+    /// configuration for every document.
+    ///
+    /// No configuration files are read.
+    pub fn disabled() -> Self {
+        Self::with_config(Config::default())
+    }
+
+    /// Gets the configuration for the document at the given path.
+    ///
+    /// Returns an error if a configuration file could not be read or parsed.
+    pub fn config_for(&mut self, path: impl AsRef<Path>) -> Result<Arc<Config>> {
+        Ok(self.effective_config_for(path)?.config)
+    }
+
+    /// Gets the effective configuration for the document at the given path.
+    ///
+    /// Unlike [`ConfigDiscovery::config_for`], the returned configuration
+    /// records which configuration file set each value.
+    ///
+    /// Returns an error if a configuration file could not be read or parsed.
+    pub fn effective_config_for(&mut self, path: impl AsRef<Path>) -> Result<EffectiveConfig> {
+        let (layers, configs) = match &mut self.0 {
+            Source::Discover { layers, configs } => (layers, configs),
+            Source::Explicit(config) => {
+                return Ok(EffectiveConfig {
+                    config: config.clone(),
+                    files: Default::default(),
+                    sources: Default::default(),
+                });
+            }
+        };
+
+        let path = path.as_ref();
+        let path = absolute(path).with_context(|| {
+            format!(
+                "failed to make path `{path}` absolute",
+                path = path.display()
+            )
+        })?;
+        let dir = path.parent().unwrap_or(&path);
+        let layer = Self::layer_for(layers, dir)?;
+        let config = match configs.get(dir) {
+            Some(config) => config.clone(),
+            None => {
+                let config: Config = toml::Value::Table(layer.table.clone())
+                    .try_into()
+                    .with_context(|| {
+                        format!(
+                            "invalid configuration for directory `{dir}`",
+                            dir = dir.display()
+                        )
+                    })?;
+
+                let config = Arc::new(config);
+                configs.insert(dir.to_path_buf(), config.clone());
+                config
+            }
+        };
+
+        Ok(EffectiveConfig {
+            config,
+            files: layer.files.clone(),
+            sources: layer.sources.clone(),
+        })
+    }
+
+    /// Gets the merged configuration table for the given directory.
+    fn layer_for(layers: &mut HashMap<PathBuf, Arc<Layer>>, dir: &Path) -> Result<Arc<Layer>> {
+        if let Some(layer) = layers.get(dir) {
+            return Ok(layer.clone());
+        }
+
+        // Read the configuration files of the directory first, as a root
+        // configuration file stops the discovery
+        let mut tables = Vec::new();
+        for name in [CONFIG_FILE_NAME, LINT_CONFIG_FILE_NAME] {
+            let path = dir.join(name);
+            if !path.is_file() {
+                continue;
+            }
+
+            let contents = fs::read_to_string(&path).with_context(|| {
+                format!(
+                    "failed to read configuration file `{path}`",
+                    path = path.display()
+                )
+            })?;
+
+            let table: toml::Table = contents.parse().with_context(|| {
+                format!(
+                    "failed to parse configuration file `{path}`",
+                    path = path.display()
+                )
+            })?;
+
+            tables.push((path, table));
+        }
+
+        let root = tables
+            .iter()
+            .any(|(_, t)| t.get("root").and_then(toml::Value::as_bool) == Some(true));
+
+        let mut layer = match dir.parent() {
+            Some(parent) if !root => Self::layer_for(layers, parent)?.as_ref().clone(),
+            _ => Layer::default(),
+        };
+
+        for (path, table) in tables {
+            merge(&mut layer.table, table, "", &path, &mut layer.sources);
+            layer.files.push(path);
+        }
+
+        let layer = Arc::new(layer);
+        layers.insert(dir.to_path_buf(), layer.clone());
+        Ok(layer)
+    }
+}
+
+impl Default for ConfigDiscovery {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+    use wdl_ast::Document;
+    use wdl_ast::Validator;
+
+    use super::*;
+    use crate::LintVisitor;
+
+    /// Gets the identifiers of the rules for the given configuration.
+    fn ids(config: &Config) -> Vec<&'static str> {
+        config
+            .lint
+            .rules()
+            .expect("should get rules")
+            .iter()
+            .map(|r| r.id())
+            .collect()
+    }
+
+    #[test]
+    fn it_merges_nested_configs() {
+        let root = TempDir::new().expect("failed to create temporary directory");
+        let foo = root.path().join("pipelines").join("foo");
+        let bar = root.path().join("pipelines").join("bar");
+        fs::create_dir_all(&foo).unwrap();
+        fs::create_dir_all(&bar).unwrap();
+
+        fs::write(
+            root.path().join(CONFIG_FILE_NAME),
+            r#"
+[lint]
+enable = ["ShellCheck"]
+
+[lint.rules.LineWidth]
+max_width = 120
+"#,
+        )
+        .unwrap();
+
+        fs::write(
+            foo.join(CONFIG_FILE_NAME),
+            r#"
+[lint.rules.SnakeCase]
+enabled = false
+
+[lint.rules.LineWidth]
+enabled = true
+"#,
+        )
+        .unwrap();
+
+        let mut discovery = ConfigDiscovery::new();
+        let foo_config = discovery.config_for(foo.join("main.wdl")).unwrap();
+        let bar_config = discovery.config_for(bar.join("main.wdl")).unwrap();
+        let root_config = discovery.config_for(root.path().join("main.wdl")).unwrap();
+
+        // The rule is only disabled under `pipelines/foo`
+        assert!(!ids(&foo_config).contains(&"SnakeCase"));
+        assert!(ids(&bar_config).contains(&"SnakeCase"));
+        assert!(ids(&root_config).contains(&"SnakeCase"));
+        assert_eq!(ids(&foo_config).len(), ids(&bar_config).len() - 1);
+
+        // Root settings are inherited and per-rule tables are merged
+        assert!(ids(&foo_config).contains(&"ShellCheck"));
+        let line_width = &foo_config.lint.rules["LineWidth"];
+        assert_eq!(line_width.enabled, Some(true));
+        assert_eq!(line_width.options["max_width"].as_integer(), Some(120));
+        assert_eq!(bar_config, root_config);
+
+        // The merged configuration is cached per directory
+        assert!(Arc::ptr_eq(
+            &foo_config,
+            &discovery.config_for(foo.join("other.wdl")).unwrap()
+        ));
+    }
+
+    #[test]
+    fn it_replaces_arrays() {
+        let root = TempDir::new().expect("failed to create temporary directory");
+        let foo = root.path().join("foo");
+        fs::create_dir_all(&foo).unwrap();
+        fs::write(
+            root.path().join(CONFIG_FILE_NAME),
+            "[lint]\ndisable = [\"SnakeCase\", \"LineWidth\"]\n",
+        )
+        .unwrap();
+        fs::write(foo.join(CONFIG_FILE_NAME), "[lint]\ndisable = [\"Todo\"]\n").unwrap();
+
+        let mut discovery = ConfigDiscovery::new();
+        let config = discovery.config_for(foo.join("main.wdl")).unwrap();
+        assert_eq!(config.lint.disable, ["Todo"]);
+        assert!(ids(&config).contains(&"SnakeCase"));
+        assert!(!ids(&config).contains(&"Todo"));
+    }
+
+    #[test]
+    fn it_bypasses_discovery() {
+        let root = TempDir::new().expect("failed to create temporary directory");
+        fs::write(root.path().join(CONFIG_FILE_NAME), "not valid toml").unwrap();
+
+        let path = root.path().join("main.wdl");
+        assert!(ConfigDiscovery::new().config_for(&path).is_err());
+        assert_eq!(
+            *ConfigDiscovery::disabled().config_for(&path).unwrap(),
+            Config::default()
+        );
+
+        let config = Config::parse("[lint]\nprofile = \"minimal\"\n").unwrap();
+        let mut discovery = ConfigDiscovery::with_config(config.clone());
+        assert_eq!(*discovery.config_for(&path).unwrap(), config);
+        assert_eq!(ids(&config).len(), Profile::Minimal.rules().len());
+    }
+
+    #[test]
+    fn it_reports_effective_configs() {
+        let root = TempDir::new().expect("failed to create temporary directory");
+        let pipelines = root.path().join("pipelines");
+        let legacy = pipelines.join("legacy");
+        fs::create_dir_all(&legacy).unwrap();
+
+        let top = root.path().join(CONFIG_FILE_NAME);
+        fs::write(
+            &top,
+            r#"
+[lint]
+disable = ["Todo"]
+
+[lint.rules.LineWidth]
+max_width = 120
+severity = "warning"
+
+[lint.rules.SnakeCase]
+severity = "error"
+"#,
+        )
+        .unwrap();
+
+        let middle = pipelines.join(LINT_CONFIG_FILE_NAME);
+        fs::write(
+            &middle,
+            r#"
+[lint.rules.LineWidth]
+max_width = 100
+"#,
+        )
+        .unwrap();
+
+        let nearest = legacy.join(CONFIG_FILE_NAME);
+        fs::write(
+            &nearest,
+            r#"
+[lint]
+disable = ["SnakeCase"]
+
+[lint.rules.LineWidth]
+severity = "note"
+"#,
+        )
+        .unwrap();
+
+        let mut discovery = ConfigDiscovery::new();
+        let effective = discovery
+            .effective_config_for(legacy.join("main.wdl"))
+            .unwrap();
+        assert_eq!(
+            effective.files(),
+            [top.clone(), middle.clone(), nearest.clone()]
+        );
+
+        // The nearest value of each setting wins
+        let config = effective.config();
+        assert_eq!(config.lint.disable, ["SnakeCase"]);
+        assert!(!ids(config).contains(&"SnakeCase"));
+        assert!(ids(config).contains(&"Todo"));
+        let line_width = &config.lint.rules["LineWidth"];
+        assert_eq!(line_width.options["max_width"].as_integer(), Some(100));
+        assert_eq!(config.lint.severity("LineWidth"), Some(Severity::Note));
+        assert_eq!(config.lint.severity("SnakeCase"), Some(Severity::Error));
+        assert_eq!(config.lint.severity("Todo"), None);
+
+        // The file that set each value is reported
+        assert_eq!(effective.source("lint.disable"), Some(nearest.as_path()));
+        assert_eq!(
+            effective.source("lint.rules.LineWidth.max_width"),
+            Some(middle.as_path())
+        );
+        assert_eq!(
+            effective.source("lint.rules.LineWidth.severity"),
+            Some(nearest.as_path())
+        );
+        assert_eq!(
+            effective.source("lint.rules.SnakeCase.severity"),
+            Some(top.as_path())
+        );
+        assert_eq!(effective.source("lint.profile"), None);
+        assert_eq!(effective.sources().count(), 4);
+
+        // The effective configuration matches the discovered configuration
+        assert!(Arc::ptr_eq(
+            effective.config(),
+            &discovery.config_for(legacy.join("other.wdl")).unwrap()
+        ));
+    }
+
+    #[test]
+    fn it_stops_at_root_configs() {
+        let root = TempDir::new().expect("failed to create temporary directory");
+        let pipelines = root.path().join("pipelines");
+        let legacy = pipelines.join("legacy");
+        fs::create_dir_all(&legacy).unwrap();
+
+        fs::write(
+            root.path().join(CONFIG_FILE_NAME),
+            "[lint]\ndisable = [\"Todo\"]\nenable = [\"ShellCheck\"]\n",
+        )
+        .unwrap();
+
+        let middle = pipelines.join(CONFIG_FILE_NAME);
+        fs::write(&middle, "root = true\n\n[lint]\nprofile = \"minimal\"\n").unwrap();
+
+        let nearest = legacy.join(LINT_CONFIG_FILE_NAME);
+        fs::write(&nearest, "[lint]\nenable = [\"Todo\"]\n").unwrap();
+
+        let mut discovery = ConfigDiscovery::new();
+        let effective = discovery
+            .effective_config_for(legacy.join("main.wdl"))
+            .unwrap();
+
+        // The configuration above the root configuration is not read
+        assert_eq!(effective.files(), [middle.clone(), nearest.clone()]);
+        let config = effective.config();
+        assert!(config.root);
+        assert!(config.lint.disable.is_empty());
+        assert_eq!(config.lint.enable, ["Todo"]);
+        assert_eq!(effective.source("root"), Some(middle.as_path()));
+        assert_eq!(effective.source("lint.profile"), Some(middle.as_path()));
+        assert!(ids(config).contains(&"Todo"));
+        assert_eq!(ids(config).len(), Profile::Minimal.rules().len() + 1);
+
+        // Documents outside of the root configuration's directory are unaffected
+        let config = discovery.config_for(root.path().join("main.wdl")).unwrap();
+        assert!(!config.root);
+        assert_eq!(config.lint.disable, ["Todo"]);
+    }
+
+    #[test]
+    fn it_applies_severities() {
+        let config = Config::parse(
+            "[lint.rules.SnakeCase]\nseverity = \"error\"\n\n[lint.rules.Todo]\nenabled = true\n",
+        )
+        .unwrap();
+
+        let mut diagnostics = [
+            Diagnostic::note("foo").with_rule("SnakeCase"),
+            Diagnostic::note("bar").with_rule("Todo"),
+            Diagnostic::warning("baz"),
+        ];
+        config.lint.apply_severities(&mut diagnostics);
+        assert_eq!(diagnostics[0].severity(), Severity::Error);
+        assert_eq!(diagnostics[1].severity(), Severity::Note);
+        assert_eq!(diagnostics[2].severity(), Severity::Warning);
+
+        assert!(Config::parse("[lint.rules.SnakeCase]\nseverity = \"fatal\"\n").is_err());
+    }
+
+    #[test]
+    fn it_filters_by_tags() {
+        let config = Config::parse("[lint.tags]\nexclude = [\"style\"]\n").unwrap();
+        let rules = config.lint.rules().unwrap();
+        assert!(!rules.is_empty());
+        assert!(rules.iter().all(|r| !r.tags().contains(Tag::Style)));
+
+        let config =
+            Config::parse("[lint.tags]\ninclude = [\"Style\"]\nexclude = [\"Style\"]\n").unwrap();
+        assert!(config.lint.rules().is_err());
+
+        assert!(Config::parse("[lint]\nunknown = true\n").is_err());
+        let config = Config::parse("[lint]\nprofile = \"strict\"\n").unwrap();
+        assert_eq!(
+            config.lint.rules().unwrap_err().to_string(),
+            "unknown lint profile `strict`"
+        );
+    }
+
+    #[test]
+    fn it_configures_rules() {
+        let config = Config::parse("[lint.rules.LineWidth]\nmax_width = 20\n").unwrap();
+        let mut validator = Validator::empty();
+        validator.add_visitor(LintVisitor::new(config.lint.rules().unwrap()));
+        let (document, _) = Document::parse("version 1.1\n\nworkflow a_long_workflow_name {}\n");
+        let diagnostics = validator.validate(&document).unwrap_err();
+        assert!(diagnostics.iter().any(|d| d.rule() == Some("LineWidth")));
+
+        for (source, rule) in [
+            ("[lint.rules.LineWidth]\nwidth = 20\n", "LineWidth"),
+            ("[lint.rules.LineWidth]\nmax_width = \"20\"\n", "LineWidth"),
+            (
+                "[lint.rules.EndingNewline]\nmax_width = 20\n",
+                "EndingNewline",
+            ),
+            (
+                "[lint.rules.ConsistentIndentation]\nstyle = \"both\"\n",
+                "ConsistentIndentation",
+            ),
+        ] {
+            let config = Config::parse(source).unwrap();
+            assert_eq!(
+                config.lint.rules().unwrap_err().to_string(),
+                format!("invalid configuration of lint rule `{rule}`")
+            );
+        }
+
+        // Options of a rule that is not enabled are not checked
+        let config = Config::parse("[lint.rules.ShellCheck]\nunknown = true\n").unwrap();
+        assert!(config.lint.rules().is_ok());
+    }
+}
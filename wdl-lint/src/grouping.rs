@@ -0,0 +1,425 @@
+//! Grouping of lint diagnostics.
+//!
+//! Some rules (e.g. `ShellCheck`) may report many diagnostics for a single
+//! definition; grouping the diagnostics by rule and by the top-level
+//! definition that contains them allows consumers to present them compactly.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::num::NonZeroUsize;
+
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Document;
+use wdl_ast::v1::DocumentItem;
+
+/// Represents the kind of a top-level definition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DefinitionKind {
+    /// The definition is a struct.
+    Struct,
+    /// The definition is a task.
+    Task,
+    /// The definition is a workflow.
+    Workflow,
+}
+
+impl fmt::Display for DefinitionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Struct => write!(f, "struct"),
+            Self::Task => write!(f, "task"),
+            Self::Workflow => write!(f, "workflow"),
+        }
+    }
+}
+
+/// Represents a top-level definition of a document.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Definition {
+    /// The kind of the definition.
+    kind: DefinitionKind,
+    /// The name of the definition.
+    name: String,
+}
+
+impl Definition {
+    /// Gets the top-level definition of the document that contains the given
+    /// offset.
+    ///
+    /// Returns `None` if the offset is not within a struct, task, or workflow.
+    pub fn at(document: &Document, offset: usize) -> Option<Self> {
+        let (kind, name) = match document.ast().as_v1()?.item_at(offset)? {
+            DocumentItem::Import(_) => return None,
+            DocumentItem::Struct(s) => (DefinitionKind::Struct, s.name()),
+            DocumentItem::Task(t) => (DefinitionKind::Task, t.name()),
+            DocumentItem::Workflow(w) => (DefinitionKind::Workflow, w.name()),
+        };
+
+        Some(Self {
+            kind,
+            name: name.as_str().to_string(),
+        })
+    }
+
+    /// Gets the kind of the definition.
+    pub fn kind(&self) -> DefinitionKind {
+        self.kind
+    }
+
+    /// Gets the name of the definition.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl fmt::Display for Definition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{kind} {name}", kind = self.kind, name = self.name)
+    }
+}
+
+/// Represents a group of diagnostics with the same rule in the same top-level
+/// definition.
+#[derive(Debug, Clone)]
+pub struct DiagnosticGroup {
+    /// The rule of the diagnostics in the group.
+    rule: Option<String>,
+    /// The top-level definition that contains the diagnostics in the group.
+    definition: Option<Definition>,
+    /// The number of diagnostics in the group, including any truncated
+    /// diagnostics.
+    count: usize,
+    /// The number of diagnostics that were truncated from the group.
+    truncated: usize,
+    /// The index of the representative diagnostic.
+    representative: usize,
+    /// The diagnostics of the group, ordered by position.
+    ///
+    /// If the group was truncated, the last diagnostic is a summary of the
+    /// diagnostics that were removed.
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl DiagnosticGroup {
+    /// Gets the rule of the diagnostics in the group.
+    ///
+    /// Returns `None` for diagnostics not associated with a rule.
+    pub fn rule(&self) -> Option<&str> {
+        self.rule.as_deref()
+    }
+
+    /// Gets the top-level definition that contains the diagnostics in the
+    /// group.
+    ///
+    /// Returns `None` for diagnostics outside of any struct, task, or
+    /// workflow.
+    pub fn definition(&self) -> Option<&Definition> {
+        self.definition.as_ref()
+    }
+
+    /// Gets the number of diagnostics in the group, including any that were
+    /// truncated.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Gets the number of diagnostics that were truncated from the group.
+    pub fn truncated(&self) -> usize {
+        self.truncated
+    }
+
+    /// Gets the representative diagnostic of the group.
+    ///
+    /// This is the first of the most severe diagnostics in the group.
+    pub fn representative(&self) -> &Diagnostic {
+        &self.diagnostics[self.representative]
+    }
+
+    /// Gets the diagnostics of the group, ordered by position.
+    ///
+    /// If the group was truncated, the last diagnostic is a summary of the
+    /// diagnostics that were removed.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// Truncates the group to the given number of diagnostics, replacing the
+    /// remainder with a summary diagnostic.
+    fn truncate(&mut self, max: NonZeroUsize) {
+        let max = max.get();
+        if self.diagnostics.len() <= max {
+            return;
+        }
+
+        // Keep the representative diagnostic in place of the last retained one;
+        // as it follows every retained diagnostic, the order is preserved
+        if self.representative >= max {
+            self.diagnostics.swap(self.representative, max - 1);
+            self.representative = max - 1;
+        }
+
+        self.truncated = self.diagnostics.len() - max;
+        self.diagnostics.truncate(max);
+
+        let findings = match &self.rule {
+            Some(rule) => format!("{rule} findings"),
+            None => "findings".to_string(),
+        };
+        let location = match &self.definition {
+            Some(definition) => definition.to_string(),
+            None => "the document".to_string(),
+        };
+        self.diagnostics.push(Diagnostic::note(format!(
+            "and {truncated} more {findings} in {location}",
+            truncated = self.truncated
+        )));
+    }
+}
+
+/// Gets the position of a diagnostic, which is the start of its primary label.
+///
+/// Diagnostics without a label are positioned after all others.
+fn position(diagnostic: &Diagnostic) -> usize {
+    diagnostic
+        .labels()
+        .next()
+        .map(|l| l.span().start())
+        .unwrap_or(usize::MAX)
+}
+
+/// Groups diagnostics of the given document by rule and by the top-level
+/// definition that contains them.
+///
+/// The diagnostics of each group are ordered by position and the groups are
+/// ordered by the position of their first diagnostic, then by rule.
+///
+/// If `max_per_group` is specified, groups with more diagnostics are truncated
+/// to that many, with the remainder replaced by a summary diagnostic (e.g. "and
+/// 17 more ShellCheck findings in task align"). The representative diagnostic
+/// of a group is always retained.
+pub fn group_diagnostics(
+    document: &Document,
+    diagnostics: impl IntoIterator<Item = Diagnostic>,
+    max_per_group: Option<NonZeroUsize>,
+) -> Vec<DiagnosticGroup> {
+    let mut indexes: HashMap<(Option<String>, Option<Definition>), usize> = HashMap::new();
+    let mut groups: Vec<DiagnosticGroup> = Vec::new();
+
+    let mut diagnostics: Vec<_> = diagnostics.into_iter().collect();
+    diagnostics.sort_by_key(position);
+
+    for diagnostic in diagnostics {
+        let rule = diagnostic.rule().map(str::to_string);
+        let definition = diagnostic
+            .labels()
+            .next()
+            .and_then(|l| Definition::at(document, l.span().start()));
+
+        let index = *indexes
+            .entry((rule.clone(), definition.clone()))
+            .or_insert_with(|| {
+                groups.push(DiagnosticGroup {
+                    rule,
+                    definition,
+                    count: 0,
+                    truncated: 0,
+                    representative: 0,
+                    diagnostics: Vec::new(),
+                });
+                groups.len() - 1
+            });
+
+        // The representative is the first of the most severe diagnostics
+        let group = &mut groups[index];
+        if group
+            .diagnostics
+            .get(group.representative)
+            .is_none_or(|r| diagnostic.severity() < r.severity())
+        {
+            group.representative = group.diagnostics.len();
+        }
+
+        group.count += 1;
+        group.diagnostics.push(diagnostic);
+    }
+
+    groups.sort_by(|a, b| {
+        position(&a.diagnostics[0])
+            .cmp(&position(&b.diagnostics[0]))
+            .then_with(|| a.rule.cmp(&b.rule))
+    });
+
+    if let Some(max) = max_per_group {
+        for group in &mut groups {
+            group.truncate(max);
+        }
+    }
+
+    groups
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use wdl_ast::Span;
+
+    use super::*;
+
+    /// The source of the test document.
+    const SOURCE: &str = r#"version 1.1
+
+struct Point {
+    Int x
+}
+
+task align {
+    command <<<
+        echo $a $b $c $d $e
+    >>>
+}
+
+workflow main {
+    call align
+}
+"#;
+
+    /// Gets the span of the first occurrence of the given text in the source.
+    fn span(text: &str) -> Span {
+        Span::new(SOURCE.find(text).expect("text should exist"), text.len())
+    }
+
+    /// Creates a synthetic pile of diagnostics, out of order.
+    fn diagnostics() -> Vec<Diagnostic> {
+        let shellcheck = |severity: fn(String) -> Diagnostic, var: &str| {
+            severity(format!("`{var}` is not quoted"))
+                .with_rule("ShellCheck")
+                .with_highlight(span(var))
+        };
+
+        vec![
+            Diagnostic::error("a diagnostic without a label"),
+            shellcheck(Diagnostic::note, "$e"),
+            Diagnostic::note("workflow name")
+                .with_rule("SnakeCase")
+                .with_highlight(span("main")),
+            shellcheck(Diagnostic::warning, "$d"),
+            shellcheck(Diagnostic::note, "$a"),
+            Diagnostic::note("struct name")
+                .with_rule("SnakeCase")
+                .with_highlight(span("Point")),
+            shellcheck(Diagnostic::warning, "$b"),
+            Diagnostic::note("version")
+                .with_rule("Version")
+                .with_highlight(span("1.1")),
+            shellcheck(Diagnostic::note, "$c"),
+        ]
+    }
+
+    /// Summarizes groups as their rule, definition, count, and messages.
+    fn summarize(
+        groups: &[DiagnosticGroup],
+    ) -> Vec<(Option<&str>, Option<String>, usize, Vec<&str>)> {
+        groups
+            .iter()
+            .map(|g| {
+                (
+                    g.rule(),
+                    g.definition().map(ToString::to_string),
+                    g.count(),
+                    g.diagnostics().iter().map(Diagnostic::message).collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn it_maps_spans_to_definitions() {
+        let (document, diagnostics) = Document::parse(SOURCE);
+        assert!(diagnostics.is_empty());
+
+        let at = |text| Definition::at(&document, span(text).start());
+        assert_eq!(at("1.1"), None);
+        assert_eq!(at("Point").unwrap().to_string(), "struct Point");
+        assert_eq!(at("$c").unwrap().kind(), DefinitionKind::Task);
+        assert_eq!(at("$c").unwrap().name(), "align");
+        assert_eq!(at("call").unwrap().to_string(), "workflow main");
+    }
+
+    #[test]
+    fn it_groups_diagnostics() {
+        let (document, _) = Document::parse(SOURCE);
+        let groups = group_diagnostics(&document, diagnostics(), None);
+        assert_eq!(
+            summarize(&groups),
+            [
+                (Some("Version"), None, 1, vec!["version"]),
+                (
+                    Some("SnakeCase"),
+                    Some("struct Point".to_string()),
+                    1,
+                    vec!["struct name"]
+                ),
+                (
+                    Some("ShellCheck"),
+                    Some("task align".to_string()),
+                    5,
+                    vec![
+                        "`$a` is not quoted",
+                        "`$b` is not quoted",
+                        "`$c` is not quoted",
+                        "`$d` is not quoted",
+                        "`$e` is not quoted",
+                    ]
+                ),
+                (
+                    Some("SnakeCase"),
+                    Some("workflow main".to_string()),
+                    1,
+                    vec!["workflow name"]
+                ),
+                (None, None, 1, vec!["a diagnostic without a label"]),
+            ]
+        );
+
+        // The representative is the first of the most severe diagnostics
+        assert_eq!(groups[2].representative().message(), "`$b` is not quoted");
+        assert!(groups.iter().all(|g| g.truncated() == 0));
+    }
+
+    #[test]
+    fn it_truncates_groups() {
+        let (document, _) = Document::parse(SOURCE);
+        let groups = group_diagnostics(&document, diagnostics(), NonZeroUsize::new(2));
+        assert_eq!(groups.len(), 5);
+        assert_eq!(groups[2].count(), 5);
+        assert_eq!(groups[2].truncated(), 3);
+        assert_eq!(
+            summarize(&groups[2..3])[0].3,
+            [
+                "`$a` is not quoted",
+                "`$b` is not quoted",
+                "and 3 more ShellCheck findings in task align"
+            ]
+        );
+        assert_eq!(groups[2].representative().message(), "`$b` is not quoted");
+        assert!(
+            groups
+                .iter()
+                .enumerate()
+                .all(|(i, g)| i == 2 || g.truncated() == 0)
+        );
+
+        // The representative is retained even if it falls past the maximum
+        let groups = group_diagnostics(&document, diagnostics(), NonZeroUsize::new(1));
+        assert_eq!(groups[2].truncated(), 4);
+        assert_eq!(
+            summarize(&groups[2..3])[0].3,
+            [
+                "`$b` is not quoted",
+                "and 4 more ShellCheck findings in task align"
+            ]
+        );
+        assert_eq!(groups[2].representative().message(), "`$b` is not quoted");
+    }
+}
@@ -0,0 +1,61 @@
+//! Implementation of the documents imported by a linted document.
+
+use std::collections::HashMap;
+
+use wdl_ast::Document;
+
+/// The documents imported by the document being linted, keyed by import
+/// namespace.
+///
+/// Imports are not resolved by the linter itself; the imported documents are
+/// provided by the caller of the lint visitor (see
+/// [`LintVisitor::with_imports`](crate::LintVisitor::with_imports)) when
+/// available. The collection is empty if the caller did not resolve the
+/// imports, and an import that could not be resolved is absent from the
+/// collection; rules must not assume that every import of a document is
+/// present.
+#[derive(Debug, Clone, Default)]
+pub struct ImportedDocuments {
+    /// The imported documents, keyed by import namespace.
+    documents: HashMap<String, Document>,
+}
+
+impl ImportedDocuments {
+    /// Inserts the document imported with the given namespace.
+    ///
+    /// Returns the previous document imported with the namespace, if any.
+    pub fn insert(&mut self, namespace: impl Into<String>, document: Document) -> Option<Document> {
+        self.documents.insert(namespace.into(), document)
+    }
+
+    /// Gets the document imported with the given namespace.
+    ///
+    /// Returns `None` if there is no import with the namespace or the import
+    /// could not be resolved.
+    pub fn get(&self, namespace: &str) -> Option<&Document> {
+        self.documents.get(namespace)
+    }
+
+    /// Iterates over the imported documents and their namespaces.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Document)> {
+        self.documents.iter().map(|(ns, d)| (ns.as_str(), d))
+    }
+
+    /// Gets the number of imported documents.
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    /// Determines if there are no imported documents.
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+}
+
+impl<S: Into<String>> FromIterator<(S, Document)> for ImportedDocuments {
+    fn from_iter<T: IntoIterator<Item = (S, Document)>>(iter: T) -> Self {
+        Self {
+            documents: iter.into_iter().map(|(ns, d)| (ns.into(), d)).collect(),
+        }
+    }
+}
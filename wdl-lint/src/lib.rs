@@ -29,15 +29,53 @@
 #![warn(clippy::missing_docs_in_private_items)]
 #![warn(rustdoc::broken_intra_doc_links)]
 
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
 use wdl_ast::Diagnostics;
 use wdl_ast::SyntaxKind;
 use wdl_ast::Visitor;
 
+/// The base URL of the lint rule documentation.
+///
+/// Each lint rule is documented in its own section of the listing, anchored by
+/// the lowercase rule identifier.
+pub const RULES_URL: &str = "https://github.com/stjude-rust-labs/wdl/blob/main/wdl-lint/RULES.md";
+
+/// Expands to the documentation URL of a lint rule given the anchor of its
+/// section in the rule listing (the lowercase rule identifier).
+macro_rules! rule_url {
+    ($anchor:literal) => {
+        concat!(
+            "https://github.com/stjude-rust-labs/wdl/blob/main/wdl-lint/RULES.md#",
+            $anchor
+        )
+    };
+}
+
+mod batch;
+mod config;
+mod grouping;
+mod imports;
+mod profile;
+mod rule_set;
 pub mod rules;
+mod session;
+mod summary;
+mod suppression;
 mod tags;
 pub(crate) mod util;
 mod visitor;
 
+pub use batch::*;
+pub use config::*;
+pub use grouping::*;
+pub use imports::*;
+pub use profile::*;
+pub use rule_set::*;
+pub use session::*;
+pub use summary::*;
+pub use suppression::*;
 pub use tags::*;
 pub use visitor::*;
 pub use wdl_ast as ast;
@@ -70,15 +108,74 @@ pub trait Rule: Visitor<State = Diagnostics> {
     /// Get the tags of the lint rule.
     fn tags(&self) -> TagSet;
 
-    /// Gets the optional URL of the lint rule.
+    /// Gets the optional URL of the lint rule's documentation.
     fn url(&self) -> Option<&'static str> {
         None
     }
 
+    /// Gets the identifiers of the lint rules that are related to this rule.
+    fn related_rules(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Gets the identifiers of the lint rules that supersede this rule.
+    ///
+    /// A [`LintVisitor`] does not run this rule if any of the given rules are
+    /// also in its rule set.
+    fn superseded_by(&self) -> &'static [&'static str] {
+        &[]
+    }
+
     /// Gets the nodes that are exceptable for this rule.
     ///
     /// If `None` is returned, all nodes are exceptable.
     fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]>;
+
+    /// Determines if the diagnostics of the rule may be cached for a document
+    /// whose content has not changed (see [`LintSession`]).
+    ///
+    /// The imported documents (see [`set_imports`](Self::set_imports)) are
+    /// part of the cached content. Rules whose diagnostics depend on other
+    /// state external to the document, such as the availability of a program,
+    /// should return `false`; such rules are run every time a document is
+    /// linted.
+    fn cacheable(&self) -> bool {
+        true
+    }
+
+    /// Determines if the rule intentionally reports identical diagnostics
+    /// more than once.
+    ///
+    /// By default, a diagnostic with the same rule, severity, message, and
+    /// primary label span as one previously reported is discarded; rules
+    /// that return `true` opt out of this deduplication.
+    fn allows_duplicates(&self) -> bool {
+        false
+    }
+
+    /// Sets the documents imported by the document being linted.
+    ///
+    /// This is called upon entry of each document, after
+    /// [`Visitor::document`]; rules that need to inspect imported documents
+    /// should retain what they need from them.
+    ///
+    /// The collection may be empty or incomplete (see [`ImportedDocuments`]).
+    fn set_imports(&mut self, _: &ImportedDocuments) {}
+
+    /// Configures the rule with the options of its table in a lint
+    /// configuration file (e.g. `max_width` of `[lint.rules.LineWidth]`).
+    ///
+    /// The options exclude the keys common to every rule (see
+    /// [`RuleConfig`]).
+    ///
+    /// Returns an error if an option is unknown or has an invalid value; by
+    /// default, a rule has no options.
+    fn configure(&mut self, options: &toml::Table) -> anyhow::Result<()> {
+        match options.keys().next() {
+            Some(key) => anyhow::bail!("unknown option `{key}`"),
+            None => Ok(()),
+        }
+    }
 }
 
 /// Gets the default rule set.
@@ -125,6 +222,35 @@ pub fn rules() -> Vec<Box<dyn Rule>> {
         Box::<rules::PreambleCommentAfterVersionRule>::default(),
         Box::<rules::MalformedLintDirectiveRule>::default(),
         Box::<rules::RedundantInputAssignment>::default(),
+        Box::<rules::CommandSectionLineCountRule>::default(),
+        Box::<rules::BasenameSuffixMismatchRule>::default(),
+        Box::<rules::InconsistentPlaceholderSigilsRule>::default(),
+        Box::<rules::SecretInSourceRule>::default(),
+        Box::<rules::StubParameterMetaRule>::default(),
+        Box::<rules::BasicShellPortabilityRule>::default(),
+        Box::<rules::StringFileOutputRule>::default(),
+        Box::<rules::DuplicateCallInputRule>::default(),
+        Box::<rules::StructMemberTypeRule>::default(),
+        Box::<rules::NestingDepthRule>::default(),
+        Box::<rules::ConsistentIndentationRule>::default(),
+        Box::<rules::StableVersionRule>::default(),
+        Box::<rules::HeredocDelimiterAdjacencyRule>::default(),
+        Box::<rules::ShadowedFunctionNameRule>::default(),
+        Box::<rules::MemoryValueRule>::default(),
+        Box::<rules::BackslashPathSeparatorRule>::default(),
+        Box::<rules::DisksValueRule>::default(),
+        Box::<rules::UnknownSectionKeyRule>::default(),
+        Box::<rules::HostDependentCommandRule>::default(),
+        Box::<rules::AbsoluteHostPathRule>::default(),
+        Box::<rules::NetworkAccessRule>::default(),
+        Box::<rules::ConditionalSelectFirstRule>::default(),
+        Box::<rules::ConstantConditionRule>::default(),
+        Box::<rules::ShadowedDeclarationRule>::default(),
+        Box::<rules::OutputFileNotCreatedRule>::default(),
+        Box::<rules::HeterogeneousArrayRule>::default(),
+        Box::<rules::LargeArrayLiteralRule>::default(),
+        Box::<rules::GlobPortabilityRule>::default(),
+        Box::<rules::UnquotedFilePlaceholderRule>::default(),
     ];
 
     // Ensure all the rule ids are unique and pascal case
@@ -153,7 +279,21 @@ pub fn rules() -> Vec<Box<dyn Rule>> {
 
 /// Gets the optional rule set.
 pub fn optional_rules() -> Vec<Box<dyn Rule>> {
-    let opt_rules: Vec<Box<dyn Rule>> = vec![Box::<rules::ShellCheckRule>::default()];
+    let opt_rules: Vec<Box<dyn Rule>> = vec![
+        Box::<rules::UnusedLintDirectiveRule>::default(),
+        Box::<rules::GpuRequirementMismatchRule>::default(),
+        Box::<rules::ScatterInvariantInputRule>::default(),
+        Box::<rules::UndefinedCallTargetRule>::default(),
+        Box::<rules::DuplicateImportedTaskRule>::default(),
+        Box::<rules::DuplicateDescriptionRule>::default(),
+        Box::<rules::MissingContainerRule>::default(),
+        Box::<rules::ContainerUriRule>::default(),
+        Box::<rules::ShellCheckRule>::default(),
+        Box::<rules::MetaDescriptionRule>::default(),
+        Box::<rules::StaticDiskSizeRule>::default(),
+        Box::<rules::LocalizationOptionalRule>::default(),
+        Box::<rules::EmptySectionRule>::default(),
+    ];
 
     // Ensure all the rule ids are unique and pascal case
     #[cfg(debug_assertions)]
@@ -181,3 +321,136 @@ pub fn optional_rules() -> Vec<Box<dyn Rule>> {
 
     opt_rules
 }
+
+/// Gets the documentation URL of the lint rule with the given identifier.
+///
+/// Both the default and optional rules are searched.
+///
+/// Returns `None` if there is no rule with the given identifier or the rule
+/// has no URL.
+pub fn rule_url(id: &str) -> Option<&'static str> {
+    /// The documentation URLs of the rules, keyed by rule identifier.
+    static URLS: LazyLock<HashMap<&'static str, &'static str>> = LazyLock::new(|| {
+        rules()
+            .into_iter()
+            .chain(optional_rules())
+            .filter_map(|r| Some((r.id(), r.url()?)))
+            .collect()
+    });
+
+    URLS.get(id).copied()
+}
+
+/// Gets the rules, including the optional rules, filtered by tag.
+///
+/// A rule is included if any of its tags are in `include`; an empty `include`
+/// set includes every rule.
+///
+/// A rule is excluded if any of its tags are in `exclude`.
+///
+/// Returns an error if a tag is both included and excluded.
+pub fn rules_with_tags(include: TagSet, exclude: TagSet) -> anyhow::Result<Vec<Box<dyn Rule>>> {
+    if let Some(tag) = include.intersection(exclude).iter().next() {
+        anyhow::bail!("lint tag `{tag}` cannot be both included and excluded");
+    }
+
+    Ok(rules()
+        .into_iter()
+        .chain(optional_rules())
+        .filter(|r| {
+            let tags = r.tags();
+            (include.is_empty() || !tags.intersection(include).is_empty())
+                && tags.intersection(exclude).is_empty()
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_filters_rules_by_tag() {
+        let shellcheck = rules::ShellCheckRule::default().tags();
+        let has_shellcheck = |rules: &[Box<dyn Rule>]| rules.iter().any(|r| r.id() == "ShellCheck");
+
+        // Included when any of its tags are requested
+        for tag in shellcheck.iter() {
+            let rules = rules_with_tags(TagSet::new(&[tag]), TagSet::empty()).unwrap();
+            assert!(has_shellcheck(&rules));
+            assert!(rules.iter().all(|r| r.tags().contains(tag)));
+        }
+
+        // Included when none of its tags are excluded
+        let rules = rules_with_tags(TagSet::empty(), TagSet::new(&[Tag::Sorting])).unwrap();
+        assert!(has_shellcheck(&rules));
+        assert!(rules.iter().all(|r| !r.tags().contains(Tag::Sorting)));
+
+        // Excluded only when one of its tags is explicitly excluded
+        for tag in shellcheck.iter() {
+            let rules = rules_with_tags(TagSet::empty(), TagSet::new(&[tag])).unwrap();
+            assert!(!has_shellcheck(&rules));
+        }
+
+        let rules = rules_with_tags(
+            TagSet::new(&[Tag::Correctness]),
+            TagSet::new(&[Tag::Portability]),
+        )
+        .unwrap();
+        assert!(!has_shellcheck(&rules));
+
+        // Not included when none of its tags are requested
+        let rules = rules_with_tags(TagSet::new(&[Tag::Naming]), TagSet::empty()).unwrap();
+        assert!(!has_shellcheck(&rules));
+        assert!(rules.iter().any(|r| r.id() == "SnakeCase"));
+    }
+
+    #[test]
+    fn it_documents_every_rule() {
+        let rules: Vec<_> = rules().into_iter().chain(optional_rules()).collect();
+        let ids: Vec<_> = rules.iter().map(|r| r.id()).collect();
+        for rule in &rules {
+            let id = rule.id();
+            assert!(
+                !rule.description().trim().is_empty(),
+                "rule `{id}` has an empty description"
+            );
+            assert!(
+                !rule.explanation().trim().is_empty(),
+                "rule `{id}` has an empty explanation"
+            );
+
+            let url = rule
+                .url()
+                .unwrap_or_else(|| panic!("rule `{id}` has no URL"));
+            assert_eq!(
+                url,
+                format!("{RULES_URL}#{anchor}", anchor = id.to_lowercase()),
+                "rule `{id}` has an unexpected URL"
+            );
+            assert_eq!(rule_url(id), Some(url));
+
+            for related in rule.related_rules() {
+                assert!(
+                    *related != id && ids.contains(related),
+                    "rule `{id}` has an invalid related rule `{related}`"
+                );
+            }
+        }
+
+        assert_eq!(rule_url("NotARule"), None);
+    }
+
+    #[test]
+    fn it_rejects_conflicting_tags() {
+        let err = rules_with_tags(
+            TagSet::new(&[Tag::Correctness, Tag::Style]),
+            TagSet::new(&[Tag::Style]),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "lint tag `Style` cannot be both included and excluded"
+        );
+    }
+}
@@ -0,0 +1,214 @@
+//! Definition of lint rule profiles.
+
+use std::collections::HashSet;
+
+use crate::Rule;
+use crate::Tag;
+use crate::optional_rules;
+use crate::rules;
+
+/// A named, preset group of lint rules.
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Profile {
+    /// Only the rules associated with correctness, portability, and security.
+    Minimal,
+
+    /// The default rule set.
+    #[default]
+    Recommended,
+
+    /// Every rule, including the optional rules.
+    Pedantic,
+}
+
+impl Profile {
+    /// All of the profiles, ordered from least to most strict.
+    pub const ALL: &[Self] = &[Self::Minimal, Self::Recommended, Self::Pedantic];
+
+    /// Gets the name of the profile.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Minimal => "minimal",
+            Self::Recommended => "recommended",
+            Self::Pedantic => "pedantic",
+        }
+    }
+
+    /// Gets a profile by its name.
+    ///
+    /// Returns `None` if there is no profile with the given name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|p| p.name() == name)
+    }
+
+    /// Determines if the given rule is a member of the profile.
+    pub fn contains(&self, rule: &dyn Rule) -> bool {
+        match self {
+            Self::Minimal => {
+                let tags = rule.tags();
+                (tags.contains(Tag::Correctness)
+                    || tags.contains(Tag::Portability)
+                    || tags.contains(Tag::Security))
+                    && Self::Recommended.contains(rule)
+            }
+            Self::Recommended => rules().iter().any(|r| r.id() == rule.id()),
+            Self::Pedantic => true,
+        }
+    }
+
+    /// Gets the rules that are members of the profile.
+    pub fn rules(&self) -> Vec<Box<dyn Rule>> {
+        let rules = rules();
+        match self {
+            Self::Minimal => rules
+                .into_iter()
+                .filter(|r| {
+                    let tags = r.tags();
+                    tags.contains(Tag::Correctness)
+                        || tags.contains(Tag::Portability)
+                        || tags.contains(Tag::Security)
+                })
+                .collect(),
+            Self::Recommended => rules,
+            Self::Pedantic => rules.into_iter().chain(optional_rules()).collect(),
+        }
+    }
+
+    /// Gets the rules of the profile with the given per-rule overrides applied.
+    ///
+    /// Rules with an identifier in `enable` are added to the profile's rules,
+    /// even if they are not a member of the profile.
+    ///
+    /// Rules with an identifier in `disable` are removed from the resulting
+    /// rules; `disable` takes precedence over `enable`.
+    pub fn rules_with_overrides<'a>(
+        &self,
+        enable: impl IntoIterator<Item = &'a str>,
+        disable: impl IntoIterator<Item = &'a str>,
+    ) -> Vec<Box<dyn Rule>> {
+        let enable: HashSet<_> = enable.into_iter().collect();
+        let disable: HashSet<_> = disable.into_iter().collect();
+
+        let mut rules = self.rules();
+        let ids: HashSet<_> = rules.iter().map(|r| r.id()).collect();
+        rules.extend(
+            crate::rules()
+                .into_iter()
+                .chain(optional_rules())
+                .filter(|r| !ids.contains(r.id()) && enable.contains(r.id())),
+        );
+        rules.retain(|r| !disable.contains(r.id()));
+        rules
+    }
+}
+
+impl std::fmt::Display for Profile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{name}", name = self.name())
+    }
+}
+
+/// Gets the rules for the profile with the given name.
+///
+/// Returns `None` if there is no profile with the given name.
+pub fn rules_for_profile(name: &str) -> Option<Vec<Box<dyn Rule>>> {
+    Profile::from_name(name).map(|p| p.rules())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Gets the sorted identifiers of the given rules.
+    fn ids(rules: &[Box<dyn Rule>]) -> Vec<&'static str> {
+        let mut ids: Vec<_> = rules.iter().map(|r| r.id()).collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn minimal_profile() {
+        let rules = Profile::Minimal.rules();
+        assert_eq!(
+            ids(&rules),
+            [
+                "BasicShellPortability",
+                "CommandSectionMixedIndentation",
+                "ContainerValue",
+                "DuplicateCallInput",
+                "MalformedLintDirective",
+                "MisplacedLintDirective",
+                "MissingOutput",
+                "MissingRequirements",
+                "MissingRuntime",
+                "SecretInSource",
+                "StringFileOutput",
+            ]
+        );
+
+        for rule in &rules {
+            assert!(Profile::Minimal.contains(rule.as_ref()));
+            assert!(
+                rule.tags().contains(Tag::Correctness)
+                    || rule.tags().contains(Tag::Portability)
+                    || rule.tags().contains(Tag::Security)
+            );
+        }
+    }
+
+    #[test]
+    fn recommended_profile() {
+        assert_eq!(ids(&Profile::Recommended.rules()), ids(&rules()));
+        assert_eq!(Profile::default(), Profile::Recommended);
+    }
+
+    #[test]
+    fn pedantic_profile() {
+        let rules = Profile::Pedantic.rules();
+        let expected: Vec<_> = crate::rules().into_iter().chain(optional_rules()).collect();
+        assert_eq!(ids(&rules), ids(&expected));
+        assert!(rules.iter().any(|r| r.id() == "ShellCheck"));
+    }
+
+    #[test]
+    fn profiles_are_nested() {
+        let minimal = ids(&Profile::Minimal.rules());
+        let recommended = ids(&Profile::Recommended.rules());
+        let pedantic = ids(&Profile::Pedantic.rules());
+        assert!(minimal.iter().all(|id| recommended.contains(id)));
+        assert!(recommended.iter().all(|id| pedantic.contains(id)));
+    }
+
+    #[test]
+    fn profile_names() {
+        for profile in Profile::ALL {
+            assert_eq!(Profile::from_name(profile.name()), Some(*profile));
+            assert_eq!(
+                rules_for_profile(profile.name()).map(|r| ids(&r)),
+                Some(ids(&profile.rules()))
+            );
+        }
+
+        assert_eq!(Profile::from_name("strict"), None);
+        assert!(rules_for_profile("strict").is_none());
+    }
+
+    #[test]
+    fn profile_overrides() {
+        let rules = Profile::Minimal.rules_with_overrides(
+            ["SnakeCase", "ShellCheck", "MissingRuntime"],
+            ["ContainerValue", "ShellCheck"],
+        );
+        let ids = ids(&rules);
+        assert!(ids.contains(&"SnakeCase"));
+        assert!(ids.contains(&"MissingRuntime"));
+        assert!(!ids.contains(&"ContainerValue"));
+        assert!(!ids.contains(&"ShellCheck"));
+        assert!(!ids.contains(&"LineWidth"));
+        assert_eq!(ids.len(), Profile::Minimal.rules().len());
+
+        let rules = Profile::Pedantic.rules_with_overrides([], ["ShellCheck"]);
+        assert!(rules.iter().all(|r| r.id() != "ShellCheck"));
+        assert_eq!(rules.len(), Profile::Pedantic.rules().len() - 1);
+    }
+}
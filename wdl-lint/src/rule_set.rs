@@ -0,0 +1,331 @@
+//! Implementation of the lint rule set.
+
+use anyhow::Result;
+use anyhow::bail;
+use convert_case::Case;
+use convert_case::Casing;
+use indexmap::IndexMap;
+
+use crate::RESERVED_RULE_IDS;
+use crate::Rule;
+use crate::rules;
+use crate::rules::RULE_MAP;
+use crate::rules::UnknownRule;
+
+/// The identifier of the unknown rule rule.
+const UNKNOWN_RULE_ID: &str = "UnknownRule";
+
+/// Represents a set of lint rules driven by a [`LintVisitor`].
+///
+/// The default rule set contains the default rules (see [`rules()`]);
+/// additional rules, such as rules defined outside of this crate, may be
+/// registered with [`RuleSet::add`].
+///
+/// Registered rules are treated exactly like the built-in rules: they are
+/// visited in registration order and respect `#@ except` comments.
+///
+/// [`LintVisitor`]: crate::LintVisitor
+///
+/// # Examples
+///
+/// An example of registering a custom rule:
+///
+/// ```rust
+/// use wdl_lint::LintVisitor;
+/// use wdl_lint::Rule;
+/// use wdl_lint::RuleSet;
+/// use wdl_lint::Tag;
+/// use wdl_lint::TagSet;
+/// use wdl_lint::ast::AstToken;
+/// use wdl_lint::ast::Diagnostic;
+/// use wdl_lint::ast::Diagnostics;
+/// use wdl_lint::ast::Document;
+/// use wdl_lint::ast::SupportedVersion;
+/// use wdl_lint::ast::SyntaxKind;
+/// use wdl_lint::ast::Validator;
+/// use wdl_lint::ast::VisitReason;
+/// use wdl_lint::ast::Visitor;
+/// use wdl_lint::ast::v1::TaskDefinition;
+///
+/// /// Requires task names to start with `lims_`.
+/// #[derive(Default)]
+/// struct LimsTaskNameRule;
+///
+/// impl Rule for LimsTaskNameRule {
+///     fn id(&self) -> &'static str {
+///         "LimsTaskName"
+///     }
+///
+///     fn description(&self) -> &'static str {
+///         "Ensures that task names start with `lims_`."
+///     }
+///
+///     fn explanation(&self) -> &'static str {
+///         "Tasks are tracked by name in the LIMS and must start with `lims_`."
+///     }
+///
+///     fn tags(&self) -> TagSet {
+///         TagSet::new(&[Tag::Naming])
+///     }
+///
+///     fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+///         Some(&[SyntaxKind::VersionStatementNode, SyntaxKind::TaskDefinitionNode])
+///     }
+/// }
+///
+/// impl Visitor for LimsTaskNameRule {
+///     type State = Diagnostics;
+///
+///     fn document(&mut self, _: &mut Self::State, _: VisitReason, _: &Document, _: SupportedVersion) {
+///         // This rule has no state.
+///     }
+///
+///     fn task_definition(
+///         &mut self,
+///         state: &mut Self::State,
+///         reason: VisitReason,
+///         task: &TaskDefinition,
+///     ) {
+///         let name = task.name();
+///         if reason == VisitReason::Enter && !name.as_str().starts_with("lims_") {
+///             state.add(
+///                 Diagnostic::warning(format!(
+///                     "task name `{name}` does not start with `lims_`",
+///                     name = name.as_str()
+///                 ))
+///                 .with_rule(self.id())
+///                 .with_highlight(name.span()),
+///             );
+///         }
+///     }
+/// }
+///
+/// let source = r#"version 1.1
+///
+/// task sequence {
+///     command <<<>>>
+/// }
+/// "#;
+///
+/// let (document, diagnostics) = Document::parse(source);
+/// assert!(diagnostics.is_empty());
+///
+/// let mut rules = RuleSet::default();
+/// rules.add(Box::new(LimsTaskNameRule)).unwrap();
+///
+/// // Registering a rule with the same identifier is an error
+/// assert!(rules.add(Box::new(LimsTaskNameRule)).is_err());
+///
+/// let mut validator = Validator::default();
+/// validator.add_visitor(LintVisitor::from(rules));
+/// let diagnostics = validator.validate(&document).unwrap_err();
+/// assert!(diagnostics.iter().any(|d| {
+///     d.rule() == Some("LimsTaskName")
+///         && d.message() == "task name `sequence` does not start with `lims_`"
+/// }));
+/// ```
+#[allow(missing_debug_implementations)]
+pub struct RuleSet {
+    /// The map of rule identifier to rule.
+    rules: IndexMap<&'static str, Box<dyn Rule>>,
+    /// The identifiers of the registered rules that are not built-in rules.
+    external: Vec<&'static str>,
+}
+
+impl RuleSet {
+    /// Constructs a new, empty rule set.
+    pub fn empty() -> Self {
+        Self {
+            rules: IndexMap::new(),
+            external: Vec::new(),
+        }
+    }
+
+    /// Adds a rule to the set.
+    ///
+    /// Returns an error if the rule's identifier is not pascal case, is
+    /// reserved, or is already in the set.
+    pub fn add(&mut self, rule: Box<dyn Rule>) -> Result<()> {
+        let id = rule.id();
+        if id.to_case(Case::Pascal) != id {
+            bail!("lint rule id `{id}` is not pascal case");
+        }
+
+        if RESERVED_RULE_IDS.contains(&id) {
+            bail!("lint rule id `{id}` is reserved");
+        }
+
+        if self.rules.contains_key(id) {
+            bail!("duplicate lint rule id `{id}`");
+        }
+
+        self.rules.insert(id, rule);
+
+        if !RULE_MAP.contains_key(id) {
+            self.external.push(id);
+        }
+
+        // Inform the unknown rule rule of any external rules so that they are known to
+        // `#@ except` comments
+        if !self.external.is_empty() {
+            if let Some(rule) = self.rules.get_mut(UNKNOWN_RULE_ID) {
+                *rule = Box::new(UnknownRule::new(self.external.iter().copied()));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Removes a rule from the set by its identifier.
+    ///
+    /// Returns `true` if the rule was in the set.
+    pub(crate) fn remove(&mut self, id: &str) -> bool {
+        self.rules.shift_remove(id).is_some()
+    }
+
+    /// Gets a rule in the set by its identifier.
+    pub fn get(&self, id: &str) -> Option<&dyn Rule> {
+        self.rules.get(id).map(|r| r.as_ref())
+    }
+
+    /// Determines if the set contains a rule with the given identifier.
+    pub fn contains(&self, id: &str) -> bool {
+        self.rules.contains_key(id)
+    }
+
+    /// Gets the number of rules in the set.
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Determines if the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Iterates the rules in the set in registration order.
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Rule> {
+        self.rules.values().map(|r| r.as_ref())
+    }
+
+    /// Iterates the identifiers and mutable rules in the set in registration
+    /// order.
+    pub(crate) fn iter_mut(&mut self) -> impl Iterator<Item = (&'static str, &mut dyn Rule)> {
+        self.rules
+            .iter_mut()
+            .map(|(id, r)| (*id, r.as_mut() as &mut dyn Rule))
+    }
+}
+
+impl Default for RuleSet {
+    fn default() -> Self {
+        Self {
+            rules: rules().into_iter().map(|r| (r.id(), r)).collect(),
+            external: Vec::new(),
+        }
+    }
+}
+
+impl FromIterator<Box<dyn Rule>> for RuleSet {
+    /// Collects rules into a rule set.
+    ///
+    /// Unlike [`RuleSet::add`], the rules are not validated and a rule
+    /// replaces any previous rule with the same identifier.
+    fn from_iter<T: IntoIterator<Item = Box<dyn Rule>>>(iter: T) -> Self {
+        Self {
+            rules: iter.into_iter().map(|r| (r.id(), r)).collect(),
+            external: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use wdl_ast::Document;
+    use wdl_ast::Validator;
+
+    use super::*;
+    use crate::LintVisitor;
+    use crate::optional_rules;
+
+    #[test]
+    fn it_rejects_invalid_rules() {
+        let mut set = RuleSet::default();
+        assert_eq!(set.len(), rules().len());
+
+        let shellcheck = || {
+            optional_rules()
+                .into_iter()
+                .find(|r| r.id() == "ShellCheck")
+                .expect("should have the ShellCheck rule")
+        };
+
+        set.add(shellcheck()).unwrap();
+        let err = set.add(shellcheck()).unwrap_err();
+        assert_eq!(err.to_string(), "duplicate lint rule id `ShellCheck`");
+
+        let err = set
+            .add(Box::<rules::DoubleQuotesRule>::default())
+            .unwrap_err();
+        assert_eq!(err.to_string(), "duplicate lint rule id `DoubleQuotes`");
+        assert_eq!(set.len(), rules().len() + 1);
+    }
+
+    #[test]
+    fn it_knows_external_rules_in_exceptions() {
+        /// A rule that flags nothing.
+        #[derive(Default)]
+        struct ExternalRule;
+
+        impl Rule for ExternalRule {
+            fn id(&self) -> &'static str {
+                "ExternalRule"
+            }
+
+            fn description(&self) -> &'static str {
+                "An external rule."
+            }
+
+            fn explanation(&self) -> &'static str {
+                "An external rule."
+            }
+
+            fn tags(&self) -> crate::TagSet {
+                crate::TagSet::new(&[crate::Tag::Style])
+            }
+
+            fn exceptable_nodes(&self) -> Option<&'static [wdl_ast::SyntaxKind]> {
+                None
+            }
+        }
+
+        impl wdl_ast::Visitor for ExternalRule {
+            type State = wdl_ast::Diagnostics;
+
+            fn document(
+                &mut self,
+                _: &mut Self::State,
+                _: wdl_ast::VisitReason,
+                _: &Document,
+                _: wdl_ast::SupportedVersion,
+            ) {
+            }
+        }
+
+        let (document, diagnostics) = Document::parse(
+            "#@ except: ExternalRule, ExternalRul\nversion 1.1\n\nworkflow test {}\n",
+        );
+        assert!(diagnostics.is_empty());
+
+        let mut set = RuleSet::empty();
+        set.add(Box::<UnknownRule>::default()).unwrap();
+        set.add(Box::new(ExternalRule)).unwrap();
+
+        let mut validator = Validator::default();
+        validator.add_visitor(LintVisitor::from(set));
+        let diagnostics = validator.validate(&document).unwrap_err();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message(), "unknown lint rule `ExternalRul`");
+        assert_eq!(diagnostics[0].fix(), Some("did you mean `ExternalRule`?"));
+    }
+}
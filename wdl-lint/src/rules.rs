@@ -1,87 +1,169 @@
 //! Module for the lint rules.
 
+mod absolute_host_path;
+mod backslash_path_separator;
+mod basename_suffix_mismatch;
+mod basic_shell_portability;
 mod blank_lines_between_elements;
 mod call_input_spacing;
+mod command_line_count;
 mod command_mixed_indentation;
 mod comment_whitespace;
+mod conditional_select_first;
+mod consistent_indentation;
+mod constant_condition;
+mod container_uri;
 mod container_value;
 mod deprecated_object;
 mod deprecated_placeholder_option;
 mod description_missing;
 mod disallowed_input_name;
 mod disallowed_output_name;
+mod disks_value;
 mod double_quotes;
+mod duplicate_call_input;
+mod duplicate_description;
+mod duplicate_imported_task;
+mod empty_section;
 mod ending_newline;
 mod expression_spacing;
+mod glob_portability;
+mod gpu_requirement_mismatch;
+mod heredoc_delimiter_adjacency;
+mod heterogeneous_array;
+mod host_dependent_command;
 mod import_placement;
 mod import_sort;
 mod import_whitespace;
 mod inconsistent_newlines;
+mod inconsistent_placeholder_sigils;
 mod input_not_sorted;
 mod key_value_pairs;
+mod large_array_literal;
 mod line_width;
+mod localization_optional;
 mod malformed_lint_directive;
 mod matching_parameter_meta;
+mod memory_value;
+mod meta_description;
 mod misplaced_lint_directive;
+mod missing_container;
 mod missing_metas;
 mod missing_output;
 mod missing_requirements;
 mod missing_runtime;
+mod nesting_depth;
+mod network_access;
 mod no_curly_commands;
 mod nonmatching_output;
+mod output_file_not_created;
 mod pascal_case;
 mod preamble_comment_after_version;
 mod preamble_formatting;
 mod redundant_input_assignment;
 mod runtime_section_keys;
+mod scatter_invariant_input;
+mod secret_in_source;
 mod section_order;
+mod shadowed_declaration;
+mod shadowed_function_name;
 mod shellcheck;
 mod snake_case;
+mod stable_version;
+mod static_disk_size;
+mod string_file_output;
+mod struct_member_type;
+mod stub_parameter_meta;
 mod todo;
 mod trailing_comma;
+mod undefined_call_target;
 mod unknown_rule;
+mod unknown_section_key;
+mod unquoted_file_placeholder;
+mod unused_lint_directive;
 mod version_formatting;
 mod whitespace;
 
+pub use absolute_host_path::*;
+pub use backslash_path_separator::*;
+pub use basename_suffix_mismatch::*;
+pub use basic_shell_portability::*;
 pub use blank_lines_between_elements::*;
 pub use call_input_spacing::*;
+pub use command_line_count::*;
 pub use command_mixed_indentation::*;
 pub use comment_whitespace::*;
+pub use conditional_select_first::*;
+pub use consistent_indentation::*;
+pub use constant_condition::*;
+pub use container_uri::*;
 pub use container_value::*;
 pub use deprecated_object::*;
 pub use deprecated_placeholder_option::*;
 pub use description_missing::*;
 pub use disallowed_input_name::*;
 pub use disallowed_output_name::*;
+pub use disks_value::*;
 pub use double_quotes::*;
+pub use duplicate_call_input::*;
+pub use duplicate_description::*;
+pub use duplicate_imported_task::*;
+pub use empty_section::*;
 pub use ending_newline::*;
 pub use expression_spacing::*;
+pub use glob_portability::*;
+pub use gpu_requirement_mismatch::*;
+pub use heredoc_delimiter_adjacency::*;
+pub use heterogeneous_array::*;
+pub use host_dependent_command::*;
 pub use import_placement::*;
 pub use import_sort::*;
 pub use import_whitespace::*;
 pub use inconsistent_newlines::*;
+pub use inconsistent_placeholder_sigils::*;
 pub use input_not_sorted::*;
 pub use key_value_pairs::*;
+pub use large_array_literal::*;
 pub use line_width::*;
+pub use localization_optional::*;
 pub use malformed_lint_directive::*;
 pub use matching_parameter_meta::*;
+pub use memory_value::*;
+pub use meta_description::*;
 pub use misplaced_lint_directive::*;
+pub use missing_container::*;
 pub use missing_metas::*;
 pub use missing_output::*;
 pub use missing_requirements::*;
 pub use missing_runtime::*;
+pub use nesting_depth::*;
+pub use network_access::*;
 pub use no_curly_commands::*;
 pub use nonmatching_output::*;
+pub use output_file_not_created::*;
 pub use pascal_case::*;
 pub use preamble_comment_after_version::*;
 pub use preamble_formatting::*;
 pub use redundant_input_assignment::*;
 pub use runtime_section_keys::*;
+pub use scatter_invariant_input::*;
+pub use secret_in_source::*;
 pub use section_order::*;
+pub use shadowed_declaration::*;
+pub use shadowed_function_name::*;
 pub use shellcheck::*;
 pub use snake_case::*;
+pub use stable_version::*;
+pub use static_disk_size::*;
+pub use string_file_output::*;
+pub use struct_member_type::*;
+pub use stub_parameter_meta::*;
 pub use todo::*;
 pub use trailing_comma::*;
+pub use undefined_call_target::*;
 pub use unknown_rule::*;
+pub use unknown_section_key::*;
+pub use unquoted_file_placeholder::*;
+pub use unused_lint_directive::*;
 pub use version_formatting::*;
 pub use whitespace::*;
@@ -0,0 +1,414 @@
+//! A lint rule for absolute host paths in commands and declarations.
+
+use serde::Deserialize;
+use wdl_ast::AstNode;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+use wdl_ast::v1::BoundDecl;
+use wdl_ast::v1::CommandPart;
+use wdl_ast::v1::CommandSection;
+use wdl_ast::v1::Expr;
+use wdl_ast::v1::LiteralExpr;
+use wdl_ast::v1::PrimitiveTypeKind;
+use wdl_ast::v1::StringPart;
+use wdl_ast::v1::Type;
+
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+use crate::config::rule_options;
+use crate::util::lines_with_offset;
+
+/// The identifier for the absolute host path rule.
+const ID: &str = "AbsoluteHostPath";
+
+/// The paths that are allowed by default.
+///
+/// These paths are available in any container and on any host; a path is
+/// allowed if it is one of these paths or is within one of them.
+const DEFAULT_ALLOWED_PATHS: &[&str] = &[
+    "/bin/bash",
+    "/bin/sh",
+    "/dev/fd",
+    "/dev/null",
+    "/dev/stderr",
+    "/dev/stdin",
+    "/dev/stdout",
+    "/proc",
+    "/tmp",
+    "/usr/bin/env",
+];
+
+/// The characters that may precede the start of a path.
+const BOUNDARIES: &[char] = &['=', '(', ':', ',', ';', '|', '&', '<', '>', '`'];
+
+/// The characters that end a path.
+const TERMINATORS: &[char] = &[
+    '"', '\'', '`', '(', ')', ',', ';', '|', '&', '<', '>', ':', '{', '}', '[', ']',
+];
+
+/// Creates an "absolute host path" diagnostic.
+fn absolute_host_path(context: &str, path: &str, span: Span) -> Diagnostic {
+    Diagnostic::warning(format!(
+        "{context} contains the absolute host path `{path}`"
+    ))
+    .with_rule(ID)
+    .with_highlight(span)
+    .with_fix(
+        "pass the path as a `File` or `String` input, or add it to the rule's allowed paths if \
+             it exists on every host and in every container",
+    )
+}
+
+/// Determines if a path may start after the given text.
+///
+/// `leading` is whether or not the start of the text is a boundary; it is
+/// `false` for text that follows a placeholder, as the placeholder's value
+/// forms the start of the path (e.g. `~{dir}/file`).
+fn starts_path(before: &str, leading: bool) -> bool {
+    let boundary = |c: Option<char>| match c {
+        None => leading,
+        Some(c) => c.is_whitespace() || BOUNDARIES.contains(&c),
+    };
+
+    let mut chars = before.chars().rev();
+    match chars.next() {
+        // A quote only starts a path when it opens a quoted word (e.g. not
+        // `"$dir"/file`)
+        Some('"' | '\'') => boundary(chars.next()),
+        c => boundary(c),
+    }
+}
+
+/// Gets the length of the absolute path that starts the given text.
+///
+/// Returns `None` if the text does not start with an absolute Unix path or a
+/// Windows drive path.
+fn path_len(text: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+    let root = match bytes {
+        [b'/', c, ..] if c.is_ascii_alphabetic() || matches!(c, b'_' | b'.') => 1,
+        [d, b':', b'/' | b'\\', ..] if d.is_ascii_alphabetic() => 3,
+        _ => return None,
+    };
+
+    Some(
+        text[root..]
+            .find(|c: char| c.is_whitespace() || TERMINATORS.contains(&c))
+            .map(|len| root + len)
+            .unwrap_or(text.len()),
+    )
+}
+
+/// Finds the absolute paths in the given text.
+///
+/// `leading` is whether or not the start of the text is a boundary.
+///
+/// Returns the offset and text of each path.
+fn find_paths(text: &str, leading: bool) -> Vec<(usize, &str)> {
+    let mut paths = Vec::new();
+    let mut i = 0;
+    while i < text.len() {
+        if !text.is_char_boundary(i) {
+            i += 1;
+            continue;
+        }
+
+        if starts_path(&text[..i], leading) {
+            if let Some(len) = path_len(&text[i..]) {
+                paths.push((i, &text[i..i + len]));
+                i += len;
+                continue;
+            }
+        }
+
+        i += 1;
+    }
+
+    paths
+}
+
+/// Determines if a `#` following the given text of a command line starts a
+/// comment.
+fn starts_comment(before: &str) -> bool {
+    before.chars().next_back().is_none_or(char::is_whitespace)
+}
+
+/// Detects absolute host paths in command sections and declarations.
+#[derive(Debug, Clone, Default)]
+pub struct AbsoluteHostPathRule {
+    /// The paths that are allowed in addition to the default allowed paths.
+    allowed_paths: Vec<String>,
+}
+
+impl AbsoluteHostPathRule {
+    /// Constructs a new absolute host path rule that allows the given paths in
+    /// addition to the default allowed paths.
+    ///
+    /// A path is allowed if it is one of the given paths or is within one of
+    /// them (e.g. allowing `/opt/tools` allows `/opt/tools/bin/bwa`).
+    pub fn new(allowed_paths: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            allowed_paths: allowed_paths.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Gets the paths that are allowed in addition to the default allowed
+    /// paths.
+    pub fn allowed_paths(&self) -> &[String] {
+        &self.allowed_paths
+    }
+
+    /// Determines if the given path is allowed.
+    fn is_allowed(&self, path: &str) -> bool {
+        DEFAULT_ALLOWED_PATHS
+            .iter()
+            .copied()
+            .chain(self.allowed_paths.iter().map(String::as_str))
+            .any(|allowed| {
+                let allowed = allowed.trim_end_matches('/');
+                path.strip_prefix(allowed)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+            })
+    }
+
+    /// Checks the given text for absolute host paths.
+    fn check_text(
+        &self,
+        state: &mut Diagnostics,
+        context: &str,
+        text: &str,
+        start: usize,
+        leading: bool,
+        syntax: &SyntaxElement,
+    ) {
+        for (offset, path) in find_paths(text, leading) {
+            if self.is_allowed(path) {
+                continue;
+            }
+
+            state.exceptable_add(
+                absolute_host_path(context, path, Span::new(start + offset, path.len())),
+                syntax.clone(),
+                &self.exceptable_nodes(),
+            );
+        }
+    }
+}
+
+/// The options of the absolute host path rule.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Options {
+    /// The paths that are allowed in addition to the default allowed paths.
+    allowed_paths: Option<Vec<String>>,
+}
+
+impl Rule for AbsoluteHostPathRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags absolute host paths in command sections and declarations."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Paths that only exist on a particular host (e.g. `/home/alice/ref.fa` or \
+         `/mnt/cluster/tools/bwa`) make a document fail on any other host or in a container. \
+         The text of command sections and string literals assigned to `File`, `Directory`, and \
+         `String` declarations are checked for absolute Unix paths and Windows drive paths; \
+         placeholders interrupt a path, so the prefix of `/data/~{sample}.bam` is reported. \
+         Paths that are available everywhere, such as `/tmp`, `/dev/null`, and `/usr/bin/env`, \
+         are allowed, and additional paths may be allowed (e.g. paths installed in a task's \
+         container). Paths should instead be passed as inputs to the task or workflow."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Portability])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("absolutehostpath"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["BackslashPathSeparator", "HostDependentCommand"]
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+        Some(&[
+            SyntaxKind::VersionStatementNode,
+            SyntaxKind::TaskDefinitionNode,
+            SyntaxKind::WorkflowDefinitionNode,
+            SyntaxKind::CommandSectionNode,
+            SyntaxKind::BoundDeclNode,
+        ])
+    }
+
+    fn configure(&mut self, options: &toml::Table) -> anyhow::Result<()> {
+        let options: Options = rule_options(options)?;
+
+        if let Some(allowed_paths) = options.allowed_paths {
+            self.allowed_paths = allowed_paths;
+        }
+
+        Ok(())
+    }
+}
+
+impl Visitor for AbsoluteHostPathRule {
+    type State = Diagnostics;
+
+    fn document(&mut self, _: &mut Self::State, _: VisitReason, _: &Document, _: SupportedVersion) {
+        // This is intentionally empty, as this rule has no state.
+    }
+
+    fn bound_decl(&mut self, state: &mut Self::State, reason: VisitReason, decl: &BoundDecl) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        let Type::Primitive(ty) = decl.ty() else {
+            return;
+        };
+
+        let kind = match ty.kind() {
+            PrimitiveTypeKind::File => "File",
+            PrimitiveTypeKind::Directory => "Directory",
+            PrimitiveTypeKind::String => "String",
+            _ => return,
+        };
+
+        let Expr::Literal(LiteralExpr::String(string)) = decl.expr() else {
+            return;
+        };
+
+        let context = format!("`{kind}` declaration `{name}`", name = decl.name().as_str());
+        let syntax = SyntaxElement::from(decl.syntax().clone());
+        let mut leading = true;
+        for part in string.parts() {
+            match part {
+                StringPart::Text(text) => self.check_text(
+                    state,
+                    &context,
+                    text.as_str(),
+                    text.span().start(),
+                    leading,
+                    &syntax,
+                ),
+                StringPart::Placeholder(_) => leading = false,
+            }
+        }
+    }
+
+    fn command_section(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        section: &CommandSection,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        let syntax = SyntaxElement::from(section.syntax().clone());
+        let mut after_placeholder = false;
+        for part in section.parts() {
+            let text = match part {
+                CommandPart::Text(text) => text,
+                CommandPart::Placeholder(_) => {
+                    after_placeholder = true;
+                    continue;
+                }
+            };
+
+            let start = text.span().start();
+            for (i, (line, line_start, _)) in lines_with_offset(text.as_str()).enumerate() {
+                // Paths in comments are not used by the command
+                let line = line
+                    .char_indices()
+                    .find(|(i, c)| *c == '#' && starts_comment(&line[..*i]))
+                    .map(|(i, _)| &line[..i])
+                    .unwrap_or(line);
+
+                self.check_text(
+                    state,
+                    "command section",
+                    line,
+                    start + line_start,
+                    i > 0 || !after_placeholder,
+                    &syntax,
+                );
+            }
+
+            after_placeholder = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use wdl_ast::Validator;
+
+    use super::*;
+
+    #[test]
+    fn it_finds_paths() {
+        assert_eq!(
+            find_paths("bwa mem /home/alice/ref.fa reads.fq > /data/out.sam", true),
+            [(8, "/home/alice/ref.fa"), (38, "/data/out.sam")]
+        );
+        assert_eq!(
+            find_paths(r#"--ref="/mnt/ref.fa" PATH=$PATH:/opt/bin"#, true),
+            [(7, "/mnt/ref.fa"), (31, "/opt/bin")]
+        );
+        assert_eq!(
+            find_paths(r"C:\data\ref.fa", true),
+            [(0, r"C:\data\ref.fa")]
+        );
+        assert_eq!(find_paths("/data/", true), [(0, "/data/")]);
+        assert_eq!(find_paths("/data/", false), []);
+        assert_eq!(
+            find_paths(r#"cat "$dir"/a.txt https://host/path $((x / 2))"#, true),
+            []
+        );
+    }
+
+    #[test]
+    fn it_allows_paths() {
+        let source = r#"version 1.1
+
+task test {
+    input {
+        File tools = "/opt/tools/bin/bwa"
+    }
+
+    command <<<
+        ~{tools} > /dev/null 2>/tmp/log.txt
+    >>>
+}
+"#;
+
+        let (document, diagnostics) = Document::parse(source);
+        assert!(diagnostics.is_empty());
+
+        let mut validator = Validator::empty();
+        validator.add_visitor(AbsoluteHostPathRule::default());
+        let diagnostics = validator.validate(&document).unwrap_err();
+        assert_eq!(diagnostics.len(), 1);
+
+        let mut validator = Validator::empty();
+        validator.add_visitor(AbsoluteHostPathRule::new(["/opt/tools/"]));
+        assert!(validator.validate(&document).is_ok());
+    }
+}
@@ -0,0 +1,465 @@
+//! A lint rule for Windows-style path separators in paths.
+
+use serde::Deserialize;
+use wdl_ast::AstNode;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+use wdl_ast::v1::BoundDecl;
+use wdl_ast::v1::CommandPart;
+use wdl_ast::v1::CommandSection;
+use wdl_ast::v1::Expr;
+use wdl_ast::v1::LiteralExpr;
+use wdl_ast::v1::LiteralString;
+use wdl_ast::v1::PrimitiveTypeKind;
+use wdl_ast::v1::StringPart;
+use wdl_ast::v1::Type;
+
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+use crate::config::rule_options;
+
+/// The identifier for the backslash path separator rule.
+const ID: &str = "BackslashPathSeparator";
+
+/// The standard library functions whose arguments are paths.
+const PATH_FUNCTIONS: &[&str] = &["basename", "glob", "join_paths"];
+
+/// Creates a "backslash path separator" diagnostic.
+///
+/// The replacement is only added if converting the separators to forward
+/// slashes is unambiguous.
+fn backslash_path_separator(context: &str, span: Span, replacement: Option<String>) -> Diagnostic {
+    let diagnostic = Diagnostic::warning(format!("{context} uses backslashes as path separators"))
+        .with_rule(ID)
+        .with_highlight(span);
+
+    match replacement {
+        Some(replacement) => diagnostic
+            .with_fix("replace the backslashes with forward slashes")
+            .with_replacement(span, replacement),
+        None => diagnostic.with_fix(
+            "Windows drive and UNC paths are not portable; use a path with forward slashes as \
+             separators",
+        ),
+    }
+}
+
+/// Represents a path found to use backslash separators.
+struct BackslashPath {
+    /// The replacement of the path with forward slashes.
+    ///
+    /// This is `None` if the conversion is ambiguous.
+    replacement: Option<String>,
+    /// Whether or not the path is a UNC path (e.g. `\\server\share`).
+    unc: bool,
+}
+
+/// Checks the source text of a string literal for backslash separators.
+///
+/// Separators are written as the `\\` escape sequence; other escape sequences
+/// (e.g. `\n` or `\t`) are not separators and are left untouched.
+fn check_string_text(text: &str, first: bool) -> Option<BackslashPath> {
+    let mut replacement = String::with_capacity(text.len());
+    let mut found = false;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            replacement.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('\\') => {
+                found = true;
+                replacement.push('/');
+            }
+            Some(c) => {
+                replacement.push('\\');
+                replacement.push(c);
+            }
+            None => replacement.push('\\'),
+        }
+    }
+
+    if !found {
+        return None;
+    }
+
+    let unc = first && text.starts_with(r"\\\\");
+    let drive = first
+        && text
+            .split_once(':')
+            .is_some_and(|(d, rest)| d.len() == 1 && rest.starts_with(r"\\"));
+    Some(BackslashPath {
+        replacement: (!unc && !drive).then_some(replacement),
+        unc,
+    })
+}
+
+/// Checks the target of a command redirect for backslash separators.
+///
+/// In a command, a backslash followed by whitespace escapes the whitespace and
+/// is not a separator.
+fn check_redirect_target(target: &str) -> Option<BackslashPath> {
+    /// Determines if the given byte may follow a separator.
+    fn separated(b: &u8) -> bool {
+        b.is_ascii_alphanumeric() || b"._-".contains(b)
+    }
+
+    let bytes = target.as_bytes();
+    if !bytes.windows(2).any(|w| w[0] == b'\\' && separated(&w[1])) {
+        return None;
+    }
+
+    // The conversion is ambiguous if any backslash escapes a character
+    let escapes = bytes
+        .iter()
+        .enumerate()
+        .any(|(i, b)| *b == b'\\' && !bytes.get(i + 1).is_some_and(separated));
+    let unc = target.starts_with(r"\\");
+    let drive = target
+        .split_once(':')
+        .is_some_and(|(d, rest)| d.len() == 1 && rest.starts_with('\\'));
+    Some(BackslashPath {
+        replacement: (!unc && !drive && !escapes).then(|| target.replace('\\', "/")),
+        unc,
+    })
+}
+
+/// Finds the targets of redirects in the given command text.
+///
+/// Returns the offset and text of each target.
+fn redirect_targets(text: &str) -> Vec<(usize, &str)> {
+    let bytes = text.as_bytes();
+    let mut targets = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'>' && bytes[i] != b'<' {
+            i += 1;
+            continue;
+        }
+
+        // Skip the rest of the operator (e.g. `>>` or `<<`)
+        let op = bytes[i];
+        i += 1;
+        while i < bytes.len() && bytes[i] == op {
+            i += 1;
+        }
+
+        // Duplicating a file descriptor (e.g. `2>&1`) or a process substitution
+        // (e.g. `<(cmd)`) does not redirect to a path
+        if i < bytes.len() && (bytes[i] == b'&' || bytes[i] == b'(') {
+            continue;
+        }
+
+        while i < bytes.len() && (bytes[i] == b' ' || bytes[i] == b'\t') {
+            i += 1;
+        }
+
+        let start = i;
+        while i < bytes.len() && !bytes[i].is_ascii_whitespace() {
+            // A backslash escapes the following character, including whitespace
+            if bytes[i] == b'\\' && i + 1 < bytes.len() {
+                i += 1;
+            }
+
+            i += 1;
+        }
+
+        if i > start {
+            targets.push((start, &text[start..i]));
+        }
+    }
+
+    targets
+}
+
+/// Detects Windows-style path separators in strings used as paths.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct BackslashPathSeparatorRule {
+    /// Whether or not UNC paths (e.g. `\\server\share`) are allowed.
+    allow_unc: bool,
+}
+
+impl BackslashPathSeparatorRule {
+    /// Creates a new backslash path separator rule.
+    ///
+    /// If `allow_unc` is `true`, UNC paths (e.g. `\\server\share`) are
+    /// assumed to be intentional and are not reported.
+    pub fn new(allow_unc: bool) -> Self {
+        Self { allow_unc }
+    }
+
+    /// Gets whether or not UNC paths are allowed.
+    pub fn allow_unc(&self) -> bool {
+        self.allow_unc
+    }
+
+    /// Checks a string literal used as a path.
+    fn check_string(
+        &self,
+        state: &mut Diagnostics,
+        context: &str,
+        string: &LiteralString,
+        syntax: SyntaxElement,
+    ) {
+        for (i, part) in string.parts().enumerate() {
+            let StringPart::Text(text) = part else {
+                continue;
+            };
+
+            let Some(path) = check_string_text(text.as_str(), i == 0) else {
+                continue;
+            };
+
+            if path.unc && self.allow_unc {
+                continue;
+            }
+
+            state.exceptable_add(
+                backslash_path_separator(context, text.span(), path.replacement),
+                syntax.clone(),
+                &self.exceptable_nodes(),
+            );
+        }
+    }
+}
+
+/// The options of the backslash path separator rule.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Options {
+    /// Whether or not UNC paths are allowed.
+    allow_unc: Option<bool>,
+}
+
+impl Rule for BackslashPathSeparatorRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags Windows-style path separators in strings used as paths."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Paths using backslashes as separators (e.g. `\"data\\\\samples\\\\a.txt\"`) only work \
+         on Windows; on the Linux hosts used by most execution backends, the backslashes are \
+         part of the file name. String literals in `File` and `Directory` declarations, string \
+         literal arguments to `glob`, `basename`, and `join_paths`, and the targets of command \
+         redirects are checked. Escape sequences such as `\\n` and `\\t` are not separators. \
+         Where converting the backslashes to forward slashes is unambiguous, a fix is provided; \
+         Windows drive paths (e.g. `C:\\\\data`) and UNC paths (e.g. `\\\\\\\\server\\\\share`) \
+         are reported without one. Optionally, UNC paths may be allowed as intentional."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Portability, Tag::Correctness])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("backslashpathseparator"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["BasicShellPortability", "StringFileOutput"]
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+        Some(&[
+            SyntaxKind::VersionStatementNode,
+            SyntaxKind::TaskDefinitionNode,
+            SyntaxKind::WorkflowDefinitionNode,
+            SyntaxKind::CommandSectionNode,
+            SyntaxKind::BoundDeclNode,
+        ])
+    }
+
+    fn configure(&mut self, options: &toml::Table) -> anyhow::Result<()> {
+        let options: Options = rule_options(options)?;
+
+        if let Some(allow_unc) = options.allow_unc {
+            self.allow_unc = allow_unc;
+        }
+
+        Ok(())
+    }
+}
+
+impl Visitor for BackslashPathSeparatorRule {
+    type State = Diagnostics;
+
+    fn document(&mut self, _: &mut Self::State, _: VisitReason, _: &Document, _: SupportedVersion) {
+        // This is intentionally empty, as this rule has no state.
+    }
+
+    fn bound_decl(&mut self, state: &mut Self::State, reason: VisitReason, decl: &BoundDecl) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        let Type::Primitive(ty) = decl.ty() else {
+            return;
+        };
+
+        let kind = match ty.kind() {
+            PrimitiveTypeKind::File => "File",
+            PrimitiveTypeKind::Directory => "Directory",
+            _ => return,
+        };
+
+        if let Expr::Literal(LiteralExpr::String(string)) = decl.expr() {
+            self.check_string(
+                state,
+                &format!("`{kind}` declaration `{name}`", name = decl.name().as_str()),
+                &string,
+                SyntaxElement::from(decl.syntax().clone()),
+            );
+        }
+    }
+
+    fn expr(&mut self, state: &mut Self::State, reason: VisitReason, expr: &Expr) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        let Expr::Call(call) = expr else {
+            return;
+        };
+
+        let target = call.target();
+        let function = target.as_str();
+        if !PATH_FUNCTIONS.contains(&function) {
+            return;
+        }
+
+        for argument in call.arguments() {
+            if let Expr::Literal(LiteralExpr::String(string)) = argument {
+                self.check_string(
+                    state,
+                    &format!("argument to `{function}`"),
+                    &string,
+                    SyntaxElement::from(call.syntax().clone()),
+                );
+            }
+        }
+    }
+
+    fn command_section(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        section: &CommandSection,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        for part in section.parts() {
+            let CommandPart::Text(text) = part else {
+                continue;
+            };
+
+            let start = text.span().start();
+            for (offset, target) in redirect_targets(text.as_str()) {
+                let Some(path) = check_redirect_target(target) else {
+                    continue;
+                };
+
+                if path.unc && self.allow_unc {
+                    continue;
+                }
+
+                state.exceptable_add(
+                    backslash_path_separator(
+                        &format!("command redirect target `{target}`"),
+                        Span::new(start + offset, target.len()),
+                        path.replacement,
+                    ),
+                    SyntaxElement::from(section.syntax().clone()),
+                    &self.exceptable_nodes(),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use wdl_ast::Validator;
+
+    use super::*;
+    use crate::util::apply_replacements;
+
+    #[test]
+    fn it_fixes_separators() {
+        let source = r#"version 1.1
+
+task test {
+    File samples = "data\\samples\\a.txt"
+    File drive = "C:\\data\\a.txt"
+    String name = basename("data\\b.txt", ".txt")
+
+    command <<<
+        cat ~{samples} > out\results.txt 2>&1
+    >>>
+}
+"#;
+
+        let (document, diagnostics) = Document::parse(source);
+        assert!(diagnostics.is_empty());
+
+        let mut validator = Validator::empty();
+        validator.add_visitor(BackslashPathSeparatorRule::default());
+        let diagnostics = validator.validate(&document).unwrap_err();
+        assert_eq!(diagnostics.len(), 4);
+        assert_eq!(
+            apply_replacements(source, &diagnostics),
+            r#"version 1.1
+
+task test {
+    File samples = "data/samples/a.txt"
+    File drive = "C:\\data\\a.txt"
+    String name = basename("data/b.txt", ".txt")
+
+    command <<<
+        cat ~{samples} > out/results.txt 2>&1
+    >>>
+}
+"#
+        );
+    }
+
+    #[test]
+    fn it_allows_unc_paths() {
+        let source = r#"version 1.1
+
+workflow test {
+    File share = "\\\\server\\share\\a.txt"
+}
+"#;
+
+        let (document, diagnostics) = Document::parse(source);
+        assert!(diagnostics.is_empty());
+
+        let mut validator = Validator::empty();
+        validator.add_visitor(BackslashPathSeparatorRule::default());
+        let diagnostics = validator.validate(&document).unwrap_err();
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].replacements().next().is_none());
+
+        let mut validator = Validator::empty();
+        validator.add_visitor(BackslashPathSeparatorRule::new(true));
+        assert!(validator.validate(&document).is_ok());
+    }
+}
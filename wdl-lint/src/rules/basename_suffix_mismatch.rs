@@ -0,0 +1,198 @@
+//! A lint rule for `basename` calls with a suffix that cannot match.
+
+use std::collections::HashMap;
+
+use wdl_ast::AstNode;
+use wdl_ast::AstNodeExt;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+use wdl_ast::v1::BoundDecl;
+use wdl_ast::v1::Expr;
+use wdl_ast::v1::InputSection;
+use wdl_ast::v1::LiteralExpr;
+use wdl_ast::v1::TaskDefinition;
+use wdl_ast::v1::WorkflowDefinition;
+
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+
+/// The identifier for the basename suffix mismatch rule.
+const ID: &str = "BasenameSuffixMismatch";
+
+/// Creates a "suffix mismatch" diagnostic.
+fn suffix_mismatch(name: &str, suffix: &str, span: Span) -> Diagnostic {
+    Diagnostic::note(format!(
+        "`basename` suffix `{suffix}` does not match the file name `{name}`"
+    ))
+    .with_rule(ID)
+    .with_label(format!("`basename` will return `{name}` unchanged"), span)
+    .with_fix("change the suffix to one that matches the end of the file name")
+}
+
+/// Gets the unescaped text of a string literal expression.
+///
+/// Returns `None` if the expression is not a string literal or if the string
+/// is interpolated.
+fn literal_text(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Literal(LiteralExpr::String(s)) => {
+            let mut text = String::new();
+            s.text()?.unescape_to(&mut text);
+            Some(text)
+        }
+        _ => None,
+    }
+}
+
+/// Detects `basename` calls with a suffix that cannot match the file name.
+#[derive(Default, Debug, Clone)]
+pub struct BasenameSuffixMismatchRule {
+    /// The literal string values of the declarations seen in the current task
+    /// or workflow.
+    values: HashMap<String, String>,
+    /// Whether or not the rule is visiting an input section.
+    in_input: bool,
+}
+
+impl Rule for BasenameSuffixMismatchRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Ensures that the suffix passed to `basename` can match the file name."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "The `basename` function only removes the given suffix if the file name ends with it; \
+         otherwise, the file name is returned unchanged. A call such as `basename(bam, \".bai\")` \
+         for a file named `sample.bam` silently returns `sample.bam`, which is almost certainly \
+         not what was intended. This rule only flags calls where the file name is statically \
+         known from a string literal, either directly or through a non-input declaration."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Correctness])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("basenamesuffixmismatch"))
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+        Some(&[
+            SyntaxKind::VersionStatementNode,
+            SyntaxKind::TaskDefinitionNode,
+            SyntaxKind::WorkflowDefinitionNode,
+            SyntaxKind::CommandSectionNode,
+            SyntaxKind::OutputSectionNode,
+            SyntaxKind::BoundDeclNode,
+        ])
+    }
+}
+
+impl Visitor for BasenameSuffixMismatchRule {
+    type State = Diagnostics;
+
+    fn document(
+        &mut self,
+        _: &mut Self::State,
+        reason: VisitReason,
+        _: &Document,
+        _: SupportedVersion,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        // Reset the visitor upon document entry
+        *self = Default::default();
+    }
+
+    fn task_definition(&mut self, _: &mut Self::State, _: VisitReason, _: &TaskDefinition) {
+        self.values.clear();
+    }
+
+    fn workflow_definition(&mut self, _: &mut Self::State, _: VisitReason, _: &WorkflowDefinition) {
+        self.values.clear();
+    }
+
+    fn input_section(&mut self, _: &mut Self::State, reason: VisitReason, _: &InputSection) {
+        self.in_input = reason == VisitReason::Enter;
+    }
+
+    fn bound_decl(&mut self, _: &mut Self::State, reason: VisitReason, decl: &BoundDecl) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        // The defaults of inputs may be overridden, so only the values of other
+        // declarations are known
+        let name = decl.name().as_str().to_string();
+        match literal_text(&decl.expr()).filter(|_| !self.in_input) {
+            Some(value) => {
+                self.values.insert(name, value);
+            }
+            None => {
+                self.values.remove(&name);
+            }
+        }
+    }
+
+    fn expr(&mut self, state: &mut Self::State, reason: VisitReason, expr: &Expr) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        let call = match expr {
+            Expr::Call(call) if call.target().as_str() == "basename" => call,
+            _ => return,
+        };
+
+        let mut arguments = call.arguments();
+        let (Some(path), Some(suffix_expr), None) =
+            (arguments.next(), arguments.next(), arguments.next())
+        else {
+            return;
+        };
+
+        let path = match &path {
+            Expr::Name(name) => match self.values.get(name.name().as_str()) {
+                Some(value) => value.clone(),
+                None => return,
+            },
+            path => match literal_text(path) {
+                Some(path) => path,
+                None => return,
+            },
+        };
+
+        let Some(suffix) = literal_text(&suffix_expr) else {
+            return;
+        };
+
+        let name = path
+            .trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .unwrap_or_default();
+        if name.is_empty() || suffix.is_empty() || name.ends_with(&suffix) {
+            return;
+        }
+
+        state.exceptable_add(
+            suffix_mismatch(name, &suffix, suffix_expr.span()),
+            SyntaxElement::from(call.syntax().clone()),
+            &self.exceptable_nodes(),
+        );
+    }
+}
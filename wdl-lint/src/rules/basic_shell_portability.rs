@@ -0,0 +1,328 @@
+//! A lint rule for non-portable shell constructs in command sections.
+
+use serde::Deserialize;
+use wdl_ast::AstNode;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+use wdl_ast::v1::CommandPart;
+use wdl_ast::v1::CommandSection;
+
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+use crate::config::rule_options;
+use crate::util::command_words;
+use crate::util::lines_with_offset;
+use crate::util::words;
+
+/// The identifier for the basic shell portability rule.
+const ID: &str = "BasicShellPortability";
+
+/// The default GNU-only flags, as pairs of program and flag.
+const DEFAULT_GNU_FLAGS: &[(&str, &str)] = &[("sed", "-i"), ("grep", "-P")];
+
+/// The words that end the arguments of a command.
+const COMMAND_SEPARATORS: &[&str] = &["|", "||", "&", "&&", ";"];
+
+/// Creates a "non-portable echo" diagnostic.
+fn non_portable_echo(flag: &str, span: Span) -> Diagnostic {
+    Diagnostic::note(format!("`echo {flag}` is not portable"))
+        .with_rule(ID)
+        .with_highlight(span)
+        .with_fix("use `printf` to write escape sequences")
+}
+
+/// Creates a "non-portable which" diagnostic.
+fn non_portable_which(span: Span) -> Diagnostic {
+    Diagnostic::note("`which` is not portable")
+        .with_rule(ID)
+        .with_highlight(span)
+        .with_fix("use `command -v` instead of `which`")
+}
+
+/// Creates a "non-portable function keyword" diagnostic.
+fn non_portable_function(name: Option<&str>, span: Span) -> Diagnostic {
+    Diagnostic::note("the `function` keyword is not portable")
+        .with_rule(ID)
+        .with_highlight(span)
+        .with_fix(format!(
+            "define the function as `{name}() {{ ... }}` instead",
+            name = name.unwrap_or("name")
+        ))
+}
+
+/// Creates an "inconsistent source" diagnostic.
+fn inconsistent_source(span: Span) -> Diagnostic {
+    Diagnostic::note("command section uses both `source` and `.` to read a file")
+        .with_rule(ID)
+        .with_highlight(span)
+        .with_fix("use `.` consistently, as `source` is not portable")
+}
+
+/// Creates a "GNU-only flag" diagnostic.
+fn gnu_only_flag(program: &str, flag: &str, span: Span) -> Diagnostic {
+    Diagnostic::note(format!(
+        "`{program} {flag}` is a GNU extension and is not portable"
+    ))
+    .with_rule(ID)
+    .with_highlight(span)
+    .with_fix(format!("use a portable alternative to `{program} {flag}`"))
+}
+
+/// A non-portable construct found in a line of a command.
+#[derive(Debug, Clone, Copy)]
+enum Construct<'a> {
+    /// `echo` with a flag that interprets escape sequences.
+    Echo(&'a str),
+    /// The `which` program.
+    Which,
+    /// The `function` keyword with the name of the function.
+    Function(Option<&'a str>),
+    /// The `source` builtin.
+    Source,
+    /// The `.` builtin.
+    Dot,
+    /// A GNU-only flag of a program.
+    GnuFlag(&'a str, &'a str),
+}
+
+/// Determines if the given word is an `echo` flag that interprets escape
+/// sequences (e.g. `-e` or `-ne`).
+fn is_echo_escape_flag(word: &str) -> bool {
+    word.strip_prefix('-').is_some_and(|flags| {
+        flags.contains('e') && flags.chars().all(|c| matches!(c, 'n' | 'e' | 'E'))
+    })
+}
+
+/// Finds the non-portable constructs in a line of a command.
+///
+/// `command` is whether or not the first word of the line is in command
+/// position; it is `false` for text that continues the line of a placeholder.
+///
+/// Returns the constructs with their offsets and lengths within the line.
+fn scan<'a>(
+    line: &'a str,
+    command: bool,
+    gnu_flags: &'a [(String, String)],
+) -> Vec<(Construct<'a>, usize, usize)> {
+    let words = words(line);
+    let mut constructs = Vec::new();
+    for (i, offset, name) in command_words(&words, command) {
+        match name {
+            "echo" => {
+                if let Some(&(flag_offset, flag)) = words.get(i + 1) {
+                    if is_echo_escape_flag(flag) {
+                        constructs.push((
+                            Construct::Echo(flag),
+                            offset,
+                            flag_offset + flag.len() - offset,
+                        ));
+                    }
+                }
+            }
+            "which" => constructs.push((Construct::Which, offset, name.len())),
+            "function" => {
+                let function = words
+                    .get(i + 1)
+                    .map(|(_, name)| name.trim_end_matches("()"));
+                constructs.push((Construct::Function(function), offset, name.len()));
+            }
+            "source" => constructs.push((Construct::Source, offset, name.len())),
+            "." => constructs.push((Construct::Dot, offset, name.len())),
+            _ => {
+                for (program, flag) in gnu_flags.iter().filter(|(p, _)| p == name) {
+                    if let Some(&(flag_offset, _)) = words[i + 1..]
+                        .iter()
+                        .take_while(|(_, w)| !COMMAND_SEPARATORS.contains(w) && !w.ends_with(';'))
+                        .find(|(_, w)| w == flag)
+                    {
+                        constructs.push((
+                            Construct::GnuFlag(program, flag),
+                            flag_offset,
+                            flag.len(),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    constructs
+}
+
+/// Detects non-portable shell constructs in command sections.
+///
+/// This rule is a lightweight subset of the `ShellCheck` rule for when
+/// `shellcheck` is unavailable; it is superseded by the `ShellCheck` rule when
+/// both are enabled.
+#[derive(Debug, Clone)]
+pub struct BasicShellPortabilityRule {
+    /// The GNU-only flags to detect, as pairs of program and flag.
+    gnu_flags: Vec<(String, String)>,
+}
+
+impl BasicShellPortabilityRule {
+    /// Constructs a new basic shell portability rule with the given GNU-only
+    /// flags, as pairs of program and flag (e.g. `("sed", "-i")`).
+    pub fn new(
+        gnu_flags: impl IntoIterator<Item = (impl Into<String>, impl Into<String>)>,
+    ) -> Self {
+        Self {
+            gnu_flags: gnu_flags
+                .into_iter()
+                .map(|(program, flag)| (program.into(), flag.into()))
+                .collect(),
+        }
+    }
+
+    /// Gets the GNU-only flags to detect, as pairs of program and flag.
+    pub fn gnu_flags(&self) -> &[(String, String)] {
+        &self.gnu_flags
+    }
+}
+
+impl Default for BasicShellPortabilityRule {
+    fn default() -> Self {
+        Self::new(DEFAULT_GNU_FLAGS.iter().copied())
+    }
+}
+
+/// The options of the basic shell portability rule.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Options {
+    /// The GNU-only flags to detect, as pairs of program and flag.
+    gnu_flags: Option<Vec<(String, String)>>,
+}
+
+impl Rule for BasicShellPortabilityRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Ensures that command sections do not use common non-portable shell constructs."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Command sections may be run by shells other than bash and with tools other than GNU \
+         coreutils, such as in Alpine or BusyBox containers. This rule detects a handful of \
+         common non-portable constructs without requiring `shellcheck`: `echo -e` (use \
+         `printf`), `which` (use `command -v`), the `function` keyword, mixing `source` and `.`, \
+         and GNU-only flags such as `sed -i` and `grep -P`. The list of GNU-only flags is \
+         configurable. This rule is automatically disabled when the `ShellCheck` rule is enabled \
+         to avoid duplicate findings."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Portability])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("basicshellportability"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["ShellCheck"]
+    }
+
+    fn superseded_by(&self) -> &'static [&'static str] {
+        &["ShellCheck"]
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+        Some(&[
+            SyntaxKind::VersionStatementNode,
+            SyntaxKind::TaskDefinitionNode,
+            SyntaxKind::CommandSectionNode,
+        ])
+    }
+
+    fn configure(&mut self, options: &toml::Table) -> anyhow::Result<()> {
+        let options: Options = rule_options(options)?;
+
+        if let Some(gnu_flags) = options.gnu_flags {
+            self.gnu_flags = gnu_flags;
+        }
+
+        Ok(())
+    }
+}
+
+impl Visitor for BasicShellPortabilityRule {
+    type State = Diagnostics;
+
+    fn document(&mut self, _: &mut Self::State, _: VisitReason, _: &Document, _: SupportedVersion) {
+        // This rule has no state to reset.
+    }
+
+    fn command_section(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        section: &CommandSection,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        let element = SyntaxElement::from(section.syntax().clone());
+        let mut sources = Vec::new();
+        let mut uses_dot = false;
+        let mut after_placeholder = false;
+        for part in section.parts() {
+            let text = match part {
+                CommandPart::Text(text) => text,
+                CommandPart::Placeholder(_) => {
+                    after_placeholder = true;
+                    continue;
+                }
+            };
+
+            let start = text.span().start();
+            for (i, (line, line_start, _)) in lines_with_offset(text.as_str()).enumerate() {
+                // Text following a placeholder continues the placeholder's line
+                let command = i > 0 || !after_placeholder;
+                for (construct, offset, len) in scan(line, command, &self.gnu_flags) {
+                    let span = Span::new(start + line_start + offset, len);
+                    let diagnostic = match construct {
+                        Construct::Echo(flag) => non_portable_echo(flag, span),
+                        Construct::Which => non_portable_which(span),
+                        Construct::Function(name) => non_portable_function(name, span),
+                        Construct::Source => {
+                            sources.push(span);
+                            continue;
+                        }
+                        Construct::Dot => {
+                            uses_dot = true;
+                            continue;
+                        }
+                        Construct::GnuFlag(program, flag) => gnu_only_flag(program, flag, span),
+                    };
+
+                    state.exceptable_add(diagnostic, element.clone(), &self.exceptable_nodes());
+                }
+            }
+
+            after_placeholder = false;
+        }
+
+        if uses_dot {
+            for span in sources {
+                state.exceptable_add(
+                    inconsistent_source(span),
+                    element.clone(),
+                    &self.exceptable_nodes(),
+                );
+            }
+        }
+    }
+}
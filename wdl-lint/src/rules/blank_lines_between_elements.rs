@@ -105,6 +105,14 @@ impl Rule for BlankLinesBetweenElementsRule {
         TagSet::new(&[Tag::Spacing])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("blanklinesbetweenelements"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["Whitespace", "EndingNewline"]
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
         Some(&[
             SyntaxKind::VersionStatementNode,
@@ -17,15 +17,30 @@ use crate::Rule;
 use crate::Tag;
 use crate::TagSet;
 
-/// The identifier for the input not sorted rule.
+/// The identifier for the call input spacing rule.
 const ID: &str = "CallInputSpacing";
 
+/// Creates an empty span at the given offset for inserting text.
+fn insertion(offset: usize) -> Span {
+    Span::new(offset, 0)
+}
+
 /// Creates a input spacing diagnostic.
 fn call_input_keyword_spacing(span: Span) -> Diagnostic {
     Diagnostic::note("call input keyword not properly spaced")
         .with_rule(ID)
         .with_highlight(span)
         .with_fix("add a single space prior to the input keyword")
+        .with_replacement(insertion(span.start()), " ")
+}
+
+/// Creates a missing whitespace after the input keyword diagnostic.
+fn call_input_colon_spacing(span: Span) -> Diagnostic {
+    Diagnostic::note("call input keyword must be followed by whitespace")
+        .with_rule(ID)
+        .with_highlight(span)
+        .with_fix("add a single space after `input:`")
+        .with_replacement(insertion(span.end()), " ")
 }
 
 /// Creates an incorrect call input whitespace diagnostic.
@@ -34,6 +49,7 @@ fn call_input_incorrect_spacing(span: Span) -> Diagnostic {
         .with_rule(ID)
         .with_highlight(span)
         .with_fix("change this whitespace to a single space")
+        .with_replacement(span, " ")
 }
 
 /// Creates an input call spacing diagnostic.
@@ -45,14 +61,29 @@ fn call_input_missing_newline(span: Span) -> Diagnostic {
 }
 
 /// Creates call input assignment diagnostic.
-fn call_input_assignment(span: Span) -> Diagnostic {
-    Diagnostic::note("call inputs assignments must be surrounded with whitespace")
+///
+/// The fix inserts a space on each side of the assignment that is missing
+/// whitespace.
+fn call_input_assignment(span: Span, before: bool, after: bool) -> Diagnostic {
+    let diagnostic = Diagnostic::note("call inputs assignments must be surrounded with whitespace")
         .with_rule(ID)
         .with_highlight(span)
-        .with_fix("surround '=' with whitespace on each side")
+        .with_fix("surround '=' with whitespace on each side");
+
+    let diagnostic = if before {
+        diagnostic.with_replacement(insertion(span.start()), " ")
+    } else {
+        diagnostic
+    };
+
+    if after {
+        diagnostic.with_replacement(insertion(span.end()), " ")
+    } else {
+        diagnostic
+    }
 }
 
-/// Detects unsorted input declarations.
+/// Detects improperly spaced call inputs.
 #[derive(Default, Debug, Clone, Copy)]
 pub struct CallInputSpacingRule;
 
@@ -72,13 +103,26 @@ impl Rule for CallInputSpacingRule {
          input specification should occupy its own line. This does inflate the line count of a WDL \
          document, but it is worth it for the consistent readability. An exception can be made \
          (but does not have to be made), for calls with only a single parameter. In those cases, \
-         it is permissable to keep the input on the same line as the call."
+         it is permissable to keep the input on the same line as the call. The `input:` keyword \
+         should be followed by a single space or a newline, and the `=` of each input should be \
+         surrounded by a single space; aligning the `=` of consecutive inputs with additional \
+         whitespace is not permitted. Trailing commas are left to the `TrailingComma` rule, which \
+         applies to call inputs in every WDL version, so this rule neither requires nor flags \
+         them."
     }
 
     fn tags(&self) -> TagSet {
         TagSet::new(&[Tag::Style, Tag::Clarity, Tag::Spacing])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("callinputspacing"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["ExpressionSpacing", "TrailingComma", "Whitespace"]
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
         Some(&[
             SyntaxKind::VersionStatementNode,
@@ -122,7 +166,7 @@ impl Visitor for CallInputSpacingRule {
             return;
         }
 
-        // Check for "{ input:" spacing
+        // Check for "{ input:" spacing; the keyword is optional in WDL 1.2
         if let Some(input_keyword) = call
             .syntax()
             .children_with_tokens()
@@ -145,6 +189,35 @@ impl Visitor for CallInputSpacingRule {
                     );
                 }
             }
+
+            // Check for "input: " spacing; a newline or comment may follow the colon
+            if let Some(colon) = input_keyword
+                .next_sibling_or_token()
+                .filter(|c| c.kind() == SyntaxKind::Colon)
+            {
+                match colon.next_sibling_or_token() {
+                    Some(whitespace) if whitespace.kind() == SyntaxKind::Whitespace => {
+                        let text = whitespace.as_token().unwrap().text();
+                        let comment = whitespace
+                            .next_sibling_or_token()
+                            .is_some_and(|c| c.kind() == SyntaxKind::Comment);
+                        if text != " " && !text.contains('\n') && !comment {
+                            state.exceptable_add(
+                                call_input_incorrect_spacing(whitespace.text_range().to_span()),
+                                SyntaxElement::from(call.syntax().clone()),
+                                &self.exceptable_nodes(),
+                            );
+                        }
+                    }
+                    _ => {
+                        state.exceptable_add(
+                            call_input_colon_spacing(colon.text_range().to_span()),
+                            SyntaxElement::from(call.syntax().clone()),
+                            &self.exceptable_nodes(),
+                        );
+                    }
+                }
+            }
         }
 
         call.inputs().for_each(|input| {
@@ -154,14 +227,29 @@ impl Visitor for CallInputSpacingRule {
                 .children_with_tokens()
                 .find(|c| c.kind() == SyntaxKind::Assignment)
             {
-                match (
-                    assign.next_sibling_or_token().unwrap().kind(),
-                    assign.prev_sibling_or_token().unwrap().kind(),
-                ) {
-                    (SyntaxKind::Whitespace, SyntaxKind::Whitespace) => {}
-                    _ => {
+                let before = assign.prev_sibling_or_token().unwrap();
+                let after = assign.next_sibling_or_token().unwrap();
+                let missing_before = before.kind() != SyntaxKind::Whitespace;
+                let missing_after = after.kind() != SyntaxKind::Whitespace;
+                if missing_before || missing_after {
+                    state.exceptable_add(
+                        call_input_assignment(
+                            assign.text_range().to_span(),
+                            missing_before,
+                            missing_after,
+                        ),
+                        SyntaxElement::from(call.syntax().clone()),
+                        &self.exceptable_nodes(),
+                    );
+                }
+
+                // Each whitespace around the assignment must be a single space
+                for whitespace in [before, after] {
+                    if whitespace.kind() == SyntaxKind::Whitespace
+                        && whitespace.as_token().unwrap().text() != " "
+                    {
                         state.exceptable_add(
-                            call_input_assignment(assign.text_range().to_span()),
+                            call_input_incorrect_spacing(whitespace.text_range().to_span()),
                             SyntaxElement::from(call.syntax().clone()),
                             &self.exceptable_nodes(),
                         );
@@ -194,3 +282,49 @@ impl Visitor for CallInputSpacingRule {
             });
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use wdl_ast::Validator;
+
+    use super::*;
+    use crate::util::apply_replacements;
+
+    #[test]
+    fn it_fixes_spacing() {
+        let source = r#"version 1.1
+
+workflow test {
+    call foo {input:a=1}
+    call foo as bar {  input:   b =  2 }
+    call foo as baz { input:
+        c   = 3,
+        d=4,
+    }
+}
+"#;
+
+        let (document, diagnostics) = Document::parse(source);
+        assert!(diagnostics.is_empty());
+
+        let mut validator = Validator::empty();
+        validator.add_visitor(CallInputSpacingRule);
+        let diagnostics = validator.validate(&document).unwrap_err();
+        assert_eq!(diagnostics.len(), 8);
+        assert_eq!(
+            apply_replacements(source, &diagnostics),
+            r#"version 1.1
+
+workflow test {
+    call foo { input: a = 1}
+    call foo as bar { input: b = 2 }
+    call foo as baz { input:
+        c = 3,
+        d = 4,
+    }
+}
+"#
+        );
+    }
+}
@@ -0,0 +1,290 @@
+//! A lint rule for checking the number of lines in a command section.
+
+use serde::Deserialize;
+use wdl_ast::AstNode;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::ToSpan;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+use wdl_ast::support;
+use wdl_ast::v1::CommandPart;
+use wdl_ast::v1::CommandSection;
+
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+use crate::config::rule_options;
+
+/// The identifier for the command section line count rule.
+const ID: &str = "CommandSectionLineCount";
+
+/// The default number of lines a command may have before a note is emitted.
+const DEFAULT_NOTE_THRESHOLD: usize = 100;
+
+/// The default number of lines a command may have before a warning is
+/// emitted.
+const DEFAULT_WARNING_THRESHOLD: usize = 150;
+
+/// Creates a "command too long" diagnostic.
+fn command_too_long(span: Span, count: usize, threshold: usize, warning: bool) -> Diagnostic {
+    let message = format!("command section has more than {threshold} lines");
+    let diagnostic = if warning {
+        Diagnostic::warning(message)
+    } else {
+        Diagnostic::note(message)
+    };
+
+    diagnostic
+        .with_rule(ID)
+        .with_label(
+            format!("this command section has {count} lines of script"),
+            span,
+        )
+        .with_fix(
+            "move the script into a separate, versioned file distributed alongside the workflow \
+             and invoke it from the command section",
+        )
+}
+
+/// Gets the delimiter of a heredoc started on the given line.
+///
+/// Returns `None` if the line does not start a heredoc.
+fn heredoc_delimiter(line: &str) -> Option<&str> {
+    let mut rest = line;
+    while let Some(index) = rest.find("<<") {
+        rest = &rest[index + 2..];
+
+        // Skip over here-strings (`<<<`)
+        if let Some(r) = rest.strip_prefix('<') {
+            rest = r.trim_start_matches('<');
+            continue;
+        }
+
+        let word = rest.strip_prefix('-').unwrap_or(rest).trim_start();
+        let word = word.trim_start_matches(['\'', '"']);
+        if word.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+            let len = word
+                .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                .unwrap_or(word.len());
+            return Some(&word[..len]);
+        }
+    }
+
+    None
+}
+
+/// Counts the logical lines of the given command text.
+///
+/// Blank lines and comment-only lines are not counted, except for those
+/// within the body of a heredoc as the body is being fed to another program.
+fn count_lines(text: &str) -> usize {
+    let mut count = 0;
+    let mut delimiter = None;
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        match delimiter {
+            Some(d) => {
+                if trimmed == d {
+                    delimiter = None;
+                }
+            }
+            None => {
+                if trimmed.starts_with('#') {
+                    continue;
+                }
+
+                delimiter = heredoc_delimiter(trimmed);
+            }
+        }
+
+        count += 1;
+    }
+
+    count
+}
+
+/// Detects command sections with too many lines.
+#[derive(Debug, Clone, Copy)]
+pub struct CommandSectionLineCountRule {
+    /// The number of lines a command may have before a note is emitted.
+    note_threshold: usize,
+    /// The number of lines a command may have before a warning is emitted.
+    warning_threshold: usize,
+}
+
+impl CommandSectionLineCountRule {
+    /// Constructs a new command section line count rule with the given
+    /// thresholds.
+    ///
+    /// A command with more lines than `note_threshold` results in a note and a
+    /// command with more lines than `warning_threshold` results in a warning.
+    pub fn new(note_threshold: usize, warning_threshold: usize) -> Self {
+        Self {
+            note_threshold,
+            warning_threshold,
+        }
+    }
+}
+
+/// Implements the default command section line count rule.
+impl Default for CommandSectionLineCountRule {
+    fn default() -> Self {
+        Self {
+            note_threshold: DEFAULT_NOTE_THRESHOLD,
+            warning_threshold: DEFAULT_WARNING_THRESHOLD,
+        }
+    }
+}
+
+/// The options of the command section line count rule.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Options {
+    /// The number of lines a command may have before a note is emitted.
+    note_threshold: Option<usize>,
+    /// The number of lines a command may have before a warning is emitted.
+    warning_threshold: Option<usize>,
+}
+
+impl Rule for CommandSectionLineCountRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Ensures that command sections do not contain overly long scripts."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Long scripts embedded within a command section are difficult to read, test, and \
+         maintain. Such scripts should instead be moved into separate, versioned files that are \
+         distributed alongside the workflow. Blank lines and comment-only lines are not counted, \
+         but every line of a heredoc feeding another interpreter is. A note is emitted for \
+         commands with more than 100 lines and a warning for commands with more than 150 lines."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Clarity])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("commandsectionlinecount"))
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+        Some(&[
+            SyntaxKind::VersionStatementNode,
+            SyntaxKind::TaskDefinitionNode,
+            SyntaxKind::CommandSectionNode,
+        ])
+    }
+
+    fn configure(&mut self, options: &toml::Table) -> anyhow::Result<()> {
+        let options: Options = rule_options(options)?;
+
+        if let Some(note_threshold) = options.note_threshold {
+            self.note_threshold = note_threshold;
+        }
+
+        if let Some(warning_threshold) = options.warning_threshold {
+            self.warning_threshold = warning_threshold;
+        }
+
+        Ok(())
+    }
+}
+
+impl Visitor for CommandSectionLineCountRule {
+    type State = Diagnostics;
+
+    fn document(&mut self, _: &mut Self::State, _: VisitReason, _: &Document, _: SupportedVersion) {
+        // This rule has no state to reset.
+    }
+
+    fn command_section(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        section: &CommandSection,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        // Placeholders are replaced with a stand-in so that they remain part of
+        // their line
+        let mut text = String::new();
+        for part in section.parts() {
+            match part {
+                CommandPart::Text(t) => text.push_str(t.as_str()),
+                CommandPart::Placeholder(_) => text.push_str("~{}"),
+            }
+        }
+
+        let count = count_lines(&text);
+        let (threshold, warning) = if count > self.warning_threshold {
+            (self.warning_threshold, true)
+        } else if count > self.note_threshold {
+            (self.note_threshold, false)
+        } else {
+            return;
+        };
+
+        let command_keyword = support::token(section.syntax(), SyntaxKind::CommandKeyword)
+            .expect("should have a command keyword token");
+
+        state.exceptable_add(
+            command_too_long(
+                command_keyword.text_range().to_span(),
+                count,
+                threshold,
+                warning,
+            ),
+            SyntaxElement::from(section.syntax().clone()),
+            &self.exceptable_nodes(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_finds_heredoc_delimiters() {
+        assert_eq!(heredoc_delimiter("cat <<EOF"), Some("EOF"));
+        assert_eq!(
+            heredoc_delimiter("python3 <<- 'SCRIPT' > out.txt"),
+            Some("SCRIPT")
+        );
+        assert_eq!(
+            heredoc_delimiter(r#"cat << "END_OF_FILE""#),
+            Some("END_OF_FILE")
+        );
+        assert_eq!(heredoc_delimiter("cat <<< \"$value\""), None);
+        assert_eq!(heredoc_delimiter("echo $((1 << 2))"), None);
+        assert_eq!(heredoc_delimiter("echo hello"), None);
+    }
+
+    #[test]
+    fn it_counts_lines() {
+        assert_eq!(
+            count_lines("\n  echo one\n\n  # a comment\n  echo two\n"),
+            2
+        );
+        assert_eq!(
+            count_lines("python3 <<EOF\n# a python comment\n\nprint('hi')\nEOF\n# done\n"),
+            4
+        );
+    }
+}
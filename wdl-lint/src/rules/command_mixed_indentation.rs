@@ -98,6 +98,14 @@ impl Rule for CommandSectionMixedIndentationRule {
         TagSet::new(&[Tag::Correctness, Tag::Spacing, Tag::Clarity])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("commandsectionmixedindentation"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["Whitespace", "NoCurlyCommands"]
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
         Some(&[
             SyntaxKind::VersionStatementNode,
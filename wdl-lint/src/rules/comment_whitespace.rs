@@ -92,6 +92,14 @@ impl Rule for CommentWhitespaceRule {
         TagSet::new(&[Tag::Spacing])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("commentwhitespace"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["Whitespace", "PreambleFormatting"]
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
         None
     }
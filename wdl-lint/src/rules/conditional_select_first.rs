@@ -0,0 +1,188 @@
+//! A lint rule for conditional expressions that can be written as a call to
+//! `select_first`.
+
+use wdl_ast::AstNode;
+use wdl_ast::AstNodeExt;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+use wdl_ast::v1::Expr;
+use wdl_ast::v1::LiteralExpr;
+
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+
+/// The identifier for the conditional select first rule.
+const ID: &str = "ConditionalSelectFirst";
+
+/// Creates a "conditional select first" diagnostic.
+fn conditional_select_first(replacement: &str, span: Span) -> Diagnostic {
+    Diagnostic::note("conditional expression can be written as a call to `select_first`")
+        .with_rule(ID)
+        .with_highlight(span)
+        .with_fix(format!(
+            "replace the conditional expression with `{replacement}`"
+        ))
+        .with_replacement(span, replacement)
+}
+
+/// Removes any parentheses around an expression.
+fn unparenthesize(mut expr: Expr) -> Expr {
+    while let Expr::Parenthesized(inner) = expr {
+        expr = inner.inner();
+    }
+
+    expr
+}
+
+/// Gets the name of the single argument of a call to the given function.
+///
+/// Returns `None` if the expression is not a call to the function with a
+/// single name reference argument.
+fn call_name_argument(expr: &Expr, function: &str) -> Option<String> {
+    let Expr::Call(call) = expr else {
+        return None;
+    };
+
+    if call.target().as_str() != function {
+        return None;
+    }
+
+    let mut arguments = call.arguments();
+    let argument = unparenthesize(arguments.next()?);
+    if arguments.next().is_some() {
+        return None;
+    }
+
+    Some(argument.as_name_ref()?.name().as_str().to_string())
+}
+
+/// Determines if the given expression is the value of the given name (e.g.
+/// `x` or `select_first([x])`).
+fn is_value_of(expr: &Expr, name: &str) -> bool {
+    match expr {
+        Expr::Name(r) => r.name().as_str() == name,
+        Expr::Call(call) if call.target().as_str() == "select_first" => {
+            let mut arguments = call.arguments();
+            let (Some(Expr::Literal(LiteralExpr::Array(array))), None) =
+                (arguments.next().map(unparenthesize), arguments.next())
+            else {
+                return false;
+            };
+
+            let mut elements = array.elements();
+            match (elements.next().map(unparenthesize), elements.next()) {
+                (Some(Expr::Name(r)), None) => r.name().as_str() == name,
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+/// Detects conditional expressions that can be written as a call to
+/// `select_first`.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct ConditionalSelectFirstRule;
+
+impl Rule for ConditionalSelectFirstRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags conditional expressions that can be written as a call to `select_first`."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A conditional expression that checks whether an optional value is defined and evaluates \
+         to either the value or a default (e.g. `if defined(x) then select_first([x]) else 1` or \
+         `if !defined(x) then 1 else x`) is more concisely written as \
+         `select_first([x, 1])`. Conditional expressions where the value is used in additional \
+         computation (e.g. `if defined(x) then select_first([x]) + 1 else 0`) are not flagged, \
+         nor are those with a default of `None`, as `select_first` requires a defined value."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Style, Tag::Clarity])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("conditionalselectfirst"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["DeprecatedPlaceholderOption"]
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+        Some(&[
+            SyntaxKind::VersionStatementNode,
+            SyntaxKind::TaskDefinitionNode,
+            SyntaxKind::WorkflowDefinitionNode,
+            SyntaxKind::BoundDeclNode,
+        ])
+    }
+}
+
+impl Visitor for ConditionalSelectFirstRule {
+    type State = Diagnostics;
+
+    fn document(&mut self, _: &mut Self::State, _: VisitReason, _: &Document, _: SupportedVersion) {
+        // This is intentionally empty, as this rule has no state.
+    }
+
+    fn expr(&mut self, state: &mut Self::State, reason: VisitReason, expr: &Expr) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        let Expr::If(conditional) = expr else {
+            return;
+        };
+
+        let (condition, then, otherwise) = conditional.exprs();
+
+        // A negated condition swaps the value and the default
+        let (name, value, default) = match unparenthesize(condition) {
+            Expr::LogicalNot(not) => (
+                call_name_argument(&unparenthesize(not.operand()), "defined"),
+                otherwise,
+                then,
+            ),
+            condition => (call_name_argument(&condition, "defined"), then, otherwise),
+        };
+
+        let Some(name) = name else {
+            return;
+        };
+
+        if !is_value_of(&unparenthesize(value), &name)
+            || matches!(
+                unparenthesize(default.clone()),
+                Expr::Literal(LiteralExpr::None(_))
+            )
+        {
+            return;
+        }
+
+        let replacement = format!(
+            "select_first([{name}, {default}])",
+            default = default.syntax().text()
+        );
+
+        state.exceptable_add(
+            conditional_select_first(&replacement, conditional.span()),
+            SyntaxElement::from(expr.syntax().clone()),
+            &self.exceptable_nodes(),
+        );
+    }
+}
@@ -0,0 +1,339 @@
+//! A lint rule for consistent indentation outside of command sections.
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::bail;
+use serde::Deserialize;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+use wdl_ast::Whitespace;
+
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+use crate::config::rule_options;
+use crate::util::lines_with_offset;
+
+/// The identifier for the consistent indentation rule.
+const ID: &str = "ConsistentIndentation";
+
+/// The default number of spaces that a tab is equivalent to.
+pub const DEFAULT_INDENT_WIDTH: usize = 4;
+
+/// Represents an indentation style.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum IndentStyle {
+    /// Lines are indented with spaces.
+    Spaces,
+    /// Lines are indented with tabs.
+    Tabs,
+}
+
+impl fmt::Display for IndentStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Spaces => write!(f, "spaces"),
+            Self::Tabs => write!(f, "tabs"),
+        }
+    }
+}
+
+impl FromStr for IndentStyle {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "spaces" => Ok(Self::Spaces),
+            "tabs" => Ok(Self::Tabs),
+            _ => bail!("unknown indentation style `{s}`; expected `spaces` or `tabs`"),
+        }
+    }
+}
+
+/// Creates an "inconsistent indentation" diagnostic.
+fn inconsistent_indentation(
+    style: IndentStyle,
+    configured: bool,
+    span: Span,
+    replacement: Option<String>,
+) -> Diagnostic {
+    let other = match style {
+        IndentStyle::Spaces => IndentStyle::Tabs,
+        IndentStyle::Tabs => IndentStyle::Spaces,
+    };
+
+    let message = if configured {
+        format!("line is indented with {other} but the configured indentation is {style}")
+    } else {
+        format!("line is indented with {other} but the document is indented with {style}")
+    };
+
+    let diagnostic = Diagnostic::note(message)
+        .with_rule(ID)
+        .with_highlight(span)
+        .with_fix(format!("indent the line with {style}"));
+
+    match replacement {
+        Some(replacement) => diagnostic.with_replacement(span, replacement),
+        None => diagnostic,
+    }
+}
+
+/// Creates a "mixed indentation" diagnostic.
+fn mixed_indentation(style: IndentStyle, span: Span, replacement: Option<String>) -> Diagnostic {
+    let diagnostic = Diagnostic::note("line is indented with both tabs and spaces")
+        .with_rule(ID)
+        .with_highlight(span)
+        .with_fix(format!("indent the line with {style}"));
+
+    match replacement {
+        Some(replacement) => diagnostic.with_replacement(span, replacement),
+        None => diagnostic,
+    }
+}
+
+/// Represents the leading whitespace of a line.
+#[derive(Debug, Clone)]
+struct Indentation {
+    /// The span of the leading whitespace.
+    span: Span,
+    /// The leading whitespace.
+    text: String,
+    /// The whitespace token containing the leading whitespace.
+    element: SyntaxElement,
+}
+
+/// Detects lines that are not indented with the document's indentation style.
+///
+/// Lines within command sections are checked by the
+/// `CommandSectionMixedIndentation` rule instead.
+#[derive(Debug, Clone)]
+pub struct ConsistentIndentationRule {
+    /// The configured indentation style.
+    ///
+    /// If `None`, the style used by most lines of the document is expected.
+    style: Option<IndentStyle>,
+    /// The number of spaces that a tab is equivalent to.
+    width: usize,
+    /// The leading whitespace of the lines of the current document.
+    indentation: Vec<Indentation>,
+}
+
+impl ConsistentIndentationRule {
+    /// Constructs a new consistent indentation rule.
+    ///
+    /// If `style` is `None`, the style used by most lines of a document is
+    /// expected.
+    ///
+    /// The `width` is the number of spaces that a tab is equivalent to when
+    /// converting between styles.
+    pub fn new(style: Option<IndentStyle>, width: usize) -> Self {
+        Self {
+            style,
+            width: width.max(1),
+            indentation: Vec::new(),
+        }
+    }
+
+    /// Gets the configured indentation style.
+    pub fn style(&self) -> Option<IndentStyle> {
+        self.style
+    }
+
+    /// Gets the number of spaces that a tab is equivalent to.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Converts the given leading whitespace to the given style.
+    ///
+    /// Returns `None` if the whitespace cannot be converted to tabs without
+    /// remaining spaces.
+    fn convert(&self, text: &str, style: IndentStyle) -> Option<String> {
+        let column = text.chars().fold(0, |column, c| match c {
+            '\t' => (column / self.width + 1) * self.width,
+            _ => column + 1,
+        });
+
+        match style {
+            IndentStyle::Spaces => Some(" ".repeat(column)),
+            IndentStyle::Tabs if column % self.width == 0 => Some("\t".repeat(column / self.width)),
+            IndentStyle::Tabs => None,
+        }
+    }
+}
+
+impl Default for ConsistentIndentationRule {
+    fn default() -> Self {
+        Self::new(None, DEFAULT_INDENT_WIDTH)
+    }
+}
+
+/// The options of the consistent indentation rule.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Options {
+    /// The indentation style (`spaces` or `tabs`).
+    style: Option<String>,
+    /// The number of spaces that a tab is equivalent to.
+    width: Option<usize>,
+}
+
+impl Rule for ConsistentIndentationRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Ensures that lines outside of command sections are consistently indented."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Mixing tabs and spaces for indentation causes a document to render differently \
+         depending on the editor's tab width. Lines should be indented using the style used by \
+         most lines of the document (or the configured style), and a single line should never be \
+         indented with both tabs and spaces. Indentation within command sections is checked by \
+         the `CommandSectionMixedIndentation` rule instead."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Spacing, Tag::Style])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("consistentindentation"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["CommandSectionMixedIndentation", "Whitespace"]
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+        None
+    }
+
+    fn configure(&mut self, options: &toml::Table) -> anyhow::Result<()> {
+        let options: Options = rule_options(options)?;
+
+        if let Some(style) = options.style {
+            self.style = Some(style.parse()?);
+        }
+
+        if let Some(width) = options.width {
+            self.width = width.max(1);
+        }
+
+        Ok(())
+    }
+}
+
+impl Visitor for ConsistentIndentationRule {
+    type State = Diagnostics;
+
+    fn document(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        _: &Document,
+        _: SupportedVersion,
+    ) {
+        if reason == VisitReason::Enter {
+            // Reset the visitor upon document entry
+            *self = Self::new(self.style, self.width);
+            return;
+        }
+
+        let indentation = std::mem::take(&mut self.indentation);
+        let (configured, style) = match self.style {
+            Some(style) => (true, style),
+            None => {
+                let tabs = indentation
+                    .iter()
+                    .filter(|i| i.text.bytes().all(|b| b == b'\t'))
+                    .count();
+                let spaces = indentation
+                    .iter()
+                    .filter(|i| i.text.bytes().all(|b| b == b' '))
+                    .count();
+
+                // Spaces are preferred when neither style is used by most lines
+                (
+                    false,
+                    if tabs > spaces {
+                        IndentStyle::Tabs
+                    } else {
+                        IndentStyle::Spaces
+                    },
+                )
+            }
+        };
+
+        for indentation in indentation {
+            let has_tab = indentation.text.contains('\t');
+            let has_space = indentation.text.contains(' ');
+            let replacement = self.convert(&indentation.text, style);
+            let diagnostic = match (has_tab, has_space, style) {
+                (true, true, _) => mixed_indentation(style, indentation.span, replacement),
+                (true, false, IndentStyle::Spaces) | (false, true, IndentStyle::Tabs) => {
+                    inconsistent_indentation(style, configured, indentation.span, replacement)
+                }
+                _ => continue,
+            };
+
+            state.exceptable_add(diagnostic, indentation.element, &self.exceptable_nodes());
+        }
+    }
+
+    fn whitespace(&mut self, _: &mut Self::State, whitespace: &Whitespace) {
+        // Whitespace within a command section (e.g. within a placeholder) is not
+        // indentation of the document
+        if whitespace
+            .syntax()
+            .parent_ancestors()
+            .any(|n| n.kind() == SyntaxKind::CommandSectionNode)
+        {
+            return;
+        }
+
+        // Only the last line of the whitespace precedes a token on the same line; any
+        // other line that follows a newline is blank and is checked by the
+        // `Whitespace` rule
+        let text = whitespace.as_str();
+        if text.ends_with(['\r', '\n']) {
+            return;
+        }
+
+        let Some((line, start, _)) = lines_with_offset(text).skip(1).last() else {
+            return;
+        };
+
+        if line.is_empty() {
+            return;
+        }
+
+        // Whitespace at the end of the document does not indent a line
+        if whitespace.syntax().next_sibling_or_token().is_none()
+            && whitespace
+                .syntax()
+                .parent()
+                .is_some_and(|p| p.kind() == SyntaxKind::RootNode)
+        {
+            return;
+        }
+
+        self.indentation.push(Indentation {
+            span: Span::new(whitespace.span().start() + start, line.len()),
+            text: line.to_string(),
+            element: SyntaxElement::from(whitespace.syntax().clone()),
+        });
+    }
+}
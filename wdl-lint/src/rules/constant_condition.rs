@@ -0,0 +1,248 @@
+//! A lint rule for conditional statements with constant conditions and
+//! scatter statements over empty arrays.
+
+use std::cmp::Ordering;
+
+use wdl_ast::AstNode;
+use wdl_ast::AstNodeExt;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+use wdl_ast::v1::ConditionalStatement;
+use wdl_ast::v1::Expr;
+use wdl_ast::v1::LiteralExpr;
+use wdl_ast::v1::ScatterStatement;
+
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+
+/// The identifier for the constant condition rule.
+const ID: &str = "ConstantCondition";
+
+/// Creates a "constant condition" diagnostic.
+fn constant_condition(value: bool, span: Span) -> Diagnostic {
+    let diagnostic = Diagnostic::warning(format!(
+        "condition of the conditional statement is always `{value}`"
+    ))
+    .with_rule(ID);
+
+    if value {
+        diagnostic
+            .with_label("the body of the conditional always runs", span)
+            .with_fix(
+                "remove the conditional; note that the outputs of its body will no longer be \
+                 optional",
+            )
+    } else {
+        diagnostic
+            .with_label("the body of the conditional never runs", span)
+            .with_fix("remove the conditional and its body")
+    }
+}
+
+/// Creates an "empty scatter" diagnostic.
+fn empty_scatter(span: Span) -> Diagnostic {
+    Diagnostic::warning("scatter statement iterates over an empty array")
+        .with_rule(ID)
+        .with_label("the body of the scatter never runs", span)
+        .with_fix("remove the scatter and its body")
+}
+
+/// Represents a constant value of an expression.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Constant {
+    /// The value is a boolean.
+    Boolean(bool),
+    /// The value is an integer.
+    Integer(i64),
+    /// The value is a float.
+    Float(f64),
+}
+
+impl Constant {
+    /// Gets the value as a boolean.
+    ///
+    /// Returns `None` if the value is not a boolean.
+    fn as_bool(self) -> Option<bool> {
+        match self {
+            Self::Boolean(value) => Some(value),
+            Self::Integer(_) | Self::Float(_) => None,
+        }
+    }
+
+    /// Compares the value with another numeric value.
+    ///
+    /// Integers are compared as integers; an integer is only converted to a
+    /// float when compared with a float.
+    ///
+    /// Returns `None` if either value is not a number.
+    fn compare(self, other: Self) -> Option<Ordering> {
+        match (self, other) {
+            (Self::Integer(lhs), Self::Integer(rhs)) => Some(lhs.cmp(&rhs)),
+            (Self::Integer(lhs), Self::Float(rhs)) => (lhs as f64).partial_cmp(&rhs),
+            (Self::Float(lhs), Self::Integer(rhs)) => lhs.partial_cmp(&(rhs as f64)),
+            (Self::Float(lhs), Self::Float(rhs)) => lhs.partial_cmp(&rhs),
+            _ => None,
+        }
+    }
+}
+
+/// Folds an expression of literals into a constant value.
+///
+/// Returns `None` if the expression is not constant, such as when it contains
+/// a name reference, or if it contains an operation other than a logical or
+/// comparison operation.
+fn fold(expr: &Expr) -> Option<Constant> {
+    /// Folds the operands of a comparison of numbers.
+    fn compare(operands: (Expr, Expr), op: impl FnOnce(Ordering) -> bool) -> Option<Constant> {
+        let lhs = fold(&operands.0)?;
+        let rhs = fold(&operands.1)?;
+        Some(Constant::Boolean(op(lhs.compare(rhs)?)))
+    }
+
+    /// Folds the operands of an equality comparison.
+    fn equals(operands: (Expr, Expr)) -> Option<bool> {
+        match (fold(&operands.0)?, fold(&operands.1)?) {
+            (Constant::Boolean(lhs), Constant::Boolean(rhs)) => Some(lhs == rhs),
+            (lhs, rhs) => Some(lhs.compare(rhs)? == Ordering::Equal),
+        }
+    }
+
+    match expr {
+        Expr::Literal(LiteralExpr::Boolean(b)) => Some(Constant::Boolean(b.value())),
+        Expr::Literal(LiteralExpr::Integer(i)) => Some(Constant::Integer(i.value()?)),
+        Expr::Literal(LiteralExpr::Float(f)) => Some(Constant::Float(f.value()?)),
+        Expr::Parenthesized(expr) => fold(&expr.inner()),
+        Expr::Negation(expr) => match fold(&expr.operand())? {
+            Constant::Boolean(_) => None,
+            Constant::Integer(value) => Some(Constant::Integer(value.checked_neg()?)),
+            Constant::Float(value) => Some(Constant::Float(-value)),
+        },
+        Expr::LogicalNot(expr) => Some(Constant::Boolean(!fold(&expr.operand())?.as_bool()?)),
+        Expr::LogicalAnd(expr) => {
+            let (lhs, rhs) = expr.operands();
+            let lhs = fold(&lhs)?.as_bool()?;
+            let rhs = fold(&rhs)?.as_bool()?;
+            Some(Constant::Boolean(lhs && rhs))
+        }
+        Expr::LogicalOr(expr) => {
+            let (lhs, rhs) = expr.operands();
+            let lhs = fold(&lhs)?.as_bool()?;
+            let rhs = fold(&rhs)?.as_bool()?;
+            Some(Constant::Boolean(lhs || rhs))
+        }
+        Expr::Equality(expr) => equals(expr.operands()).map(Constant::Boolean),
+        Expr::Inequality(expr) => equals(expr.operands()).map(|eq| Constant::Boolean(!eq)),
+        Expr::Less(expr) => compare(expr.operands(), Ordering::is_lt),
+        Expr::LessEqual(expr) => compare(expr.operands(), Ordering::is_le),
+        Expr::Greater(expr) => compare(expr.operands(), Ordering::is_gt),
+        Expr::GreaterEqual(expr) => compare(expr.operands(), Ordering::is_ge),
+        _ => None,
+    }
+}
+
+/// Determines if an expression is an empty array literal.
+fn is_empty_array(expr: &Expr) -> bool {
+    match expr {
+        Expr::Literal(LiteralExpr::Array(array)) => array.elements().next().is_none(),
+        Expr::Parenthesized(expr) => is_empty_array(&expr.inner()),
+        _ => false,
+    }
+}
+
+/// Detects conditional statements with constant conditions and scatter
+/// statements over empty arrays.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct ConstantConditionRule;
+
+impl Rule for ConstantConditionRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags conditional statements with constant conditions and scatter statements over empty \
+         arrays."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A conditional statement whose condition is always `true` or always `false` (e.g. `if \
+         (true)` or `if (2 > 1)`), often left over from debugging, either always or never runs \
+         its body; in the former case, the outputs of the body are needlessly optional. \
+         Likewise, a scatter statement over an empty array never runs its body. Conditions made \
+         up of boolean and number literals, comparisons, `!`, `&&`, `||`, and parentheses are \
+         evaluated; conditions that reference any name are not."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Clarity, Tag::Correctness])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("constantcondition"))
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+        Some(&[
+            SyntaxKind::VersionStatementNode,
+            SyntaxKind::WorkflowDefinitionNode,
+            SyntaxKind::ConditionalStatementNode,
+            SyntaxKind::ScatterStatementNode,
+        ])
+    }
+}
+
+impl Visitor for ConstantConditionRule {
+    type State = Diagnostics;
+
+    fn document(&mut self, _: &mut Self::State, _: VisitReason, _: &Document, _: SupportedVersion) {
+        // This is intentionally empty, as this rule has no state.
+    }
+
+    fn conditional_statement(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        stmt: &ConditionalStatement,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        let expr = stmt.expr();
+        if let Some(Constant::Boolean(value)) = fold(&expr) {
+            state.exceptable_add(
+                constant_condition(value, expr.span()),
+                SyntaxElement::from(stmt.syntax().clone()),
+                &self.exceptable_nodes(),
+            );
+        }
+    }
+
+    fn scatter_statement(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        stmt: &ScatterStatement,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        let expr = stmt.expr();
+        if is_empty_array(&expr) {
+            state.exceptable_add(
+                empty_scatter(expr.span()),
+                SyntaxElement::from(stmt.syntax().clone()),
+                &self.exceptable_nodes(),
+            );
+        }
+    }
+}
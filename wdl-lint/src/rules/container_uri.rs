@@ -0,0 +1,396 @@
+//! A lint rule for the syntax of container URIs.
+
+use wdl_ast::AstNode;
+use wdl_ast::AstNodeExt;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+use wdl_ast::v1::Expr;
+use wdl_ast::v1::LiteralExpr;
+use wdl_ast::v1::LiteralString;
+use wdl_ast::v1::RequirementsItem;
+use wdl_ast::v1::RequirementsSection;
+use wdl_ast::v1::RuntimeItem;
+use wdl_ast::v1::RuntimeSection;
+use wdl_ast::v1::StringPart;
+use wdl_ast::v1::common::container::value::uri::ANY_CONTAINER_VALUE;
+
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+
+/// The identifier for the container URI rule.
+const ID: &str = "ContainerUri";
+
+/// The separator between a protocol and the rest of a container URI.
+const PROTOCOL_SEPARATOR: &str = "://";
+
+/// The tag that always refers to the most recently pushed image.
+const LATEST_TAG: &str = "latest";
+
+/// The maximum length of a tag.
+const MAX_TAG_LEN: usize = 128;
+
+/// Creates a "malformed container URI" diagnostic.
+fn malformed_uri(uri: &str, reason: &str, span: Span) -> Diagnostic {
+    Diagnostic::error(format!(
+        "container URI `{uri}` is not a valid image reference"
+    ))
+    .with_rule(ID)
+    .with_label(reason.to_string(), span)
+    .with_fix("use a reference of the form `registry/repository[:tag][@digest]`")
+}
+
+/// Creates a "latest tag" diagnostic.
+fn latest_tag(span: Span) -> Diagnostic {
+    Diagnostic::warning(format!(
+        "container URI uses the `{LATEST_TAG}` tag, which is not reproducible"
+    ))
+    .with_rule(ID)
+    .with_highlight(span)
+    .with_fix("pin the image to a digest (e.g., `ubuntu@sha256:...`)")
+}
+
+/// Creates a "missing tag" diagnostic.
+fn missing_tag(span: Span) -> Diagnostic {
+    Diagnostic::warning("container URI does not specify a tag, which is not reproducible")
+        .with_rule(ID)
+        .with_highlight(span)
+        .with_fix("pin the image to a digest (e.g., `ubuntu@sha256:...`)")
+}
+
+/// A parsed container image reference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Reference<'a> {
+    /// The tag of the reference.
+    tag: Option<&'a str>,
+    /// The digest of the reference.
+    digest: Option<&'a str>,
+}
+
+/// Determines if the given path component of a repository is valid.
+///
+/// Components are lowercase alphanumeric with `.`, `_`, or `-` separators.
+fn is_valid_component(component: &str) -> bool {
+    let bytes = component.as_bytes();
+    !bytes.is_empty()
+        && bytes[0].is_ascii_alphanumeric()
+        && bytes[bytes.len() - 1].is_ascii_alphanumeric()
+        && bytes
+            .iter()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b"._-".contains(b))
+}
+
+/// Determines if the given registry host (with an optional port) is valid.
+fn is_valid_registry(registry: &str) -> bool {
+    let (host, port) = match registry.split_once(':') {
+        Some((host, port)) => (host, Some(port)),
+        None => (registry, None),
+    };
+
+    !host.is_empty()
+        && host
+            .split('.')
+            .all(|l| !l.is_empty() && l.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-'))
+        && port.is_none_or(|p| !p.is_empty() && p.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Determines if the given tag is valid.
+fn is_valid_tag(tag: &str) -> bool {
+    tag.len() <= MAX_TAG_LEN
+        && tag.starts_with(|c: char| c.is_ascii_alphanumeric() || c == '_')
+        && tag
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"._-".contains(&b))
+}
+
+/// Determines if the given digest (e.g. `sha256:...`) is valid.
+fn is_valid_digest(digest: &str) -> bool {
+    let Some((algorithm, encoded)) = digest.split_once(':') else {
+        return false;
+    };
+
+    !algorithm.is_empty()
+        && algorithm
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b"+._-".contains(&b))
+        && !encoded.is_empty()
+        && encoded
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"=_-".contains(&b))
+}
+
+/// Parses a container URI of the form
+/// `[protocol://][registry/]repository[:tag][@digest]`.
+///
+/// Returns the reason the URI is malformed upon error.
+fn parse(uri: &str) -> Result<Reference<'_>, &'static str> {
+    if uri.is_empty() {
+        return Err("the container URI is empty");
+    }
+
+    if uri.contains(char::is_whitespace) {
+        return Err("container URIs cannot contain whitespace");
+    }
+
+    let uri = match uri.split_once(PROTOCOL_SEPARATOR) {
+        Some((protocol, rest)) => {
+            if protocol.is_empty() || !protocol.bytes().all(|b| b.is_ascii_alphanumeric()) {
+                return Err("the protocol of the container URI is malformed");
+            }
+
+            rest
+        }
+        None => uri,
+    };
+
+    let (name, digest) = match uri.split_once('@') {
+        Some((name, digest)) => {
+            if !is_valid_digest(digest) {
+                return Err("the digest of the container URI is malformed");
+            }
+
+            (name, Some(digest))
+        }
+        None => (uri, None),
+    };
+
+    // A tag separator only follows the last `/` (a `:` before it is a port)
+    let (repository, tag) = match name.rfind(':') {
+        Some(index) if !name[index..].contains('/') => (&name[..index], Some(&name[index + 1..])),
+        _ => (name, None),
+    };
+
+    if tag.is_some_and(|t| !is_valid_tag(t)) {
+        return Err("the tag of the container URI is malformed");
+    }
+
+    let mut components = repository.split('/').peekable();
+    let first = components.next().expect("should have a first component");
+
+    // The first component is a registry if it looks like a host name
+    let has_registry =
+        components.peek().is_some() && (first.contains(['.', ':']) || first == "localhost");
+    if has_registry {
+        if !is_valid_registry(first) {
+            return Err("the registry of the container URI is malformed");
+        }
+    } else if !is_valid_component(first) {
+        return Err("the repository of the container URI is malformed");
+    }
+
+    if !components.all(is_valid_component) {
+        return Err("the repository of the container URI is malformed");
+    }
+
+    Ok(Reference { tag, digest })
+}
+
+/// Validates the syntax of container URIs and discourages mutable tags.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct ContainerUriRule;
+
+impl Rule for ContainerUriRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Ensures that container URIs are valid image references that are pinned to a tag."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "The value of the `container` (or `docker`) key of a `runtime` or `requirements` section \
+         should be a valid image reference of the form `registry/repository[:tag][@digest]`; a \
+         malformed reference causes the task to fail when the execution engine pulls the image. \
+         References that use the `latest` tag or have no tag (and no digest) refer to whatever \
+         image was most recently pushed, so the task is not reproducible; pinning the image to a \
+         digest is recommended. Values built from placeholders cannot be checked and are \
+         ignored."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Correctness, Tag::Portability])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("containeruri"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["ContainerValue", "MissingContainer"]
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+        Some(&[
+            SyntaxKind::VersionStatementNode,
+            SyntaxKind::TaskDefinitionNode,
+            SyntaxKind::RuntimeSectionNode,
+            SyntaxKind::RequirementsSectionNode,
+        ])
+    }
+}
+
+impl Visitor for ContainerUriRule {
+    type State = Diagnostics;
+
+    fn document(&mut self, _: &mut Self::State, _: VisitReason, _: &Document, _: SupportedVersion) {
+        // This rule has no state to reset.
+    }
+
+    fn runtime_section(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        section: &RuntimeSection,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        if let Some(container) = section.container() {
+            let item = RuntimeItem::cast(container.syntax().clone())
+                .expect("container should be a runtime item");
+            check(
+                state,
+                &item.expr(),
+                SyntaxElement::from(section.syntax().clone()),
+                &self.exceptable_nodes(),
+            );
+        }
+    }
+
+    fn requirements_section(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        section: &RequirementsSection,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        if let Some(container) = section.container() {
+            let item = RequirementsItem::cast(container.syntax().clone())
+                .expect("container should be a requirements item");
+            check(
+                state,
+                &item.expr(),
+                SyntaxElement::from(section.syntax().clone()),
+                &self.exceptable_nodes(),
+            );
+        }
+    }
+}
+
+/// Checks the value of a `container` item.
+///
+/// The value may be a string literal or an array of string literals; any
+/// other expression is ignored.
+fn check(
+    state: &mut Diagnostics,
+    expr: &Expr,
+    syntax: SyntaxElement,
+    exceptable_nodes: &Option<&'static [SyntaxKind]>,
+) {
+    match expr {
+        Expr::Literal(LiteralExpr::String(s)) => check_uri(state, s, syntax, exceptable_nodes),
+        Expr::Literal(LiteralExpr::Array(array)) => {
+            for element in array.elements() {
+                if let Expr::Literal(LiteralExpr::String(s)) = element {
+                    check_uri(state, &s, syntax.clone(), exceptable_nodes);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Checks a single container URI.
+fn check_uri(
+    state: &mut Diagnostics,
+    s: &LiteralString,
+    syntax: SyntaxElement,
+    exceptable_nodes: &Option<&'static [SyntaxKind]>,
+) {
+    // Values built from placeholders cannot be checked
+    if s.parts().any(|p| matches!(p, StringPart::Placeholder(_))) {
+        return;
+    }
+
+    let text = s.text();
+    let uri = text.as_ref().map(|t| t.as_str()).unwrap_or_default();
+    if uri == ANY_CONTAINER_VALUE {
+        return;
+    }
+
+    let span = s.span();
+    let diagnostic = match parse(uri) {
+        Ok(Reference {
+            digest: Some(_), ..
+        }) => return,
+        Ok(Reference {
+            tag: Some(LATEST_TAG),
+            ..
+        }) => latest_tag(span),
+        Ok(Reference { tag: None, .. }) => missing_tag(span),
+        Ok(_) => return,
+        Err(reason) => malformed_uri(uri, reason, span),
+    };
+
+    state.exceptable_add(diagnostic, syntax, exceptable_nodes);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_parses_references() {
+        assert_eq!(
+            parse("ubuntu"),
+            Ok(Reference {
+                tag: None,
+                digest: None
+            })
+        );
+        assert_eq!(
+            parse("ubuntu:22.04"),
+            Ok(Reference {
+                tag: Some("22.04"),
+                digest: None
+            })
+        );
+        assert_eq!(
+            parse("localhost:5000/team/tool:1.0@sha256:abc123"),
+            Ok(Reference {
+                tag: Some("1.0"),
+                digest: Some("sha256:abc123")
+            })
+        );
+        assert_eq!(
+            parse("docker://quay.io/biocontainers/samtools"),
+            Ok(Reference {
+                tag: None,
+                digest: None
+            })
+        );
+    }
+
+    #[test]
+    fn it_rejects_malformed_references() {
+        assert!(parse("").is_err());
+        assert!(parse("ubuntu 22.04").is_err());
+        assert!(parse("Ubuntu:22.04").is_err());
+        assert!(parse("ubuntu:").is_err());
+        assert!(parse("ubuntu@sha256:").is_err());
+        assert!(parse("quay.io:port/tool").is_err());
+        assert!(parse("team//tool").is_err());
+    }
+}
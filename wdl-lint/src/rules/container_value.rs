@@ -133,6 +133,18 @@ impl Rule for ContainerValue {
         TagSet::new(&[Tag::Clarity, Tag::Portability])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("containervalue"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &[
+            "MissingRuntime",
+            "MissingRequirements",
+            "RuntimeSectionKeys",
+        ]
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [wdl_ast::SyntaxKind]> {
         Some(&[
             SyntaxKind::VersionStatementNode,
@@ -1,36 +1,97 @@
 //! A lint rule for flagging `Object`s as deprecated.
 
 use wdl_ast::AstNode;
-use wdl_ast::AstNodeExt;
+use wdl_ast::AstToken;
 use wdl_ast::Diagnostic;
 use wdl_ast::Diagnostics;
 use wdl_ast::Document;
+use wdl_ast::Severity;
 use wdl_ast::Span;
 use wdl_ast::SupportedVersion;
 use wdl_ast::SyntaxElement;
 use wdl_ast::SyntaxKind;
 use wdl_ast::VisitReason;
 use wdl_ast::Visitor;
+use wdl_ast::v1::Expr;
 use wdl_ast::v1::Type;
+use wdl_ast::version::V1;
 
 use crate::Rule;
 use crate::Tag;
 use crate::TagSet;
+use crate::rules::struct_member_type::object_spans;
 
 /// The identifier for the deprecated object rule.
 const ID: &str = "DeprecatedObject";
 
+/// The standard library functions that read or write `Object` values.
+const OBJECT_FUNCTIONS: &[&str] = &[
+    "read_object",
+    "read_objects",
+    "write_object",
+    "write_objects",
+];
+
 /// Creates a deprecated object use diagnostic.
-fn deprecated_object_use(span: Span) -> Diagnostic {
+fn deprecated_object_use(severity: Severity, span: Span) -> Diagnostic {
     Diagnostic::note(String::from("use of a deprecated `Object` type"))
+        .with_severity(severity)
+        .with_rule(ID)
+        .with_highlight(span)
+        .with_fix(
+            "replace the `Object` with a struct; use `read_json` to read a file into a declared \
+             struct",
+        )
+}
+
+/// Creates a deprecated object function diagnostic.
+fn deprecated_object_function(severity: Severity, function: &str, span: Span) -> Diagnostic {
+    let fix = if function.starts_with("read_") {
+        "use `read_json` to read the file into a declared struct"
+    } else {
+        "use `write_json` to write a value of a declared struct"
+    };
+
+    Diagnostic::note(format!("use of the deprecated `{function}` function"))
+        .with_severity(severity)
         .with_rule(ID)
         .with_highlight(span)
-        .with_fix("replace the `Object` with a `Map` or a `Struct`")
+        .with_fix(fix)
 }
 
 /// Detects the use of the deprecated `Object` types.
 #[derive(Default, Debug, Clone, Copy)]
-pub struct DeprecatedObjectRule;
+pub struct DeprecatedObjectRule {
+    /// The version of the document being linted.
+    version: Option<SupportedVersion>,
+}
+
+impl DeprecatedObjectRule {
+    /// Gets the severity of the rule's diagnostics.
+    ///
+    /// Uses of `Object` are reported as warnings for WDL v1.2 and beyond, as
+    /// the type is removed in the next major WDL release.
+    fn severity(&self) -> Severity {
+        if self.version.expect("should have version") >= SupportedVersion::V1(V1::Two) {
+            Severity::Warning
+        } else {
+            Severity::Note
+        }
+    }
+
+    /// Checks a declared type for `Object` types.
+    fn check_type(&self, state: &mut Diagnostics, ty: &Type, syntax: &SyntaxElement) {
+        let mut spans = Vec::new();
+        object_spans(ty, &mut spans);
+        for span in spans {
+            state.exceptable_add(
+                deprecated_object_use(self.severity(), span),
+                syntax.clone(),
+                &self.exceptable_nodes(),
+            );
+        }
+    }
+}
 
 impl Rule for DeprecatedObjectRule {
     fn id(&self) -> &'static str {
@@ -49,6 +110,14 @@ impl Rule for DeprecatedObjectRule {
          the time of the v1.0 release, the type was not removed. It was later decided \
          that `Object`s overlapped with `Map`s and `Struct`s in functionality, and the type was marked for removal.
 
+         Uses of `Object` types, including those nested within other types (e.g. \
+         `Array[Object]`), and calls to the `read_object`, `read_objects`, `write_object`, and \
+         `write_objects` functions are reported. Values read from files should instead be read \
+         with `read_json` into a declared struct. Uses are reported as notes in WDL v1.0 and \
+         v1.1 documents and as warnings in WDL v1.2 documents.
+
+         `Object` members of structs are reported by the `StructMemberType` rule.
+
          See this issue for more details: https://github.com/openwdl/wdl/pull/228."
     }
 
@@ -56,6 +125,14 @@ impl Rule for DeprecatedObjectRule {
         TagSet::new(&[Tag::Deprecated])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("deprecatedobject"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["DeprecatedPlaceholderOption", "StructMemberType"]
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [wdl_ast::SyntaxKind]> {
         Some(&[
             SyntaxKind::VersionStatementNode,
@@ -75,14 +152,16 @@ impl Visitor for DeprecatedObjectRule {
         _: &mut Self::State,
         reason: VisitReason,
         _: &Document,
-        _: SupportedVersion,
+        version: SupportedVersion,
     ) {
         if reason == VisitReason::Exit {
             return;
         }
 
         // Reset the visitor upon document entry
-        *self = Default::default();
+        *self = Self {
+            version: Some(version),
+        };
     }
 
     fn bound_decl(
@@ -95,13 +174,11 @@ impl Visitor for DeprecatedObjectRule {
             return;
         }
 
-        if let Type::Object(ty) = decl.ty() {
-            state.exceptable_add(
-                deprecated_object_use(ty.span()),
-                SyntaxElement::from(decl.syntax().clone()),
-                &self.exceptable_nodes(),
-            )
-        }
+        self.check_type(
+            state,
+            &decl.ty(),
+            &SyntaxElement::from(decl.syntax().clone()),
+        );
     }
 
     fn unbound_decl(
@@ -114,12 +191,40 @@ impl Visitor for DeprecatedObjectRule {
             return;
         }
 
-        if let Type::Object(ty) = decl.ty() {
-            state.exceptable_add(
-                deprecated_object_use(ty.span()),
-                SyntaxElement::from(decl.syntax().clone()),
-                &self.exceptable_nodes(),
-            )
+        // Struct members are checked by the `StructMemberType` rule
+        if decl
+            .syntax()
+            .parent()
+            .is_some_and(|p| p.kind() == SyntaxKind::StructDefinitionNode)
+        {
+            return;
+        }
+
+        self.check_type(
+            state,
+            &decl.ty(),
+            &SyntaxElement::from(decl.syntax().clone()),
+        );
+    }
+
+    fn expr(&mut self, state: &mut Self::State, reason: VisitReason, expr: &Expr) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        let Expr::Call(call) = expr else {
+            return;
+        };
+
+        let target = call.target();
+        if !OBJECT_FUNCTIONS.contains(&target.as_str()) {
+            return;
         }
+
+        state.exceptable_add(
+            deprecated_object_function(self.severity(), target.as_str(), target.span()),
+            SyntaxElement::from(expr.syntax().clone()),
+            &self.exceptable_nodes(),
+        );
     }
 }
@@ -11,8 +11,10 @@ use wdl_ast::SyntaxElement;
 use wdl_ast::SyntaxKind;
 use wdl_ast::VisitReason;
 use wdl_ast::Visitor;
+use wdl_ast::v1::LiteralString;
 use wdl_ast::v1::Placeholder;
 use wdl_ast::v1::PlaceholderOption;
+use wdl_ast::v1::StringPart;
 use wdl_ast::version::V1;
 
 use crate::Rule;
@@ -24,39 +26,83 @@ const ID: &str = "DeprecatedPlaceholderOption";
 
 /// Creates a diagnostic for the use of the deprecated `default` placeholder
 /// option.
-fn deprecated_default_placeholder_option(span: Span) -> Diagnostic {
+fn deprecated_default_placeholder_option(span: Span, replacement: &str) -> Diagnostic {
     Diagnostic::note(String::from(
         "use of the deprecated `default` placeholder option",
     ))
     .with_rule(ID)
     .with_highlight(span)
-    .with_fix(
+    .with_fix(format!(
         "replace the `default` placeholder option with a call to the `select_first()` standard \
-         library function",
-    )
+         library function: `{replacement}`"
+    ))
 }
 
 /// Creates a diagnostic for the use of the deprecated `sep` placeholder option.
-fn deprecated_sep_placeholder_option(span: Span) -> Diagnostic {
+fn deprecated_sep_placeholder_option(span: Span, replacement: &str) -> Diagnostic {
     Diagnostic::note(String::from(
         "use of the deprecated `sep` placeholder option",
     ))
     .with_rule(ID)
     .with_highlight(span)
-    .with_fix(
-        "replace the `sep` placeholder option with a call to the `sep()` standard library function",
-    )
+    .with_fix(format!(
+        "replace the `sep` placeholder option with a call to the `sep()` standard library \
+         function: `{replacement}`"
+    ))
 }
 
 /// Creates a diagnostic for the use of the deprecated `true`/`false`
 /// placeholder option.
-fn deprecated_true_false_placeholder_option(span: Span) -> Diagnostic {
+fn deprecated_true_false_placeholder_option(span: Span, replacement: &str) -> Diagnostic {
     Diagnostic::note(String::from(
         "use of the deprecated `true`/`false` placeholder option",
     ))
     .with_rule(ID)
     .with_highlight(span)
-    .with_fix("replace the `true`/`false` placeholder option with an `if`/`else` expression")
+    .with_fix(format!(
+        "replace the `true`/`false` placeholder option with an `if`/`else` expression: \
+         `{replacement}`"
+    ))
+}
+
+/// Determines if the given string literal is interpolated.
+fn is_interpolated(s: &LiteralString) -> bool {
+    s.parts().any(|p| matches!(p, StringPart::Placeholder(_)))
+}
+
+/// Gets the expression that is equivalent to a placeholder's expression with
+/// the given option applied.
+///
+/// Returns the expression text and whether or not the option's values are all
+/// literal text; only then is the rewrite applied as a replacement.
+fn equivalent_expr(option: &PlaceholderOption, expr: &str) -> (String, bool) {
+    match option {
+        PlaceholderOption::Sep(option) => {
+            let separator = option.separator();
+            (
+                format!("sep({separator}, {expr})", separator = separator.syntax()),
+                !is_interpolated(&separator),
+            )
+        }
+        PlaceholderOption::Default(option) => {
+            let value = option.value();
+            (
+                format!("select_first([{expr}, {value}])", value = value.syntax()),
+                !is_interpolated(&value),
+            )
+        }
+        PlaceholderOption::TrueFalse(option) => {
+            let (t, f) = option.values();
+            (
+                format!(
+                    "if {expr} then {t} else {f}",
+                    t = t.syntax(),
+                    f = f.syntax()
+                ),
+                !is_interpolated(&t) && !is_interpolated(&f),
+            )
+        }
+    }
 }
 
 /// Detects the use of a deprecated placeholder option.
@@ -100,6 +146,14 @@ impl Rule for DeprecatedPlaceholderOptionRule {
     fn tags(&self) -> TagSet {
         TagSet::new(&[Tag::Deprecated])
     }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("deprecatedplaceholderoption"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["DeprecatedObject"]
+    }
 }
 
 impl Visitor for DeprecatedPlaceholderOptionRule {
@@ -143,15 +197,32 @@ impl Visitor for DeprecatedPlaceholderOptionRule {
         };
 
         if let Some(option) = placeholder.option() {
-            let diagnostic = match option {
-                PlaceholderOption::Sep(option) => deprecated_sep_placeholder_option(option.span()),
+            let expr = placeholder.expr();
+            let (equivalent, mechanical) = equivalent_expr(&option, &expr.syntax().to_string());
+            let replacement = format!(
+                "{sigil}{{{equivalent}}}",
+                sigil = if placeholder.has_tilde() { '~' } else { '$' }
+            );
+
+            let mut diagnostic = match &option {
+                PlaceholderOption::Sep(option) => {
+                    deprecated_sep_placeholder_option(option.span(), &replacement)
+                }
                 PlaceholderOption::Default(option) => {
-                    deprecated_default_placeholder_option(option.span())
+                    deprecated_default_placeholder_option(option.span(), &replacement)
                 }
                 PlaceholderOption::TrueFalse(option) => {
-                    deprecated_true_false_placeholder_option(option.span())
+                    deprecated_true_false_placeholder_option(option.span(), &replacement)
                 }
             };
+
+            // The option and the expression are replaced with the equivalent expression
+            if mechanical {
+                let start = option.span().start();
+                diagnostic = diagnostic
+                    .with_replacement(Span::new(start, expr.span().end() - start), equivalent);
+            }
+
             state.exceptable_add(
                 diagnostic,
                 SyntaxElement::from(placeholder.syntax().clone()),
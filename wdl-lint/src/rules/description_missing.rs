@@ -69,6 +69,14 @@ impl Rule for DescriptionMissingRule {
         TagSet::new(&[Tag::Completeness])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("descriptionmissing"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["MissingMetas"]
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [wdl_ast::SyntaxKind]> {
         Some(&[
             SyntaxKind::VersionStatementNode,
@@ -75,6 +75,14 @@ impl Rule for DisallowedInputNameRule {
         TagSet::new(&[Tag::Naming])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("disallowedinputname"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["DisallowedOutputName", "SnakeCase"]
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [wdl_ast::SyntaxKind]> {
         Some(&[
             SyntaxKind::VersionStatementNode,
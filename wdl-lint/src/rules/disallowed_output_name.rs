@@ -75,6 +75,14 @@ impl Rule for DisallowedOutputNameRule {
         TagSet::new(&[Tag::Naming])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("disallowedoutputname"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["DisallowedInputName", "SnakeCase"]
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [wdl_ast::SyntaxKind]> {
         Some(&[
             SyntaxKind::VersionStatementNode,
@@ -0,0 +1,300 @@
+//! A lint rule for the value of `disks` keys in `runtime`/`requirements`
+//! sections.
+
+use wdl_ast::AstNode;
+use wdl_ast::AstNodeExt;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+use wdl_ast::v1::Expr;
+use wdl_ast::v1::LiteralExpr;
+use wdl_ast::v1::LiteralString;
+use wdl_ast::v1::RequirementsSection;
+use wdl_ast::v1::RuntimeSection;
+use wdl_ast::v1::StringPart;
+use wdl_ast::v1::TASK_REQUIREMENT_DISKS;
+use wdl_ast::v1::common::units::StorageUnit;
+
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+
+/// The identifier for the disks value rule.
+const ID: &str = "DisksValue";
+
+/// The mount point of the working directory in Cromwell-style disk
+/// specifications.
+const LOCAL_DISK: &str = "local-disk";
+
+/// The disk types of Cromwell-style disk specifications.
+const DISK_TYPES: &[&str] = &["HDD", "SSD", "LOCAL"];
+
+/// Creates an "interpreted disks" diagnostic.
+fn interpreted_disks(interpretation: &str, span: Span) -> Diagnostic {
+    Diagnostic::note(format!("disks value is interpreted as {interpretation}"))
+        .with_rule(ID)
+        .with_highlight(span)
+}
+
+/// Creates a "malformed disks" diagnostic.
+fn malformed_disks(value: &str, span: Span) -> Diagnostic {
+    Diagnostic::warning(format!("malformed disks value `{value}`"))
+        .with_rule(ID)
+        .with_highlight(span)
+        .with_fix(
+            "specify each disk as `SIZE [UNIT]`, `MOUNT SIZE [UNIT]`, or `MOUNT SIZE TYPE` (e.g. \
+             `\"local-disk 50 SSD\"`), separating multiple disks with commas",
+        )
+}
+
+/// Interprets a disk size with an optional unit.
+///
+/// The size may be immediately followed by the unit (e.g. `50GB`).
+fn size(size: &str, unit: Option<&str>) -> Option<(u64, StorageUnit)> {
+    let (size, unit) = match (size.find(|c: char| c.is_ascii_alphabetic()), unit) {
+        (Some(index), None) => (&size[..index], Some(&size[index..])),
+        (Some(_), Some(_)) => return None,
+        (None, unit) => (size, unit),
+    };
+
+    Some((
+        size.parse().ok()?,
+        match unit {
+            Some(unit) => unit.parse().ok()?,
+            None => StorageUnit::Gibibytes,
+        },
+    ))
+}
+
+/// Determines if the given text is a mount point.
+fn is_mount_point(text: &str) -> bool {
+    text == LOCAL_DISK || text.starts_with('/')
+}
+
+/// Interprets a single disk specification.
+///
+/// The accepted forms are `SIZE [UNIT]` and `MOUNT SIZE [UNIT]` from the WDL
+/// specification and `MOUNT SIZE TYPE` from Cromwell, where the size of the
+/// latter is in gigabytes.
+///
+/// Returns `None` if the specification matches none of the forms.
+fn interpret_disk(spec: &str) -> Option<String> {
+    let tokens: Vec<_> = spec.split_whitespace().collect();
+    let (mount, (n, unit), ty) = match tokens.as_slice() {
+        [s] => (None, size(s, None)?, None),
+        [m, s] if is_mount_point(m) => (Some(*m), size(s, None)?, None),
+        [s, u] => (None, size(s, Some(*u))?, None),
+        [m, s, t] if is_mount_point(m) && DISK_TYPES.contains(t) => {
+            (Some(*m), size(s, Some("GB"))?, Some(*t))
+        }
+        [m, s, u] if is_mount_point(m) => (Some(*m), size(s, Some(*u))?, None),
+        _ => return None,
+    };
+
+    let mut interpretation = format!("{n} {unit} of ");
+    if let Some(ty) = ty {
+        interpretation.push_str(ty);
+        interpretation.push(' ');
+    }
+
+    interpretation.push_str("disk");
+    if let Some(mount) = mount {
+        interpretation.push_str(&format!(" mounted at `{mount}`"));
+    }
+
+    Some(interpretation)
+}
+
+/// Interprets a disks value of one or more comma-separated disk
+/// specifications.
+///
+/// Returns `None` if any of the specifications is malformed.
+fn interpret(value: &str) -> Option<String> {
+    let disks = value
+        .split(',')
+        .map(interpret_disk)
+        .collect::<Option<Vec<_>>>()?;
+    Some(disks.join(", "))
+}
+
+/// Checks a string literal value of a `disks` item.
+fn check_string(
+    state: &mut Diagnostics,
+    string: &LiteralString,
+    syntax: SyntaxElement,
+    exceptable_nodes: &Option<&'static [SyntaxKind]>,
+) {
+    // Values with placeholders cannot be checked until evaluation
+    if string
+        .parts()
+        .any(|p| matches!(p, StringPart::Placeholder(_)))
+    {
+        return;
+    }
+
+    let text = string.text();
+    let value = text.as_ref().map(|t| t.as_str()).unwrap_or_default();
+    let diagnostic = match interpret(value) {
+        Some(interpretation) => interpreted_disks(&interpretation, string.span()),
+        None => malformed_disks(value, string.span()),
+    };
+
+    state.exceptable_add(diagnostic, syntax, exceptable_nodes);
+}
+
+/// Checks the value of a `disks` item.
+fn check_disks(
+    state: &mut Diagnostics,
+    expr: &Expr,
+    syntax: SyntaxElement,
+    exceptable_nodes: &Option<&'static [SyntaxKind]>,
+) {
+    match expr {
+        Expr::Literal(LiteralExpr::String(s)) => check_string(state, s, syntax, exceptable_nodes),
+        Expr::Literal(LiteralExpr::Array(a)) => {
+            for element in a.elements() {
+                if let Expr::Literal(LiteralExpr::String(s)) = element {
+                    check_string(state, &s, syntax.clone(), exceptable_nodes);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Ensures that values for `disks` keys within `runtime`/`requirements`
+/// sections are well-formed.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct DisksValueRule;
+
+impl Rule for DisksValueRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Ensures that values for the `disks` key within `runtime`/`requirements` sections are \
+         well-formed."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "The format of a `disks` value is backend-specific, and a typo is usually only noticed \
+         when the task fails at runtime. Each comma-separated disk specification must match one \
+         of the common forms: `SIZE [UNIT]` or `MOUNT SIZE [UNIT]` from the WDL specification, \
+         where the size defaults to GiB, or Cromwell's `MOUNT SIZE TYPE` (e.g. `local-disk 50 \
+         SSD`), where the size is in GB and the type is one of HDD, SSD, or LOCAL. A mount \
+         point is either `local-disk` or an absolute path. Values matching none of the forms are \
+         reported as warnings; values that do match are reported as notes describing how they \
+         are interpreted, so that authors can confirm the intent. Values containing \
+         placeholders are not checked, as they are only known at evaluation time."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Correctness, Tag::Portability])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("disksvalue"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["MemoryValue", "RuntimeSectionKeys", "StaticDiskSize"]
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+        Some(&[
+            SyntaxKind::VersionStatementNode,
+            SyntaxKind::TaskDefinitionNode,
+            SyntaxKind::RequirementsSectionNode,
+            SyntaxKind::RequirementsItemNode,
+            SyntaxKind::RuntimeSectionNode,
+            SyntaxKind::RuntimeItemNode,
+        ])
+    }
+}
+
+impl Visitor for DisksValueRule {
+    type State = Diagnostics;
+
+    fn document(&mut self, _: &mut Self::State, _: VisitReason, _: &Document, _: SupportedVersion) {
+        // This rule has no state to reset.
+    }
+
+    fn runtime_section(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        section: &RuntimeSection,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        for item in section.items() {
+            if item.name().as_str() == TASK_REQUIREMENT_DISKS {
+                check_disks(
+                    state,
+                    &item.expr(),
+                    SyntaxElement::from(item.syntax().clone()),
+                    &self.exceptable_nodes(),
+                );
+            }
+        }
+    }
+
+    fn requirements_section(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        section: &RequirementsSection,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        for item in section.items() {
+            if item.name().as_str() == TASK_REQUIREMENT_DISKS {
+                check_disks(
+                    state,
+                    &item.expr(),
+                    SyntaxElement::from(item.syntax().clone()),
+                    &self.exceptable_nodes(),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn it_interprets_disks() {
+        assert_eq!(interpret("50").as_deref(), Some("50 GiB of disk"));
+        assert_eq!(interpret("50 GB").as_deref(), Some("50 GB of disk"));
+        assert_eq!(interpret("50GB").as_deref(), Some("50 GB of disk"));
+        assert_eq!(
+            interpret("/mnt/data 100 TiB").as_deref(),
+            Some("100 TiB of disk mounted at `/mnt/data`")
+        );
+        assert_eq!(
+            interpret("local-disk 50 SSD, /data 100 HDD").as_deref(),
+            Some(
+                "50 GB of SSD disk mounted at `local-disk`, 100 GB of HDD disk mounted at `/data`"
+            )
+        );
+        assert_eq!(interpret("local-disk fifty SSD"), None);
+        assert_eq!(interpret("50 GB,"), None);
+        assert_eq!(interpret("data 50"), None);
+    }
+}
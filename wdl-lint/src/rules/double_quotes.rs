@@ -1,6 +1,8 @@
 //! A lint rule for using double quoted strings.
 
+use serde::Deserialize;
 use wdl_ast::AstNodeExt;
+use wdl_ast::AstToken;
 use wdl_ast::Diagnostic;
 use wdl_ast::Diagnostics;
 use wdl_ast::Document;
@@ -12,26 +14,85 @@ use wdl_ast::VisitReason;
 use wdl_ast::Visitor;
 use wdl_ast::v1::Expr;
 use wdl_ast::v1::LiteralExpr;
+use wdl_ast::v1::LiteralString;
 use wdl_ast::v1::LiteralStringKind;
+use wdl_ast::v1::StringPart;
 
 use crate::Rule;
 use crate::Tag;
 use crate::TagSet;
+use crate::config::rule_options;
 
 /// The identifier for the double quotes rule.
 const ID: &str = "DoubleQuotes";
 
 /// Creates a "use double quotes" diagnostic.
+///
+/// The replacement swaps the delimiters of the string.
 fn use_double_quotes(span: Span) -> Diagnostic {
     Diagnostic::note("string defined with single quotes")
         .with_rule(ID)
         .with_highlight(span)
         .with_fix("change the string to use double quotes")
+        .with_replacement(Span::new(span.start(), 1), "\"")
+        .with_replacement(Span::new(span.end() - 1, 1), "\"")
+}
+
+/// Creates a "use double quotes" diagnostic for a string that contains a
+/// double quote.
+fn use_double_quotes_escaped(span: Span) -> Diagnostic {
+    Diagnostic::note("string defined with single quotes")
+        .with_rule(ID)
+        .with_highlight(span)
+        .with_fix(
+            "change the string to use double quotes and make sure the double quotes it contains \
+             are escaped",
+        )
+}
+
+/// Determines if the literal text of a string contains a double quote.
+///
+/// Placeholders are not considered as any strings within them are separate
+/// literals. Escaped double quotes are also counted so that a string is only
+/// rewritten when doing so cannot change its escaping.
+fn contains_double_quote(s: &LiteralString) -> bool {
+    s.parts().any(|part| match part {
+        StringPart::Text(text) => text.as_str().contains('"'),
+        StringPart::Placeholder(_) => false,
+    })
 }
 
 /// Detects strings that are not defined with double quotes.
 #[derive(Default, Debug, Clone, Copy)]
-pub struct DoubleQuotesRule;
+pub struct DoubleQuotesRule {
+    /// Whether or not strings that contain a double quote are skipped.
+    skip_escaped: bool,
+}
+
+impl DoubleQuotesRule {
+    /// Creates a new double quotes rule.
+    ///
+    /// Single quoted strings that contain a double quote cannot have their
+    /// delimiters swapped without changing their escaping; if `skip_escaped`
+    /// is `true`, such strings are not reported, otherwise they are reported
+    /// without a replacement.
+    pub fn new(skip_escaped: bool) -> Self {
+        Self { skip_escaped }
+    }
+
+    /// Gets whether or not strings that contain a double quote are skipped.
+    pub fn skip_escaped(&self) -> bool {
+        self.skip_escaped
+    }
+}
+
+/// The options of the double quotes rule.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Options {
+    /// Whether or not strings that contain a double quote are skipped.
+    skip_escaped: Option<bool>,
+}
 
 impl Rule for DoubleQuotesRule {
     fn id(&self) -> &'static str {
@@ -45,13 +106,19 @@ impl Rule for DoubleQuotesRule {
     fn explanation(&self) -> &'static str {
         "All strings should be defined using double quotes. There is no semantic difference \
          between single and double quotes in WDL, but double quotes should be used exclusively to \
-         ensure consistency and avoid any confusion."
+         ensure consistency and avoid any confusion. Strings that contain a double quote must \
+         have it escaped when converted to double quotes, so they are not fixed automatically \
+         and may optionally be ignored."
     }
 
     fn tags(&self) -> TagSet {
         TagSet::new(&[Tag::Clarity, Tag::Style])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("doublequotes"))
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [wdl_ast::SyntaxKind]> {
         Some(&[
             SyntaxKind::VersionStatementNode,
@@ -64,6 +131,16 @@ impl Rule for DoubleQuotesRule {
             SyntaxKind::LiteralStringNode,
         ])
     }
+
+    fn configure(&mut self, options: &toml::Table) -> anyhow::Result<()> {
+        let options: Options = rule_options(options)?;
+
+        if let Some(skip_escaped) = options.skip_escaped {
+            self.skip_escaped = skip_escaped;
+        }
+
+        Ok(())
+    }
 }
 
 impl Visitor for DoubleQuotesRule {
@@ -81,7 +158,7 @@ impl Visitor for DoubleQuotesRule {
         }
 
         // Reset the visitor upon document entry
-        *self = Default::default();
+        *self = Self::new(self.skip_escaped);
     }
 
     fn expr(&mut self, state: &mut Self::State, reason: VisitReason, expr: &Expr) {
@@ -91,8 +168,16 @@ impl Visitor for DoubleQuotesRule {
 
         if let Expr::Literal(LiteralExpr::String(s)) = expr {
             if s.kind() == LiteralStringKind::SingleQuoted {
+                let diagnostic = if !contains_double_quote(s) {
+                    use_double_quotes(s.span())
+                } else if self.skip_escaped {
+                    return;
+                } else {
+                    use_double_quotes_escaped(s.span())
+                };
+
                 state.exceptable_add(
-                    use_double_quotes(s.span()),
+                    diagnostic,
                     SyntaxElement::from(expr.syntax().clone()),
                     &self.exceptable_nodes(),
                 );
@@ -100,3 +185,48 @@ impl Visitor for DoubleQuotesRule {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use wdl_ast::Validator;
+
+    use super::*;
+    use crate::util::apply_replacements;
+
+    #[test]
+    fn it_swaps_delimiters() {
+        let source = r#"version 1.1
+
+workflow test {
+    String plain = 'plain'
+    String placeholder = 'hello ~{'world'}!'
+    String quoted = 'say "hi"'
+}
+"#;
+
+        let (document, diagnostics) = Document::parse(source);
+        assert!(diagnostics.is_empty());
+
+        let mut validator = Validator::empty();
+        validator.add_visitor(DoubleQuotesRule::default());
+        let diagnostics = validator.validate(&document).unwrap_err();
+        assert_eq!(diagnostics.len(), 4);
+        assert_eq!(
+            apply_replacements(source, &diagnostics),
+            r#"version 1.1
+
+workflow test {
+    String plain = "plain"
+    String placeholder = "hello ~{"world"}!"
+    String quoted = 'say "hi"'
+}
+"#
+        );
+
+        let mut validator = Validator::empty();
+        validator.add_visitor(DoubleQuotesRule::new(true));
+        let diagnostics = validator.validate(&document).unwrap_err();
+        assert_eq!(diagnostics.len(), 3);
+    }
+}
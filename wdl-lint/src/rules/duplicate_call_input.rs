@@ -0,0 +1,231 @@
+//! A lint rule for calls that pass the same expression to multiple inputs.
+
+use indexmap::IndexMap;
+use serde::Deserialize;
+use wdl_ast::AstNode;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Ident;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+use wdl_ast::v1::CallStatement;
+use wdl_ast::v1::Expr;
+use wdl_ast::v1::LiteralExpr;
+
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+use crate::config::rule_options;
+
+/// The identifier for the duplicate call input rule.
+const ID: &str = "DuplicateCallInput";
+
+/// The default minimum length of a name reference for it to be checked.
+const DEFAULT_MIN_NAME_LENGTH: usize = 2;
+
+/// Formats a list of input names (e.g. "`a`, `b`, and `c`").
+fn format_names(names: &[Ident]) -> String {
+    match names {
+        [first, second] => format!(
+            "`{first}` and `{second}`",
+            first = first.as_str(),
+            second = second.as_str()
+        ),
+        _ => {
+            let (last, rest) = names.split_last().expect("should have names");
+            format!(
+                "{rest}, and `{last}`",
+                rest = rest
+                    .iter()
+                    .map(|n| format!("`{n}`", n = n.as_str()))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                last = last.as_str()
+            )
+        }
+    }
+}
+
+/// Creates a "duplicate call input" diagnostic.
+fn duplicate_call_input(call: &str, names: &[Ident]) -> Diagnostic {
+    let mut diagnostic = Diagnostic::note(format!(
+        "inputs {names} of call `{call}` are assigned the same expression",
+        names = format_names(names)
+    ))
+    .with_rule(ID)
+    .with_fix("ensure each input is assigned the intended expression");
+
+    for name in names {
+        diagnostic = diagnostic.with_highlight(name.span());
+    }
+
+    diagnostic
+}
+
+/// Detects calls that pass the same expression to multiple inputs.
+#[derive(Debug, Clone, Copy)]
+pub struct DuplicateCallInputRule {
+    /// The minimum length of a name reference for it to be checked.
+    min_name_length: usize,
+}
+
+impl DuplicateCallInputRule {
+    /// Constructs a new duplicate call input rule.
+    ///
+    /// Expressions that are a reference to a name shorter than
+    /// `min_name_length` are not checked.
+    pub fn new(min_name_length: usize) -> Self {
+        Self { min_name_length }
+    }
+
+    /// Gets the minimum length of a name reference for it to be checked.
+    pub fn min_name_length(&self) -> usize {
+        self.min_name_length
+    }
+
+    /// Determines if the given expression is trivial.
+    ///
+    /// Trivial expressions are commonly repeated on purpose, so they are not
+    /// checked.
+    fn is_trivial(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::Literal(
+                LiteralExpr::Boolean(_)
+                | LiteralExpr::Integer(_)
+                | LiteralExpr::Float(_)
+                | LiteralExpr::None(_),
+            ) => true,
+            Expr::Literal(LiteralExpr::String(s)) => {
+                s.text().is_some() || s.parts().next().is_none()
+            }
+            Expr::Name(name) => name.name().as_str().len() < self.min_name_length,
+            _ => false,
+        }
+    }
+}
+
+impl Default for DuplicateCallInputRule {
+    fn default() -> Self {
+        Self::new(DEFAULT_MIN_NAME_LENGTH)
+    }
+}
+
+/// Gets the text of an expression without any whitespace or comments.
+fn expr_text(expr: &Expr) -> String {
+    expr.syntax()
+        .descendants_with_tokens()
+        .filter_map(|e| e.into_token())
+        .filter(|t| !t.kind().is_trivia())
+        .map(|t| t.text().to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The options of the duplicate call input rule.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Options {
+    /// The minimum length of a name reference for it to be checked.
+    min_name_length: Option<usize>,
+}
+
+impl Rule for DuplicateCallInputRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags calls that assign the same expression to multiple inputs."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Assigning the same expression to more than one input of a call (e.g. `call align { \
+         input: reads1 = fq, reads2 = fq }`) is occasionally intentional but is usually a \
+         copy-paste mistake. Literals and references to names shorter than a configurable \
+         length are commonly repeated on purpose, so they are not flagged."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Correctness])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("duplicatecallinput"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["RedundantInputAssignment"]
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+        Some(&[
+            SyntaxKind::VersionStatementNode,
+            SyntaxKind::WorkflowDefinitionNode,
+            SyntaxKind::CallStatementNode,
+        ])
+    }
+
+    fn configure(&mut self, options: &toml::Table) -> anyhow::Result<()> {
+        let options: Options = rule_options(options)?;
+
+        if let Some(min_name_length) = options.min_name_length {
+            self.min_name_length = min_name_length;
+        }
+
+        Ok(())
+    }
+}
+
+impl Visitor for DuplicateCallInputRule {
+    type State = Diagnostics;
+
+    fn document(&mut self, _: &mut Self::State, _: VisitReason, _: &Document, _: SupportedVersion) {
+        // This rule has no state to reset.
+    }
+
+    fn call_statement(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        stmt: &CallStatement,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        let mut inputs: IndexMap<String, Vec<Ident>> = IndexMap::new();
+        for input in stmt.inputs() {
+            let Some(expr) = input.expr() else {
+                continue;
+            };
+
+            if self.is_trivial(&expr) {
+                continue;
+            }
+
+            inputs
+                .entry(expr_text(&expr))
+                .or_default()
+                .push(input.name());
+        }
+
+        let call = stmt
+            .alias()
+            .map(|a| a.name())
+            .or_else(|| stmt.target().names().last())
+            .expect("call should have a name");
+
+        for names in inputs.values().filter(|n| n.len() > 1) {
+            state.exceptable_add(
+                duplicate_call_input(call.as_str(), names),
+                SyntaxElement::from(stmt.syntax().clone()),
+                &self.exceptable_nodes(),
+            );
+        }
+    }
+}
@@ -0,0 +1,256 @@
+//! A lint rule for `meta` descriptions that are duplicated verbatim across
+//! multiple tasks or workflows.
+
+use indexmap::IndexMap;
+use serde::Deserialize;
+use wdl_ast::AstNode;
+use wdl_ast::AstNodeExt;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+use wdl_ast::v1::MetadataSection;
+use wdl_ast::v1::MetadataValue;
+use wdl_ast::v1::TaskDefinition;
+use wdl_ast::v1::WorkflowDefinition;
+
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+use crate::config::rule_options;
+
+/// The identifier for the duplicate description rule.
+const ID: &str = "DuplicateDescription";
+
+/// The default minimum length of a description for it to be checked.
+const DEFAULT_MIN_LENGTH: usize = 20;
+
+/// Creates a "duplicate description" diagnostic.
+fn duplicate_description(definition: &Definition, others: &[&Definition]) -> Diagnostic {
+    let others = others
+        .iter()
+        .map(|d| format!("{ty} `{name}`", ty = d.ty, name = d.name))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    Diagnostic::note(format!(
+        "{ty} `{name}` has the same description as {others}",
+        ty = definition.ty,
+        name = definition.name
+    ))
+    .with_rule(ID)
+    .with_highlight(definition.span)
+    .with_fix(format!(
+        "write a description specific to {ty} `{name}`",
+        ty = definition.ty,
+        name = definition.name
+    ))
+}
+
+/// A task or workflow with a description.
+#[derive(Debug, Clone)]
+struct Definition {
+    /// The type of the definition (i.e. `task` or `workflow`).
+    ty: &'static str,
+    /// The name of the definition.
+    name: String,
+    /// The span of the description value.
+    span: Span,
+    /// The `description` metadata item.
+    element: SyntaxElement,
+}
+
+/// Detects `meta` descriptions that are duplicated verbatim across multiple
+/// tasks or workflows in a document.
+///
+/// Descriptions are compared after whitespace normalization; descriptions
+/// shorter than a configurable minimum length are not checked.
+#[derive(Debug, Clone)]
+pub struct DuplicateDescriptionRule {
+    /// The minimum length of a description, in characters, for it to be
+    /// checked.
+    min_length: usize,
+    /// The definitions with a description, keyed by normalized description.
+    descriptions: IndexMap<String, Vec<Definition>>,
+}
+
+impl DuplicateDescriptionRule {
+    /// Constructs a new duplicate description rule with the given minimum
+    /// description length.
+    pub fn new(min_length: usize) -> Self {
+        Self {
+            min_length,
+            descriptions: Default::default(),
+        }
+    }
+
+    /// Gets the minimum length of a description, in characters, for it to be
+    /// checked.
+    pub fn min_length(&self) -> usize {
+        self.min_length
+    }
+
+    /// Records the description of a task or workflow.
+    fn record(&mut self, ty: &'static str, name: &str, section: Option<MetadataSection>) {
+        let Some(item) =
+            section.and_then(|s| s.items().find(|i| i.name().as_str() == "description"))
+        else {
+            return;
+        };
+
+        let MetadataValue::String(s) = item.value() else {
+            return;
+        };
+
+        let Some(text) = s.text() else {
+            return;
+        };
+
+        let normalized = text
+            .as_str()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        if normalized.chars().count() < self.min_length {
+            return;
+        }
+
+        self.descriptions
+            .entry(normalized)
+            .or_default()
+            .push(Definition {
+                ty,
+                name: name.to_string(),
+                span: s.span(),
+                element: SyntaxElement::from(item.syntax().clone()),
+            });
+    }
+}
+
+impl Default for DuplicateDescriptionRule {
+    fn default() -> Self {
+        Self::new(DEFAULT_MIN_LENGTH)
+    }
+}
+
+/// The options of the duplicate description rule.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Options {
+    /// The minimum length of a description for it to be checked.
+    min_length: Option<usize>,
+}
+
+impl Rule for DuplicateDescriptionRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Ensures that `meta` descriptions are not duplicated verbatim across tasks and workflows."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A description copied verbatim across multiple tasks or workflows (e.g., \"Runs the \
+         analysis\") does not explain what distinguishes them. Each task or workflow should have \
+         a description specific to it. Descriptions are compared after whitespace normalization. \
+         Descriptions shorter than a configurable minimum length (20 characters by default) are \
+         not checked, as short, generic descriptions are commonly shared."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Completeness, Tag::Clarity])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("duplicatedescription"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["DescriptionMissing"]
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+        Some(&[
+            SyntaxKind::VersionStatementNode,
+            SyntaxKind::TaskDefinitionNode,
+            SyntaxKind::WorkflowDefinitionNode,
+            SyntaxKind::MetadataSectionNode,
+        ])
+    }
+
+    fn configure(&mut self, options: &toml::Table) -> anyhow::Result<()> {
+        let options: Options = rule_options(options)?;
+
+        if let Some(min_length) = options.min_length {
+            self.min_length = min_length;
+        }
+
+        Ok(())
+    }
+}
+
+impl Visitor for DuplicateDescriptionRule {
+    type State = Diagnostics;
+
+    fn document(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        _: &Document,
+        _: SupportedVersion,
+    ) {
+        if reason == VisitReason::Enter {
+            // Reset the visitor upon document entry
+            *self = Self::new(self.min_length);
+            return;
+        }
+
+        for definitions in self.descriptions.values() {
+            if definitions.len() < 2 {
+                continue;
+            }
+
+            for (i, definition) in definitions.iter().enumerate() {
+                let others = definitions
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .map(|(_, d)| d)
+                    .collect::<Vec<_>>();
+                state.exceptable_add(
+                    duplicate_description(definition, &others),
+                    definition.element.clone(),
+                    &self.exceptable_nodes(),
+                );
+            }
+        }
+    }
+
+    fn task_definition(&mut self, _: &mut Self::State, reason: VisitReason, task: &TaskDefinition) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        self.record("task", task.name().as_str(), task.metadata());
+    }
+
+    fn workflow_definition(
+        &mut self,
+        _: &mut Self::State,
+        reason: VisitReason,
+        workflow: &WorkflowDefinition,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        self.record("workflow", workflow.name().as_str(), workflow.metadata());
+    }
+}
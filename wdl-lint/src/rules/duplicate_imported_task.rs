@@ -0,0 +1,163 @@
+//! A lint rule for local tasks that duplicate a task available from an
+//! imported document.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use wdl_ast::Ast;
+use wdl_ast::AstNode;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+use wdl_ast::v1::TaskDefinition;
+
+use crate::ImportedDocuments;
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+
+/// The identifier for the duplicate imported task rule.
+const ID: &str = "DuplicateImportedTask";
+
+/// Creates a "duplicate imported task" diagnostic.
+fn duplicate_imported_task(name: &str, span: Span, namespace: &str, uri: &str) -> Diagnostic {
+    Diagnostic::warning(format!(
+        "task `{name}` is also available from the import of `{uri}` as `{namespace}.{name}`"
+    ))
+    .with_rule(ID)
+    .with_label("this is likely a stale copy of the imported task", span)
+    .with_fix(format!(
+        "remove the local task and call `{namespace}.{name}` instead"
+    ))
+}
+
+/// Detects local tasks with the same name as a task available from an
+/// imported document.
+///
+/// This rule uses the imported documents provided to the lint visitor;
+/// imports that were not resolved are not checked.
+#[derive(Default, Debug, Clone)]
+pub struct DuplicateImportedTaskRule {
+    /// The names of the tasks of each imported document, keyed by import
+    /// namespace.
+    tasks: HashMap<String, HashSet<String>>,
+    /// The namespace and URI of each import of the document, in source order.
+    imports: Vec<(String, String)>,
+}
+
+impl Rule for DuplicateImportedTaskRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags local tasks with the same name as a task available from an imported document."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "When shared tasks are maintained in library documents, a local task with the same name \
+         as a task of an imported document is likely a stale copy of the library task. The local \
+         definition should be removed in favor of calling the imported task. Only task names are \
+         compared; if more than one import has a task of the same name, the first import in the \
+         document is reported. This rule only checks imports that have been resolved by the \
+         caller of the linter."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Clarity])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("duplicateimportedtask"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["UndefinedCallTarget"]
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+        Some(&[
+            SyntaxKind::VersionStatementNode,
+            SyntaxKind::TaskDefinitionNode,
+        ])
+    }
+
+    fn set_imports(&mut self, imports: &ImportedDocuments) {
+        self.tasks = imports
+            .iter()
+            .filter_map(|(namespace, document)| {
+                let Ast::V1(ast) = document.ast() else {
+                    return None;
+                };
+
+                let names = ast.tasks().map(|t| t.name().as_str().to_string()).collect();
+                Some((namespace.to_string(), names))
+            })
+            .collect();
+    }
+}
+
+impl Visitor for DuplicateImportedTaskRule {
+    type State = Diagnostics;
+
+    fn document(
+        &mut self,
+        _: &mut Self::State,
+        reason: VisitReason,
+        document: &Document,
+        _: SupportedVersion,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        // Reset the visitor upon document entry; the imported documents are set
+        // afterwards
+        *self = Default::default();
+
+        // Imports may appear after a task, so collect them all upfront
+        if let Ast::V1(ast) = document.ast() {
+            self.imports = ast
+                .imports()
+                .filter_map(|stmt| {
+                    let (namespace, _) = stmt.namespace()?;
+                    Some((namespace, stmt.uri().text()?.as_str().to_string()))
+                })
+                .collect();
+        }
+    }
+
+    fn task_definition(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        task: &TaskDefinition,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        // The first import in source order with a task of the same name is reported
+        let name = task.name();
+        let Some((namespace, uri)) = self.imports.iter().find(|(namespace, _)| {
+            self.tasks
+                .get(namespace)
+                .is_some_and(|tasks| tasks.contains(name.as_str()))
+        }) else {
+            return;
+        };
+
+        state.exceptable_add(
+            duplicate_imported_task(name.as_str(), name.span(), namespace, uri),
+            SyntaxElement::from(task.syntax().clone()),
+            &self.exceptable_nodes(),
+        );
+    }
+}
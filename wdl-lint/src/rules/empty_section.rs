@@ -0,0 +1,226 @@
+//! A lint rule for empty sections.
+
+use wdl_ast::AstNode;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::SyntaxNode;
+use wdl_ast::ToSpan;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+use wdl_ast::v1::CommandPart;
+use wdl_ast::v1::CommandSection;
+use wdl_ast::v1::InputSection;
+use wdl_ast::v1::MetadataSection;
+use wdl_ast::v1::OutputSection;
+
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+
+/// The identifier for the empty section rule.
+const ID: &str = "EmptySection";
+
+/// Creates an "empty command" diagnostic.
+///
+/// A command section that contains comments is reported as a note, as it is
+/// likely a placeholder for a command that is yet to be written.
+fn empty_command(comments: bool, span: Span) -> Diagnostic {
+    let diagnostic = if comments {
+        Diagnostic::note("command section contains only comments")
+    } else {
+        Diagnostic::warning("command section is empty")
+    };
+
+    diagnostic
+        .with_rule(ID)
+        .with_label("the task does nothing when run", span)
+        .with_fix("add the command that the task runs")
+}
+
+/// Creates an "empty section" diagnostic.
+fn empty_section(section: &str, span: Span) -> Diagnostic {
+    Diagnostic::note(format!("`{section}` section is empty"))
+        .with_rule(ID)
+        .with_highlight(span)
+        .with_fix(format!("remove the empty `{section}` section"))
+}
+
+/// Creates a "section with only comments" diagnostic.
+fn only_comments(section: &str, span: Span) -> Diagnostic {
+    Diagnostic::note(format!("`{section}` section contains only comments"))
+        .with_rule(ID)
+        .with_highlight(span)
+        .with_fix(format!(
+            "remove the `{section}` section; its comments will need to be moved elsewhere"
+        ))
+}
+
+/// Gets the span of the keyword of a section.
+fn keyword_span(section: &SyntaxNode) -> Span {
+    section
+        .children_with_tokens()
+        .filter_map(SyntaxElement::into_token)
+        .find(|t| !t.kind().is_trivia())
+        .map(|t| t.text_range().to_span())
+        .unwrap_or_else(|| section.text_range().to_span())
+}
+
+/// Determines if the braces of a section contain a comment.
+fn has_comments(section: &SyntaxNode) -> bool {
+    section
+        .children_with_tokens()
+        .filter_map(SyntaxElement::into_token)
+        .skip_while(|t| t.kind() != SyntaxKind::OpenBrace)
+        .any(|t| t.kind() == SyntaxKind::Comment)
+}
+
+/// Detects empty sections.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct EmptySectionRule;
+
+impl EmptySectionRule {
+    /// Reports a section that is empty.
+    fn report(&self, state: &mut Diagnostics, section: &str, syntax: &SyntaxNode) {
+        let span = keyword_span(syntax);
+        let diagnostic = if has_comments(syntax) {
+            only_comments(section, span)
+        } else {
+            empty_section(section, span)
+        };
+
+        state.exceptable_add(
+            diagnostic,
+            SyntaxElement::from(syntax.clone()),
+            &self.exceptable_nodes(),
+        );
+    }
+}
+
+impl Rule for EmptySectionRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags empty command, meta, input, and output sections."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A task with an empty command section does nothing when run, which is almost always a \
+         mistake. Empty `meta`, `input`, and `output` sections are clutter and may be removed. \
+         A command section containing only shell comments is also considered empty. Other \
+         sections containing only WDL comments are reported as well, as removing them requires \
+         moving their comments elsewhere. This rule is optional, as the `MissingMetas` and \
+         `MissingOutput` rules require sections that may be empty."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Clarity, Tag::Completeness])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("emptysection"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["MissingMetas", "MissingOutput"]
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+        Some(&[
+            SyntaxKind::VersionStatementNode,
+            SyntaxKind::TaskDefinitionNode,
+            SyntaxKind::WorkflowDefinitionNode,
+            SyntaxKind::CommandSectionNode,
+            SyntaxKind::MetadataSectionNode,
+            SyntaxKind::InputSectionNode,
+            SyntaxKind::OutputSectionNode,
+        ])
+    }
+}
+
+impl Visitor for EmptySectionRule {
+    type State = Diagnostics;
+
+    fn document(&mut self, _: &mut Self::State, _: VisitReason, _: &Document, _: SupportedVersion) {
+        // This is intentionally empty, as this rule has no state.
+    }
+
+    fn command_section(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        section: &CommandSection,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        // Only whitespace and shell comments are considered empty
+        let mut comments = false;
+        for part in section.parts() {
+            let CommandPart::Text(text) = part else {
+                return;
+            };
+
+            for line in text.as_str().lines().map(str::trim) {
+                if line.starts_with('#') {
+                    comments = true;
+                } else if !line.is_empty() {
+                    return;
+                }
+            }
+        }
+
+        state.exceptable_add(
+            empty_command(comments, keyword_span(section.syntax())),
+            SyntaxElement::from(section.syntax().clone()),
+            &self.exceptable_nodes(),
+        );
+    }
+
+    fn metadata_section(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        section: &MetadataSection,
+    ) {
+        if reason == VisitReason::Exit || section.items().next().is_some() {
+            return;
+        }
+
+        self.report(state, "meta", section.syntax());
+    }
+
+    fn input_section(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        section: &InputSection,
+    ) {
+        if reason == VisitReason::Exit || section.declarations().next().is_some() {
+            return;
+        }
+
+        self.report(state, "input", section.syntax());
+    }
+
+    fn output_section(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        section: &OutputSection,
+    ) {
+        if reason == VisitReason::Exit || section.declarations().next().is_some() {
+            return;
+        }
+
+        self.report(state, "output", section.syntax());
+    }
+}
@@ -62,6 +62,14 @@ impl Rule for EndingNewlineRule {
         TagSet::new(&[Tag::Spacing, Tag::Style])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("endingnewline"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["Whitespace", "InconsistentNewlines"]
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
         Some(&[SyntaxKind::VersionStatementNode])
     }
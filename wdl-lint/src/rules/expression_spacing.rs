@@ -2,6 +2,7 @@
 
 use rowan::Direction;
 use wdl_ast::AstNode;
+use wdl_ast::AstToken;
 use wdl_ast::Diagnostic;
 use wdl_ast::Diagnostics;
 use wdl_ast::Document;
@@ -9,6 +10,7 @@ use wdl_ast::Span;
 use wdl_ast::SupportedVersion;
 use wdl_ast::SyntaxElement;
 use wdl_ast::SyntaxKind;
+use wdl_ast::SyntaxNode;
 use wdl_ast::ToSpan;
 use wdl_ast::VisitReason;
 use wdl_ast::Visitor;
@@ -22,12 +24,25 @@ use crate::TagSet;
 /// The identifier for the expression spacing rule.
 const ID: &str = "ExpressionSpacing";
 
+/// Creates an empty span at the given offset for inserting text.
+fn insertion(offset: usize) -> Span {
+    Span::new(offset, 0)
+}
+
 /// Reports disallowed whitespace after prefix operators.
-fn prefix_whitespace(span: Span) -> Diagnostic {
-    Diagnostic::note("prefix operators may not contain whitespace")
+///
+/// The given whitespace spans are removed by the fix.
+fn prefix_whitespace(span: Span, whitespace: impl IntoIterator<Item = Span>) -> Diagnostic {
+    let mut diagnostic = Diagnostic::note("prefix operators may not contain whitespace")
         .with_rule(ID)
         .with_highlight(span)
-        .with_fix("remove the whitespace")
+        .with_fix("remove the whitespace");
+
+    for ws in whitespace {
+        diagnostic = diagnostic.with_replacement(ws, "");
+    }
+
+    diagnostic
 }
 
 /// Reports missing following whitespace around operators
@@ -36,6 +51,8 @@ fn missing_surrounding_whitespace(span: Span) -> Diagnostic {
         .with_rule(ID)
         .with_highlight(span)
         .with_fix("add whitespace around this operator")
+        .with_replacement(insertion(span.start()), " ")
+        .with_replacement(insertion(span.end()), " ")
 }
 
 /// Reports missing preceding whitespace around operators
@@ -44,6 +61,7 @@ fn missing_preceding_whitespace(span: Span) -> Diagnostic {
         .with_rule(ID)
         .with_highlight(span)
         .with_fix("add whitespace before this operator")
+        .with_replacement(insertion(span.start()), " ")
 }
 
 /// Reports missing following whitespace around operators
@@ -52,6 +70,16 @@ fn missing_following_whitespace(span: Span) -> Diagnostic {
         .with_rule(ID)
         .with_highlight(span)
         .with_fix("add whitespace after this operator")
+        .with_replacement(insertion(span.end()), " ")
+}
+
+/// Reports missing following whitespace after commas
+fn missing_comma_whitespace(span: Span) -> Diagnostic {
+    Diagnostic::note("commas must be followed by whitespace")
+        .with_rule(ID)
+        .with_highlight(span)
+        .with_fix("add whitespace after this comma")
+        .with_replacement(insertion(span.end()), " ")
 }
 
 /// Report disallowed space
@@ -60,6 +88,7 @@ fn disallowed_space(span: Span) -> Diagnostic {
         .with_rule(ID)
         .with_highlight(span)
         .with_fix("remove the space")
+        .with_replacement(span, "")
 }
 
 /// Reports missing preceding whitespace around assignments
@@ -68,6 +97,7 @@ fn assignment_missing_preceding_whitespace(span: Span) -> Diagnostic {
         .with_rule(ID)
         .with_highlight(span)
         .with_fix("add whitespace before this assignment")
+        .with_replacement(insertion(span.start()), " ")
 }
 
 /// Reports missing following whitespace around assignments
@@ -76,6 +106,7 @@ fn assignment_missing_following_whitespace(span: Span) -> Diagnostic {
         .with_rule(ID)
         .with_highlight(span)
         .with_fix("add whitespace after this assignment")
+        .with_replacement(insertion(span.end()), " ")
 }
 
 /// Reports missing surrounding whitespace around assignments
@@ -84,6 +115,8 @@ fn assignment_missing_surrounding_whitespace(span: Span) -> Diagnostic {
         .with_rule(ID)
         .with_highlight(span)
         .with_fix("add whitespace around this assignment")
+        .with_replacement(insertion(span.start()), " ")
+        .with_replacement(insertion(span.end()), " ")
 }
 
 /// Reports missing open paren for multiline if...then...else constructs
@@ -165,6 +198,10 @@ impl Rule for ExpressionSpacingRule {
          The following tokens should not be followed by whitespace when used as a prefix: `-`, and \
          `!`.
          
+         Commas separating function call arguments or the elements of a literal should be \
+         followed by whitespace. A function name should not be separated from the opening \
+         parenthesis of its arguments.
+         
          Opening brackets (`(`, `[`, and `{`) should not be followed by a space, but may be \
          followed by a newline. Closing brackets (`)`, `]`, and `}`) should not be preceded by a \
          space, but may be preceded by a newline.
@@ -200,6 +237,14 @@ impl Rule for ExpressionSpacingRule {
         TagSet::new(&[Tag::Spacing])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("expressionspacing"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["CallInputSpacing", "LineWidth", "Whitespace"]
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
         None
     }
@@ -230,16 +275,17 @@ impl Visitor for ExpressionSpacingRule {
 
         match expr {
             Expr::LogicalNot(_) | Expr::Negation(_) => {
-                // No following spacing allowed
-                if expr
+                // No following spacing allowed (e.g. `-x` is fine, but `- x` is not)
+                let whitespace: Vec<_> = expr
                     .syntax()
                     .children_with_tokens()
                     .filter(|t| t.kind() == SyntaxKind::Whitespace)
-                    .count()
-                    > 0
-                {
+                    .map(|t| t.text_range().to_span())
+                    .collect();
+
+                if !whitespace.is_empty() {
                     state.exceptable_add(
-                        prefix_whitespace(expr.syntax().text_range().to_span()),
+                        prefix_whitespace(expr.syntax().text_range().to_span(), whitespace),
                         SyntaxElement::from(expr.syntax().clone()),
                         &self.exceptable_nodes(),
                     );
@@ -313,9 +359,15 @@ impl Visitor for ExpressionSpacingRule {
                                 .next_sibling_or_token()
                                 .is_some_and(|t| t.kind() != SyntaxKind::Comment)
                         {
-                            // opening parens should not be followed by non-newline whitespace
+                            // opening parens should not be followed by non-newline whitespace;
+                            // only the whitespace preceding any newline is disallowed
+                            let text = token.text();
+                            let len = text.len() - text.trim_start_matches([' ', '\t']).len();
                             state.exceptable_add(
-                                disallowed_space(token.text_range().to_span()),
+                                disallowed_space(Span::new(
+                                    token.text_range().to_span().start(),
+                                    len,
+                                )),
                                 SyntaxElement::from(expr.syntax().clone()),
                                 &self.exceptable_nodes(),
                             );
@@ -619,7 +671,38 @@ impl Visitor for ExpressionSpacingRule {
                     );
                 }
             }
+            Expr::Call(call) => {
+                // The function name may not be separated from its opening parenthesis
+                if let Some(ws) = call
+                    .target()
+                    .syntax()
+                    .next_sibling_or_token()
+                    .filter(|t| t.kind() == SyntaxKind::Whitespace)
+                {
+                    state.exceptable_add(
+                        disallowed_space(ws.text_range().to_span()),
+                        SyntaxElement::from(call.syntax().clone()),
+                        &self.exceptable_nodes(),
+                    );
+                }
+
+                check_comma_ws(state, call.syntax(), &self.exceptable_nodes());
+            }
             Expr::Literal(l) => {
+                if matches!(
+                    l,
+                    LiteralExpr::Array(_)
+                        | LiteralExpr::Pair(_)
+                        | LiteralExpr::Map(_)
+                        | LiteralExpr::Object(_)
+                        | LiteralExpr::Struct(_)
+                        | LiteralExpr::Hints(_)
+                        | LiteralExpr::Input(_)
+                        | LiteralExpr::Output(_)
+                ) {
+                    check_comma_ws(state, l.syntax(), &self.exceptable_nodes());
+                }
+
                 match l {
                     LiteralExpr::Array(_) | LiteralExpr::Map(_) | LiteralExpr::Object(_) => {
                         let newlines = l
@@ -737,7 +820,7 @@ impl Visitor for ExpressionSpacingRule {
                     _ => {}
                 }
             }
-            // Expr::Literal, Expr::Name, Expr::Call
+            // Expr::Name
             _ => {}
         }
     }
@@ -788,6 +871,36 @@ impl Visitor for ExpressionSpacingRule {
     }
 }
 
+/// Checks to ensure the commas of a node are followed by whitespace.
+///
+/// Trailing commas (i.e. those followed by a closing token) are not checked.
+fn check_comma_ws(
+    state: &mut Diagnostics,
+    node: &SyntaxNode,
+    exceptable_nodes: &Option<&'static [SyntaxKind]>,
+) {
+    for comma in node
+        .children_with_tokens()
+        .filter(|t| t.kind() == SyntaxKind::Comma)
+    {
+        if comma.next_sibling_or_token().is_some_and(|t| {
+            !matches!(
+                t.kind(),
+                SyntaxKind::Whitespace
+                    | SyntaxKind::CloseParen
+                    | SyntaxKind::CloseBracket
+                    | SyntaxKind::CloseBrace
+            )
+        }) {
+            state.exceptable_add(
+                missing_comma_whitespace(comma.text_range().to_span()),
+                comma.clone(),
+                exceptable_nodes,
+            );
+        }
+    }
+}
+
 /// Checks to ensure a token is surrounded by whitespace.
 fn check_required_surrounding_ws(
     state: &mut Diagnostics,
@@ -820,3 +933,53 @@ fn check_required_surrounding_ws(
         );
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use wdl_ast::Validator;
+
+    use super::*;
+    use crate::util::apply_replacements;
+
+    #[test]
+    fn it_fixes_spacing() {
+        let source = r#"version 1.1
+
+workflow test {
+    Int x = 1
+    Int sum = x+x* x -x
+    Int negative = - x
+    Int largest = max (x,x)
+    Array[Int] numbers = [x,x,]
+    Boolean equal = [x]== [x]
+    String text = "a+b,c"
+    Int assigned=x
+}
+"#;
+
+        let (document, diagnostics) = Document::parse(source);
+        assert!(diagnostics.is_empty());
+
+        let mut validator = Validator::empty();
+        validator.add_visitor(ExpressionSpacingRule);
+        let diagnostics = validator.validate(&document).unwrap_err();
+        assert_eq!(diagnostics.len(), 9);
+        assert_eq!(
+            apply_replacements(source, &diagnostics),
+            r#"version 1.1
+
+workflow test {
+    Int x = 1
+    Int sum = x + x * x - x
+    Int negative = -x
+    Int largest = max(x, x)
+    Array[Int] numbers = [x, x,]
+    Boolean equal = [x] == [x]
+    String text = "a+b,c"
+    Int assigned = x
+}
+"#
+        );
+    }
+}
@@ -0,0 +1,150 @@
+//! A lint rule for calls to `glob`.
+
+use wdl_ast::AstNode;
+use wdl_ast::AstNodeExt;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+use wdl_ast::v1::Expr;
+use wdl_ast::v1::LiteralExpr;
+use wdl_ast::v1::StringPart;
+
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+
+/// The identifier for the glob portability rule.
+const ID: &str = "GlobPortability";
+
+/// Creates a "glob" diagnostic.
+///
+/// `pattern` is the reason the pattern of the call is not portable, if any,
+/// and the span of the pattern; a call with such a pattern is reported as a
+/// warning with the pattern labeled rather than the call's target.
+fn glob_call(span: Span, pattern: Option<(&str, Span)>) -> Diagnostic {
+    let message = "`glob` returns files in an order that may differ across backends";
+    let diagnostic = match pattern {
+        Some((reason, pattern)) => Diagnostic::warning(message)
+            .with_rule(ID)
+            .with_label(reason, pattern),
+        None => Diagnostic::note(message).with_rule(ID).with_highlight(span),
+    };
+
+    diagnostic.with_fix(
+        "sort the files where they are used or enumerate the expected files as individual \
+         outputs; note that `glob` returns an empty array when no files match",
+    )
+}
+
+/// Gets the reason the given `glob` pattern is not portable.
+///
+/// Returns `None` if the pattern is portable or is not a string literal.
+fn unportable_pattern(pattern: &Expr) -> Option<&'static str> {
+    let Expr::Literal(LiteralExpr::String(s)) = pattern else {
+        return None;
+    };
+
+    let mut parts = s.parts().peekable();
+    if matches!(parts.peek(), Some(StringPart::Text(text)) if text.as_str().starts_with('/')) {
+        return Some("an absolute pattern matches files outside of the task's working directory");
+    }
+
+    parts
+        .any(|part| matches!(part, StringPart::Text(text) if text.as_str().contains("**")))
+        .then_some("a recursive `**` pattern is not supported by every backend")
+}
+
+/// Detects calls to `glob`.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct GlobPortabilityRule;
+
+impl Rule for GlobPortabilityRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags calls to `glob`, whose results differ across backends."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "The order of the files returned by `glob` is not specified and may differ across \
+         backends, and `glob` silently returns an empty array when no files match its pattern. \
+         Outputs should instead enumerate the expected files or the files should be sorted \
+         where they are used. Calls to `glob` are reported as notes; calls in output sections \
+         whose pattern is recursive (i.e. contains `**`) or absolute (i.e. starts with `/`) are \
+         reported as warnings, as such patterns are not supported by every backend or match \
+         files outside of the task's working directory."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Portability])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("globportability"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["StringFileOutput"]
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+        Some(&[
+            SyntaxKind::VersionStatementNode,
+            SyntaxKind::TaskDefinitionNode,
+            SyntaxKind::WorkflowDefinitionNode,
+            SyntaxKind::OutputSectionNode,
+            SyntaxKind::BoundDeclNode,
+        ])
+    }
+}
+
+impl Visitor for GlobPortabilityRule {
+    type State = Diagnostics;
+
+    fn document(&mut self, _: &mut Self::State, _: VisitReason, _: &Document, _: SupportedVersion) {
+        // This is intentionally empty, as this rule has no state.
+    }
+
+    fn expr(&mut self, state: &mut Self::State, reason: VisitReason, expr: &Expr) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        let Expr::Call(call) = expr else {
+            return;
+        };
+
+        let target = call.target();
+        if target.as_str() != "glob" {
+            return;
+        }
+
+        // Only calls in output sections are escalated
+        let pattern = if expr
+            .syntax()
+            .ancestors()
+            .any(|n| n.kind() == SyntaxKind::OutputSectionNode)
+        {
+            call.arguments()
+                .next()
+                .and_then(|pattern| Some((unportable_pattern(&pattern)?, pattern.span())))
+        } else {
+            None
+        };
+
+        state.exceptable_add(
+            glob_call(target.span(), pattern),
+            SyntaxElement::from(expr.syntax().clone()),
+            &self.exceptable_nodes(),
+        );
+    }
+}
@@ -0,0 +1,280 @@
+//! A lint rule for GPU requirements that do not match the command.
+
+use serde::Deserialize;
+use wdl_ast::AstNode;
+use wdl_ast::AstNodeExt;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Ident;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::ToSpan;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+use wdl_ast::v1::CommandPart;
+use wdl_ast::v1::CommandSection;
+use wdl_ast::v1::Expr;
+use wdl_ast::v1::LiteralExpr;
+use wdl_ast::v1::TASK_REQUIREMENT_GPU;
+use wdl_ast::v1::TaskDefinition;
+
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+use crate::config::rule_options;
+
+/// The identifier for the GPU requirement mismatch rule.
+const ID: &str = "GpuRequirementMismatch";
+
+/// The default terms that indicate a command uses a GPU.
+///
+/// Terms are matched case-insensitively against the text of the command.
+pub const DEFAULT_GPU_INDICATORS: &[&str] = &[
+    "nvidia-smi",
+    "CUDA_VISIBLE_DEVICES",
+    "NVIDIA_VISIBLE_DEVICES",
+    "nvcc",
+    "--gpu",
+    "--cuda",
+];
+
+/// The engine-specific keys that request a GPU by type or count.
+const GPU_KEYS: &[&str] = &["gpuType", "gpuCount"];
+
+/// Creates an "unused GPU" diagnostic.
+fn unused_gpu(span: Span) -> Diagnostic {
+    Diagnostic::note("task requests a GPU but its command does not appear to use one")
+        .with_rule(ID)
+        .with_label("a GPU is requested here", span)
+        .with_fix(
+            "remove the GPU requirement if the command does not use a GPU; otherwise, except this \
+             rule for the task",
+        )
+}
+
+/// Creates a "missing GPU" diagnostic.
+fn missing_gpu(indicator: &str, span: Span) -> Diagnostic {
+    Diagnostic::warning("task command appears to use a GPU but the task does not request one")
+        .with_rule(ID)
+        .with_label(format!("`{indicator}` indicates GPU usage"), span)
+        .with_fix("add `gpu: true` to the task's `requirements` (or `runtime`) section")
+}
+
+/// Determines if the given item requests a GPU.
+fn requests_gpu(name: &Ident, expr: &Expr) -> bool {
+    let name = name.as_str();
+    if GPU_KEYS.contains(&name) {
+        // A literal count of zero does not request a GPU
+        return !matches!(expr, Expr::Literal(LiteralExpr::Integer(i)) if i.value() == Some(0));
+    }
+
+    name == TASK_REQUIREMENT_GPU
+        && match expr {
+            Expr::Literal(LiteralExpr::Boolean(b)) => b.value(),
+            Expr::Literal(LiteralExpr::Integer(i)) => i.value().is_some_and(|v| v > 0),
+            _ => false,
+        }
+}
+
+/// Detects tasks that request a GPU without using one and tasks that use a
+/// GPU without requesting one.
+#[derive(Debug, Clone)]
+pub struct GpuRequirementMismatchRule {
+    /// The lowercase terms that indicate a command uses a GPU.
+    indicators: Vec<String>,
+}
+
+impl GpuRequirementMismatchRule {
+    /// Constructs a new GPU requirement mismatch rule with the given terms that
+    /// indicate a command uses a GPU.
+    ///
+    /// Terms are matched case-insensitively against the text of the command.
+    pub fn new(indicators: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+        Self {
+            indicators: indicators
+                .into_iter()
+                .map(|i| i.as_ref().to_ascii_lowercase())
+                .filter(|i| !i.is_empty())
+                .collect(),
+        }
+    }
+
+    /// Finds the first GPU indicator in the given command section.
+    ///
+    /// Returns the indicator and its span.
+    fn find_indicator(&self, section: &CommandSection) -> Option<(String, Span)> {
+        for part in section.parts() {
+            let CommandPart::Text(text) = part else {
+                continue;
+            };
+
+            // ASCII lowercasing preserves the byte offsets of the text
+            let lower = text.as_str().to_ascii_lowercase();
+            let found = self
+                .indicators
+                .iter()
+                .filter_map(|i| Some((lower.find(i.as_str())?, i.len())))
+                .min();
+
+            if let Some((offset, len)) = found {
+                let start = text.syntax().text_range().to_span().start() + offset;
+                return Some((
+                    text.as_str()[offset..offset + len].to_string(),
+                    Span::new(start, len),
+                ));
+            }
+        }
+
+        None
+    }
+}
+
+/// Implements the default GPU requirement mismatch rule.
+impl Default for GpuRequirementMismatchRule {
+    fn default() -> Self {
+        Self::new(DEFAULT_GPU_INDICATORS)
+    }
+}
+
+/// The options of the GPU requirement mismatch rule.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Options {
+    /// The terms that indicate a command uses a GPU.
+    indicators: Option<Vec<String>>,
+}
+
+impl Rule for GpuRequirementMismatchRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Ensures that tasks requesting a GPU use one and that tasks using a GPU request one."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "GPUs are expensive to schedule; a task that requests a GPU (via `gpu: true`, `gpuType`, \
+         or `gpuCount`) but never uses one wastes resources, while a task that uses a GPU without \
+         requesting one may be scheduled on a host without a GPU and fail. This rule is a \
+         heuristic: a command is considered to use a GPU if its text contains a known GPU \
+         indicator, such as `nvidia-smi`, `CUDA_VISIBLE_DEVICES`, `NVIDIA_VISIBLE_DEVICES`, \
+         `nvcc`, `--gpu`, or `--cuda` (matched case-insensitively). Text produced by placeholders \
+         is not considered. A note is emitted for a GPU request that is not used and a warning \
+         for GPU usage that is not requested."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Correctness, Tag::Portability])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("gpurequirementmismatch"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["MissingRequirements", "RuntimeSectionKeys"]
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+        Some(&[
+            SyntaxKind::VersionStatementNode,
+            SyntaxKind::TaskDefinitionNode,
+            SyntaxKind::CommandSectionNode,
+            SyntaxKind::RequirementsSectionNode,
+            SyntaxKind::RequirementsItemNode,
+            SyntaxKind::TaskHintsSectionNode,
+            SyntaxKind::TaskHintsItemNode,
+            SyntaxKind::RuntimeSectionNode,
+            SyntaxKind::RuntimeItemNode,
+        ])
+    }
+
+    fn configure(&mut self, options: &toml::Table) -> anyhow::Result<()> {
+        let options: Options = rule_options(options)?;
+
+        if let Some(indicators) = options.indicators {
+            self.indicators = Self::new(indicators).indicators;
+        }
+
+        Ok(())
+    }
+}
+
+impl Visitor for GpuRequirementMismatchRule {
+    type State = Diagnostics;
+
+    fn document(&mut self, _: &mut Self::State, _: VisitReason, _: &Document, _: SupportedVersion) {
+        // This rule has no state to reset.
+    }
+
+    fn task_definition(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        task: &TaskDefinition,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        let Some(command) = task.command() else {
+            return;
+        };
+
+        // Find the first item that requests a GPU
+        let requirements = task
+            .requirements()
+            .into_iter()
+            .flat_map(|s| s.items().map(|i| (i.name(), i.expr(), i.syntax().clone())));
+        let runtime = task
+            .runtime()
+            .into_iter()
+            .flat_map(|s| s.items().map(|i| (i.name(), i.expr(), i.syntax().clone())));
+        let hints = task
+            .hints()
+            .into_iter()
+            .flat_map(|s| s.items().map(|i| (i.name(), i.expr(), i.syntax().clone())));
+        let request = requirements
+            .chain(runtime)
+            .chain(hints)
+            .find(|(name, expr, _)| requests_gpu(name, expr));
+
+        match (request, self.find_indicator(&command)) {
+            (Some((_, expr, item)), None) => {
+                state.exceptable_add(
+                    unused_gpu(expr.span()),
+                    SyntaxElement::from(item),
+                    &self.exceptable_nodes(),
+                );
+            }
+            (None, Some((indicator, span))) => {
+                state.exceptable_add(
+                    missing_gpu(&indicator, span),
+                    SyntaxElement::from(command.syntax().clone()),
+                    &self.exceptable_nodes(),
+                );
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_normalizes_indicators() {
+        let rule = GpuRequirementMismatchRule::new(["Torch.CUDA", "", "--GPU"]);
+        assert_eq!(rule.indicators, ["torch.cuda", "--gpu"]);
+
+        let rule = GpuRequirementMismatchRule::default();
+        assert_eq!(rule.indicators.len(), DEFAULT_GPU_INDICATORS.len());
+        assert!(rule.indicators.iter().all(|i| *i == i.to_ascii_lowercase()));
+    }
+}
@@ -0,0 +1,160 @@
+//! A lint rule for heredoc command text that may join with placeholders to
+//! form the closing delimiter.
+
+use wdl_ast::AstNode;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+use wdl_ast::v1::CommandPart;
+use wdl_ast::v1::CommandSection;
+
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+
+/// The identifier for the heredoc delimiter adjacency rule.
+const ID: &str = "HeredocDelimiterAdjacency";
+
+/// The closing delimiter of a heredoc command section.
+const DELIMITER: &str = ">>>";
+
+/// Creates a "delimiter before placeholder" diagnostic.
+fn delimiter_before_placeholder(span: Span) -> Diagnostic {
+    Diagnostic::note(format!(
+        "command text ending in `>` may join with the following placeholder to form `{DELIMITER}`"
+    ))
+    .with_rule(ID)
+    .with_label(
+        "if the placeholder's value starts with `>`, the rendered command will contain the \
+         heredoc closing delimiter",
+        span,
+    )
+    .with_fix("separate the `>` from the placeholder with whitespace")
+}
+
+/// Creates a "delimiter after placeholder" diagnostic.
+fn delimiter_after_placeholder(span: Span) -> Diagnostic {
+    Diagnostic::note(format!(
+        "command text starting with `>>` may join with the preceding placeholder to form \
+         `{DELIMITER}`"
+    ))
+    .with_rule(ID)
+    .with_label(
+        "if the placeholder's value ends with `>`, the rendered command will contain the heredoc \
+         closing delimiter",
+        span,
+    )
+    .with_fix("separate the `>>` from the placeholder with whitespace")
+}
+
+/// Detects heredoc command text adjacent to placeholders that may join with
+/// the placeholder's value to form the closing delimiter.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct HeredocDelimiterAdjacencyRule;
+
+impl Rule for HeredocDelimiterAdjacencyRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags heredoc command text that may join with placeholders to form the closing \
+         delimiter."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A heredoc command section is terminated by `>>>`, so commands that need to emit that \
+         sequence (e.g. a task that writes a WDL document) must escape it. Escaping only applies \
+         to the literal text of the command: when text ending in `>` is immediately followed by a \
+         placeholder, or a placeholder is immediately followed by text starting with `>>`, the \
+         rendered command may contain `>>>` depending on the value of the placeholder. A rendered \
+         command containing the closing delimiter is hazardous when it is itself written into a \
+         heredoc (e.g. when generating WDL) and is easily misread. Separating the `>` characters \
+         from the placeholder with whitespace avoids the ambiguity."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Correctness])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("heredocdelimiteradjacency"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["NoCurlyCommands"]
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+        Some(&[
+            SyntaxKind::VersionStatementNode,
+            SyntaxKind::TaskDefinitionNode,
+            SyntaxKind::CommandSectionNode,
+        ])
+    }
+}
+
+impl Visitor for HeredocDelimiterAdjacencyRule {
+    type State = Diagnostics;
+
+    fn document(&mut self, _: &mut Self::State, _: VisitReason, _: &Document, _: SupportedVersion) {
+        // This is intentionally empty, as this rule has no state.
+    }
+
+    fn command_section(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        section: &CommandSection,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        if !section.is_heredoc() {
+            return;
+        }
+
+        let parts: Vec<_> = section.parts().collect();
+        for (i, part) in parts.iter().enumerate() {
+            let CommandPart::Text(text) = part else {
+                continue;
+            };
+
+            let s = text.as_str();
+            let start = text.span().start();
+
+            if matches!(parts.get(i + 1), Some(CommandPart::Placeholder(_))) {
+                let trimmed = s.trim_end_matches('>');
+                if trimmed.len() < s.len() {
+                    state.exceptable_add(
+                        delimiter_before_placeholder(Span::new(
+                            start + trimmed.len(),
+                            s.len() - trimmed.len(),
+                        )),
+                        SyntaxElement::from(section.syntax().clone()),
+                        &self.exceptable_nodes(),
+                    );
+                }
+            }
+
+            if i > 0 && matches!(parts.get(i - 1), Some(CommandPart::Placeholder(_))) {
+                let len = s.len() - s.trim_start_matches('>').len();
+                if len >= 2 {
+                    state.exceptable_add(
+                        delimiter_after_placeholder(Span::new(start, len)),
+                        SyntaxElement::from(section.syntax().clone()),
+                        &self.exceptable_nodes(),
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,216 @@
+//! A lint rule for array literals with elements of different types.
+
+use std::fmt;
+
+use wdl_ast::AstNode;
+use wdl_ast::AstNodeExt;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+use wdl_ast::v1::Expr;
+use wdl_ast::v1::LiteralExpr;
+
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+
+/// The identifier for the heterogeneous array rule.
+const ID: &str = "HeterogeneousArray";
+
+/// Represents the type of a literal array element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ElementType {
+    /// The element is a `Boolean`.
+    Boolean,
+    /// The element is an `Int`.
+    Int,
+    /// The element is a `Float`.
+    Float,
+    /// The element is a `String`.
+    String,
+}
+
+impl ElementType {
+    /// Gets the type of a literal element.
+    ///
+    /// Returns `None` if the element is not a literal of a primitive type.
+    fn of(expr: &Expr) -> Option<Self> {
+        match expr {
+            Expr::Literal(LiteralExpr::Boolean(_)) => Some(Self::Boolean),
+            Expr::Literal(LiteralExpr::Integer(_)) => Some(Self::Int),
+            Expr::Literal(LiteralExpr::Float(_)) => Some(Self::Float),
+            Expr::Literal(LiteralExpr::String(_)) => Some(Self::String),
+            Expr::Negation(expr) => match Self::of(&expr.operand())? {
+                ty @ (Self::Int | Self::Float) => Some(ty),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Gets the common type of two element types.
+    ///
+    /// An `Int` widens to a `Float`; any other mixture requires coercion to
+    /// a `String`.
+    fn join(self, other: Self) -> Self {
+        match (self, other) {
+            (a, b) if a == b => a,
+            (Self::Int, Self::Float) | (Self::Float, Self::Int) => Self::Float,
+            _ => Self::String,
+        }
+    }
+}
+
+impl fmt::Display for ElementType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Boolean => write!(f, "Boolean"),
+            Self::Int => write!(f, "Int"),
+            Self::Float => write!(f, "Float"),
+            Self::String => write!(f, "String"),
+        }
+    }
+}
+
+/// Creates a "heterogeneous array" diagnostic.
+///
+/// `widens` is whether or not the element widened the array to the common
+/// type, rather than being coerced to it.
+///
+/// Widening an `Int` to a `Float` is reported as a note, as the coercion is
+/// lossless; any other mixture is reported as a warning.
+fn heterogeneous_array(
+    common: ElementType,
+    element: ElementType,
+    widens: bool,
+    span: Span,
+) -> Diagnostic {
+    let message = format!("array literal elements are coerced to `{common}`");
+    let diagnostic = if common == ElementType::Float {
+        Diagnostic::note(message)
+    } else {
+        Diagnostic::warning(message)
+    };
+
+    let label = if widens {
+        format!("this `{element}` element widens the array to `Array[{common}]`")
+    } else {
+        format!("this `{element}` element is coerced to `{common}`")
+    };
+
+    diagnostic
+        .with_rule(ID)
+        .with_label(label, span)
+        .with_fix(if common == ElementType::Float {
+            "write the integer elements as floats (e.g. `1.0`)"
+        } else {
+            "change the elements of the array to have the same type"
+        })
+}
+
+/// Detects array literals with elements of different types.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct HeterogeneousArrayRule;
+
+impl Rule for HeterogeneousArrayRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags array literals with elements of different types."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "An array literal that mixes `Int`, `Float`, `String`, and `Boolean` elements (e.g. `[1, \
+         1.5, \"2\"]`) requires its elements to be coerced to a common type, which is usually a \
+         mistake. Mixing `Int` and `Float` elements is reported as a note, as the integers are \
+         widened to floats without loss; any other mixture is reported as a warning, as the \
+         elements can only be coerced to strings, which some engines do not support. Only \
+         arrays whose elements are all literals are checked; arrays containing names, calls, or \
+         other expressions are left to type checking."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Clarity, Tag::Correctness])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("heterogeneousarray"))
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+        Some(&[
+            SyntaxKind::VersionStatementNode,
+            SyntaxKind::TaskDefinitionNode,
+            SyntaxKind::WorkflowDefinitionNode,
+            SyntaxKind::BoundDeclNode,
+        ])
+    }
+}
+
+impl Visitor for HeterogeneousArrayRule {
+    type State = Diagnostics;
+
+    fn document(&mut self, _: &mut Self::State, _: VisitReason, _: &Document, _: SupportedVersion) {
+        // This is intentionally empty, as this rule has no state.
+    }
+
+    fn expr(&mut self, state: &mut Self::State, reason: VisitReason, expr: &Expr) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        let Expr::Literal(LiteralExpr::Array(array)) = expr else {
+            return;
+        };
+
+        let mut elements = Vec::new();
+        for element in array.elements() {
+            let Some(ty) = ElementType::of(&element) else {
+                return;
+            };
+
+            elements.push((ty, element.span()));
+        }
+
+        let Some(&(first, _)) = elements.first() else {
+            return;
+        };
+
+        // The last element to change the common type is the first element that
+        // forced the widening to the final common type
+        let mut common = first;
+        let mut widened = None;
+        for &(ty, span) in &elements[1..] {
+            let joined = common.join(ty);
+            if joined != common {
+                common = joined;
+                widened = Some((ty, span, true));
+            }
+        }
+
+        // Otherwise, the first element is of the common type and a later element
+        // is coerced to it (e.g. `[1.5, 1]`)
+        let Some((ty, span, widens)) = widened.or_else(|| {
+            elements
+                .iter()
+                .find(|(ty, _)| *ty != common)
+                .map(|&(ty, span)| (ty, span, false))
+        }) else {
+            return;
+        };
+
+        state.exceptable_add(
+            heterogeneous_array(common, ty, widens, span),
+            SyntaxElement::from(expr.syntax().clone()),
+            &self.exceptable_nodes(),
+        );
+    }
+}
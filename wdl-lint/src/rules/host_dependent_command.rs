@@ -0,0 +1,268 @@
+//! A lint rule for command sections that depend on the host environment.
+
+use wdl_ast::AstNode;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+use wdl_ast::v1::CommandPart;
+use wdl_ast::v1::CommandSection;
+
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+use crate::util::command_words;
+use crate::util::lines_with_offset;
+use crate::util::words;
+
+/// The identifier for the host dependent command rule.
+const ID: &str = "HostDependentCommand";
+
+/// The interpreters that are expected to be found on the `PATH`.
+///
+/// Versioned names (e.g. `python3.11`) are matched by their unversioned name.
+const INTERPRETERS: &[&str] = &[
+    "java", "julia", "node", "perl", "python", "R", "Rscript", "ruby",
+];
+
+/// The prefixes of paths within the user's home directory.
+const HOME_PREFIXES: &[&str] = &["~/", "$HOME/", "${HOME}/"];
+
+/// Creates a "shebang" diagnostic.
+fn shebang(line: &str, span: Span) -> Diagnostic {
+    Diagnostic::note(format!(
+        "shebang `{line}` has no effect in a command section"
+    ))
+    .with_rule(ID)
+    .with_highlight(span)
+    .with_fix(
+        "remove the shebang, as the command section is always run by the execution engine's \
+             shell",
+    )
+}
+
+/// Creates an "absolute interpreter path" diagnostic.
+fn absolute_interpreter(path: &str, name: &str, span: Span) -> Diagnostic {
+    Diagnostic::warning(format!(
+        "interpreter is invoked with the absolute path `{path}`"
+    ))
+    .with_rule(ID)
+    .with_highlight(span)
+    .with_fix(format!(
+        "invoke `{name}` by name so that it is found on the `PATH` of the task's environment"
+    ))
+    .with_replacement(span, name)
+}
+
+/// Creates a "user environment" diagnostic.
+fn user_environment(path: &str, span: Span) -> Diagnostic {
+    Diagnostic::warning(format!(
+        "command section relies on the user's environment by reading `{path}`"
+    ))
+    .with_rule(ID)
+    .with_highlight(span)
+    .with_fix(
+        "set up the environment in the command section or the task's container instead of \
+         reading files from the user's home directory",
+    )
+}
+
+/// Gets the name of the interpreter invoked by the given absolute path.
+///
+/// Returns `None` if the path is not absolute or is not to a known
+/// interpreter.
+fn interpreter(path: &str) -> Option<&str> {
+    if !path.starts_with('/') {
+        return None;
+    }
+
+    let name = &path[path.rfind('/')? + 1..];
+    let unversioned = name.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+    INTERPRETERS.contains(&unversioned).then_some(name)
+}
+
+/// A host-dependent construct found in a line of a command.
+#[derive(Debug, Clone, Copy)]
+enum Construct<'a> {
+    /// An interpreter invoked with an absolute path, with the name of the
+    /// interpreter.
+    AbsoluteInterpreter(&'a str),
+    /// A file in the user's home directory read with `source` or `.`.
+    UserEnvironment(&'a str),
+}
+
+/// Finds the host-dependent constructs in a line of a command.
+///
+/// `command` is whether or not the first word of the line is in command
+/// position; it is `false` for text that continues the line of a placeholder.
+///
+/// Returns the constructs with their offsets and lengths within the line.
+fn scan(line: &str, command: bool) -> Vec<(Construct<'_>, usize, usize)> {
+    let words = words(line);
+    let mut constructs = Vec::new();
+    for (i, offset, name) in command_words(&words, command) {
+        match name {
+            "source" | "." => {
+                if let Some(&(path_offset, path)) = words.get(i + 1) {
+                    let path = path.trim_end_matches(';');
+                    if HOME_PREFIXES.iter().any(|p| path.starts_with(p)) {
+                        constructs.push((
+                            Construct::UserEnvironment(path),
+                            offset,
+                            path_offset + path.len() - offset,
+                        ));
+                    }
+                }
+            }
+            _ => {
+                if let Some(program) = interpreter(name) {
+                    constructs.push((Construct::AbsoluteInterpreter(program), offset, name.len()));
+                }
+            }
+        }
+    }
+
+    constructs
+}
+
+/// Detects command sections that depend on the host environment.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct HostDependentCommandRule;
+
+impl Rule for HostDependentCommandRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Ensures that command sections do not depend on the environment of a particular host."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A command section is an embedded script that the execution engine runs with its own \
+         shell, possibly on a different host or in a container. A shebang line (e.g. \
+         `#!/bin/bash`) at the start of a command section has no effect and misleads readers \
+         about which interpreter runs the command. Invoking an interpreter by an absolute path \
+         (e.g. `/usr/local/bin/python3`) only works where it is installed at that path; invoke \
+         it by name instead. Reading files from the user's home directory (e.g. `source \
+         ~/.bashrc`) relies on an environment that is not part of the task. Unlike the \
+         `ShellCheck` rule, this rule does not require `shellcheck` to be installed."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Portability, Tag::Clarity])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("hostdependentcommand"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["BasicShellPortability", "ShellCheck"]
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+        Some(&[
+            SyntaxKind::VersionStatementNode,
+            SyntaxKind::TaskDefinitionNode,
+            SyntaxKind::CommandSectionNode,
+        ])
+    }
+}
+
+impl Visitor for HostDependentCommandRule {
+    type State = Diagnostics;
+
+    fn document(&mut self, _: &mut Self::State, _: VisitReason, _: &Document, _: SupportedVersion) {
+        // This rule has no state to reset.
+    }
+
+    fn command_section(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        section: &CommandSection,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        let element = SyntaxElement::from(section.syntax().clone());
+        let mut first_line = true;
+        let mut after_placeholder = false;
+        for part in section.parts() {
+            let text = match part {
+                CommandPart::Text(text) => text,
+                CommandPart::Placeholder(_) => {
+                    first_line = false;
+                    after_placeholder = true;
+                    continue;
+                }
+            };
+
+            let start = text.span().start();
+            for (i, (line, line_start, _)) in lines_with_offset(text.as_str()).enumerate() {
+                // Only the first line of the command could be a shebang; a shebang on a
+                // later line is usually part of a script written by the command
+                let trimmed = line.trim();
+                if first_line && !trimmed.is_empty() {
+                    first_line = false;
+                    if trimmed.starts_with("#!") {
+                        let offset = line.len() - line.trim_start().len();
+                        state.exceptable_add(
+                            shebang(
+                                trimmed,
+                                Span::new(start + line_start + offset, trimmed.len()),
+                            ),
+                            element.clone(),
+                            &self.exceptable_nodes(),
+                        );
+                        continue;
+                    }
+                }
+
+                // Text following a placeholder continues the placeholder's line
+                let command = i > 0 || !after_placeholder;
+                for (construct, offset, len) in scan(line, command) {
+                    let span = Span::new(start + line_start + offset, len);
+                    let diagnostic = match construct {
+                        Construct::AbsoluteInterpreter(name) => {
+                            absolute_interpreter(&line[offset..offset + len], name, span)
+                        }
+                        Construct::UserEnvironment(path) => user_environment(path, span),
+                    };
+
+                    state.exceptable_add(diagnostic, element.clone(), &self.exceptable_nodes());
+                }
+            }
+
+            after_placeholder = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn it_finds_interpreters() {
+        assert_eq!(interpreter("/usr/local/bin/python3"), Some("python3"));
+        assert_eq!(
+            interpreter("/opt/python/bin/python3.11"),
+            Some("python3.11")
+        );
+        assert_eq!(interpreter("/usr/bin/Rscript"), Some("Rscript"));
+        assert_eq!(interpreter("python3"), None);
+        assert_eq!(interpreter("/usr/bin/env"), None);
+        assert_eq!(interpreter("/bin/bash"), None);
+    }
+}
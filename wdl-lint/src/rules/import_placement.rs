@@ -59,6 +59,14 @@ impl Rule for ImportPlacementRule {
         TagSet::new(&[Tag::Clarity])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("importplacement"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["ImportSort", "ImportWhitespace"]
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [wdl_ast::SyntaxKind]> {
         Some(&[
             SyntaxKind::VersionStatementNode,
@@ -1,13 +1,13 @@
 //! A lint rule for ensuring that imports are sorted lexicographically.
 
 use wdl_ast::AstNode;
+use wdl_ast::AstToken;
 use wdl_ast::Diagnostic;
 use wdl_ast::Diagnostics;
 use wdl_ast::Document;
 use wdl_ast::Span;
 use wdl_ast::SupportedVersion;
 use wdl_ast::SyntaxKind;
-use wdl_ast::SyntaxNode;
 use wdl_ast::ToSpan;
 use wdl_ast::VisitReason;
 use wdl_ast::Visitor;
@@ -21,11 +21,15 @@ use crate::TagSet;
 const ID: &str = "ImportSort";
 
 /// Creates an import not sorted diagnostic.
-fn import_not_sorted(span: Span) -> Diagnostic {
+///
+/// The import block at the given span is replaced with the sorted import
+/// block.
+fn import_not_sorted(span: Span, block: Span, sorted: String) -> Diagnostic {
     Diagnostic::note("imports are not sorted lexicographically")
         .with_rule(ID)
         .with_highlight(span)
-        .with_fix("sort the imports lexicographically") // TODO: Provide the correct sorting
+        .with_fix("sort the imports lexicographically by URI")
+        .with_replacement(block, sorted)
 }
 
 /// Creates an improper comment diagnostic.
@@ -36,6 +40,113 @@ fn improper_comment(span: Span) -> Diagnostic {
         .with_fix("remove the comment from the import statement")
 }
 
+/// Represents an import statement along with the comments that move with it
+/// when the imports are sorted.
+#[derive(Debug)]
+struct SortableImport {
+    /// The import statement.
+    import: ImportStatement,
+    /// The sort key of the import (i.e. the lowercase URI).
+    key: String,
+    /// The span of the import statement, its preceding comments, and its
+    /// trailing comment.
+    span: Span,
+}
+
+/// Gets the imports at the start of the document that are sortable.
+///
+/// Imports following the first non-import document item are reported by the
+/// `ImportPlacement` rule and are not sorted.
+///
+/// Comments on the lines immediately preceding an import and a comment on the
+/// same line following an import belong to the import.
+fn sortable_imports(doc: &Document) -> Vec<SortableImport> {
+    let mut imports: Vec<SortableImport> = Vec::new();
+    let mut comments: Option<Span> = None;
+    let mut trailing = false;
+    for element in doc.syntax().children_with_tokens() {
+        match element.kind() {
+            SyntaxKind::ImportStatementNode => {
+                let import =
+                    ImportStatement::cast(element.into_node().unwrap()).expect("node should cast");
+                let span = import.syntax().text_range().to_span();
+                let start = comments.take().map(|c| c.start()).unwrap_or(span.start());
+                let uri = import.uri();
+                let key = uri
+                    .text()
+                    .map(|t| t.as_str().to_string())
+                    .unwrap_or_else(|| uri.syntax().text().to_string())
+                    .to_lowercase();
+
+                imports.push(SortableImport {
+                    import,
+                    key,
+                    span: Span::new(start, span.end() - start),
+                });
+                trailing = true;
+            }
+            SyntaxKind::Comment => {
+                let span = element.text_range().to_span();
+                match (trailing, imports.last_mut()) {
+                    (true, Some(import)) => {
+                        import.span =
+                            Span::new(import.span.start(), span.end() - import.span.start());
+                    }
+                    _ => {
+                        let start = comments.map(|c| c.start()).unwrap_or(span.start());
+                        comments = Some(Span::new(start, span.end() - start));
+                    }
+                }
+                trailing = false;
+            }
+            SyntaxKind::Whitespace => {
+                let text = element.as_token().unwrap().text();
+                if text.contains('\n') {
+                    trailing = false;
+                }
+
+                // A blank line separates comments from the following import
+                if text.matches('\n').count() > 1 {
+                    comments = None;
+                }
+            }
+            SyntaxKind::VersionStatementNode => {
+                comments = None;
+                trailing = false;
+            }
+            _ if !imports.is_empty() => break,
+            _ => {
+                comments = None;
+                trailing = false;
+            }
+        }
+    }
+
+    imports
+}
+
+/// Sorts the given imports and returns the span of the import block and the
+/// sorted import block.
+///
+/// The text between the imports (and their comments) is left in place.
+fn sort_imports(source: &str, imports: &[SortableImport]) -> (Span, String) {
+    let mut sorted: Vec<_> = imports.iter().collect();
+    sorted.sort_by(|a, b| a.key.cmp(&b.key));
+
+    let start = imports.first().expect("should have an import").span.start();
+    let end = imports.last().expect("should have an import").span.end();
+
+    let mut text = String::new();
+    for (i, import) in sorted.iter().enumerate() {
+        text.push_str(&source[import.span.start()..import.span.end()]);
+        if let Some(next) = imports.get(i + 1) {
+            text.push_str(&source[imports[i].span.end()..next.span.start()]);
+        }
+    }
+
+    (Span::new(start, end - start), text)
+}
+
 /// Detects imports that are not sorted lexicographically.
 #[derive(Default, Debug, Clone, Copy)]
 pub struct ImportSortRule;
@@ -51,15 +162,25 @@ impl Rule for ImportSortRule {
 
     fn explanation(&self) -> &'static str {
         "Imports should be sorted lexicographically to make it easier to find specific imports. \
-         This rule ensures that imports are sorted in a consistent manner. Specifically, the \
-         desired sort can be acheived with a GNU compliant `sort` and `LC_COLLATE=C`. No comments \
-         are permitted within an import statement."
+         This rule ensures that imports are sorted in a consistent manner. Specifically, imports \
+         are sorted by URI, ignoring case. Comments on the lines immediately preceding an import \
+         and a comment following an import on the same line move with the import when sorted. \
+         Imports that follow other document items are flagged by the `ImportPlacement` rule \
+         instead. No comments are permitted within an import statement."
     }
 
     fn tags(&self) -> TagSet {
         TagSet::new(&[Tag::Style, Tag::Clarity])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("importsort"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["ImportPlacement", "ImportWhitespace"]
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
         Some(&[SyntaxKind::VersionStatementNode])
     }
@@ -82,24 +203,19 @@ impl Visitor for ImportSortRule {
         // Reset the visitor upon document entry
         *self = Default::default();
 
-        let imports = doc
-            .syntax()
-            .children_with_tokens()
-            .filter(|c| c.kind() == SyntaxKind::ImportStatementNode)
-            .map(|c| c.into_node().unwrap());
-
-        let mut prev_import: Option<SyntaxNode> = None;
-        for import in imports {
-            if let Some(prev) = prev_import {
-                if import.text().to_string() < prev.text().to_string() {
-                    // Since this rule can only be excepted in a document-wide fashion,
-                    // if the rule is running we can directly add the diagnostic
-                    // without checking for the exceptable nodes
-                    state.add(import_not_sorted(import.text_range().to_span()));
-                    return; // Only report one sorting diagnostic at a time.
-                }
-            }
-            prev_import = Some(import);
+        // Only report one sorting diagnostic at a time.
+        let imports = sortable_imports(doc);
+        if let Some(w) = imports.windows(2).find(|w| w[1].key < w[0].key) {
+            let (block, sorted) = sort_imports(&doc.syntax().text().to_string(), &imports);
+
+            // Since this rule can only be excepted in a document-wide fashion,
+            // if the rule is running we can directly add the diagnostic
+            // without checking for the exceptable nodes
+            state.add(import_not_sorted(
+                w[1].import.keyword().span(),
+                block,
+                sorted,
+            ));
         }
     }
 
@@ -128,3 +244,64 @@ impl Visitor for ImportSortRule {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn it_sorts_imports_with_their_comments() {
+        let source = r#"## A preamble comment
+
+version 1.1
+
+# A comment on the first import
+import "Zeta.wdl" as zeta  # zeta
+
+# A detached comment that stays in place
+
+# The alpha import
+# spans two lines
+import "alpha.wdl" alias Foo as Bar  # alpha
+import "beta.wdl"
+
+struct Foo {}
+
+import "aaa.wdl"
+"#;
+
+        let (document, diagnostics) = Document::parse(source);
+        assert!(diagnostics.is_empty());
+
+        let imports = sortable_imports(&document);
+        assert_eq!(imports.len(), 3);
+
+        let (span, sorted) = sort_imports(source, &imports);
+        assert_eq!(
+            &source[span.start()..span.end()],
+            r#"# A comment on the first import
+import "Zeta.wdl" as zeta  # zeta
+
+# A detached comment that stays in place
+
+# The alpha import
+# spans two lines
+import "alpha.wdl" alias Foo as Bar  # alpha
+import "beta.wdl""#
+        );
+        assert_eq!(
+            sorted,
+            r#"# The alpha import
+# spans two lines
+import "alpha.wdl" alias Foo as Bar  # alpha
+
+# A detached comment that stays in place
+
+import "beta.wdl"
+# A comment on the first import
+import "Zeta.wdl" as zeta  # zeta"#
+        );
+    }
+}
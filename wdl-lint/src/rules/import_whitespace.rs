@@ -76,6 +76,14 @@ impl Rule for ImportWhitespaceRule {
         TagSet::new(&[Tag::Style, Tag::Clarity, Tag::Spacing])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("importwhitespace"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["ImportPlacement", "ImportSort"]
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
         Some(&[
             SyntaxKind::VersionStatementNode,
@@ -60,6 +60,14 @@ impl Rule for InconsistentNewlinesRule {
         TagSet::new(&[Tag::Style, Tag::Clarity])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("inconsistentnewlines"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["EndingNewline", "Whitespace"]
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
         Some(&[SyntaxKind::VersionStatementNode])
     }
@@ -0,0 +1,157 @@
+//! A lint rule for command sections that mix placeholder sigils.
+
+use wdl_ast::AstNode;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::ToSpan;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+use wdl_ast::support;
+use wdl_ast::v1::CommandPart;
+use wdl_ast::v1::CommandSection;
+use wdl_ast::v1::Placeholder;
+
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+
+/// The identifier for the inconsistent placeholder sigils rule.
+const ID: &str = "InconsistentPlaceholderSigils";
+
+/// Creates an "inconsistent placeholder sigils" diagnostic.
+///
+/// The given placeholders are those using the minority sigil; if they use the
+/// `$` sigil, replacements that swap the sigil to `~` are added.
+fn inconsistent_sigils(task: &str, placeholders: &[Placeholder], tilde: bool) -> Diagnostic {
+    let (sigil, other) = if tilde { ('~', '$') } else { ('$', '~') };
+    let mut diagnostic = Diagnostic::note(format!(
+        "command section of task `{task}` mixes `~{{}}` and `${{}}` placeholders"
+    ))
+    .with_rule(ID)
+    .with_fix(
+        "use `~{}` for every placeholder in the command section, as `~{}` cannot be mistaken for \
+         a shell variable reference",
+    );
+
+    for placeholder in placeholders {
+        diagnostic = diagnostic.with_label(
+            format!("this placeholder uses `{sigil}{{}}` while most use `{other}{{}}`"),
+            placeholder.syntax().text_range().to_span(),
+        );
+
+        // Swapping `$` to `~` is valid for every supported WDL version
+        if !tilde {
+            let open = support::token(placeholder.syntax(), SyntaxKind::PlaceholderOpen)
+                .expect("should have a placeholder open token");
+            let start = usize::from(open.text_range().start());
+            diagnostic = diagnostic.with_replacement(Span::new(start, 1), "~");
+        }
+    }
+
+    diagnostic
+}
+
+/// Detects brace command sections that mix `~{}` and `${}` placeholders.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct InconsistentPlaceholderSigilsRule;
+
+impl Rule for InconsistentPlaceholderSigilsRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Ensures that a command section does not mix `~{}` and `${}` placeholders."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Within a command section using curly braces, placeholders may begin with either `~{` or \
+         `${`. Mixing the two in a single command section is confusing, as `${}` is easily \
+         mistaken for a shell variable reference, and is error-prone when converting the \
+         command section to heredoc syntax, where `${}` is not a placeholder. The placeholders \
+         using the less common sigil are flagged; `~{}` should be used for every placeholder. \
+         Heredoc command sections are not checked."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Clarity, Tag::Style])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("inconsistentplaceholdersigils"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["NoCurlyCommands"]
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+        Some(&[
+            SyntaxKind::VersionStatementNode,
+            SyntaxKind::TaskDefinitionNode,
+            SyntaxKind::CommandSectionNode,
+        ])
+    }
+}
+
+impl Visitor for InconsistentPlaceholderSigilsRule {
+    type State = Diagnostics;
+
+    fn document(
+        &mut self,
+        _: &mut Self::State,
+        reason: VisitReason,
+        _: &Document,
+        _: SupportedVersion,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        // Reset the visitor upon document entry
+        *self = Default::default();
+    }
+
+    fn command_section(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        section: &CommandSection,
+    ) {
+        // Only `~{}` is a placeholder in a heredoc command section
+        if reason == VisitReason::Exit || section.is_heredoc() {
+            return;
+        }
+
+        let (tilde, dollar): (Vec<_>, Vec<_>) = section
+            .parts()
+            .filter_map(|part| match part {
+                CommandPart::Placeholder(p) => Some(p),
+                CommandPart::Text(_) => None,
+            })
+            .partition(Placeholder::has_tilde);
+
+        if tilde.is_empty() || dollar.is_empty() {
+            return;
+        }
+
+        // Ties flag the `$` sigil as `~` is preferred
+        let (placeholders, tilde) = if tilde.len() < dollar.len() {
+            (tilde, true)
+        } else {
+            (dollar, false)
+        };
+
+        state.exceptable_add(
+            inconsistent_sigils(section.parent().name().as_str(), &placeholders, tilde),
+            SyntaxElement::from(section.syntax().clone()),
+            &self.exceptable_nodes(),
+        );
+    }
+}
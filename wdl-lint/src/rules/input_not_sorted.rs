@@ -1,11 +1,16 @@
 //! A lint rule for sorting of inputs.
 
 use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
 
+use anyhow::bail;
+use serde::Deserialize;
 use wdl_ast::AstNode;
 use wdl_ast::AstToken;
 use wdl_ast::Diagnostic;
 use wdl_ast::Diagnostics;
+use wdl_ast::Direction;
 use wdl_ast::Document;
 use wdl_ast::Span;
 use wdl_ast::SupportedVersion;
@@ -20,6 +25,7 @@ use wdl_ast::v1::PrimitiveType;
 use crate::Rule;
 use crate::Tag;
 use crate::TagSet;
+use crate::config::rule_options;
 
 /// The identifier for the input not sorted rule.
 const ID: &str = "InputSorting";
@@ -32,6 +38,137 @@ fn input_not_sorted(span: Span, sorted_inputs: String) -> Diagnostic {
         .with_fix(format!("sort input statements as: \n{}", sorted_inputs))
 }
 
+/// Creates a "input out of order" diagnostic.
+fn input_out_of_order(
+    decl: &v1::Decl,
+    group: InputGroup,
+    before: &v1::Decl,
+    groups: &[InputGroup; 3],
+    sorted_inputs: String,
+) -> Diagnostic {
+    let name = decl.name();
+    let order = groups
+        .iter()
+        .map(|g| format!("{g} inputs"))
+        .collect::<Vec<_>>()
+        .join(", then ");
+
+    Diagnostic::note(format!(
+        "input `{name}` is not sorted",
+        name = name.as_str()
+    ))
+    .with_rule(ID)
+    .with_label(
+        format!(
+            "this {group} input should come before `{before}`",
+            before = before.name().as_str()
+        ),
+        name.span(),
+    )
+    .with_fix(format!(
+        "sort the inputs as {order}, each alphabetically: \n{sorted_inputs}"
+    ))
+}
+
+/// Represents a group of input declarations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputGroup {
+    /// Inputs without a default value that are not optional.
+    Required,
+    /// Inputs with a default value.
+    Defaulted,
+    /// Inputs without a default value that are optional.
+    Optional,
+}
+
+impl InputGroup {
+    /// Classifies the given input declaration.
+    fn of(decl: &v1::Decl) -> Self {
+        match decl {
+            v1::Decl::Bound(_) => Self::Defaulted,
+            v1::Decl::Unbound(u) if u.ty().is_optional() => Self::Optional,
+            v1::Decl::Unbound(_) => Self::Required,
+        }
+    }
+}
+
+impl fmt::Display for InputGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Required => write!(f, "required"),
+            Self::Defaulted => write!(f, "defaulted"),
+            Self::Optional => write!(f, "optional"),
+        }
+    }
+}
+
+impl FromStr for InputGroup {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "required" => Ok(Self::Required),
+            "defaulted" => Ok(Self::Defaulted),
+            "optional" => Ok(Self::Optional),
+            _ => {
+                bail!("unknown input group `{s}`; expected `required`, `defaulted`, or `optional`")
+            }
+        }
+    }
+}
+
+/// The default order of input groups when sorting inputs by group.
+pub const DEFAULT_INPUT_GROUP_ORDER: [InputGroup; 3] = [
+    InputGroup::Required,
+    InputGroup::Defaulted,
+    InputGroup::Optional,
+];
+
+/// Gets the text of a declaration along with its attached comments.
+///
+/// Comments on the lines immediately preceding the declaration and a comment
+/// on the same line following the declaration are attached to it.
+fn decl_text(decl: &v1::Decl) -> String {
+    let syntax = decl.syntax();
+    let mut preceding = Vec::new();
+    let mut element = syntax.prev_sibling_or_token();
+    while let Some(e) = element {
+        match e.kind() {
+            SyntaxKind::Whitespace if e.to_string().matches('\n').count() <= 1 => {}
+            SyntaxKind::Comment
+                if e.prev_sibling_or_token().is_some_and(|p| {
+                    p.kind() != SyntaxKind::Whitespace || p.to_string().contains('\n')
+                }) =>
+            {
+                preceding.push(e.to_string());
+            }
+            _ => break,
+        }
+
+        element = e.prev_sibling_or_token();
+    }
+
+    let mut text = String::new();
+    for comment in preceding.iter().rev() {
+        text.push_str(comment);
+        text.push('\n');
+    }
+
+    text.push_str(&syntax.text().to_string());
+
+    let trailing = syntax
+        .siblings_with_tokens(Direction::Next)
+        .skip(1)
+        .take_while(|e| e.kind() != SyntaxKind::Whitespace || !e.to_string().contains('\n'))
+        .find(|e| e.kind() == SyntaxKind::Comment);
+    if let Some(comment) = trailing {
+        text.push_str("  ");
+        text.push_str(&comment.to_string());
+    }
+
+    text
+}
+
 /// Define an ordering for declarations.
 fn decl_index(decl: &v1::Decl) -> usize {
     match decl {
@@ -187,8 +324,83 @@ fn compare_decl(a: &v1::Decl, b: &v1::Decl) -> Ordering {
 }
 
 /// Detects unsorted input declarations.
+///
+/// By default, inputs are sorted by the opinionated type-based sort described
+/// in the rule's explanation. Alternatively, inputs may be sorted by group and
+/// then alphabetically by name.
 #[derive(Default, Debug, Clone, Copy)]
-pub struct InputNotSortedRule;
+pub struct InputNotSortedRule {
+    /// The order of input groups, if inputs are sorted by group.
+    groups: Option<[InputGroup; 3]>,
+}
+
+impl InputNotSortedRule {
+    /// Constructs a new input sorting rule that sorts inputs by the given
+    /// order of groups and then alphabetically by name within each group.
+    pub fn new(groups: [InputGroup; 3]) -> Self {
+        Self {
+            groups: Some(groups),
+        }
+    }
+
+    /// Gets the order of input groups, if inputs are sorted by group.
+    pub fn groups(&self) -> Option<[InputGroup; 3]> {
+        self.groups
+    }
+
+    /// Checks that the given input declarations are sorted by group.
+    ///
+    /// Only the first declaration out of order is reported.
+    fn check_groups(
+        &self,
+        state: &mut Diagnostics,
+        input: &v1::InputSection,
+        groups: &[InputGroup; 3],
+    ) {
+        let key = |decl: &v1::Decl| {
+            let group = InputGroup::of(decl);
+            let rank = groups
+                .iter()
+                .position(|g| *g == group)
+                .unwrap_or(groups.len());
+            let name = decl.name().as_str().to_string();
+            (rank, name.to_lowercase(), name)
+        };
+
+        let decls: Vec<_> = input.declarations().collect();
+        let Some(index) = (1..decls.len()).find(|i| key(&decls[*i]) < key(&decls[i - 1])) else {
+            return;
+        };
+
+        // The declaration should come before the first declaration that sorts after it
+        let decl = &decls[index];
+        let before = decls[..index]
+            .iter()
+            .find(|d| key(decl) < key(*d))
+            .expect("should have a preceding declaration");
+
+        let mut sorted = decls.clone();
+        sorted.sort_by_key(key);
+        let sorted_inputs = sorted
+            .iter()
+            .map(|d| decl_text(d) + "\n")
+            .collect::<String>();
+
+        state.exceptable_add(
+            input_out_of_order(decl, InputGroup::of(decl), before, groups, sorted_inputs),
+            SyntaxElement::from(input.syntax().clone()),
+            &self.exceptable_nodes(),
+        );
+    }
+}
+
+/// The options of the input sorting rule.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Options {
+    /// The order of input groups, if inputs are sorted by group.
+    groups: Option<Vec<String>>,
+}
 
 impl Rule for InputNotSortedRule {
     fn id(&self) -> &'static str {
@@ -200,27 +412,68 @@ impl Rule for InputNotSortedRule {
     }
 
     fn explanation(&self) -> &'static str {
-        "Each input declaration section should be sorted. This rule enforces an opinionated \
-         sorting. First sorts by 1. required inputs, 2. optional inputs without defaults, 3. \
+        "Each input declaration section should be sorted. By default, this rule enforces an \
+         opinionated sorting. First sorts by 1. required inputs, 2. optional inputs without defaults, 3. \
          optional inputs with defaults, and 4. inputs with a default value. Then by the type: 1. \
          File, 2. Array[*]+, 3. Array[*], 4. struct, 5. Object, 6. Map[*, *], 7. Pair[*, *], 8. \
          String, 9. Boolean, 10. Float, 11. Int. For ordering of the same compound type (Array[*], \
          Map[*, *], Pair[*, *]), drop the outermost type (Array, Map, etc.) and recursively apply \
          above sorting on the first inner type *, with ties broken by the second inner type. \
          Continue this pattern as far as possible. Once this ordering is satisfied, it is up to \
-         the developer for final order of inputs of the same type."
+         the developer for final order of inputs of the same type. Alternatively, the rule may be \
+         configured to sort inputs by group (by default, required inputs, then inputs with a \
+         default value, then optional inputs) and then alphabetically within each group, \
+         reporting only the first input that is out of order."
     }
 
     fn tags(&self) -> TagSet {
         TagSet::new(&[Tag::Style, Tag::Clarity, Tag::Sorting])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("inputsorting"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["SectionOrdering"]
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [wdl_ast::SyntaxKind]> {
         Some(&[
             SyntaxKind::VersionStatementNode,
             SyntaxKind::InputSectionNode,
         ])
     }
+
+    fn configure(&mut self, options: &toml::Table) -> anyhow::Result<()> {
+        let options: Options = rule_options(options)?;
+
+        if let Some(groups) = options.groups {
+            let groups = groups
+                .iter()
+                .map(|g| g.parse())
+                .collect::<anyhow::Result<Vec<InputGroup>>>()?;
+
+            let groups: [InputGroup; 3] = groups
+                .try_into()
+                .ok()
+                .filter(|groups: &[InputGroup; 3]| {
+                    DEFAULT_INPUT_GROUP_ORDER
+                        .iter()
+                        .all(|group| groups.contains(group))
+                })
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "option `groups` must list each of `required`, `defaulted`, and \
+                         `optional` once"
+                    )
+                })?;
+
+            self.groups = Some(groups);
+        }
+
+        Ok(())
+    }
 }
 
 impl Visitor for InputNotSortedRule {
@@ -238,7 +491,9 @@ impl Visitor for InputNotSortedRule {
         }
 
         // Reset the visitor upon document entry
-        *self = Default::default();
+        *self = Self {
+            groups: self.groups,
+        };
     }
 
     fn input_section(
@@ -251,6 +506,11 @@ impl Visitor for InputNotSortedRule {
             return;
         }
 
+        if let Some(groups) = self.groups {
+            self.check_groups(state, input, &groups);
+            return;
+        }
+
         // Get input section declarations
         let decls: Vec<_> = input.declarations().collect();
         let mut sorted_decls = decls.clone();
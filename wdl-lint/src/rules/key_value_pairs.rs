@@ -91,6 +91,14 @@ impl Rule for KeyValuePairsRule {
         TagSet::new(&[Tag::Style])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("keyvaluepairs"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["TrailingComma", "LineWidth"]
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
         Some(&[
             SyntaxKind::VersionStatementNode,
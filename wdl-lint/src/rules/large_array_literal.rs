@@ -0,0 +1,166 @@
+//! A lint rule for very large array literals in declarations.
+
+use serde::Deserialize;
+use wdl_ast::AstNode;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::ToSpan;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+use wdl_ast::v1::Expr;
+use wdl_ast::v1::LiteralExpr;
+
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+use crate::config::rule_options;
+
+/// The identifier for the large array literal rule.
+const ID: &str = "LargeArrayLiteral";
+
+/// The default maximum number of elements of an array literal.
+pub const DEFAULT_MAX_ELEMENTS: usize = 100;
+
+/// Creates a "large array literal" diagnostic.
+fn large_array_literal(count: usize, max: usize, span: Span) -> Diagnostic {
+    Diagnostic::note(format!(
+        "array literal has {count} elements, which exceeds the maximum of {max}"
+    ))
+    .with_rule(ID)
+    .with_label("this array literal is too large to maintain inline", span)
+    .with_fix(
+        "move the elements to a file that is checked in alongside the document and read it with \
+         `read_lines` or `read_tsv`",
+    )
+}
+
+/// Detects very large array literals in declarations.
+#[derive(Debug, Clone, Copy)]
+pub struct LargeArrayLiteralRule {
+    /// The maximum number of elements of an array literal.
+    max_elements: usize,
+}
+
+impl LargeArrayLiteralRule {
+    /// Constructs a new large array literal rule with the given maximum
+    /// number of elements.
+    pub fn new(max_elements: usize) -> Self {
+        Self { max_elements }
+    }
+
+    /// Gets the maximum number of elements of an array literal.
+    pub fn max_elements(&self) -> usize {
+        self.max_elements
+    }
+}
+
+impl Default for LargeArrayLiteralRule {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_ELEMENTS)
+    }
+}
+
+/// The options of the large array literal rule.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Options {
+    /// The maximum number of elements of an array literal.
+    max_elements: Option<usize>,
+}
+
+impl Rule for LargeArrayLiteralRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags very large array literals in declarations."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Declarations and input defaults containing very large array literals (e.g. thousands of \
+         sample names) slow down parsing and formatting and make changes to the document \
+         difficult to review. Such values should instead be kept in a file that is checked in \
+         alongside the document and read with `read_lines` or `read_tsv`. Array literals with \
+         more than a configurable number of elements (100 by default) are flagged; the elements \
+         of nested array literals are counted separately."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Clarity])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("largearrayliteral"))
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+        Some(&[
+            SyntaxKind::VersionStatementNode,
+            SyntaxKind::TaskDefinitionNode,
+            SyntaxKind::WorkflowDefinitionNode,
+            SyntaxKind::InputSectionNode,
+            SyntaxKind::BoundDeclNode,
+        ])
+    }
+
+    fn configure(&mut self, options: &toml::Table) -> anyhow::Result<()> {
+        let options: Options = rule_options(options)?;
+
+        if let Some(max_elements) = options.max_elements {
+            self.max_elements = max_elements;
+        }
+
+        Ok(())
+    }
+}
+
+impl Visitor for LargeArrayLiteralRule {
+    type State = Diagnostics;
+
+    fn document(&mut self, _: &mut Self::State, _: VisitReason, _: &Document, _: SupportedVersion) {
+        // This rule has no state to reset.
+    }
+
+    fn expr(&mut self, state: &mut Self::State, reason: VisitReason, expr: &Expr) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        let Expr::Literal(LiteralExpr::Array(array)) = expr else {
+            return;
+        };
+
+        // Only array literals within declarations are checked
+        if !expr
+            .syntax()
+            .ancestors()
+            .any(|n| n.kind() == SyntaxKind::BoundDeclNode)
+        {
+            return;
+        }
+
+        let count = array.elements().count();
+        if count <= self.max_elements {
+            return;
+        }
+
+        // Only the opening bracket is highlighted, as the array spans many lines
+        let span = array
+            .syntax()
+            .first_token()
+            .map(|t| t.text_range().to_span())
+            .unwrap_or_else(|| array.syntax().text_range().to_span());
+
+        state.exceptable_add(
+            large_array_literal(count, self.max_elements, span),
+            SyntaxElement::from(expr.syntax().clone()),
+            &self.exceptable_nodes(),
+        );
+    }
+}
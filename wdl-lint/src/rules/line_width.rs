@@ -1,5 +1,9 @@
 //! Ensures that lines do not exceed a certain width.
 
+use rowan::TextRange;
+use rowan::TextSize;
+use serde::Deserialize;
+use wdl_ast::AstNodeExt;
 use wdl_ast::AstToken;
 use wdl_ast::Diagnostic;
 use wdl_ast::Diagnostics;
@@ -15,6 +19,7 @@ use wdl_ast::v1;
 use crate::Rule;
 use crate::Tag;
 use crate::TagSet;
+use crate::config::rule_options;
 
 /// The identifier for the line width rule.
 const ID: &str = "LineWidth";
@@ -27,11 +32,49 @@ fn line_too_long(span: Span, max_width: usize) -> Diagnostic {
         .with_fix("split the line into multiple lines")
 }
 
+/// Determines if a line consists of a single unbreakable token, such as a
+/// long URL in a comment.
+///
+/// Any leading comment markers are not considered part of the token.
+fn is_unbreakable(line: &str) -> bool {
+    !line
+        .trim()
+        .trim_start_matches('#')
+        .trim_start()
+        .contains(char::is_whitespace)
+}
+
+/// Gets the text of the given span of the document containing the given
+/// element.
+fn document_text(element: &SyntaxElement, span: Span) -> String {
+    let root = element
+        .ancestors()
+        .last()
+        .expect("element should be part of a document");
+
+    root.text()
+        .slice(TextRange::new(
+            TextSize::new(span.start() as u32),
+            TextSize::new(span.end() as u32),
+        ))
+        .to_string()
+}
+
 /// Detects lines that exceed a certain width.
+///
+/// Optionally, lines within a command section and lines that consist of a
+/// single unbreakable token are not checked.
 #[derive(Clone, Copy, Debug)]
 pub struct LineWidthRule {
     /// The maximum width of a line.
     max_width: usize,
+    /// Whether or not lines within a command section are ignored.
+    ignore_command_sections: bool,
+    /// Whether or not lines that consist of a single unbreakable token are
+    /// ignored.
+    ignore_unbreakable_lines: bool,
+    /// The end offset of the last command section entered (if it exists).
+    command_section_end: Option<usize>,
     /// The offset of the last newline character seen (if it exists).
     previous_newline_offset: Option<usize>,
     /// Whether we are in a section that should be ignored.
@@ -47,6 +90,36 @@ impl LineWidthRule {
         }
     }
 
+    /// Sets whether or not lines within a command section are ignored.
+    pub fn with_ignore_command_sections(mut self, ignore: bool) -> Self {
+        self.ignore_command_sections = ignore;
+        self
+    }
+
+    /// Sets whether or not lines that consist of a single unbreakable token
+    /// (such as a long URL in a comment) are ignored.
+    pub fn with_ignore_unbreakable_lines(mut self, ignore: bool) -> Self {
+        self.ignore_unbreakable_lines = ignore;
+        self
+    }
+
+    /// Determines if the line with the given span is ignored.
+    fn is_ignored(&self, span: Span, element: &SyntaxElement) -> bool {
+        if self.ignored_section {
+            return true;
+        }
+
+        if self.ignore_command_sections
+            && self
+                .command_section_end
+                .is_some_and(|end| span.start() < end)
+        {
+            return true;
+        }
+
+        self.ignore_unbreakable_lines && is_unbreakable(&document_text(element, span))
+    }
+
     /// Detects lines that exceed a certain width.
     fn detect_line_too_long(
         &mut self,
@@ -65,9 +138,8 @@ impl LineWidthRule {
             let previous_offset = self.previous_newline_offset.unwrap_or_default();
             let length = current_offset - previous_offset;
 
-            if !self.ignored_section && length > self.max_width {
-                let span = Span::new(previous_offset, length);
-
+            let span = Span::new(previous_offset, length);
+            if length > self.max_width && !self.is_ignored(span, &element) {
                 state.exceptable_add(
                     line_too_long(span, self.max_width),
                     element.clone(),
@@ -85,12 +157,28 @@ impl Default for LineWidthRule {
     fn default() -> Self {
         Self {
             max_width: 90,
+            ignore_command_sections: false,
+            ignore_unbreakable_lines: false,
+            command_section_end: None,
             previous_newline_offset: None,
             ignored_section: false,
         }
     }
 }
 
+/// The options of the line width rule.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Options {
+    /// The maximum width of a line.
+    max_width: Option<usize>,
+    /// Whether or not lines within a command section are ignored.
+    ignore_command_sections: Option<bool>,
+    /// Whether or not lines that consist of a single unbreakable token are
+    /// ignored.
+    ignore_unbreakable_lines: Option<bool>,
+}
+
 impl Rule for LineWidthRule {
     fn id(&self) -> &'static str {
         ID
@@ -103,16 +191,47 @@ impl Rule for LineWidthRule {
     fn explanation(&self) -> &'static str {
         "Lines should not exceed a certain width to make it easier to read and understand the \
          code. Code within the either the meta or parameter meta sections is not checked. Comments \
-         are included in the line width check. The current maximum width is 90 characters."
+         are included in the line width check. The maximum width is 90 characters by default and \
+         is configurable with the `max_width` option. Lines within a command section, where long \
+         tool invocations are sometimes unavoidable, may be ignored with the \
+         `ignore_command_sections` option, and lines that consist of a single unbreakable token \
+         (such as a long URL in a comment) may be ignored with the `ignore_unbreakable_lines` \
+         option."
     }
 
     fn tags(&self) -> TagSet {
         TagSet::new(&[Tag::Style, Tag::Clarity, Tag::Spacing])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("linewidth"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["ExpressionSpacing", "KeyValuePairs"]
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [wdl_ast::SyntaxKind]> {
         None
     }
+
+    fn configure(&mut self, options: &toml::Table) -> anyhow::Result<()> {
+        let options: Options = rule_options(options)?;
+
+        if let Some(max_width) = options.max_width {
+            self.max_width = max_width;
+        }
+
+        if let Some(ignore) = options.ignore_command_sections {
+            self.ignore_command_sections = ignore;
+        }
+
+        if let Some(ignore) = options.ignore_unbreakable_lines {
+            self.ignore_unbreakable_lines = ignore;
+        }
+
+        Ok(())
+    }
 }
 
 impl Visitor for LineWidthRule {
@@ -132,6 +251,8 @@ impl Visitor for LineWidthRule {
         // Reset the visitor upon document entry
         *self = Self {
             max_width: self.max_width,
+            ignore_command_sections: self.ignore_command_sections,
+            ignore_unbreakable_lines: self.ignore_unbreakable_lines,
             ..Default::default()
         };
     }
@@ -159,6 +280,17 @@ impl Visitor for LineWidthRule {
         );
     }
 
+    fn command_section(
+        &mut self,
+        _: &mut Self::State,
+        reason: VisitReason,
+        section: &v1::CommandSection,
+    ) {
+        if reason == VisitReason::Enter {
+            self.command_section_end = Some(section.span().end());
+        }
+    }
+
     fn metadata_section(
         &mut self,
         _: &mut Self::State,
@@ -0,0 +1,252 @@
+//! A lint rule for `File` inputs that do not need to be localized.
+
+use wdl_ast::AstNode;
+use wdl_ast::AstNodeExt;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+use wdl_ast::v1::CallExpr;
+use wdl_ast::v1::NameRef;
+use wdl_ast::v1::PrimitiveTypeKind;
+use wdl_ast::v1::TaskDefinition;
+use wdl_ast::v1::Type;
+use wdl_ast::version::V1;
+
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+use crate::util::LOCALIZATION_OPTIONAL;
+use crate::util::is_localization_optional;
+
+/// The identifier for the localization optional rule.
+const ID: &str = "LocalizationOptional";
+
+/// The standard library functions that read the contents of a file.
+const READ_FUNCTIONS: &[&str] = &[
+    "read_boolean",
+    "read_float",
+    "read_int",
+    "read_json",
+    "read_lines",
+    "read_map",
+    "read_object",
+    "read_objects",
+    "read_string",
+    "read_tsv",
+];
+
+/// Creates a "localization not required" diagnostic.
+fn localization_not_required(
+    task: &str,
+    input: &str,
+    version: SupportedVersion,
+    span: Span,
+) -> Diagnostic {
+    let fix = if version >= SupportedVersion::V1(V1::Two) {
+        format!(
+            "mark the input with a `{LOCALIZATION_OPTIONAL}` hint in the `hints` section: \
+             `inputs: input {{ {input}: hints {{ {LOCALIZATION_OPTIONAL}: true }} }}`"
+        )
+    } else {
+        format!(
+            "mark the input with a `{LOCALIZATION_OPTIONAL}` hint in the `parameter_meta` \
+             section: `{input}: {{ {LOCALIZATION_OPTIONAL}: true }}`"
+        )
+    };
+
+    Diagnostic::note(format!(
+        "input `{input}` of task `{task}` is only read by `read_*` functions"
+    ))
+    .with_rule(ID)
+    .with_label(
+        "this input does not need to be localized into the container",
+        span,
+    )
+    .with_fix(fix)
+}
+
+/// Creates a "localization optional input in command" diagnostic.
+fn localization_optional_in_command(task: &str, input: &str, span: Span) -> Diagnostic {
+    Diagnostic::warning(format!(
+        "input `{input}` of task `{task}` is marked `{LOCALIZATION_OPTIONAL}` but is referenced \
+         in the command"
+    ))
+    .with_rule(ID)
+    .with_label(
+        "this input may not be localized when the command runs",
+        span,
+    )
+    .with_fix(format!(
+        "remove the `{LOCALIZATION_OPTIONAL}` hint from input `{input}` or stop referencing it \
+         in the command"
+    ))
+}
+
+/// Represents how the references to an input within a task are used.
+#[derive(Debug, Default)]
+struct Usage {
+    /// The span of the first reference within the command section.
+    command: Option<Span>,
+    /// Whether or not the input is passed directly to a `read_*` function.
+    read: bool,
+    /// Whether or not the input is referenced anywhere else.
+    other: bool,
+}
+
+/// Determines how the given input is used within a task.
+fn usage(task: &TaskDefinition, input: &str) -> Usage {
+    let mut usage = Usage::default();
+    for name in task.syntax().descendants().filter_map(NameRef::cast) {
+        if name.name().as_str() != input {
+            continue;
+        }
+
+        if name
+            .syntax()
+            .ancestors()
+            .any(|n| n.kind() == SyntaxKind::CommandSectionNode)
+        {
+            usage.command.get_or_insert(name.span());
+        } else if name
+            .syntax()
+            .parent()
+            .and_then(CallExpr::cast)
+            .is_some_and(|c| READ_FUNCTIONS.contains(&c.target().as_str()))
+        {
+            usage.read = true;
+        } else {
+            usage.other = true;
+        }
+    }
+
+    usage
+}
+
+/// Detects `File` inputs that are only read by `read_*` functions but are
+/// localized, and inputs that are not localized but are referenced in the
+/// command.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct LocalizationOptionalRule {
+    /// The version of the document being linted.
+    version: Option<SupportedVersion>,
+}
+
+impl Rule for LocalizationOptionalRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Ensures that `File` inputs are marked `localization_optional` only when they are not \
+         used by the command."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A `File` input that is only passed to `read_*` functions (e.g. `read_lines` or \
+         `read_json`) is read by the engine and never by the command, so it does not need to be \
+         localized into the task's container. Marking such an input with the \
+         `localization_optional` hint avoids needlessly copying it. Conversely, an input marked \
+         `localization_optional` that is referenced in the command may not exist where the \
+         command expects it. Inputs that are referenced in any other way are not flagged."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Correctness])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("localizationoptional"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["ScatterInvariantInput"]
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+        Some(&[
+            SyntaxKind::VersionStatementNode,
+            SyntaxKind::TaskDefinitionNode,
+            SyntaxKind::InputSectionNode,
+            SyntaxKind::BoundDeclNode,
+            SyntaxKind::UnboundDeclNode,
+        ])
+    }
+}
+
+impl Visitor for LocalizationOptionalRule {
+    type State = Diagnostics;
+
+    fn document(
+        &mut self,
+        _: &mut Self::State,
+        reason: VisitReason,
+        _: &Document,
+        version: SupportedVersion,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        // Reset the visitor upon document entry
+        *self = Self {
+            version: Some(version),
+        };
+    }
+
+    fn task_definition(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        task: &TaskDefinition,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        let inputs = task
+            .input()
+            .into_iter()
+            .flat_map(|section| section.declarations())
+            .filter(|decl| {
+                matches!(
+                    decl.ty(),
+                    Type::Primitive(ty) if ty.kind() == PrimitiveTypeKind::File
+                )
+            });
+
+        for decl in inputs {
+            let name = decl.name();
+            let usage = usage(task, name.as_str());
+            let diagnostic = if is_localization_optional(task, name.as_str()) {
+                match usage.command {
+                    Some(span) => {
+                        localization_optional_in_command(task.name().as_str(), name.as_str(), span)
+                    }
+                    None => continue,
+                }
+            } else if usage.read && !usage.other && usage.command.is_none() {
+                localization_not_required(
+                    task.name().as_str(),
+                    name.as_str(),
+                    self.version.expect("document should have a version"),
+                    name.span(),
+                )
+            } else {
+                continue;
+            };
+
+            state.exceptable_add(
+                diagnostic,
+                SyntaxElement::from(decl.syntax().clone()),
+                &self.exceptable_nodes(),
+            );
+        }
+    }
+}
@@ -8,6 +8,7 @@ use wdl_ast::Span;
 use wdl_ast::SupportedVersion;
 use wdl_ast::VisitReason;
 use wdl_ast::Visitor;
+use wdl_ast::trailed_node;
 
 use crate::Rule;
 use crate::Tag;
@@ -86,7 +87,8 @@ impl Rule for MalformedLintDirectiveRule {
 
     fn explanation(&self) -> &'static str {
         "Comments which begin with `#@` must only contain valid lint directives. Lint directives \
-         must be on their own line, only preceded by whitespace. Lint directives should follow the \
+         must be on their own line, only preceded by whitespace, unless they trail the element \
+         they apply to on the same line (e.g. a declaration). Lint directives should follow the \
          pattern `#@ <directive>: <value>` _exactly_. Currently the only accepted lint directive \
          is `except`. For example, `#@ except: MalformedLintDirective`."
     }
@@ -95,6 +97,18 @@ impl Rule for MalformedLintDirectiveRule {
         TagSet::new(&[Tag::Clarity, Tag::Correctness])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("malformedlintdirective"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &[
+            "MisplacedLintDirective",
+            "UnknownRule",
+            "UnusedLintDirective",
+        ]
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [wdl_ast::SyntaxKind]> {
         None
     }
@@ -111,7 +125,8 @@ impl Visitor for MalformedLintDirectiveRule {
         if let Some(lint_directive) = comment.as_str().strip_prefix("#@") {
             let base_offset = comment.span().start();
 
-            if is_inline_comment(comment) {
+            // A directive may trail the element it excepts on the same line
+            if is_inline_comment(comment) && trailed_node(comment.syntax()).is_none() {
                 state.add(inline_lint_directive(comment.span()));
             }
 
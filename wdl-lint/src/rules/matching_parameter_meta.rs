@@ -58,7 +58,7 @@ fn extra_param_meta(parent: &SectionParent, extra: &str, span: Span) -> Diagnost
         SectionParent::Struct(s) => ("struct", s.name()),
     };
 
-    Diagnostic::note(format!(
+    Diagnostic::warning(format!(
         "{context} `{parent}` has an extraneous parameter metadata key named `{extra}`",
         parent = parent.as_str(),
     ))
@@ -89,17 +89,29 @@ impl Rule for MatchingParameterMetaRule {
     fn explanation(&self) -> &'static str {
         "Each input parameter within a task or workflow should have an associated `parameter_meta` \
          entry with a detailed description of the input. Non-input keys are not permitted within \
-         the `parameter_meta` block."
+         the `parameter_meta` block, as they are usually left behind when an input is renamed or \
+         removed. The order of the entries is not checked."
     }
 
     fn tags(&self) -> TagSet {
         TagSet::new(&[Tag::Completeness])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("matchingparametermeta"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["MissingMetas", "NonmatchingOutput"]
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
         Some(&[
             SyntaxKind::VersionStatementNode,
             SyntaxKind::ParameterMetadataSectionNode,
+            SyntaxKind::BoundDeclNode,
+            SyntaxKind::UnboundDeclNode,
+            SyntaxKind::MetadataObjectItemNode,
         ])
     }
 }
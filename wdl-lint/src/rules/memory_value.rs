@@ -0,0 +1,261 @@
+//! A lint rule for the value of `memory` keys in `runtime`/`requirements`
+//! sections.
+
+use wdl_ast::AstNode;
+use wdl_ast::AstNodeExt;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+use wdl_ast::v1::Expr;
+use wdl_ast::v1::LiteralExpr;
+use wdl_ast::v1::RequirementsSection;
+use wdl_ast::v1::RuntimeSection;
+use wdl_ast::v1::StringPart;
+use wdl_ast::v1::TASK_REQUIREMENT_MEMORY;
+use wdl_ast::v1::common::units::StorageUnit;
+use wdl_ast::v1::common::units::convert_unit_string;
+
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+
+/// The identifier for the memory value rule.
+const ID: &str = "MemoryValue";
+
+/// The units suggested for memory values, from largest to smallest.
+const UNITS: &[StorageUnit] = &[
+    StorageUnit::Tebibytes,
+    StorageUnit::Terabytes,
+    StorageUnit::Gibibytes,
+    StorageUnit::Gigabytes,
+    StorageUnit::Mebibytes,
+    StorageUnit::Megabytes,
+    StorageUnit::Kibibytes,
+    StorageUnit::Kilobytes,
+];
+
+/// Creates a "missing unit" diagnostic for an integer memory value.
+fn integer_memory(suggestion: &str, span: Span) -> Diagnostic {
+    Diagnostic::note("memory is specified as a number of bytes without a unit")
+        .with_rule(ID)
+        .with_highlight(span)
+        .with_fix(format!(
+            "specify the memory as a string with an explicit unit: `{suggestion}`"
+        ))
+        .with_replacement(span, suggestion)
+}
+
+/// Creates a "missing unit" diagnostic for a string memory value.
+fn missing_unit(value: &str, span: Span) -> Diagnostic {
+    Diagnostic::error(format!("memory value `{value}` is missing a unit"))
+        .with_rule(ID)
+        .with_highlight(span)
+        .with_fix(
+            "add a unit to the memory value: B, KB, KiB, MB, MiB, GB, GiB, TB, or TiB (e.g. `\"8 \
+             GiB\"`)",
+        )
+}
+
+/// Creates a "negative memory" diagnostic.
+fn negative_memory(span: Span) -> Diagnostic {
+    Diagnostic::error("memory value must not be negative")
+        .with_rule(ID)
+        .with_highlight(span)
+        .with_fix("specify a positive amount of memory (e.g. `\"8 GiB\"`)")
+}
+
+/// Creates an "invalid memory" diagnostic.
+fn invalid_memory(value: &str, span: Span) -> Diagnostic {
+    Diagnostic::error(format!("invalid memory value `{value}`"))
+        .with_rule(ID)
+        .with_highlight(span)
+        .with_fix(
+            "specify the memory as a whole number followed by a unit: B, KB, KiB, MB, MiB, GB, \
+             GiB, TB, or TiB (e.g. `\"8 GiB\"`)",
+        )
+}
+
+/// Gets a string memory value equivalent to the given number of bytes.
+///
+/// The largest unit that evenly divides the number of bytes is used.
+fn suggestion(bytes: u64) -> String {
+    let unit = UNITS
+        .iter()
+        .copied()
+        .find(|u| bytes > 0 && bytes % u.bytes(1).expect("unit should not overflow") == 0)
+        .unwrap_or(StorageUnit::Bytes);
+
+    format!(
+        "\"{n} {unit}\"",
+        n = bytes / unit.bytes(1).expect("unit should not overflow")
+    )
+}
+
+/// Checks the value of a `memory` item.
+fn check_memory(
+    state: &mut Diagnostics,
+    expr: &Expr,
+    syntax: SyntaxElement,
+    exceptable_nodes: &Option<&'static [SyntaxKind]>,
+) {
+    let diagnostic = match expr {
+        Expr::Literal(LiteralExpr::Integer(i)) => match i.value() {
+            Some(bytes) if bytes >= 0 => integer_memory(&suggestion(bytes as u64), expr.span()),
+            Some(_) => negative_memory(expr.span()),
+            None => return,
+        },
+        Expr::Negation(_) => negative_memory(expr.span()),
+        Expr::Literal(LiteralExpr::String(s)) => {
+            // Values with placeholders cannot be checked until evaluation
+            if s.parts().any(|p| matches!(p, StringPart::Placeholder(_))) {
+                return;
+            }
+
+            let text = s.text();
+            let value = text.as_ref().map(|t| t.as_str()).unwrap_or_default();
+            if convert_unit_string(value).is_some() {
+                return;
+            }
+
+            let trimmed = value.trim();
+            if trimmed.starts_with('-') {
+                negative_memory(expr.span())
+            } else if !trimmed.is_empty() && trimmed.parse::<u64>().is_ok() {
+                missing_unit(value, expr.span())
+            } else {
+                invalid_memory(value, expr.span())
+            }
+        }
+        _ => return,
+    };
+
+    state.exceptable_add(diagnostic, syntax, exceptable_nodes);
+}
+
+/// Ensures that values for `memory` keys within `runtime`/`requirements`
+/// sections are well-formed.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct MemoryValueRule;
+
+impl Rule for MemoryValueRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Ensures that values for the `memory` key within `runtime`/`requirements` sections are \
+         well-formed."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Memory may be specified in many forms (e.g. `\"8G\"`, `\"8 GiB\"`, or `8000000000`), \
+         and some execution engines reject some of them. A string value must be a whole number \
+         followed by a recognized unit: B, KB, KiB, MB, MiB, GB, GiB, TB, or TiB. Strings that \
+         do not parse, and negative values, are reported as errors. An integer value is a \
+         number of bytes, which is hard to read and easy to get wrong by an order of magnitude; \
+         a string with an explicit unit is recommended instead. Values containing placeholders \
+         are not checked, as they are only known at evaluation time."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Correctness, Tag::Portability])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("memoryvalue"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &[
+            "MissingRequirements",
+            "RuntimeSectionKeys",
+            "StaticDiskSize",
+        ]
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+        Some(&[
+            SyntaxKind::VersionStatementNode,
+            SyntaxKind::TaskDefinitionNode,
+            SyntaxKind::RequirementsSectionNode,
+            SyntaxKind::RequirementsItemNode,
+            SyntaxKind::RuntimeSectionNode,
+            SyntaxKind::RuntimeItemNode,
+        ])
+    }
+}
+
+impl Visitor for MemoryValueRule {
+    type State = Diagnostics;
+
+    fn document(&mut self, _: &mut Self::State, _: VisitReason, _: &Document, _: SupportedVersion) {
+        // This rule has no state to reset.
+    }
+
+    fn runtime_section(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        section: &RuntimeSection,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        for item in section.items() {
+            if item.name().as_str() == TASK_REQUIREMENT_MEMORY {
+                check_memory(
+                    state,
+                    &item.expr(),
+                    SyntaxElement::from(item.syntax().clone()),
+                    &self.exceptable_nodes(),
+                );
+            }
+        }
+    }
+
+    fn requirements_section(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        section: &RequirementsSection,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        for item in section.items() {
+            if item.name().as_str() == TASK_REQUIREMENT_MEMORY {
+                check_memory(
+                    state,
+                    &item.expr(),
+                    SyntaxElement::from(item.syntax().clone()),
+                    &self.exceptable_nodes(),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn it_suggests_the_largest_exact_unit() {
+        assert_eq!(suggestion(8000000000), r#""8 GB""#);
+        assert_eq!(suggestion(8589934592), r#""8 GiB""#);
+        assert_eq!(suggestion(3072), r#""3 KiB""#);
+        assert_eq!(suggestion(1536), r#""1536 B""#);
+        assert_eq!(suggestion(0), r#""0 B""#);
+    }
+}
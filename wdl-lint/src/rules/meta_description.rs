@@ -0,0 +1,243 @@
+//! A lint rule for tasks and workflows without a description in `meta`.
+
+use serde::Deserialize;
+use wdl_ast::AstNode;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Ident;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+use wdl_ast::v1::MetadataSection;
+use wdl_ast::v1::MetadataValue;
+use wdl_ast::v1::OutputSection;
+use wdl_ast::v1::TaskDefinition;
+use wdl_ast::v1::WorkflowDefinition;
+
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+use crate::config::rule_options;
+
+/// The identifier for the meta description rule.
+const ID: &str = "MetaDescription";
+
+/// Creates a "missing description" diagnostic.
+fn missing_description(ty: &str, name: &str, reason: &str, span: Span) -> Diagnostic {
+    Diagnostic::warning(format!("{ty} `{name}` {reason}"))
+        .with_rule(ID)
+        .with_label(format!("this {ty} is not described"), span)
+        .with_fix(r#"add a description to the `meta` section: `meta { description: "..." }`"#)
+}
+
+/// Creates a "missing output description" diagnostic.
+fn missing_output_description(ty: &str, name: &str, output: &str, span: Span) -> Diagnostic {
+    Diagnostic::warning(format!(
+        "output `{output}` of {ty} `{name}` is not described in `meta.outputs`"
+    ))
+    .with_rule(ID)
+    .with_highlight(span)
+    .with_fix(format!(
+        r#"add a description of the output to `meta.outputs`: `outputs: {{ {output}: "..." }}`"#
+    ))
+}
+
+/// Gets the reason the given `meta` section does not describe its task or
+/// workflow.
+///
+/// Returns `None` if the section has a non-empty `description` string.
+fn undescribed(section: Option<&MetadataSection>) -> Option<&'static str> {
+    let Some(section) = section else {
+        return Some("has no `meta` section");
+    };
+
+    let Some(item) = section.items().find(|i| i.name().as_str() == "description") else {
+        return Some("has no `description` key in its `meta` section");
+    };
+
+    match item.value() {
+        MetadataValue::String(s) if s.text().is_some_and(|t| !t.as_str().trim().is_empty()) => None,
+        MetadataValue::String(_) => Some("has an empty `description` in its `meta` section"),
+        _ => Some("has a `description` in its `meta` section that is not a string"),
+    }
+}
+
+/// Detects tasks and workflows that are not described in their `meta`
+/// sections.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct MetaDescriptionRule {
+    /// Whether or not each output must also be described in `meta.outputs`.
+    require_outputs: bool,
+}
+
+impl MetaDescriptionRule {
+    /// Constructs a new meta description rule.
+    ///
+    /// If `require_outputs` is `true`, every declared output must also have an
+    /// entry in `meta.outputs`.
+    pub fn new(require_outputs: bool) -> Self {
+        Self { require_outputs }
+    }
+
+    /// Gets whether or not each output must also be described in
+    /// `meta.outputs`.
+    pub fn require_outputs(&self) -> bool {
+        self.require_outputs
+    }
+
+    /// Checks the `meta` section of a task or workflow.
+    fn check(
+        &self,
+        state: &mut Diagnostics,
+        ty: &str,
+        name: &Ident,
+        meta: Option<MetadataSection>,
+        output: Option<OutputSection>,
+        element: SyntaxElement,
+    ) {
+        if let Some(reason) = undescribed(meta.as_ref()) {
+            state.exceptable_add(
+                missing_description(ty, name.as_str(), reason, name.span()),
+                element,
+                &self.exceptable_nodes(),
+            );
+        }
+
+        if !self.require_outputs {
+            return;
+        }
+
+        let outputs = meta
+            .and_then(|s| s.items().find(|i| i.name().as_str() == "outputs"))
+            .and_then(|i| match i.value() {
+                MetadataValue::Object(o) => Some(o),
+                _ => None,
+            });
+
+        for decl in output.iter().flat_map(|s| s.declarations()) {
+            let output = decl.name();
+            if outputs
+                .as_ref()
+                .is_some_and(|o| o.items().any(|i| i.name().as_str() == output.as_str()))
+            {
+                continue;
+            }
+
+            state.exceptable_add(
+                missing_output_description(ty, name.as_str(), output.as_str(), output.span()),
+                SyntaxElement::from(decl.syntax().clone()),
+                &self.exceptable_nodes(),
+            );
+        }
+    }
+}
+
+/// The options of the meta description rule.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Options {
+    /// Whether or not each output must also be described in `meta.outputs`.
+    require_outputs: Option<bool>,
+}
+
+impl Rule for MetaDescriptionRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Ensures that tasks and workflows are described in their `meta` sections."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Documentation generated from a WDL document is only as useful as the descriptions it \
+         contains. Every task and workflow should have a `meta` section with a non-empty \
+         `description` string. Unlike the `DescriptionMissing` rule, this rule also flags tasks \
+         and workflows that have no `meta` section at all. The rule may optionally be \
+         configured to require an entry in `meta.outputs` for every declared output."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Completeness])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("metadescription"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["DescriptionMissing", "MissingMetas", "NonmatchingOutput"]
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+        Some(&[
+            SyntaxKind::VersionStatementNode,
+            SyntaxKind::TaskDefinitionNode,
+            SyntaxKind::WorkflowDefinitionNode,
+            SyntaxKind::BoundDeclNode,
+        ])
+    }
+
+    fn configure(&mut self, options: &toml::Table) -> anyhow::Result<()> {
+        let options: Options = rule_options(options)?;
+
+        if let Some(require_outputs) = options.require_outputs {
+            self.require_outputs = require_outputs;
+        }
+
+        Ok(())
+    }
+}
+
+impl Visitor for MetaDescriptionRule {
+    type State = Diagnostics;
+
+    fn document(&mut self, _: &mut Self::State, _: VisitReason, _: &Document, _: SupportedVersion) {
+        // This rule has no state to reset.
+    }
+
+    fn task_definition(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        task: &TaskDefinition,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        self.check(
+            state,
+            "task",
+            &task.name(),
+            task.metadata(),
+            task.output(),
+            SyntaxElement::from(task.syntax().clone()),
+        );
+    }
+
+    fn workflow_definition(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        workflow: &WorkflowDefinition,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        self.check(
+            state,
+            "workflow",
+            &workflow.name(),
+            workflow.metadata(),
+            workflow.output(),
+            SyntaxElement::from(workflow.syntax().clone()),
+        );
+    }
+}
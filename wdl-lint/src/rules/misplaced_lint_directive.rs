@@ -16,6 +16,7 @@ use wdl_ast::SyntaxKind;
 use wdl_ast::ToSpan;
 use wdl_ast::VisitReason;
 use wdl_ast::Visitor;
+use wdl_ast::trailed_node;
 
 use crate::Rule;
 use crate::Tag;
@@ -27,10 +28,14 @@ use crate::rules;
 const ID: &str = "MisplacedLintDirective";
 
 /// Creates an "unknown rule" diagnostic.
+///
+/// The `trailing` argument indicates whether the directive trails the element
+/// on the same line rather than preceding it.
 fn misplaced_lint_directive(
     id: &str,
     span: Span,
     wrong_element: &SyntaxElement,
+    trailing: bool,
     exceptable_nodes: &[SyntaxKind],
 ) -> Diagnostic {
     let locations = exceptable_nodes
@@ -38,9 +43,10 @@ fn misplaced_lint_directive(
         .map(|node| node.describe())
         .collect::<Vec<_>>()
         .join(", ");
+    let position = if trailing { "after" } else { "above" };
 
     Diagnostic::note(format!(
-        "lint directive `{id}` has no effect above {elem}",
+        "lint directive `{id}` has no effect {position} {elem}",
         elem = wrong_element.kind().describe()
     ))
     .with_rule(ID)
@@ -50,7 +56,7 @@ fn misplaced_lint_directive(
         wrong_element.text_range().to_span(),
     )
     .with_fix(format!(
-        "valid locations for this directive are above: {locations}"
+        "valid locations for this directive are {position}: {locations}"
     ))
 }
 
@@ -91,6 +97,18 @@ impl Rule for MisplacedLintDirectiveRule {
         TagSet::new(&[Tag::Clarity, Tag::Correctness])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("misplacedlintdirective"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &[
+            "MalformedLintDirective",
+            "UnknownRule",
+            "UnusedLintDirective",
+        ]
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [wdl_ast::SyntaxKind]> {
         Some(&[SyntaxKind::VersionStatementNode])
     }
@@ -108,16 +126,22 @@ impl Visitor for MisplacedLintDirectiveRule {
             let start: usize = comment.span().start();
             let mut offset = EXCEPT_COMMENT_PREFIX.len();
 
-            let excepted_element = comment
-                .syntax()
-                .siblings_with_tokens(rowan::Direction::Next)
-                .find_map(|s| {
-                    if s.kind() == SyntaxKind::Whitespace || s.kind() == SyntaxKind::Comment {
-                        None
-                    } else {
-                        Some(s)
-                    }
-                });
+            // A directive trailing an element applies to that element;
+            // otherwise, it applies to the element that follows it
+            let trailed = trailed_node(comment.syntax());
+            let trailing = trailed.is_some();
+            let excepted_element = trailed.map(SyntaxElement::from).or_else(|| {
+                comment
+                    .syntax()
+                    .siblings_with_tokens(rowan::Direction::Next)
+                    .find_map(|s| {
+                        if s.kind() == SyntaxKind::Whitespace || s.kind() == SyntaxKind::Comment {
+                            None
+                        } else {
+                            Some(s)
+                        }
+                    })
+            });
 
             for id in ids.split(',') {
                 // First trim the start so we can determine how much whitespace was removed
@@ -135,6 +159,7 @@ impl Visitor for MisplacedLintDirectiveRule {
                                 trimmed,
                                 Span::new(start + offset, trimmed.len()),
                                 elem,
+                                trailing,
                                 exceptable_nodes,
                             ));
                         }
@@ -0,0 +1,126 @@
+//! A lint rule for tasks that do not specify a container.
+
+use wdl_ast::AstNode;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+use wdl_ast::v1::TaskDefinition;
+use wdl_ast::version::V1;
+
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+
+/// The identifier for the missing container rule.
+const ID: &str = "MissingContainer";
+
+/// Creates a "missing container" diagnostic.
+fn missing_container(task: &str, section: &str, span: Span) -> Diagnostic {
+    Diagnostic::warning(format!("task `{task}` does not specify a container"))
+        .with_rule(ID)
+        .with_label("this task does not specify a container", span)
+        .with_fix(format!(
+            "add a `container` key to the `{section}` section of the task"
+        ))
+}
+
+/// Detects tasks that do not specify a container.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct MissingContainerRule(Option<SupportedVersion>);
+
+impl Rule for MissingContainerRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Ensures that tasks specify a container."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Tasks that do not specify a container run in whatever environment the execution backend \
+         provides, so their behavior may differ across backends. Every task should specify a \
+         container with the `container` key (or the legacy `docker` key) of its `runtime` \
+         section or, for WDL v1.2 and beyond, its `requirements` section. Tasks that are meant \
+         to run on the host may except this rule."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Portability])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("missingcontainer"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["ContainerValue", "MissingRequirements", "MissingRuntime"]
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+        Some(&[
+            SyntaxKind::VersionStatementNode,
+            SyntaxKind::TaskDefinitionNode,
+        ])
+    }
+}
+
+impl Visitor for MissingContainerRule {
+    type State = Diagnostics;
+
+    fn document(
+        &mut self,
+        _: &mut Self::State,
+        reason: VisitReason,
+        _: &Document,
+        version: SupportedVersion,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        // Reset the visitor upon document entry
+        *self = Self(Some(version));
+    }
+
+    fn task_definition(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        task: &TaskDefinition,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        // The `requirements` section was introduced in WDL v1.2
+        let has_requirements =
+            self.0.expect("version should exist here") >= SupportedVersion::V1(V1::Two);
+
+        if task.runtime().and_then(|s| s.container()).is_some()
+            || (has_requirements && task.requirements().and_then(|s| s.container()).is_some())
+        {
+            return;
+        }
+
+        let section = if has_requirements && task.runtime().is_none() {
+            "requirements"
+        } else {
+            "runtime"
+        };
+
+        let name = task.name();
+        state.exceptable_add(
+            missing_container(name.as_str(), section, name.span()),
+            SyntaxElement::from(task.syntax().clone()),
+            &self.exceptable_nodes(),
+        );
+    }
+}
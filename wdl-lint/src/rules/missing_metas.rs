@@ -115,6 +115,18 @@ impl Rule for MissingMetasRule {
         TagSet::new(&[Tag::Completeness, Tag::Clarity])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("missingmetas"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &[
+            "DescriptionMissing",
+            "MatchingParameterMeta",
+            "NonmatchingOutput",
+        ]
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
         Some(&[
             SyntaxKind::VersionStatementNode,
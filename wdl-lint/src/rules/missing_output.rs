@@ -74,6 +74,14 @@ impl Rule for MissingOutputRule {
         TagSet::new(&[Tag::Completeness, Tag::Portability])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("missingoutput"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["NonmatchingOutput"]
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
         Some(&[
             SyntaxKind::VersionStatementNode,
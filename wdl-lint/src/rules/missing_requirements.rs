@@ -64,6 +64,14 @@ impl Rule for MissingRequirementsRule {
         TagSet::new(&[Tag::Completeness, Tag::Portability])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("missingrequirements"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["MissingRuntime", "RuntimeSectionKeys", "ContainerValue"]
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
         Some(&[
             SyntaxKind::VersionStatementNode,
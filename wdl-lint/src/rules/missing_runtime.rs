@@ -50,6 +50,18 @@ impl Rule for MissingRuntimeRule {
         TagSet::new(&[Tag::Completeness, Tag::Portability])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("missingruntime"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &[
+            "MissingRequirements",
+            "RuntimeSectionKeys",
+            "ContainerValue",
+        ]
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
         Some(&[
             SyntaxKind::VersionStatementNode,
@@ -0,0 +1,233 @@
+//! A lint rule for deeply nested scatter and conditional statements.
+
+use serde::Deserialize;
+use wdl_ast::AstNode;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::SyntaxNode;
+use wdl_ast::ToSpan;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+use wdl_ast::support;
+use wdl_ast::v1::ConditionalStatement;
+use wdl_ast::v1::ScatterStatement;
+
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+use crate::config::rule_options;
+
+/// The identifier for the nesting depth rule.
+const ID: &str = "NestingDepth";
+
+/// The default maximum nesting depth of scatter and conditional statements.
+pub const DEFAULT_MAX_STATEMENT_DEPTH: usize = 3;
+
+/// Creates a "deeply nested block" diagnostic.
+fn deeply_nested_block(ty: &str, path: &[String], max: usize, span: Span) -> Diagnostic {
+    let depth = path.len() + 1;
+    let path = path
+        .iter()
+        .map(|e| format!("`{e}`"))
+        .collect::<Vec<_>>()
+        .join(" > ");
+
+    Diagnostic::warning(format!(
+        "{ty} statement is nested {depth} levels deep (within {path})"
+    ))
+    .with_rule(ID)
+    .with_label(
+        format!("this {ty} statement exceeds the maximum nesting depth of {max}"),
+        span,
+    )
+    .with_fix(format!(
+        "move the nested statements into a subworkflow so that scatter and conditional \
+         statements are nested at most {max} levels deep"
+    ))
+}
+
+/// Detects scatter and conditional statements that are nested too deeply.
+#[derive(Debug, Clone)]
+pub struct NestingDepthRule {
+    /// The maximum nesting depth of scatter and conditional statements.
+    max_depth: usize,
+    /// The expression texts of the enclosing scatter and conditional
+    /// statements.
+    path: Vec<String>,
+    /// The depth of the statement that was flagged, if any.
+    ///
+    /// Statements nested within a flagged statement are not flagged.
+    flagged: Option<usize>,
+}
+
+impl NestingDepthRule {
+    /// Constructs a new nesting depth rule with the given maximum nesting
+    /// depth.
+    pub fn new(max_depth: usize) -> Self {
+        Self {
+            max_depth,
+            path: Vec::new(),
+            flagged: None,
+        }
+    }
+
+    /// Gets the maximum nesting depth of scatter and conditional statements.
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// Handles entering or exiting a scatter or conditional statement.
+    fn statement(
+        &mut self,
+        state: &mut Diagnostics,
+        reason: VisitReason,
+        ty: &str,
+        keyword: SyntaxKind,
+        syntax: &SyntaxNode,
+        expr: String,
+    ) {
+        if reason == VisitReason::Exit {
+            if self.flagged == Some(self.path.len()) {
+                self.flagged = None;
+            }
+
+            self.path.pop();
+            return;
+        }
+
+        if self.path.len() >= self.max_depth && self.flagged.is_none() {
+            let keyword = support::token(syntax, keyword).expect("should have a keyword token");
+            state.exceptable_add(
+                deeply_nested_block(
+                    ty,
+                    &self.path,
+                    self.max_depth,
+                    keyword.text_range().to_span(),
+                ),
+                SyntaxElement::from(syntax.clone()),
+                &self.exceptable_nodes(),
+            );
+            self.flagged = Some(self.path.len() + 1);
+        }
+
+        self.path.push(expr);
+    }
+}
+
+impl Default for NestingDepthRule {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_STATEMENT_DEPTH)
+    }
+}
+
+/// The options of the nesting depth rule.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Options {
+    /// The maximum nesting depth of scatter and conditional statements.
+    max_depth: Option<usize>,
+}
+
+impl Rule for NestingDepthRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags scatter and conditional statements that are nested too deeply."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Each level of scatter nesting multiplies the number of calls a workflow makes, and \
+         deeply nested scatter and conditional statements are difficult to read and reason \
+         about. Statements nested beyond the maximum depth (3 by default) should be moved into a \
+         subworkflow. Only the outermost statement that exceeds the maximum depth is flagged."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Clarity])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("nestingdepth"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["ScatterInvariantInput"]
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+        Some(&[
+            SyntaxKind::VersionStatementNode,
+            SyntaxKind::WorkflowDefinitionNode,
+            SyntaxKind::ScatterStatementNode,
+            SyntaxKind::ConditionalStatementNode,
+        ])
+    }
+
+    fn configure(&mut self, options: &toml::Table) -> anyhow::Result<()> {
+        let options: Options = rule_options(options)?;
+
+        if let Some(max_depth) = options.max_depth {
+            self.max_depth = max_depth;
+        }
+
+        Ok(())
+    }
+}
+
+impl Visitor for NestingDepthRule {
+    type State = Diagnostics;
+
+    fn document(
+        &mut self,
+        _: &mut Self::State,
+        reason: VisitReason,
+        _: &Document,
+        _: SupportedVersion,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        // Reset the visitor upon document entry
+        *self = Self::new(self.max_depth);
+    }
+
+    fn scatter_statement(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        stmt: &ScatterStatement,
+    ) {
+        self.statement(
+            state,
+            reason,
+            "scatter",
+            SyntaxKind::ScatterKeyword,
+            stmt.syntax(),
+            stmt.expr().syntax().text().to_string(),
+        );
+    }
+
+    fn conditional_statement(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        stmt: &ConditionalStatement,
+    ) {
+        self.statement(
+            state,
+            reason,
+            "conditional",
+            SyntaxKind::IfKeyword,
+            stmt.syntax(),
+            stmt.expr().syntax().text().to_string(),
+        );
+    }
+}
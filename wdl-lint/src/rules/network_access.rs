@@ -0,0 +1,245 @@
+//! A lint rule for command sections that access the network.
+
+use serde::Deserialize;
+use wdl_ast::AstNode;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+use wdl_ast::v1::CommandPart;
+use wdl_ast::v1::CommandSection;
+
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+use crate::config::rule_options;
+use crate::util::command_words;
+use crate::util::lines_with_offset;
+use crate::util::mask_quotes;
+use crate::util::words;
+
+/// The identifier for the network access rule.
+const ID: &str = "NetworkAccess";
+
+/// The tools that access the network.
+///
+/// A tool with a subcommand (e.g. `git clone`) only accesses the network when
+/// invoked with that subcommand.
+const TOOLS: &[&str] = &[
+    "apt-get",
+    "conda install",
+    "curl",
+    "git clone",
+    "pip install",
+    "pip3 install",
+    "wget",
+    "yum",
+];
+
+/// Creates a "network access" diagnostic.
+fn network_access(tool: &str, span: Span) -> Diagnostic {
+    Diagnostic::warning(format!(
+        "command section accesses the network with `{tool}`"
+    ))
+    .with_rule(ID)
+    .with_highlight(span)
+    .with_fix(
+        "install the dependency in the task's container or pass the downloaded file as a \
+         `File` input",
+    )
+}
+
+/// Finds the invocations of tools that access the network in a line of a
+/// command.
+///
+/// `command` is whether or not the first word of the line is in command
+/// position; it is `false` for text that continues the line of a placeholder.
+///
+/// Returns the tools with their offsets and lengths within the line.
+fn scan(line: &str, command: bool) -> Vec<(&'static str, usize, usize)> {
+    let words = words(line);
+    let mut invocations = Vec::new();
+    for (i, offset, name) in command_words(&words, command) {
+        for tool in TOOLS {
+            let len = match tool.split_once(' ') {
+                Some((program, subcommand)) if program == name => match words.get(i + 1) {
+                    Some(&(sub_offset, sub)) if sub.trim_end_matches(';') == subcommand => {
+                        sub_offset + subcommand.len() - offset
+                    }
+                    _ => continue,
+                },
+                None if *tool == name => name.len(),
+                _ => continue,
+            };
+
+            invocations.push((*tool, offset, len));
+        }
+    }
+
+    invocations
+}
+
+/// Detects command sections that access the network.
+#[derive(Debug, Clone, Default)]
+pub struct NetworkAccessRule {
+    /// The tools that are allowed to access the network.
+    allowed_tools: Vec<String>,
+}
+
+impl NetworkAccessRule {
+    /// Constructs a new network access rule that allows the given tools.
+    ///
+    /// Tools are named as they are reported (e.g. `curl` or `git clone`).
+    pub fn new(allowed_tools: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            allowed_tools: allowed_tools.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Gets the tools that are allowed to access the network.
+    pub fn allowed_tools(&self) -> &[String] {
+        &self.allowed_tools
+    }
+}
+
+/// The options of the network access rule.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Options {
+    /// The tools that are allowed to access the network.
+    allowed_tools: Option<Vec<String>>,
+}
+
+impl Rule for NetworkAccessRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags command sections that access the network."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Tasks that download files or install software when they run (e.g. with `curl`, `wget`, \
+         `git clone`, `pip install`, `conda install`, `apt-get`, or `yum`) are not reproducible, \
+         as the downloaded content may change, and fail on hosts without network access, such \
+         as air-gapped clusters. Dependencies should instead be installed in the task's \
+         container and data should be passed to the task as `File` inputs. Occurrences in shell \
+         comments and quoted strings are not reported. Specific tools (e.g. `git clone`) may be \
+         allowed with the rule's `allowed_tools` option."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Portability])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("networkaccess"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["AbsoluteHostPath", "HostDependentCommand"]
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+        Some(&[
+            SyntaxKind::VersionStatementNode,
+            SyntaxKind::TaskDefinitionNode,
+            SyntaxKind::CommandSectionNode,
+        ])
+    }
+
+    fn configure(&mut self, options: &toml::Table) -> anyhow::Result<()> {
+        let options: Options = rule_options(options)?;
+
+        if let Some(allowed_tools) = options.allowed_tools {
+            self.allowed_tools = allowed_tools;
+        }
+
+        Ok(())
+    }
+}
+
+impl Visitor for NetworkAccessRule {
+    type State = Diagnostics;
+
+    fn document(&mut self, _: &mut Self::State, _: VisitReason, _: &Document, _: SupportedVersion) {
+        // This is intentionally empty, as this rule has no state.
+    }
+
+    fn command_section(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        section: &CommandSection,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        let element = SyntaxElement::from(section.syntax().clone());
+        let mut after_placeholder = false;
+        let mut quote = None;
+        for part in section.parts() {
+            let text = match part {
+                CommandPart::Text(text) => text,
+                CommandPart::Placeholder(_) => {
+                    after_placeholder = true;
+                    continue;
+                }
+            };
+
+            let start = text.span().start();
+            for (i, (line, line_start, _)) in lines_with_offset(text.as_str()).enumerate() {
+                // A quote that is open before a placeholder continues after it
+                if i > 0 || !after_placeholder {
+                    quote = None;
+                }
+
+                let line = mask_quotes(line, &mut quote);
+                for (tool, offset, len) in scan(&line, i > 0 || !after_placeholder) {
+                    if self.allowed_tools.iter().any(|t| t == tool) {
+                        continue;
+                    }
+
+                    state.exceptable_add(
+                        network_access(tool, Span::new(start + line_start + offset, len)),
+                        element.clone(),
+                        &self.exceptable_nodes(),
+                    );
+                }
+            }
+
+            after_placeholder = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn it_finds_invocations() {
+        assert_eq!(
+            scan(
+                "curl -o out.txt https://example.com && git clone repo",
+                true
+            ),
+            [("curl", 0, 4), ("git clone", 41, 9)]
+        );
+        assert_eq!(
+            scan("git status; pip3 install numpy", true),
+            [("pip3 install", 12, 12)]
+        );
+        assert_eq!(scan("echo curl wget", true), []);
+    }
+}
@@ -13,7 +13,9 @@ use wdl_ast::ToSpan;
 use wdl_ast::VisitReason;
 use wdl_ast::Visitor;
 use wdl_ast::support;
+use wdl_ast::v1::CommandPart;
 use wdl_ast::v1::CommandSection;
+use wdl_ast::version::V1;
 
 use crate::Rule;
 use crate::Tag;
@@ -23,18 +25,76 @@ use crate::TagSet;
 const ID: &str = "NoCurlyCommands";
 
 /// Creates a "curly commands" diagnostic.
-fn curly_commands(task: &str, span: Span) -> Diagnostic {
-    Diagnostic::warning(format!(
+///
+/// If `close` is `Some`, the command can be converted as-is and replacements
+/// that swap the braces for heredoc delimiters are added.
+fn curly_commands(
+    task: &str,
+    version: SupportedVersion,
+    open: Span,
+    close: Option<Span>,
+) -> Diagnostic {
+    let mut fix = String::from("replace the curly braces with heredoc syntax (`command <<< >>>`)");
+    if version >= SupportedVersion::V1(V1::Two) {
+        fix.push_str(", as is used for multi-line strings");
+    }
+
+    if close.is_none() {
+        fix.push_str(
+            "; any `${}` placeholders must become `~{}` and any literal `>>>` must be escaped as \
+             `\\>>>`",
+        );
+    }
+
+    let diagnostic = Diagnostic::warning(format!(
         "task `{task}` uses curly braces in command section"
     ))
     .with_rule(ID)
-    .with_label("this command section uses curly braces", span)
-    .with_fix("instead of curly braces, use heredoc syntax (<<<>>>>) for command sections")
+    .with_label("this command section uses curly braces", open)
+    .with_fix(fix);
+
+    match close {
+        Some(close) => diagnostic
+            .with_replacement(open, "<<<")
+            .with_replacement(close, ">>>"),
+        None => diagnostic,
+    }
+}
+
+/// Determines if a brace command section can be converted to a heredoc
+/// command section by only replacing its braces.
+///
+/// A command cannot be converted if its text contains `>>>` or brace command
+/// escape sequences, if its text ends with `>`, or if it contains `${}`
+/// placeholders, as the meaning of the command would change.
+fn is_convertible(section: &CommandSection) -> bool {
+    let mut last = None;
+    for part in section.parts() {
+        match part {
+            CommandPart::Text(text) => {
+                let text = text.as_str();
+                if text.contains(">>>") || text.contains("\\$") || text.contains("\\}") {
+                    return false;
+                }
+
+                last = Some(text.ends_with('>'));
+            }
+            CommandPart::Placeholder(placeholder) => {
+                if !placeholder.has_tilde() {
+                    return false;
+                }
+
+                last = None;
+            }
+        }
+    }
+
+    last != Some(true)
 }
 
 /// Detects curly command section for tasks.
 #[derive(Default, Debug, Clone, Copy)]
-pub struct NoCurlyCommandsRule;
+pub struct NoCurlyCommandsRule(Option<SupportedVersion>);
 
 impl Rule for NoCurlyCommandsRule {
     fn id(&self) -> &'static str {
@@ -48,13 +108,22 @@ impl Rule for NoCurlyCommandsRule {
     fn explanation(&self) -> &'static str {
         "Curly command blocks are no longer considered idiomatic WDL. Idiomatic WDL code uses \
          heredoc command blocks instead. This is because curly command blocks create ambiguity \
-         with Bash syntax."
+         with Bash syntax. When the command contains no `${}` placeholders and no literal `>>>`, \
+         the braces may be replaced with heredoc delimiters without changing the command."
     }
 
     fn tags(&self) -> TagSet {
         TagSet::new(&[Tag::Clarity])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("nocurlycommands"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["CommandSectionMixedIndentation"]
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
         Some(&[
             SyntaxKind::VersionStatementNode,
@@ -71,14 +140,14 @@ impl Visitor for NoCurlyCommandsRule {
         _: &mut Self::State,
         reason: VisitReason,
         _: &Document,
-        _: SupportedVersion,
+        version: SupportedVersion,
     ) {
         if reason == VisitReason::Exit {
             return;
         }
 
         // Reset the visitor upon document entry
-        *self = Default::default();
+        *self = Self(Some(version));
     }
 
     fn command_section(
@@ -91,16 +160,25 @@ impl Visitor for NoCurlyCommandsRule {
             return;
         }
 
-        if !section.is_heredoc() {
-            let name = section.parent().name();
-            let command_keyword = support::token(section.syntax(), SyntaxKind::CommandKeyword)
-                .expect("should have a command keyword token");
-
-            state.exceptable_add(
-                curly_commands(name.as_str(), command_keyword.text_range().to_span()),
-                SyntaxElement::from(section.syntax().clone()),
-                &self.exceptable_nodes(),
-            );
+        if section.is_heredoc() {
+            return;
         }
+
+        let name = section.parent().name();
+        let open = support::token(section.syntax(), SyntaxKind::OpenBrace)
+            .expect("should have an open brace token");
+        let close = support::token(section.syntax(), SyntaxKind::CloseBrace)
+            .expect("should have a close brace token");
+
+        state.exceptable_add(
+            curly_commands(
+                name.as_str(),
+                self.0.expect("should have a version"),
+                open.text_range().to_span(),
+                is_convertible(section).then(|| close.text_range().to_span()),
+            ),
+            SyntaxElement::from(section.syntax().clone()),
+            &self.exceptable_nodes(),
+        );
     }
 }
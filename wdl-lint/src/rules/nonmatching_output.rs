@@ -124,13 +124,22 @@ impl Rule for NonmatchingOutputRule<'_> {
          descriptions for each output of the task/workflow. These must match exactly. i.e. for \
          each named output of a task or workflow, there should be an entry under `meta.outputs` \
          with that same name. Additionally, these entries should be in the same order (that order \
-         is up to the developer to decide). No extraneous `meta.outputs` entries are allowed."
+         is up to the developer to decide). No extraneous `meta.outputs` entries are allowed. \
+         Each entry may be either a string description or an object with a `description` key."
     }
 
     fn tags(&self) -> TagSet {
         TagSet::new(&[Tag::Completeness])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("nonmatchingoutput"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["MissingOutput", "MatchingParameterMeta"]
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
         Some(&[
             SyntaxKind::VersionStatementNode,
@@ -399,10 +408,11 @@ impl Visitor for NonmatchingOutputRule<'_> {
                                 .expect("should have seen `meta.outputs`")
                                 == "outputs"
                         {
-                            self.meta_outputs_keys.insert(
-                                item.name().as_str().to_string(),
-                                item.syntax().text_range().to_span(),
-                            );
+                            // Outputs may be described by a string or by an object
+                            // with a `description` key; only the key is compared
+                            let name = item.name();
+                            self.meta_outputs_keys
+                                .insert(name.as_str().to_string(), name.span());
                         }
                     }
                 }
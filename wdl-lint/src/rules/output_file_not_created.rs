@@ -0,0 +1,163 @@
+//! A lint rule for `File` outputs that do not appear to be created by the
+//! task's command.
+
+use wdl_ast::AstNode;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+use wdl_ast::v1::CommandPart;
+use wdl_ast::v1::Expr;
+use wdl_ast::v1::LiteralExpr;
+use wdl_ast::v1::PrimitiveTypeKind;
+use wdl_ast::v1::StringText;
+use wdl_ast::v1::TaskDefinition;
+use wdl_ast::v1::Type;
+
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+
+/// The identifier for the output file not created rule.
+const ID: &str = "OutputFileNotCreated";
+
+/// Creates an "output file not created" diagnostic.
+fn output_file_not_created(name: &str, file: &str, span: Span) -> Diagnostic {
+    Diagnostic::note(format!(
+        "output file `{name}` does not appear to be created by the command"
+    ))
+    .with_rule(ID)
+    .with_label(
+        format!("`{file}` does not appear in the command section"),
+        span,
+    )
+    .with_fix(format!(
+        "ensure the command creates `{file}` or correct the file name of the output"
+    ))
+}
+
+/// Gets the string literals that name the file of an output expression.
+///
+/// Only string literals without placeholders and additions of such literals
+/// and names (e.g. `"out/" + name`) are considered; returns `None` for any
+/// other expression, such as a call to `glob`.
+fn file_names(expr: &Expr) -> Option<Vec<StringText>> {
+    match expr {
+        Expr::Literal(LiteralExpr::String(s)) => Some(vec![s.text()?]),
+        Expr::Name(_) => Some(Vec::new()),
+        Expr::Addition(expr) => {
+            let (lhs, rhs) = expr.operands();
+            let mut names = file_names(&lhs)?;
+            names.extend(file_names(&rhs)?);
+            Some(names)
+        }
+        _ => None,
+    }
+}
+
+/// Detects `File` outputs that do not appear to be created by the task's
+/// command.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct OutputFileNotCreatedRule;
+
+impl Rule for OutputFileNotCreatedRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags `File` outputs that do not appear to be created by the task's command."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A `File` output that names a file the command never writes (e.g. `File metrics = \
+         \"metrics.txt\"` when the command writes `metrics.tsv`) fails the task after its \
+         command has run. This rule checks that the file name of each string literal in a `File` \
+         output, either alone or added to a name (e.g. `prefix + \".txt\"`), appears in the text \
+         of the command section. Literals with placeholders and other expressions, such as calls \
+         to `glob`, are not checked, as the file names they produce are unknown."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Correctness])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("outputfilenotcreated"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["StringFileOutput"]
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+        Some(&[
+            SyntaxKind::VersionStatementNode,
+            SyntaxKind::TaskDefinitionNode,
+            SyntaxKind::OutputSectionNode,
+            SyntaxKind::BoundDeclNode,
+        ])
+    }
+}
+
+impl Visitor for OutputFileNotCreatedRule {
+    type State = Diagnostics;
+
+    fn document(&mut self, _: &mut Self::State, _: VisitReason, _: &Document, _: SupportedVersion) {
+        // This is intentionally empty, as this rule has no state.
+    }
+
+    fn task_definition(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        task: &TaskDefinition,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        let (Some(command), Some(output)) = (task.command(), task.output()) else {
+            return;
+        };
+
+        let text: Vec<_> = command
+            .parts()
+            .filter_map(|part| match part {
+                CommandPart::Text(text) => Some(text),
+                CommandPart::Placeholder(_) => None,
+            })
+            .collect();
+
+        for decl in output.declarations() {
+            let Type::Primitive(ty) = decl.ty() else {
+                continue;
+            };
+
+            if ty.kind() != PrimitiveTypeKind::File {
+                continue;
+            }
+
+            for name in file_names(&decl.expr()).unwrap_or_default() {
+                // Only the file name is checked, as a command may create the file from
+                // within its parent directory
+                let file = name.as_str().rsplit('/').next().unwrap_or_default();
+                if file.is_empty() || text.iter().any(|t| t.as_str().contains(file)) {
+                    continue;
+                }
+
+                state.exceptable_add(
+                    output_file_not_created(decl.name().as_str(), name.as_str(), name.span()),
+                    SyntaxElement::from(decl.syntax().clone()),
+                    &self.exceptable_nodes(),
+                );
+            }
+        }
+    }
+}
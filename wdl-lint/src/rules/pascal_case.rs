@@ -1,4 +1,5 @@
-//! A lint rule that ensures structs are defined with pascal case names.
+//! A lint rule that ensures structs are defined with pascal case names that do
+//! not conflict with builtin type names.
 
 use convert_case::Boundary;
 use convert_case::Case;
@@ -23,6 +24,20 @@ use crate::TagSet;
 /// The identifier for the pascal case rule.
 const ID: &str = "PascalCase";
 
+/// The names of the builtin WDL types.
+const BUILTIN_TYPE_NAMES: &[&str] = &[
+    "Array",
+    "Boolean",
+    "Directory",
+    "File",
+    "Float",
+    "Int",
+    "Map",
+    "Object",
+    "Pair",
+    "String",
+];
+
 /// Creates a "use pascal case" diagnostic.
 fn use_pascal_case(name: &str, properly_cased_name: &str, span: Span) -> Diagnostic {
     Diagnostic::warning(format!("struct name `{name}` is not PascalCase"))
@@ -31,6 +46,16 @@ fn use_pascal_case(name: &str, properly_cased_name: &str, span: Span) -> Diagnos
         .with_fix(format!("replace `{name}` with `{properly_cased_name}`"))
 }
 
+/// Creates a "builtin type name" diagnostic.
+fn builtin_type_name(name: &str, builtin: &str, span: Span) -> Diagnostic {
+    Diagnostic::error(format!(
+        "struct name `{name}` conflicts with the builtin type `{builtin}`"
+    ))
+    .with_rule(ID)
+    .with_label("this name conflicts with a builtin type", span)
+    .with_fix("rename the struct to a name that is not a builtin type name")
+}
+
 /// Detects structs defined without a pascal case name.
 #[derive(Default, Debug, Clone, Copy)]
 pub struct PascalCaseRule;
@@ -46,13 +71,24 @@ impl Rule for PascalCaseRule {
 
     fn explanation(&self) -> &'static str {
         "Struct names should be in PascalCase. Maintaining a consistent naming convention makes \
-         the code easier to read and understand."
+         the code easier to read and understand. Additionally, struct names must not be a builtin \
+         type name (e.g. `String` or `File`) in any case, as such a name cannot be written in \
+         PascalCase without conflicting with the builtin type. Struct member names are checked by \
+         the `SnakeCase` rule."
     }
 
     fn tags(&self) -> TagSet {
         TagSet::new(&[Tag::Naming, Tag::Style, Tag::Clarity])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("pascalcase"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["SnakeCase"]
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
         Some(&[
             SyntaxKind::VersionStatementNode,
@@ -63,6 +99,9 @@ impl Rule for PascalCaseRule {
 
 /// Checks if the given name is pascal case, and if not adds a warning to the
 /// diagnostics.
+///
+/// Names that are a builtin type name in a different case are reported as an
+/// error instead.
 fn check_name(
     name: &str,
     span: Span,
@@ -70,6 +109,19 @@ fn check_name(
     element: SyntaxElement,
     exceptable_nodes: &Option<&'static [SyntaxKind]>,
 ) {
+    // The names of builtin types are keywords, so only other cases can be parsed
+    if let Some(builtin) = BUILTIN_TYPE_NAMES
+        .iter()
+        .find(|b| b.eq_ignore_ascii_case(name))
+    {
+        diagnostics.exceptable_add(
+            builtin_type_name(name, builtin, span),
+            element,
+            exceptable_nodes,
+        );
+        return;
+    }
+
     let converter = Converter::new()
         .remove_boundaries(&[Boundary::DigitLower, Boundary::LowerDigit])
         .to_case(Case::Pascal);
@@ -55,6 +55,14 @@ impl Rule for PreambleCommentAfterVersionRule {
         TagSet::new(&[Tag::Clarity])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("preamblecommentafterversion"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["PreambleFormatting"]
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
         None
     }
@@ -153,6 +153,18 @@ impl Rule for PreambleFormattingRule {
         TagSet::new(&[Tag::Spacing, Tag::Style, Tag::Clarity])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("preambleformatting"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &[
+            "PreambleCommentAfterVersion",
+            "VersionFormatting",
+            "CommentWhitespace",
+        ]
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
         Some(&[SyntaxKind::VersionStatementNode])
     }
@@ -23,11 +23,15 @@ use crate::TagSet;
 const ID: &str = "RedundantInputAssignment";
 
 /// Create a "Redundant Input Assignment" diagnostic.
-fn redundant_input_assignment(span: Span, name: &str) -> Diagnostic {
+///
+/// The `assignment` span is the span of the `= <name>` portion of the input,
+/// which is removed by the diagnostic's replacement.
+fn redundant_input_assignment(span: Span, name: &str, assignment: Span) -> Diagnostic {
     Diagnostic::note("redundant input assignment")
         .with_rule(ID)
         .with_highlight(span)
         .with_fix(format!("can be shortened to `{}`", name))
+        .with_replacement(assignment, "")
 }
 
 /// Detects a redundant input assignment.
@@ -52,6 +56,14 @@ impl Rule for RedundantInputAssignment {
         TagSet::new(&[Tag::Style])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("redundantinputassignment"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["CallInputSpacing"]
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [wdl_ast::SyntaxKind]> {
         Some(&[
             wdl_ast::SyntaxKind::VersionStatementNode,
@@ -97,9 +109,15 @@ impl Visitor for RedundantInputAssignment {
             stmt.inputs().for_each(|input| {
                 if let Some(expr) = input.expr() {
                     if let Some(expr_name) = expr.as_name_ref() {
-                        if expr_name.name().as_str() == input.name().as_str() {
+                        let name = input.name();
+                        if expr_name.name().as_str() == name.as_str() {
+                            let start = name.span().end();
                             state.exceptable_add(
-                                redundant_input_assignment(input.span(), input.name().as_str()),
+                                redundant_input_assignment(
+                                    input.span(),
+                                    name.as_str(),
+                                    Span::new(start, expr.span().end() - start),
+                                ),
                                 SyntaxElement::from(input.syntax().clone()),
                                 &self.exceptable_nodes(),
                             );
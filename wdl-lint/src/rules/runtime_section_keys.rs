@@ -341,6 +341,14 @@ impl Rule for RuntimeSectionKeysRule {
         TagSet::new(&[Tag::Completeness, Tag::Deprecated])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("runtimesectionkeys"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["MissingRuntime", "MissingRequirements"]
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
         Some(&[
             SyntaxKind::VersionStatementNode,
@@ -0,0 +1,241 @@
+//! A lint rule for scatter-invariant `File` and `Directory` call inputs.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use wdl_ast::Ast;
+use wdl_ast::AstNode;
+use wdl_ast::AstNodeExt;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+use wdl_ast::v1::BoundDecl;
+use wdl_ast::v1::CallStatement;
+use wdl_ast::v1::NameRef;
+use wdl_ast::v1::PrimitiveTypeKind;
+use wdl_ast::v1::ScatterStatement;
+use wdl_ast::v1::TaskDefinition;
+use wdl_ast::v1::Type;
+
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+use crate::util::LOCALIZATION_OPTIONAL;
+use crate::util::is_localization_optional;
+
+/// The identifier for the scatter invariant input rule.
+const ID: &str = "ScatterInvariantInput";
+
+/// Creates a "scatter invariant input" diagnostic.
+fn scatter_invariant_input(task: &str, input: &str, span: Span) -> Diagnostic {
+    Diagnostic::note(format!(
+        "input `{input}` of task `{task}` is the same for every shard of the scatter"
+    ))
+    .with_rule(ID)
+    .with_label("this input does not depend on the scatter variable", span)
+    .with_fix(format!(
+        "mark input `{input}` of task `{task}` with a `{LOCALIZATION_OPTIONAL}` hint or \
+         restructure the workflow so the input is not localized for every shard"
+    ))
+}
+
+/// Gets the names of the `File` and `Directory` inputs of a task that are not
+/// marked with a `localization_optional` hint.
+fn file_inputs(task: &TaskDefinition) -> HashSet<String> {
+    task.input()
+        .into_iter()
+        .flat_map(|section| section.declarations())
+        .filter(|decl| {
+            matches!(
+                decl.ty(),
+                Type::Primitive(ty)
+                    if matches!(ty.kind(), PrimitiveTypeKind::File | PrimitiveTypeKind::Directory)
+            )
+        })
+        .map(|decl| decl.name().as_str().to_string())
+        .filter(|name| !is_localization_optional(task, name))
+        .collect()
+}
+
+/// Gets the names introduced by a scatter statement that vary with each shard.
+///
+/// This includes the scatter variable and the names of every declaration and
+/// call within the scatter's body.
+fn scatter_names(stmt: &ScatterStatement) -> HashSet<String> {
+    let mut names = HashSet::new();
+    names.insert(stmt.variable().as_str().to_string());
+
+    for node in stmt.syntax().descendants() {
+        if let Some(decl) = BoundDecl::cast(node.clone()) {
+            names.insert(decl.name().as_str().to_string());
+        } else if let Some(call) = CallStatement::cast(node.clone()) {
+            let name = match call.alias() {
+                Some(alias) => Some(alias.name()),
+                None => call.target().names().last(),
+            };
+
+            if let Some(name) = name {
+                names.insert(name.as_str().to_string());
+            }
+        } else if let Some(scatter) = ScatterStatement::cast(node) {
+            names.insert(scatter.variable().as_str().to_string());
+        }
+    }
+
+    names
+}
+
+/// Detects `File` and `Directory` call inputs within a scatter that are the
+/// same for every shard of the scatter.
+#[derive(Default, Debug, Clone)]
+pub struct ScatterInvariantInputRule {
+    /// The unmarked `File` and `Directory` inputs of the document's tasks,
+    /// keyed by task name.
+    tasks: HashMap<String, HashSet<String>>,
+    /// The stack of names that vary with each shard of the enclosing scatters.
+    scatters: Vec<HashSet<String>>,
+}
+
+impl ScatterInvariantInputRule {
+    /// Determines if the given name varies with each shard of an enclosing
+    /// scatter.
+    fn is_variant(&self, name: &str) -> bool {
+        self.scatters.iter().any(|names| names.contains(name))
+    }
+}
+
+impl Rule for ScatterInvariantInputRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags `File` and `Directory` call inputs within a scatter that are the same for every \
+         shard."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A call within a scatter is executed once per shard. When a `File` or `Directory` input \
+         of the call does not depend on the scatter variable (such as a large reference genome), \
+         naive backends localize the same file for every shard. Such inputs should be marked \
+         with a `localization_optional` hint (in the task's `parameter_meta` section or, in WDL \
+         1.2, the `inputs` hint of the task's `hints` section) or the workflow restructured so \
+         that the input is not localized for each shard. Only calls to tasks defined in the same \
+         document are checked, as the input types of imported tasks are unknown to this rule."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Portability])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("scatterinvariantinput"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["RedundantInputAssignment"]
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+        Some(&[
+            SyntaxKind::VersionStatementNode,
+            SyntaxKind::WorkflowDefinitionNode,
+            SyntaxKind::ScatterStatementNode,
+            SyntaxKind::CallStatementNode,
+            SyntaxKind::CallInputItemNode,
+        ])
+    }
+}
+
+impl Visitor for ScatterInvariantInputRule {
+    type State = Diagnostics;
+
+    fn document(
+        &mut self,
+        _: &mut Self::State,
+        reason: VisitReason,
+        document: &Document,
+        _: SupportedVersion,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        // Reset the visitor upon document entry
+        *self = Default::default();
+
+        if let Ast::V1(ast) = document.ast() {
+            self.tasks = ast
+                .tasks()
+                .map(|task| (task.name().as_str().to_string(), file_inputs(&task)))
+                .collect();
+        }
+    }
+
+    fn scatter_statement(
+        &mut self,
+        _: &mut Self::State,
+        reason: VisitReason,
+        stmt: &ScatterStatement,
+    ) {
+        match reason {
+            VisitReason::Enter => self.scatters.push(scatter_names(stmt)),
+            VisitReason::Exit => {
+                self.scatters.pop();
+            }
+        }
+    }
+
+    fn call_statement(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        stmt: &CallStatement,
+    ) {
+        if reason == VisitReason::Exit || self.scatters.is_empty() {
+            return;
+        }
+
+        // Only calls to tasks in this document have known input types
+        let mut names = stmt.target().names();
+        let (Some(target), None) = (names.next(), names.next()) else {
+            return;
+        };
+
+        let Some(inputs) = self.tasks.get(target.as_str()) else {
+            return;
+        };
+
+        for input in stmt.inputs() {
+            let name = input.name();
+            if !inputs.contains(name.as_str()) {
+                continue;
+            }
+
+            // An implicit binding refers to the name of the input
+            let invariant = match input.expr() {
+                Some(expr) => !expr
+                    .syntax()
+                    .descendants()
+                    .filter_map(NameRef::cast)
+                    .any(|r| self.is_variant(r.name().as_str())),
+                None => !self.is_variant(name.as_str()),
+            };
+
+            if invariant {
+                state.exceptable_add(
+                    scatter_invariant_input(target.as_str(), name.as_str(), input.span()),
+                    SyntaxElement::from(input.syntax().clone()),
+                    &self.exceptable_nodes(),
+                );
+            }
+        }
+    }
+}
@@ -76,6 +76,14 @@ impl Rule for SectionOrderingRule {
         TagSet::new(&[Tag::Style, Tag::Sorting])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("sectionordering"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["InputSorting"]
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
         Some(&[
             SyntaxKind::VersionStatementNode,
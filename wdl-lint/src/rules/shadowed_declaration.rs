@@ -0,0 +1,245 @@
+//! A lint rule for declarations that shadow a declaration of an outer scope.
+
+use wdl_ast::AstNode;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Ident;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::SyntaxNode;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+use wdl_ast::v1::ConditionalStatement;
+use wdl_ast::v1::ScatterStatement;
+use wdl_ast::v1::WorkflowDefinition;
+use wdl_ast::v1::WorkflowStatement;
+
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+
+/// The identifier for the shadowed declaration rule.
+const ID: &str = "ShadowedDeclaration";
+
+/// Creates a "shadowed declaration" diagnostic.
+fn shadowed_declaration(inner: &Name, outer: &Name) -> Diagnostic {
+    Diagnostic::warning(format!(
+        "{inner_context} `{name}` shadows the {outer_context} `{name}`",
+        inner_context = inner.context,
+        name = inner.name,
+        outer_context = outer.context,
+    ))
+    .with_rule(ID)
+    .with_label(
+        format!(
+            "this {context} shadows an outer name",
+            context = inner.context
+        ),
+        inner.span,
+    )
+    .with_label(
+        format!(
+            "the shadowed {context} is declared here",
+            context = outer.context
+        ),
+        outer.span,
+    )
+    .with_fix(format!(
+        "rename the {context} so that it does not shadow the {outer_context}",
+        context = inner.context,
+        outer_context = outer.context,
+    ))
+}
+
+/// Represents a name introduced into a scope.
+#[derive(Debug, Clone)]
+struct Name {
+    /// The name.
+    name: String,
+    /// The span of the name.
+    span: Span,
+    /// The kind of the name (e.g. `input` or `scatter variable`).
+    context: &'static str,
+    /// The element that introduces the name.
+    element: SyntaxElement,
+}
+
+impl Name {
+    /// Constructs a new name from an identifier.
+    fn new(ident: Ident, context: &'static str, syntax: &SyntaxNode) -> Self {
+        Self {
+            name: ident.as_str().to_string(),
+            span: ident.span(),
+            context,
+            element: SyntaxElement::from(syntax.clone()),
+        }
+    }
+}
+
+/// Gets the names declared directly within the given workflow statements.
+fn declarations(statements: impl Iterator<Item = WorkflowStatement>) -> Vec<Name> {
+    statements
+        .filter_map(|stmt| match stmt {
+            WorkflowStatement::Declaration(decl) => {
+                Some(Name::new(decl.name(), "declaration", decl.syntax()))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Detects declarations and scatter variables that shadow a declaration or
+/// input of an outer scope.
+#[derive(Default, Debug, Clone)]
+pub struct ShadowedDeclarationRule {
+    /// The stack of names visible from the current scope.
+    ///
+    /// The first scope is the workflow's inputs and body; each enclosing
+    /// scatter or conditional statement pushes a scope.
+    scopes: Vec<Vec<Name>>,
+}
+
+impl ShadowedDeclarationRule {
+    /// Checks the names of a nested scope against the outer scopes and then
+    /// enters the nested scope.
+    fn enter(&mut self, state: &mut Diagnostics, names: Vec<Name>) {
+        for name in &names {
+            let outer = self
+                .scopes
+                .iter()
+                .rev()
+                .flatten()
+                .find(|outer| outer.name == name.name);
+
+            if let Some(outer) = outer {
+                state.exceptable_add(
+                    shadowed_declaration(name, outer),
+                    name.element.clone(),
+                    &self.exceptable_nodes(),
+                );
+            }
+        }
+
+        self.scopes.push(names);
+    }
+}
+
+impl Rule for ShadowedDeclarationRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags declarations and scatter variables that shadow a declaration or input of an outer \
+         scope."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A scatter variable or a declaration within a scatter or conditional statement may have \
+         the same name as a workflow input or a declaration of an enclosing scope. Within the \
+         statement's body, the name then refers to the inner declaration, which is easily \
+         mistaken for the outer one. Inner declarations should be given distinct names. Task \
+         declarations are not checked, as a task has a single scope; struct members and call \
+         aliases are also not checked."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Clarity, Tag::Correctness])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("shadoweddeclaration"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["ShadowedFunctionName"]
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+        Some(&[
+            SyntaxKind::VersionStatementNode,
+            SyntaxKind::WorkflowDefinitionNode,
+            SyntaxKind::ScatterStatementNode,
+            SyntaxKind::ConditionalStatementNode,
+            SyntaxKind::BoundDeclNode,
+        ])
+    }
+}
+
+impl Visitor for ShadowedDeclarationRule {
+    type State = Diagnostics;
+
+    fn document(
+        &mut self,
+        _: &mut Self::State,
+        reason: VisitReason,
+        _: &Document,
+        _: SupportedVersion,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        // Reset the visitor upon document entry
+        *self = Default::default();
+    }
+
+    fn workflow_definition(
+        &mut self,
+        _: &mut Self::State,
+        reason: VisitReason,
+        workflow: &WorkflowDefinition,
+    ) {
+        self.scopes.clear();
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        let mut names: Vec<_> = workflow
+            .input()
+            .into_iter()
+            .flat_map(|section| section.declarations())
+            .map(|decl| Name::new(decl.name(), "input", decl.syntax()))
+            .collect();
+        names.extend(declarations(workflow.statements()));
+        self.scopes.push(names);
+    }
+
+    fn scatter_statement(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        stmt: &ScatterStatement,
+    ) {
+        if reason == VisitReason::Exit {
+            self.scopes.pop();
+            return;
+        }
+
+        let mut names = vec![Name::new(
+            stmt.variable(),
+            "scatter variable",
+            stmt.syntax(),
+        )];
+        names.extend(declarations(stmt.statements()));
+        self.enter(state, names);
+    }
+
+    fn conditional_statement(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        stmt: &ConditionalStatement,
+    ) {
+        if reason == VisitReason::Exit {
+            self.scopes.pop();
+            return;
+        }
+
+        self.enter(state, declarations(stmt.statements()));
+    }
+}
@@ -0,0 +1,280 @@
+//! A lint rule for names that shadow standard library functions.
+
+use serde::Deserialize;
+use wdl_ast::AstNode;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Ident;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::SyntaxNode;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+use wdl_ast::v1::BoundDecl;
+use wdl_ast::v1::CallStatement;
+use wdl_ast::v1::STDLIB_FUNCTION_NAMES;
+use wdl_ast::v1::UnboundDecl;
+
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+use crate::config::rule_options;
+
+/// The identifier for the shadowed function name rule.
+const ID: &str = "ShadowedFunctionName";
+
+/// The standard library functions whose names are most commonly used for
+/// declarations.
+///
+/// These are the functions checked when the rule is restricted to the common
+/// subset.
+pub const COMMONLY_SHADOWED_FUNCTIONS: &[&str] = &[
+    "basename", "defined", "floor", "glob", "keys", "length", "max", "min", "range", "round",
+    "sep", "size", "stderr", "stdout", "zip",
+];
+
+/// Creates a "shadowed function name" diagnostic.
+fn shadowed_function_name(context: &str, name: &str, span: Span) -> Diagnostic {
+    Diagnostic::warning(format!(
+        "{context} `{name}` shadows the standard library function `{name}`"
+    ))
+    .with_rule(ID)
+    .with_highlight(span)
+    .with_fix(format!(
+        "rename the {context} to something more descriptive (e.g. `{name}_value`)"
+    ))
+}
+
+/// Gets a description of the declaration with the given parent node.
+fn decl_context(parent: Option<SyntaxNode>) -> &'static str {
+    match parent.map(|p| p.kind()) {
+        Some(SyntaxKind::InputSectionNode) => "input",
+        Some(SyntaxKind::OutputSectionNode) => "output",
+        Some(SyntaxKind::StructDefinitionNode) => "struct member",
+        _ => "private declaration",
+    }
+}
+
+/// Detects names that shadow standard library functions.
+#[derive(Debug, Clone, Copy)]
+pub struct ShadowedFunctionNameRule {
+    /// Whether or not only the commonly shadowed functions are checked.
+    common_only: bool,
+}
+
+impl ShadowedFunctionNameRule {
+    /// Creates a new shadowed function name rule.
+    ///
+    /// If `common_only` is `true`, only names of the functions in
+    /// [`COMMONLY_SHADOWED_FUNCTIONS`] are reported; otherwise, names of all
+    /// standard library functions are reported.
+    pub fn new(common_only: bool) -> Self {
+        Self { common_only }
+    }
+
+    /// Gets whether or not only the commonly shadowed functions are checked.
+    pub fn common_only(&self) -> bool {
+        self.common_only
+    }
+
+    /// Checks the given name, reporting a diagnostic if it shadows a standard
+    /// library function.
+    fn check(&self, state: &mut Diagnostics, context: &str, name: &Ident, node: &SyntaxNode) {
+        let functions = if self.common_only {
+            COMMONLY_SHADOWED_FUNCTIONS
+        } else {
+            STDLIB_FUNCTION_NAMES
+        };
+
+        if !functions.contains(&name.as_str()) {
+            return;
+        }
+
+        state.exceptable_add(
+            shadowed_function_name(context, name.as_str(), name.span()),
+            SyntaxElement::from(node.clone()),
+            &self.exceptable_nodes(),
+        );
+    }
+}
+
+impl Default for ShadowedFunctionNameRule {
+    fn default() -> Self {
+        Self::new(false)
+    }
+}
+
+/// The options of the shadowed function name rule.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Options {
+    /// Whether or not only the commonly shadowed functions are checked.
+    common_only: Option<bool>,
+}
+
+impl Rule for ShadowedFunctionNameRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Ensures that names do not shadow standard library functions."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Inputs, private declarations, outputs, struct members, and call aliases named after a \
+         standard library function (e.g. `length` or `glob`) are legal, but an expression such \
+         as `length(length)` is confusing to read and easy to get wrong when editing. Names \
+         should describe the value they hold rather than reuse the name of a function. \
+         Optionally, only the names of the most commonly shadowed functions (e.g. `length`, \
+         `size`, and `range`) are reported."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Naming, Tag::Clarity])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("shadowedfunctionname"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["DisallowedInputName", "DisallowedOutputName", "SnakeCase"]
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+        Some(&[
+            SyntaxKind::VersionStatementNode,
+            SyntaxKind::StructDefinitionNode,
+            SyntaxKind::TaskDefinitionNode,
+            SyntaxKind::WorkflowDefinitionNode,
+            SyntaxKind::InputSectionNode,
+            SyntaxKind::OutputSectionNode,
+            SyntaxKind::BoundDeclNode,
+            SyntaxKind::UnboundDeclNode,
+            SyntaxKind::CallStatementNode,
+        ])
+    }
+
+    fn configure(&mut self, options: &toml::Table) -> anyhow::Result<()> {
+        let options: Options = rule_options(options)?;
+
+        if let Some(common_only) = options.common_only {
+            self.common_only = common_only;
+        }
+
+        Ok(())
+    }
+}
+
+impl Visitor for ShadowedFunctionNameRule {
+    type State = Diagnostics;
+
+    fn document(&mut self, _: &mut Self::State, _: VisitReason, _: &Document, _: SupportedVersion) {
+        // This is intentionally empty, as this rule has no state.
+    }
+
+    fn bound_decl(&mut self, state: &mut Self::State, reason: VisitReason, decl: &BoundDecl) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        self.check(
+            state,
+            decl_context(decl.syntax().parent()),
+            &decl.name(),
+            decl.syntax(),
+        );
+    }
+
+    fn unbound_decl(&mut self, state: &mut Self::State, reason: VisitReason, decl: &UnboundDecl) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        self.check(
+            state,
+            decl_context(decl.syntax().parent()),
+            &decl.name(),
+            decl.syntax(),
+        );
+    }
+
+    fn call_statement(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        stmt: &CallStatement,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        if let Some(alias) = stmt.alias() {
+            self.check(state, "call alias", &alias.name(), stmt.syntax());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use wdl_ast::Validator;
+
+    use super::*;
+
+    /// Gets the names reported by the given rule for the source.
+    fn shadowed(rule: ShadowedFunctionNameRule, source: &str) -> Vec<String> {
+        let (document, diagnostics) = Document::parse(source);
+        assert!(diagnostics.is_empty());
+
+        let mut validator = Validator::empty();
+        validator.add_visitor(rule);
+        match validator.validate(&document) {
+            Ok(()) => Vec::new(),
+            Err(diagnostics) => diagnostics
+                .iter()
+                .map(|d| {
+                    let span = d.labels().next().expect("should have a label").span();
+                    source[span.start()..span.end()].to_string()
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn it_restricts_to_common_functions() {
+        let source = r#"version 1.1
+
+workflow test {
+    input {
+        Int length
+        String prefix
+        Array[Int] lengths
+    }
+}
+"#;
+
+        assert_eq!(
+            shadowed(ShadowedFunctionNameRule::default(), source),
+            ["length", "prefix"]
+        );
+        assert_eq!(
+            shadowed(ShadowedFunctionNameRule::new(true), source),
+            ["length"]
+        );
+    }
+
+    #[test]
+    fn it_only_restricts_to_stdlib_functions() {
+        assert!(
+            COMMONLY_SHADOWED_FUNCTIONS
+                .iter()
+                .all(|f| STDLIB_FUNCTION_NAMES.contains(f))
+        );
+    }
+}
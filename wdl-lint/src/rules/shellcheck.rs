@@ -155,12 +155,25 @@ impl Rule for ShellCheckRule {
         TagSet::new(&[Tag::Correctness, Tag::Portability])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("shellcheck"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["CommandSectionMixedIndentation", "NoCurlyCommands"]
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
         Some(&[
             SyntaxKind::VersionStatementNode,
             SyntaxKind::CommandSectionNode,
         ])
     }
+
+    fn cacheable(&self) -> bool {
+        // The diagnostics depend on the availability of `shellcheck`
+        false
+    }
 }
 
 /// Convert a WDL `Placeholder` to a bash variable declaration.
@@ -6,6 +6,7 @@ use std::fmt;
 use convert_case::Boundary;
 use convert_case::Case;
 use convert_case::Converter;
+use serde::Deserialize;
 use wdl_ast::AstNode;
 use wdl_ast::AstToken;
 use wdl_ast::Diagnostic;
@@ -18,6 +19,7 @@ use wdl_ast::SyntaxKind;
 use wdl_ast::VisitReason;
 use wdl_ast::Visitor;
 use wdl_ast::v1::BoundDecl;
+use wdl_ast::v1::CallStatement;
 use wdl_ast::v1::InputSection;
 use wdl_ast::v1::OutputSection;
 use wdl_ast::v1::StructDefinition;
@@ -28,6 +30,7 @@ use wdl_ast::v1::WorkflowDefinition;
 use crate::Rule;
 use crate::Tag;
 use crate::TagSet;
+use crate::config::rule_options;
 
 /// Represents context of an warning.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -44,6 +47,8 @@ enum Context {
     Output,
     /// The warning occurred in a private declaration.
     PrivateDecl,
+    /// The warning occurred in a call alias.
+    CallAlias,
 }
 
 impl fmt::Display for Context {
@@ -55,6 +60,7 @@ impl fmt::Display for Context {
             Self::Input => write!(f, "input"),
             Self::Output => write!(f, "output"),
             Self::PrivateDecl => write!(f, "private declaration"),
+            Self::CallAlias => write!(f, "call alias"),
         }
     }
 }
@@ -70,21 +76,43 @@ fn snake_case(context: Context, name: &str, properly_cased_name: &str, span: Spa
         .with_fix(format!("replace `{name}` with `{properly_cased_name}`"))
 }
 
+/// Converts the given name to snake_case.
+///
+/// Returns `None` if the name is already snake_case.
+///
+/// Digits are not treated as word boundaries, so names such as `bwa_mem2` are
+/// snake_case. If `allow_leading_underscore` is `true`, leading underscores are
+/// kept as-is.
+fn to_snake_case(name: &str, allow_leading_underscore: bool) -> Option<String> {
+    let (prefix, rest) = if allow_leading_underscore {
+        name.split_at(name.len() - name.trim_start_matches('_').len())
+    } else {
+        ("", name)
+    };
+
+    let converter = Converter::new()
+        .remove_boundaries(&[Boundary::DigitLower, Boundary::LowerDigit])
+        .to_case(Case::Snake);
+    let properly_cased_name = format!("{prefix}{rest}", rest = converter.convert(rest));
+    if name != properly_cased_name {
+        Some(properly_cased_name)
+    } else {
+        None
+    }
+}
+
 /// Checks if the given name is snake case, and if not adds a warning to the
 /// diagnostics.
 fn check_name(
     context: Context,
     name: &str,
     span: Span,
+    allow_leading_underscore: bool,
     diagnostics: &mut Diagnostics,
     element: SyntaxElement,
     exceptable_nodes: &Option<&'static [SyntaxKind]>,
 ) {
-    let converter = Converter::new()
-        .remove_boundaries(&[Boundary::DigitLower, Boundary::LowerDigit])
-        .to_case(Case::Snake);
-    let properly_cased_name = converter.convert(name);
-    if name != properly_cased_name {
+    if let Some(properly_cased_name) = to_snake_case(name, allow_leading_underscore) {
         let warning = snake_case(context, name, &properly_cased_name, span);
         diagnostics.exceptable_add(warning, element, exceptable_nodes);
     }
@@ -99,9 +127,27 @@ pub struct SnakeCaseRule {
     within_input: bool,
     /// Whether the visitor is currently within an output section.
     within_output: bool,
+    /// Whether names may start with underscores.
+    allow_leading_underscore: bool,
 }
 
 impl SnakeCaseRule {
+    /// Constructs a new snake_case rule.
+    ///
+    /// If `allow_leading_underscore` is `true`, names may start with
+    /// underscores (e.g. `_private`).
+    pub fn new(allow_leading_underscore: bool) -> Self {
+        Self {
+            allow_leading_underscore,
+            ..Default::default()
+        }
+    }
+
+    /// Gets whether names may start with underscores.
+    pub fn allow_leading_underscore(&self) -> bool {
+        self.allow_leading_underscore
+    }
+
     /// Determines current declaration context.
     fn determine_decl_context(&self) -> Context {
         if self.within_struct {
@@ -116,6 +162,14 @@ impl SnakeCaseRule {
     }
 }
 
+/// The options of the snake case rule.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Options {
+    /// Whether names may start with underscores.
+    allow_leading_underscore: Option<bool>,
+}
+
 impl Rule for SnakeCaseRule {
     fn id(&self) -> &'static str {
         ID
@@ -126,14 +180,25 @@ impl Rule for SnakeCaseRule {
     }
 
     fn explanation(&self) -> &'static str {
-        "Workflow, task, and variable names should be in snake case. Maintaining a consistent \
-         naming convention makes the code easier to read and understand."
+        "Workflow, task, variable, and call alias names should be in snake case. Maintaining a \
+         consistent naming convention makes the code easier to read and understand. Struct names \
+         are not checked by this rule, as they should be in PascalCase. Digits are not treated as \
+         word boundaries (e.g. `bwa_mem2` is snake case). Whether names may start with \
+         underscores is configurable."
     }
 
     fn tags(&self) -> TagSet {
         TagSet::new(&[Tag::Naming, Tag::Style, Tag::Clarity])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("snakecase"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["PascalCase", "DisallowedInputName", "DisallowedOutputName"]
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
         Some(&[
             SyntaxKind::VersionStatementNode,
@@ -142,8 +207,19 @@ impl Rule for SnakeCaseRule {
             SyntaxKind::WorkflowDefinitionNode,
             SyntaxKind::BoundDeclNode,
             SyntaxKind::UnboundDeclNode,
+            SyntaxKind::CallStatementNode,
         ])
     }
+
+    fn configure(&mut self, options: &toml::Table) -> anyhow::Result<()> {
+        let options: Options = rule_options(options)?;
+
+        if let Some(allow_leading_underscore) = options.allow_leading_underscore {
+            self.allow_leading_underscore = allow_leading_underscore;
+        }
+
+        Ok(())
+    }
 }
 
 impl Visitor for SnakeCaseRule {
@@ -161,7 +237,7 @@ impl Visitor for SnakeCaseRule {
         }
 
         // Reset the visitor upon document entry
-        *self = Default::default();
+        *self = Self::new(self.allow_leading_underscore);
     }
 
     fn struct_definition(
@@ -227,6 +303,7 @@ impl Visitor for SnakeCaseRule {
             Context::Task,
             name.as_str(),
             name.span(),
+            self.allow_leading_underscore,
             state,
             SyntaxElement::from(task.syntax().clone()),
             &self.exceptable_nodes(),
@@ -248,6 +325,7 @@ impl Visitor for SnakeCaseRule {
             Context::Workflow,
             name.as_str(),
             name.span(),
+            self.allow_leading_underscore,
             state,
             SyntaxElement::from(workflow.syntax().clone()),
             &self.exceptable_nodes(),
@@ -265,6 +343,7 @@ impl Visitor for SnakeCaseRule {
             context,
             name.as_str(),
             name.span(),
+            self.allow_leading_underscore,
             state,
             SyntaxElement::from(decl.syntax().clone()),
             &self.exceptable_nodes(),
@@ -282,9 +361,79 @@ impl Visitor for SnakeCaseRule {
             context,
             name.as_str(),
             name.span(),
+            self.allow_leading_underscore,
             state,
             SyntaxElement::from(decl.syntax().clone()),
             &self.exceptable_nodes(),
         );
     }
+
+    fn call_statement(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        stmt: &CallStatement,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        let Some(alias) = stmt.alias() else {
+            return;
+        };
+
+        let name = alias.name();
+        check_name(
+            Context::CallAlias,
+            name.as_str(),
+            name.span(),
+            self.allow_leading_underscore,
+            state,
+            SyntaxElement::from(stmt.syntax().clone()),
+            &self.exceptable_nodes(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_converts_to_snake_case() {
+        assert_eq!(to_snake_case("sample_name", false), None);
+        assert_eq!(
+            to_snake_case("SampleName", false).as_deref(),
+            Some("sample_name")
+        );
+        assert_eq!(
+            to_snake_case("sampleName", false).as_deref(),
+            Some("sample_name")
+        );
+    }
+
+    #[test]
+    fn it_keeps_digits_within_words() {
+        assert_eq!(to_snake_case("bwa_mem2", false), None);
+        assert_eq!(to_snake_case("hg38_reference", false), None);
+        assert_eq!(to_snake_case("BwaMem2", false).as_deref(), Some("bwa_mem2"));
+    }
+
+    #[test]
+    fn it_allows_leading_underscores() {
+        assert_eq!(to_snake_case("_private", false).as_deref(), Some("private"));
+        assert_eq!(to_snake_case("_private", true), None);
+        assert_eq!(to_snake_case("__private_name", true), None);
+        assert_eq!(
+            to_snake_case("_PrivateName", true).as_deref(),
+            Some("_private_name")
+        );
+    }
+
+    #[test]
+    fn it_configures_leading_underscores() {
+        let rule = SnakeCaseRule::new(true);
+        assert!(rule.allow_leading_underscore());
+        assert!(!SnakeCaseRule::default().allow_leading_underscore());
+    }
 }
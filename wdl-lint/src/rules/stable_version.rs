@@ -0,0 +1,218 @@
+//! A lint rule for the version declared by a document.
+
+use serde::Deserialize;
+use wdl_ast::AstNode;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::VersionStatement;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+use wdl_ast::version::V1;
+
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+use crate::config::rule_options;
+
+/// The identifier for the stable version rule.
+const ID: &str = "StableVersion";
+
+/// The version of the WDL specification that is under development.
+const DEVELOPMENT_VERSION: &str = "development";
+
+/// The latest stable version of WDL.
+pub const LATEST_STABLE_VERSION: SupportedVersion = SupportedVersion::V1(V1::Two);
+
+/// Creates a "development version" diagnostic.
+fn development_version(span: Span) -> Diagnostic {
+    Diagnostic::warning(format!(
+        "document declares the `{DEVELOPMENT_VERSION}` version of WDL"
+    ))
+    .with_rule(ID)
+    .with_label("this version may change without notice", span)
+    .with_fix(format!(
+        "use the latest stable version of WDL, `{LATEST_STABLE_VERSION}`"
+    ))
+}
+
+/// Creates an "unknown version" diagnostic.
+fn unknown_version(version: &str, span: Span) -> Diagnostic {
+    Diagnostic::error(format!("unknown WDL version `{version}`"))
+        .with_rule(ID)
+        .with_label("this version of WDL is not supported", span)
+        .with_fix(format!(
+            "use a supported version of WDL, such as the latest stable version \
+             `{LATEST_STABLE_VERSION}`"
+        ))
+}
+
+/// Detects documents that declare the `development` version or an unknown
+/// version of WDL.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct StableVersionRule {
+    /// Whether or not the `development` version is allowed.
+    allow_development: bool,
+}
+
+impl StableVersionRule {
+    /// Creates a new stable version rule.
+    ///
+    /// If `allow_development` is `true`, the `development` version is not
+    /// reported; unknown versions are always reported.
+    pub fn new(allow_development: bool) -> Self {
+        Self { allow_development }
+    }
+
+    /// Gets whether or not the `development` version is allowed.
+    pub fn allow_development(&self) -> bool {
+        self.allow_development
+    }
+
+    /// Checks the version declared by the given version statement.
+    ///
+    /// The parser rejects documents that do not declare a supported version
+    /// before lint rules are run, so this may be used to check the version
+    /// statement of a document that failed to parse.
+    pub fn check(&self, statement: &VersionStatement) -> Option<Diagnostic> {
+        let version = statement.version();
+        match version.as_str() {
+            DEVELOPMENT_VERSION if self.allow_development => None,
+            DEVELOPMENT_VERSION => Some(development_version(version.span())),
+            v if v.parse::<SupportedVersion>().is_ok() => None,
+            v => Some(unknown_version(v, version.span())),
+        }
+    }
+}
+
+/// The options of the stable version rule.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Options {
+    /// Whether or not the `development` version is allowed.
+    allow_development: Option<bool>,
+}
+
+impl Rule for StableVersionRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Ensures that documents declare a stable version of WDL."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "The `development` version of WDL tracks a specification that has not been released and \
+         may change without notice, so documents intended for production should declare a \
+         stable version instead. Teams that intentionally track the development specification \
+         may allow the `development` version. Documents that declare a version that is not \
+         supported are always reported."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Correctness, Tag::Portability])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("stableversion"))
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+        Some(&[SyntaxKind::VersionStatementNode])
+    }
+
+    fn configure(&mut self, options: &toml::Table) -> anyhow::Result<()> {
+        let options: Options = rule_options(options)?;
+
+        if let Some(allow_development) = options.allow_development {
+            self.allow_development = allow_development;
+        }
+
+        Ok(())
+    }
+}
+
+impl Visitor for StableVersionRule {
+    type State = Diagnostics;
+
+    fn document(
+        &mut self,
+        _: &mut Self::State,
+        reason: VisitReason,
+        _: &Document,
+        _: SupportedVersion,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        // Reset the visitor upon document entry
+        *self = Self::new(self.allow_development);
+    }
+
+    fn version_statement(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        statement: &VersionStatement,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        if let Some(diagnostic) = self.check(statement) {
+            state.exceptable_add(
+                diagnostic,
+                SyntaxElement::from(statement.syntax().clone()),
+                &self.exceptable_nodes(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    /// Checks the version statement of the given source.
+    fn check(rule: StableVersionRule, source: &str) -> Option<Diagnostic> {
+        let (document, _) = Document::parse(source);
+        rule.check(&document.version_statement().expect("should have a version"))
+    }
+
+    #[test]
+    fn it_checks_versions() {
+        let rule = StableVersionRule::default();
+        assert_eq!(check(rule, "version 1.2\n"), None);
+
+        let diagnostic = check(rule, "version development\n").expect("should warn");
+        assert_eq!(
+            diagnostic.message(),
+            "document declares the `development` version of WDL"
+        );
+        assert_eq!(diagnostic.labels().next().unwrap().span(), Span::new(8, 11));
+        assert_eq!(
+            diagnostic.fix(),
+            Some("use the latest stable version of WDL, `1.2`")
+        );
+
+        let diagnostic = check(rule, "version 2.0\n").expect("should error");
+        assert_eq!(diagnostic.message(), "unknown WDL version `2.0`");
+        assert_eq!(diagnostic.labels().next().unwrap().span(), Span::new(8, 3));
+    }
+
+    #[test]
+    fn it_allows_development() {
+        let rule = StableVersionRule::new(true);
+        assert_eq!(check(rule, "version development\n"), None);
+        assert!(check(rule, "version draft-3\n").is_some());
+    }
+}
@@ -0,0 +1,209 @@
+//! A lint rule for static disk requests in tasks with file inputs.
+
+use wdl_ast::AstNode;
+use wdl_ast::AstNodeExt;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+use wdl_ast::v1::Expr;
+use wdl_ast::v1::LiteralExpr;
+use wdl_ast::v1::PrimitiveTypeKind;
+use wdl_ast::v1::StringPart;
+use wdl_ast::v1::TASK_REQUIREMENT_DISKS;
+use wdl_ast::v1::TaskDefinition;
+use wdl_ast::v1::Type;
+
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+
+/// The identifier for the static disk size rule.
+const ID: &str = "StaticDiskSize";
+
+/// Creates a "static disk size" diagnostic.
+fn static_disk_size(task: &str, suggestion: &str, span: Span) -> Diagnostic {
+    Diagnostic::warning(format!(
+        "task `{task}` requests a static disk size but has file inputs"
+    ))
+    .with_rule(ID)
+    .with_label(
+        "this disk size does not depend on the size of the inputs",
+        span,
+    )
+    .with_fix(format!(
+        "compute the disk size from the size of the task's file inputs: `{suggestion}`"
+    ))
+}
+
+/// Determines if the given type contains a `File` or `Directory`.
+fn contains_file(ty: &Type) -> bool {
+    match ty {
+        Type::Array(ty) => contains_file(&ty.element_type()),
+        Type::Map(ty) => contains_file(&ty.types().1),
+        Type::Pair(ty) => {
+            let (left, right) = ty.types();
+            contains_file(&left) || contains_file(&right)
+        }
+        Type::Primitive(ty) => {
+            matches!(
+                ty.kind(),
+                PrimitiveTypeKind::File | PrimitiveTypeKind::Directory
+            )
+        }
+        Type::Object(_) | Type::Ref(_) => false,
+    }
+}
+
+/// Gets the suggested replacement for a static disk expression.
+///
+/// The static size becomes the base size to which twice the size of the
+/// inputs, in gigabytes, is added. Returns `None` if the expression is not a
+/// literal constant.
+fn suggestion(expr: &Expr, inputs: &[String]) -> Option<String> {
+    let size = inputs
+        .iter()
+        .map(|i| format!(r#"size({i}, "GB")"#))
+        .collect::<Vec<_>>()
+        .join(" + ");
+    let size = if inputs.len() > 1 {
+        format!("({size})")
+    } else {
+        size
+    };
+
+    match expr {
+        Expr::Literal(LiteralExpr::Integer(i)) => {
+            Some(format!("ceil({size} * 2) + {base}", base = i.value()?))
+        }
+        Expr::Literal(LiteralExpr::String(s)) => {
+            if s.parts().any(|p| matches!(p, StringPart::Placeholder(_))) {
+                return None;
+            }
+
+            // Replace the first number in the string (e.g. `local-disk 20 SSD`) with a
+            // placeholder computing the size
+            let text = s.text().map(|t| t.as_str().to_string()).unwrap_or_default();
+            let start = text.find(|c: char| c.is_ascii_digit());
+            let (prefix, base, suffix) = match start {
+                Some(start) => {
+                    let end = text[start..]
+                        .find(|c: char| !c.is_ascii_digit())
+                        .map(|i| start + i)
+                        .unwrap_or(text.len());
+                    (&text[..start], &text[start..end], &text[end..])
+                }
+                None => (text.as_str(), "0", ""),
+            };
+
+            Some(format!(
+                r#""{prefix}~{{ceil({size} * 2) + {base}}}{suffix}""#
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// Detects tasks with file inputs that request a static disk size.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct StaticDiskSizeRule;
+
+impl Rule for StaticDiskSizeRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags static disk requests in tasks with file inputs."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A task with `File` or `Directory` inputs that requests a static amount of disk (e.g. \
+         `disks: \"local-disk 20 SSD\"`) will fail when its inputs are larger than expected. The \
+         disk request should instead be computed from the size of the inputs, such as \
+         `ceil(size(reads, \"GB\") * 2) + 20`. Tasks with only primitive inputs are not flagged."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Correctness])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("staticdisksize"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["MissingRequirements", "RuntimeSectionKeys"]
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+        Some(&[
+            SyntaxKind::VersionStatementNode,
+            SyntaxKind::TaskDefinitionNode,
+            SyntaxKind::RequirementsSectionNode,
+            SyntaxKind::RequirementsItemNode,
+            SyntaxKind::RuntimeSectionNode,
+            SyntaxKind::RuntimeItemNode,
+        ])
+    }
+}
+
+impl Visitor for StaticDiskSizeRule {
+    type State = Diagnostics;
+
+    fn document(&mut self, _: &mut Self::State, _: VisitReason, _: &Document, _: SupportedVersion) {
+        // This rule has no state to reset.
+    }
+
+    fn task_definition(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        task: &TaskDefinition,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        let inputs: Vec<_> = task
+            .input()
+            .into_iter()
+            .flat_map(|s| s.declarations())
+            .filter(|d| contains_file(&d.ty()))
+            .map(|d| d.name().as_str().to_string())
+            .collect();
+
+        if inputs.is_empty() {
+            return;
+        }
+
+        let requirements = task
+            .requirements()
+            .into_iter()
+            .flat_map(|s| s.items().map(|i| (i.name(), i.expr(), i.syntax().clone())));
+        let runtime = task
+            .runtime()
+            .into_iter()
+            .flat_map(|s| s.items().map(|i| (i.name(), i.expr(), i.syntax().clone())));
+
+        for (name, expr, item) in requirements.chain(runtime) {
+            if name.as_str() != TASK_REQUIREMENT_DISKS {
+                continue;
+            }
+
+            if let Some(suggestion) = suggestion(&expr, &inputs) {
+                state.exceptable_add(
+                    static_disk_size(task.name().as_str(), &suggestion, expr.span()),
+                    SyntaxElement::from(item),
+                    &self.exceptable_nodes(),
+                );
+            }
+        }
+    }
+}
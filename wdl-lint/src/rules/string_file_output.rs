@@ -0,0 +1,233 @@
+//! A lint rule for `String` outputs whose values are clearly file paths.
+
+use serde::Deserialize;
+use wdl_ast::AstNode;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::ToSpan;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+use wdl_ast::support;
+use wdl_ast::v1::BoundDecl;
+use wdl_ast::v1::Expr;
+use wdl_ast::v1::LiteralExpr;
+use wdl_ast::v1::OutputSection;
+use wdl_ast::v1::PrimitiveTypeKind;
+use wdl_ast::v1::StringPart;
+use wdl_ast::v1::Type;
+
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+use crate::config::rule_options;
+
+/// The identifier for the string file output rule.
+const ID: &str = "StringFileOutput";
+
+/// The default file extensions that indicate a path.
+///
+/// Extensions are matched case-insensitively against the end of the value.
+pub const DEFAULT_PATH_EXTENSIONS: &[&str] = &[
+    "bam", "bai", "bcf", "bed", "bgz", "cram", "crai", "csi", "csv", "fa", "fai", "fasta", "fastq",
+    "fq", "gff", "gtf", "gz", "html", "json", "log", "pdf", "png", "sam", "tar", "tbi", "tsv",
+    "txt", "vcf", "zip",
+];
+
+/// Creates a "path string output" diagnostic.
+fn path_string_output(name: &str, span: Span, replace: bool) -> Diagnostic {
+    let diagnostic = Diagnostic::warning(format!(
+        "output `{name}` has type `String` but its value is a file path"
+    ))
+    .with_rule(ID)
+    .with_label("this type should be `File`", span)
+    .with_fix("change the type of the output to `File` so that the file is delocalized");
+
+    if replace {
+        diagnostic.with_replacement(span, "File")
+    } else {
+        diagnostic
+    }
+}
+
+/// Creates a "glob string output" diagnostic.
+fn glob_string_output(name: &str, span: Span) -> Diagnostic {
+    Diagnostic::warning(format!(
+        "output `{name}` has type `String` but its value is a `glob` call"
+    ))
+    .with_rule(ID)
+    .with_label("this type should be `Array[File]`", span)
+    .with_fix("change the type of the output to `Array[File]`")
+}
+
+/// Detects `String` outputs whose values are clearly file paths.
+///
+/// A value is a file path if it is a string literal ending with one of a
+/// configurable set of file extensions, or if it is a call to `glob`.
+#[derive(Debug, Clone)]
+pub struct StringFileOutputRule {
+    /// The file extensions that indicate a path, without a leading `.`.
+    extensions: Vec<String>,
+}
+
+impl StringFileOutputRule {
+    /// Constructs a new string file output rule with the given file
+    /// extensions.
+    ///
+    /// Extensions are normalized to lowercase and any leading `.` is removed;
+    /// empty extensions are ignored.
+    pub fn new(extensions: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+        Self {
+            extensions: extensions
+                .into_iter()
+                .map(|e| e.as_ref().trim_start_matches('.').to_lowercase())
+                .filter(|e| !e.is_empty())
+                .collect(),
+        }
+    }
+
+    /// Gets the file extensions that indicate a path.
+    pub fn extensions(&self) -> &[String] {
+        &self.extensions
+    }
+
+    /// Determines if the given text ends with one of the path extensions.
+    fn has_path_extension(&self, text: &str) -> bool {
+        let text = text.to_lowercase();
+        self.extensions.iter().any(|e| {
+            text.strip_suffix(e.as_str())
+                .and_then(|t| t.strip_suffix('.'))
+                .is_some_and(|t| !t.is_empty() && !t.ends_with('/'))
+        })
+    }
+
+    /// Checks an output declaration.
+    fn check(&self, state: &mut Diagnostics, decl: &BoundDecl) {
+        let Type::Primitive(ty) = decl.ty() else {
+            return;
+        };
+
+        if ty.kind() != PrimitiveTypeKind::String {
+            return;
+        }
+
+        let span = support::token(ty.syntax(), SyntaxKind::StringTypeKeyword)
+            .expect("should have a `String` type keyword")
+            .text_range()
+            .to_span();
+        let name = decl.name();
+        let diagnostic = match decl.expr() {
+            Expr::Literal(LiteralExpr::String(s)) => {
+                // Only the text after the last placeholder is checked for an extension
+                let Some(StringPart::Text(text)) = s.parts().last() else {
+                    return;
+                };
+
+                if !self.has_path_extension(text.as_str()) {
+                    return;
+                }
+
+                // The type is only replaced for literals without placeholders
+                path_string_output(name.as_str(), span, s.text().is_some())
+            }
+            Expr::Call(call) if call.target().as_str() == "glob" => {
+                glob_string_output(name.as_str(), span)
+            }
+            _ => return,
+        };
+
+        state.exceptable_add(
+            diagnostic,
+            SyntaxElement::from(decl.syntax().clone()),
+            &self.exceptable_nodes(),
+        );
+    }
+}
+
+impl Default for StringFileOutputRule {
+    fn default() -> Self {
+        Self::new(DEFAULT_PATH_EXTENSIONS)
+    }
+}
+
+/// The options of the string file output rule.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Options {
+    /// The file extensions that indicate a path.
+    extensions: Option<Vec<String>>,
+}
+
+impl Rule for StringFileOutputRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Ensures that outputs whose values are file paths have a file type."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Execution engines only delocalize outputs with a file type; an output such as `String \
+         out_vcf = \"out.vcf.gz\"` refers to a file that is left behind in the task's working \
+         directory. This rule flags `String` outputs whose value is a string literal ending with \
+         a common file extension (the list of extensions is configurable) or a call to `glob`. \
+         Such outputs should instead be declared as `File` (or `Array[File]` for `glob`)."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Correctness])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("stringfileoutput"))
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+        Some(&[
+            SyntaxKind::VersionStatementNode,
+            SyntaxKind::TaskDefinitionNode,
+            SyntaxKind::WorkflowDefinitionNode,
+            SyntaxKind::OutputSectionNode,
+            SyntaxKind::BoundDeclNode,
+        ])
+    }
+
+    fn configure(&mut self, options: &toml::Table) -> anyhow::Result<()> {
+        let options: Options = rule_options(options)?;
+
+        if let Some(extensions) = options.extensions {
+            self.extensions = Self::new(extensions).extensions;
+        }
+
+        Ok(())
+    }
+}
+
+impl Visitor for StringFileOutputRule {
+    type State = Diagnostics;
+
+    fn document(&mut self, _: &mut Self::State, _: VisitReason, _: &Document, _: SupportedVersion) {
+        // This rule has no state to reset.
+    }
+
+    fn output_section(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        section: &OutputSection,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        for decl in section.declarations() {
+            self.check(state, &decl);
+        }
+    }
+}
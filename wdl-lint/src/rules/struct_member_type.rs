@@ -0,0 +1,220 @@
+//! A lint rule for struct members with `Object` or deeply nested types.
+
+use serde::Deserialize;
+use wdl_ast::AstNode;
+use wdl_ast::AstNodeExt;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::ToSpan;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+use wdl_ast::v1::StructDefinition;
+use wdl_ast::v1::Type;
+
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+use crate::config::rule_options;
+
+/// The identifier for the struct member type rule.
+const ID: &str = "StructMemberType";
+
+/// The default maximum nesting depth of a struct member's type.
+pub const DEFAULT_MAX_NESTING_DEPTH: usize = 3;
+
+/// Creates an "object struct member" diagnostic.
+fn object_struct_member(name: &str, member: &str, span: Span) -> Diagnostic {
+    Diagnostic::warning(format!(
+        "member `{member}` of struct `{name}` uses the `Object` type"
+    ))
+    .with_rule(ID)
+    .with_label("this type has no member type information", span)
+    .with_fix("replace the `Object` with a struct that defines the expected members")
+}
+
+/// Creates a "deeply nested struct member" diagnostic.
+fn deeply_nested_struct_member(
+    name: &str,
+    member: &str,
+    depth: usize,
+    max: usize,
+    span: Span,
+) -> Diagnostic {
+    Diagnostic::note(format!(
+        "member `{member}` of struct `{name}` has a type nested {depth} levels deep"
+    ))
+    .with_rule(ID)
+    .with_highlight(span)
+    .with_fix(format!(
+        "define structs for the inner types so that the type is nested at most {max} levels deep"
+    ))
+}
+
+/// Gets the nesting depth of a type.
+///
+/// Each `Array`, `Map`, or `Pair` adds one level of nesting.
+fn nesting_depth(ty: &Type) -> usize {
+    match ty {
+        Type::Array(ty) => 1 + nesting_depth(&ty.element_type()),
+        Type::Map(ty) => 1 + nesting_depth(&ty.types().1),
+        Type::Pair(ty) => {
+            let (left, right) = ty.types();
+            1 + nesting_depth(&left).max(nesting_depth(&right))
+        }
+        Type::Object(_) | Type::Ref(_) | Type::Primitive(_) => 0,
+    }
+}
+
+/// Gets the spans of the `Object` types within a type.
+pub(crate) fn object_spans(ty: &Type, spans: &mut Vec<Span>) {
+    match ty {
+        Type::Array(ty) => object_spans(&ty.element_type(), spans),
+        Type::Map(ty) => object_spans(&ty.types().1, spans),
+        Type::Pair(ty) => {
+            let (left, right) = ty.types();
+            object_spans(&left, spans);
+            object_spans(&right, spans);
+        }
+        Type::Object(ty) => spans.push(ty.span()),
+        Type::Ref(_) | Type::Primitive(_) => {}
+    }
+}
+
+/// Detects struct members with `Object` or deeply nested types.
+#[derive(Debug, Clone, Copy)]
+pub struct StructMemberTypeRule {
+    /// The maximum nesting depth of a struct member's type.
+    max_depth: usize,
+}
+
+impl StructMemberTypeRule {
+    /// Constructs a new struct member type rule with the given maximum nesting
+    /// depth.
+    pub fn new(max_depth: usize) -> Self {
+        Self { max_depth }
+    }
+
+    /// Gets the maximum nesting depth of a struct member's type.
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+}
+
+impl Default for StructMemberTypeRule {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_NESTING_DEPTH)
+    }
+}
+
+/// The options of the struct member type rule.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Options {
+    /// The maximum nesting depth of a struct member's type.
+    max_depth: Option<usize>,
+}
+
+impl Rule for StructMemberTypeRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags struct members with `Object` or deeply nested types."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A struct member with an `Object` type discards all type information about the member's \
+         contents, defeating the purpose of defining a struct; a struct that defines the \
+         expected members should be used instead. Struct members with deeply nested types (e.g. \
+         a `Map` of `Array`s of `Map`s) are difficult to read and to construct in inputs; \
+         defining structs for the inner types gives each level a name."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Deprecated, Tag::Clarity])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("structmembertype"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["DeprecatedObject"]
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+        Some(&[
+            SyntaxKind::VersionStatementNode,
+            SyntaxKind::StructDefinitionNode,
+            SyntaxKind::UnboundDeclNode,
+        ])
+    }
+
+    fn configure(&mut self, options: &toml::Table) -> anyhow::Result<()> {
+        let options: Options = rule_options(options)?;
+
+        if let Some(max_depth) = options.max_depth {
+            self.max_depth = max_depth;
+        }
+
+        Ok(())
+    }
+}
+
+impl Visitor for StructMemberTypeRule {
+    type State = Diagnostics;
+
+    fn document(&mut self, _: &mut Self::State, _: VisitReason, _: &Document, _: SupportedVersion) {
+        // This rule has no state to reset.
+    }
+
+    fn struct_definition(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        def: &StructDefinition,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        let name = def.name();
+        for member in def.members() {
+            let ty = member.ty();
+            let member_name = member.name();
+            let element = SyntaxElement::from(member.syntax().clone());
+
+            let mut spans = Vec::new();
+            object_spans(&ty, &mut spans);
+            for span in spans {
+                state.exceptable_add(
+                    object_struct_member(name.as_str(), member_name.as_str(), span),
+                    element.clone(),
+                    &self.exceptable_nodes(),
+                );
+            }
+
+            let depth = nesting_depth(&ty);
+            if depth > self.max_depth {
+                state.exceptable_add(
+                    deeply_nested_struct_member(
+                        name.as_str(),
+                        member_name.as_str(),
+                        depth,
+                        self.max_depth,
+                        ty.syntax().text_range().to_span(),
+                    ),
+                    element,
+                    &self.exceptable_nodes(),
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,231 @@
+//! A lint rule for `parameter_meta` entries that are stub documentation.
+
+use serde::Deserialize;
+use wdl_ast::AstNode;
+use wdl_ast::AstNodeExt;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+use wdl_ast::v1::MetadataObjectItem;
+use wdl_ast::v1::MetadataValue;
+use wdl_ast::v1::ParameterMetadataSection;
+
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+use crate::config::rule_options;
+
+/// The identifier for the stub parameter meta rule.
+const ID: &str = "StubParameterMeta";
+
+/// The default stub markers.
+const DEFAULT_MARKERS: &[&str] = &["TODO", "FIXME", "TBD", "XXX", "fill me in"];
+
+/// The default maximum number of words of a value for it to be checked.
+const DEFAULT_MAX_WORDS: usize = 5;
+
+/// Creates a "stub parameter meta" diagnostic.
+fn stub_parameter_meta(name: &str, marker: &str, span: Span) -> Diagnostic {
+    Diagnostic::note(format!(
+        "parameter metadata for `{name}` is a stub containing `{marker}`"
+    ))
+    .with_rule(ID)
+    .with_highlight(span)
+    .with_fix(format!("replace the stub with a description of `{name}`"))
+}
+
+/// Finds the first marker in the given text.
+///
+/// Markers are matched case-insensitively and must not be part of a larger
+/// word.
+fn find_marker<'a>(text: &str, markers: &'a [String]) -> Option<&'a str> {
+    let text = text.to_lowercase();
+    markers
+        .iter()
+        .find(|marker| {
+            let marker = marker.to_lowercase();
+            text.match_indices(&marker).any(|(offset, _)| {
+                !text[..offset]
+                    .chars()
+                    .next_back()
+                    .is_some_and(char::is_alphanumeric)
+                    && !text[offset + marker.len()..]
+                        .chars()
+                        .next()
+                        .is_some_and(char::is_alphanumeric)
+            })
+        })
+        .map(String::as_str)
+}
+
+/// Detects `parameter_meta` entries that are stub documentation, such as
+/// `sample_id: "TODO"`.
+///
+/// Both string values and the `description` members of object values are
+/// checked; values with more than a configurable number of words are not
+/// checked.
+#[derive(Debug, Clone)]
+pub struct StubParameterMetaRule {
+    /// The stub markers to detect.
+    markers: Vec<String>,
+    /// The maximum number of words of a value for it to be checked.
+    max_words: usize,
+}
+
+impl StubParameterMetaRule {
+    /// Constructs a new stub parameter meta rule with the given stub markers
+    /// and maximum number of words of a checked value.
+    pub fn new(markers: impl IntoIterator<Item = impl Into<String>>, max_words: usize) -> Self {
+        Self {
+            markers: markers.into_iter().map(Into::into).collect(),
+            max_words,
+        }
+    }
+
+    /// Gets the stub markers to detect.
+    pub fn markers(&self) -> &[String] {
+        &self.markers
+    }
+
+    /// Gets the maximum number of words of a value for it to be checked.
+    pub fn max_words(&self) -> usize {
+        self.max_words
+    }
+
+    /// Checks a `parameter_meta` item for stub documentation.
+    fn check(&self, state: &mut Diagnostics, item: &MetadataObjectItem) {
+        let value = match item.value() {
+            MetadataValue::String(s) => s,
+            MetadataValue::Object(o) => {
+                let Some(MetadataValue::String(s)) = o
+                    .items()
+                    .find(|i| i.name().as_str() == "description")
+                    .map(|i| i.value())
+                else {
+                    return;
+                };
+
+                s
+            }
+            _ => return,
+        };
+
+        let Some(text) = value.text() else {
+            return;
+        };
+
+        if text.as_str().split_whitespace().count() > self.max_words {
+            return;
+        }
+
+        if let Some(marker) = find_marker(text.as_str(), &self.markers) {
+            state.exceptable_add(
+                stub_parameter_meta(item.name().as_str(), marker, value.span()),
+                SyntaxElement::from(item.syntax().clone()),
+                &self.exceptable_nodes(),
+            );
+        }
+    }
+}
+
+impl Default for StubParameterMetaRule {
+    fn default() -> Self {
+        Self::new(DEFAULT_MARKERS.iter().copied(), DEFAULT_MAX_WORDS)
+    }
+}
+
+/// The options of the stub parameter meta rule.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Options {
+    /// The stub markers to detect.
+    markers: Option<Vec<String>>,
+    /// The maximum number of words of a value for it to be checked.
+    max_words: Option<usize>,
+}
+
+impl Rule for StubParameterMetaRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Ensures that `parameter_meta` entries are not stub documentation."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Stub documentation such as `sample_id: \"TODO\"` satisfies `MatchingParameterMeta` but \
+         does not describe the parameter. This rule flags `parameter_meta` values (and the \
+         `description` members of object values) that contain a stub marker; by default, the \
+         markers are `TODO`, `FIXME`, `TBD`, `XXX`, and `fill me in`, matched case-insensitively. \
+         Values of more than five words are not checked, as a marker within a longer sentence is \
+         usually legitimate."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Completeness])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("stubparametermeta"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["MatchingParameterMeta", "Todo"]
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+        Some(&[
+            SyntaxKind::VersionStatementNode,
+            SyntaxKind::TaskDefinitionNode,
+            SyntaxKind::WorkflowDefinitionNode,
+            SyntaxKind::StructDefinitionNode,
+            SyntaxKind::ParameterMetadataSectionNode,
+            SyntaxKind::MetadataObjectItemNode,
+        ])
+    }
+
+    fn configure(&mut self, options: &toml::Table) -> anyhow::Result<()> {
+        let options: Options = rule_options(options)?;
+
+        if let Some(markers) = options.markers {
+            self.markers = markers;
+        }
+
+        if let Some(max_words) = options.max_words {
+            self.max_words = max_words;
+        }
+
+        Ok(())
+    }
+}
+
+impl Visitor for StubParameterMetaRule {
+    type State = Diagnostics;
+
+    fn document(&mut self, _: &mut Self::State, _: VisitReason, _: &Document, _: SupportedVersion) {
+        // This is intentionally empty, as this rule has no state.
+    }
+
+    fn parameter_metadata_section(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        section: &ParameterMetadataSection,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        for item in section.items() {
+            self.check(state, &item);
+        }
+    }
+}
@@ -1,5 +1,6 @@
 //! A lint rule for flagging TODOs.
 
+use serde::Deserialize;
 use wdl_ast::AstToken;
 use wdl_ast::Comment;
 use wdl_ast::Diagnostic;
@@ -14,25 +15,166 @@ use wdl_ast::Visitor;
 use crate::Rule;
 use crate::Tag;
 use crate::TagSet;
+use crate::config::rule_options;
 
 /// The identifier for the todos rule.
 const ID: &str = "Todo";
 
-/// The `TODO` token.
-const TODO: &str = "TODO";
+/// The prefix of lint directive comments, which are not checked for markers.
+const DIRECTIVE_PREFIX: &str = "#@";
 
-/// Detects remaining TODOs within comments.
-#[derive(Default, Debug, Clone, Copy)]
-pub struct TodoRule;
+/// The default markers of todo items.
+pub const DEFAULT_TODO_MARKERS: &[&str] = &["TODO", "FIXME", "XXX"];
 
 /// Creates a "todo comment" diagnostic.
-fn todo_comment(comment: &str, comment_span: Span, offset: usize) -> Diagnostic {
-    let start = comment_span.start() + offset;
+///
+/// The label of the diagnostic is the remaining text of the comment.
+fn todo_comment(marker: &str, text: &str, span: Span) -> Diagnostic {
+    let diagnostic = Diagnostic::note(format!("remaining `{marker}` item found")).with_rule(ID);
+
+    let diagnostic = if text.is_empty() {
+        diagnostic.with_highlight(span)
+    } else {
+        diagnostic.with_label(text, span)
+    };
+
+    diagnostic.with_fix(format!(
+        "remove the `{marker}` item once it has been implemented"
+    ))
+}
+
+/// Creates a "todo without reference" diagnostic.
+fn todo_without_reference(marker: &str, text: &str, span: Span) -> Diagnostic {
+    let diagnostic =
+        Diagnostic::warning(format!("`{marker}` item has no issue reference")).with_rule(ID);
+
+    let diagnostic = if text.is_empty() {
+        diagnostic.with_highlight(span)
+    } else {
+        diagnostic.with_label(text, span)
+    };
+
+    diagnostic.with_fix(format!(
+        "add an issue reference to the `{marker}` item (e.g. `{marker}(#123)`)"
+    ))
+}
+
+/// Determines if the given character is part of a word.
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Represents a marker found in a comment.
+#[derive(Debug)]
+struct Marker<'a> {
+    /// The configured marker that was matched.
+    marker: &'a str,
+    /// The offset of the marker within the comment.
+    offset: usize,
+    /// Whether or not the marker is followed by an issue reference.
+    referenced: bool,
+    /// The remaining text of the comment following the marker and any issue
+    /// reference.
+    text: &'a str,
+}
 
-    Diagnostic::note(format!("remaining `{TODO}` item found"))
-        .with_rule(ID)
-        .with_highlight(Span::new(start, comment.len()))
-        .with_fix("remove the `TODO` item once it has been implemented")
+/// Finds the markers in the given comment text.
+///
+/// Markers are matched case-insensitively and only as whole words.
+fn find_markers<'a>(comment: &'a str, markers: &'a [String]) -> Vec<Marker<'a>> {
+    let mut found = Vec::new();
+    for (offset, _) in comment.char_indices() {
+        if comment[..offset]
+            .chars()
+            .next_back()
+            .is_some_and(is_word_char)
+        {
+            continue;
+        }
+
+        let rest = &comment[offset..];
+        let Some(marker) = markers.iter().find(|m| {
+            rest.get(..m.len())
+                .is_some_and(|s| s.eq_ignore_ascii_case(m))
+                && !rest[m.len()..].chars().next().is_some_and(is_word_char)
+        }) else {
+            continue;
+        };
+
+        // An issue reference is a non-empty parenthesized suffix (e.g. `TODO(#123)`)
+        let mut text = &rest[marker.len()..];
+        let mut referenced = false;
+        if let Some(reference) = text.strip_prefix('(') {
+            if let Some(end) = reference.find(')').filter(|end| *end > 0) {
+                referenced = true;
+                text = &reference[end + 1..];
+            }
+        }
+
+        found.push(Marker {
+            marker,
+            offset,
+            referenced,
+            text: text
+                .trim_start_matches(|c: char| c.is_whitespace() || c.is_ascii_punctuation())
+                .trim_end(),
+        });
+    }
+
+    found
+}
+
+/// Detects remaining TODOs within comments.
+#[derive(Debug, Clone)]
+pub struct TodoRule {
+    /// The markers of todo items.
+    markers: Vec<String>,
+    /// Whether or not todo items are required to have an issue reference.
+    require_reference: bool,
+}
+
+impl TodoRule {
+    /// Creates a new todo rule with the given markers.
+    ///
+    /// Markers are matched case-insensitively and only as whole words.
+    ///
+    /// If `require_reference` is `true`, todo items without an issue reference
+    /// (e.g. `TODO(#123)`) are reported as warnings.
+    pub fn new(
+        markers: impl IntoIterator<Item = impl Into<String>>,
+        require_reference: bool,
+    ) -> Self {
+        Self {
+            markers: markers.into_iter().map(Into::into).collect(),
+            require_reference,
+        }
+    }
+
+    /// Gets the markers of todo items.
+    pub fn markers(&self) -> &[String] {
+        &self.markers
+    }
+
+    /// Gets whether or not todo items are required to have an issue reference.
+    pub fn require_reference(&self) -> bool {
+        self.require_reference
+    }
+}
+
+impl Default for TodoRule {
+    fn default() -> Self {
+        Self::new(DEFAULT_TODO_MARKERS.iter().copied(), false)
+    }
+}
+
+/// The options of the todo rule.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Options {
+    /// The markers of todo items.
+    markers: Option<Vec<String>>,
+    /// Whether or not todo items are required to have an issue reference.
+    require_reference: Option<bool>,
 }
 
 impl Rule for TodoRule {
@@ -48,16 +190,37 @@ impl Rule for TodoRule {
         "When writing WDL, future tasks are often marked as `TODO`. This indicates that the \
          implementor intended to go back to the code and handle the todo item. Todo items should \
          not be long-term fixtures within code and, as such, they are flagged to ensure none are \
-         forgotten."
+         forgotten. By default, the `TODO`, `FIXME`, and `XXX` markers are matched \
+         case-insensitively as whole words. Optionally, todo items may be required to reference \
+         an issue (e.g. `TODO(#123)`), in which case todo items without a reference are reported \
+         as warnings."
     }
 
     fn tags(&self) -> TagSet {
         TagSet::new(&[Tag::Completeness])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("todo"))
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [wdl_ast::SyntaxKind]> {
         None
     }
+
+    fn configure(&mut self, options: &toml::Table) -> anyhow::Result<()> {
+        let options: Options = rule_options(options)?;
+
+        if let Some(markers) = options.markers {
+            self.markers = markers;
+        }
+
+        if let Some(require_reference) = options.require_reference {
+            self.require_reference = require_reference;
+        }
+
+        Ok(())
+    }
 }
 
 impl Visitor for TodoRule {
@@ -68,9 +231,20 @@ impl Visitor for TodoRule {
     }
 
     fn comment(&mut self, state: &mut Self::State, comment: &Comment) {
-        for (offset, pattern) in comment.as_str().match_indices(TODO) {
+        if comment.as_str().starts_with(DIRECTIVE_PREFIX) {
+            return;
+        }
+
+        for marker in find_markers(comment.as_str(), &self.markers) {
+            let span = Span::new(comment.span().start() + marker.offset, marker.marker.len());
+            let diagnostic = if self.require_reference && !marker.referenced {
+                todo_without_reference(marker.marker, marker.text, span)
+            } else {
+                todo_comment(marker.marker, marker.text, span)
+            };
+
             state.exceptable_add(
-                todo_comment(pattern, comment.span(), offset),
+                diagnostic,
                 SyntaxElement::from(comment.syntax().clone()),
                 &self.exceptable_nodes(),
             );
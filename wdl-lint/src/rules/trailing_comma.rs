@@ -66,6 +66,14 @@ impl Rule for TrailingCommaRule {
         TagSet::new(&[Tag::Style])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("trailingcomma"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["KeyValuePairs"]
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
         Some(&[
             SyntaxKind::VersionStatementNode,
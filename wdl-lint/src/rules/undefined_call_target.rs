@@ -0,0 +1,151 @@
+//! A lint rule for calls to tasks or workflows that do not exist in an
+//! imported document.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use wdl_ast::Ast;
+use wdl_ast::AstNode;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+use wdl_ast::v1::CallStatement;
+
+use crate::ImportedDocuments;
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+
+/// The identifier for the undefined call target rule.
+const ID: &str = "UndefinedCallTarget";
+
+/// Creates an "undefined call target" diagnostic.
+fn undefined_call_target(namespace: &str, name: &str, span: Span) -> Diagnostic {
+    Diagnostic::warning(format!(
+        "`{name}` is not a task or workflow in the document imported as `{namespace}`"
+    ))
+    .with_rule(ID)
+    .with_label("this task or workflow does not exist", span)
+    .with_fix(format!(
+        "call a task or workflow defined in the document imported as `{namespace}`"
+    ))
+}
+
+/// Detects calls to tasks or workflows that do not exist in an imported
+/// document.
+///
+/// This rule uses the imported documents provided to the lint visitor; calls
+/// into imports that were not resolved are not checked.
+#[derive(Default, Debug, Clone)]
+pub struct UndefinedCallTargetRule {
+    /// The names of the tasks and workflows of each imported document, keyed
+    /// by import namespace.
+    imports: HashMap<String, HashSet<String>>,
+}
+
+impl Rule for UndefinedCallTargetRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Ensures that calls into an imported document name a task or workflow that exists in that \
+         document."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A call of the form `namespace.name` must name a task or workflow defined in the document \
+         imported with that namespace; otherwise, the workflow will fail to run. This rule only \
+         checks imports that have been resolved by the caller of the linter; calls into imports \
+         that were not resolved are not checked."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Correctness])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("undefinedcalltarget"))
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+        Some(&[
+            SyntaxKind::VersionStatementNode,
+            SyntaxKind::WorkflowDefinitionNode,
+            SyntaxKind::CallStatementNode,
+        ])
+    }
+
+    fn set_imports(&mut self, imports: &ImportedDocuments) {
+        self.imports = imports
+            .iter()
+            .filter_map(|(namespace, document)| {
+                let Ast::V1(ast) = document.ast() else {
+                    return None;
+                };
+
+                let names = ast
+                    .tasks()
+                    .map(|t| t.name().as_str().to_string())
+                    .chain(ast.workflows().map(|w| w.name().as_str().to_string()))
+                    .collect();
+                Some((namespace.to_string(), names))
+            })
+            .collect();
+    }
+}
+
+impl Visitor for UndefinedCallTargetRule {
+    type State = Diagnostics;
+
+    fn document(
+        &mut self,
+        _: &mut Self::State,
+        reason: VisitReason,
+        _: &Document,
+        _: SupportedVersion,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        // Reset the visitor upon document entry; the imports are set afterwards
+        *self = Default::default();
+    }
+
+    fn call_statement(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        stmt: &CallStatement,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        let mut names = stmt.target().names();
+        let (Some(namespace), Some(name), None) = (names.next(), names.next(), names.next()) else {
+            return;
+        };
+
+        // Calls into unresolved imports are not checked
+        let Some(targets) = self.imports.get(namespace.as_str()) else {
+            return;
+        };
+
+        if !targets.contains(name.as_str()) {
+            state.exceptable_add(
+                undefined_call_target(namespace.as_str(), name.as_str(), name.span()),
+                SyntaxElement::from(stmt.syntax().clone()),
+                &self.exceptable_nodes(),
+            );
+        }
+    }
+}
@@ -17,21 +17,58 @@ use crate::Rule;
 use crate::Tag;
 use crate::TagSet;
 use crate::rules::RULE_MAP;
+use crate::util::levenshtein;
 
 /// The identifier for the unknown rule rule.
 const ID: &str = "UnknownRule";
 
 /// Creates an "unknown rule" diagnostic.
-fn unknown_rule(id: &str, span: Span) -> Diagnostic {
-    Diagnostic::note(format!("unknown lint rule `{id}`"))
+fn unknown_rule(id: &str, span: Span, suggestion: Option<&str>) -> Diagnostic {
+    let diagnostic = Diagnostic::warning(format!("unknown lint rule `{id}`"))
         .with_rule(ID)
-        .with_label("cannot make an exception for this rule", span)
-        .with_fix("remove the unknown rule from the exception list")
+        .with_label("cannot make an exception for this rule", span);
+
+    match suggestion {
+        Some(suggestion) => diagnostic.with_fix(format!("did you mean `{suggestion}`?")),
+        None => diagnostic.with_fix("remove the unknown rule from the exception list"),
+    }
+}
+
+/// Finds the known rule identifier that most closely matches the given
+/// unknown identifier.
+///
+/// Identifiers are compared case-insensitively; a suggestion is only made if
+/// the edit distance is within a third of the length of the unknown identifier.
+fn suggest_rule(id: &str, additional: &[&'static str]) -> Option<&'static str> {
+    let id = id.to_lowercase();
+    let threshold = (id.chars().count() / 3).max(1);
+    RULE_MAP
+        .keys()
+        .chain(RESERVED_RULE_IDS.iter())
+        .chain(additional.iter())
+        .map(|known| (levenshtein(&id, &known.to_lowercase()), *known))
+        .filter(|(distance, _)| *distance <= threshold)
+        .min()
+        .map(|(_, known)| known)
 }
 
 /// Detects unknown rules within lint directives.
-#[derive(Default, Debug, Clone, Copy)]
-pub struct UnknownRule;
+#[derive(Default, Debug, Clone)]
+pub struct UnknownRule {
+    /// The identifiers of rules that are known in addition to the built-in
+    /// rules.
+    additional: Vec<&'static str>,
+}
+
+impl UnknownRule {
+    /// Constructs a new unknown rule rule that treats the given rule
+    /// identifiers as known in addition to the built-in rules.
+    pub fn new(additional: impl IntoIterator<Item = &'static str>) -> Self {
+        Self {
+            additional: additional.into_iter().collect(),
+        }
+    }
+}
 
 impl Rule for UnknownRule {
     fn id(&self) -> &'static str {
@@ -45,7 +82,8 @@ impl Rule for UnknownRule {
     fn explanation(&self) -> &'static str {
         "When writing WDL, lint directives are used to suppress certain rules. If a rule is \
          unknown, nothing will be suppressed. This rule flags unknown rules as they are often \
-         mistakes."
+         mistakes, and suggests the closest known rule when the unknown rule appears to be a \
+         misspelling."
     }
 
     fn tags(&self) -> TagSet {
@@ -53,6 +91,18 @@ impl Rule for UnknownRule {
         TagSet::new(&[Tag::Clarity])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("unknownrule"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &[
+            "MalformedLintDirective",
+            "MisplacedLintDirective",
+            "UnusedLintDirective",
+        ]
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [wdl_ast::SyntaxKind]> {
         Some(&[SyntaxKind::VersionStatementNode])
     }
@@ -79,13 +129,17 @@ impl Visitor for UnknownRule {
                 offset += id.len() - trimmed.len();
 
                 // Check if the rule is known
-                if !RESERVED_RULE_IDS.contains(&trimmed) && !RULE_MAP.contains_key(&trimmed) {
+                if !RESERVED_RULE_IDS.contains(&trimmed)
+                    && !RULE_MAP.contains_key(&trimmed)
+                    && !self.additional.contains(&trimmed)
+                {
                     // Since this rule can only be excepted in a document-wide fashion,
                     // if the rule is running we can directly add the diagnostic
                     // without checking for the exceptable nodes
                     state.add(unknown_rule(
                         trimmed,
                         Span::new(start + offset, trimmed.len()),
+                        suggest_rule(trimmed, &self.additional),
                     ));
                 }
 
@@ -0,0 +1,407 @@
+//! A lint rule for unknown keys in `runtime`, `requirements`, and `hints`
+//! sections.
+
+use serde::Deserialize;
+use wdl_ast::AstNode;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Ident;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::SyntaxNode;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+use wdl_ast::v1::RequirementsSection;
+use wdl_ast::v1::RuntimeSection;
+use wdl_ast::v1::TASK_HINT_DISKS;
+use wdl_ast::v1::TASK_HINT_FPGA;
+use wdl_ast::v1::TASK_HINT_GPU;
+use wdl_ast::v1::TASK_HINT_INPUTS;
+use wdl_ast::v1::TASK_HINT_LOCALIZATION_OPTIONAL;
+use wdl_ast::v1::TASK_HINT_LOCALIZATION_OPTIONAL_ALIAS;
+use wdl_ast::v1::TASK_HINT_MAX_CPU;
+use wdl_ast::v1::TASK_HINT_MAX_CPU_ALIAS;
+use wdl_ast::v1::TASK_HINT_MAX_MEMORY;
+use wdl_ast::v1::TASK_HINT_MAX_MEMORY_ALIAS;
+use wdl_ast::v1::TASK_HINT_OUTPUTS;
+use wdl_ast::v1::TASK_HINT_SHORT_TASK;
+use wdl_ast::v1::TASK_HINT_SHORT_TASK_ALIAS;
+use wdl_ast::v1::TASK_REQUIREMENT_CONTAINER;
+use wdl_ast::v1::TASK_REQUIREMENT_CONTAINER_ALIAS;
+use wdl_ast::v1::TASK_REQUIREMENT_CPU;
+use wdl_ast::v1::TASK_REQUIREMENT_DISKS;
+use wdl_ast::v1::TASK_REQUIREMENT_FPGA;
+use wdl_ast::v1::TASK_REQUIREMENT_GPU;
+use wdl_ast::v1::TASK_REQUIREMENT_MAX_RETRIES;
+use wdl_ast::v1::TASK_REQUIREMENT_MAX_RETRIES_ALIAS;
+use wdl_ast::v1::TASK_REQUIREMENT_MEMORY;
+use wdl_ast::v1::TASK_REQUIREMENT_RETURN_CODES;
+use wdl_ast::v1::TASK_REQUIREMENT_RETURN_CODES_ALIAS;
+use wdl_ast::v1::TaskHintsSection;
+use wdl_ast::version::V1;
+
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+use crate::config::rule_options;
+use crate::util::levenshtein;
+
+/// The identifier for the unknown section key rule.
+const ID: &str = "UnknownSectionKey";
+
+/// The keys of `runtime` sections in WDL v1.0.
+///
+/// The WDL v1.0 specification only describes `docker` and `memory`; the other
+/// keys are those standardized by WDL v1.1 and commonly supported by execution
+/// engines for WDL v1.0 documents.
+const RUNTIME_KEYS_V1_0: &[&str] = &[
+    TASK_REQUIREMENT_CONTAINER_ALIAS,
+    TASK_REQUIREMENT_CPU,
+    TASK_REQUIREMENT_DISKS,
+    TASK_REQUIREMENT_GPU,
+    TASK_REQUIREMENT_MAX_RETRIES_ALIAS,
+    TASK_REQUIREMENT_MEMORY,
+    TASK_REQUIREMENT_RETURN_CODES_ALIAS,
+];
+
+/// The keys of `runtime` sections in WDL v1.1.
+const RUNTIME_KEYS_V1_1: &[&str] = &[
+    TASK_REQUIREMENT_CONTAINER,
+    TASK_REQUIREMENT_CONTAINER_ALIAS,
+    TASK_REQUIREMENT_CPU,
+    TASK_REQUIREMENT_DISKS,
+    TASK_REQUIREMENT_GPU,
+    TASK_REQUIREMENT_MAX_RETRIES_ALIAS,
+    TASK_REQUIREMENT_MEMORY,
+    TASK_REQUIREMENT_RETURN_CODES_ALIAS,
+    TASK_HINT_INPUTS,
+    TASK_HINT_LOCALIZATION_OPTIONAL_ALIAS,
+    TASK_HINT_MAX_CPU_ALIAS,
+    TASK_HINT_MAX_MEMORY_ALIAS,
+    TASK_HINT_OUTPUTS,
+    TASK_HINT_SHORT_TASK_ALIAS,
+];
+
+/// The keys of `requirements` sections in WDL v1.2.
+const REQUIREMENTS_KEYS_V1_2: &[&str] = &[
+    TASK_REQUIREMENT_CONTAINER,
+    TASK_REQUIREMENT_CONTAINER_ALIAS,
+    TASK_REQUIREMENT_CPU,
+    TASK_REQUIREMENT_DISKS,
+    TASK_REQUIREMENT_FPGA,
+    TASK_REQUIREMENT_GPU,
+    TASK_REQUIREMENT_MAX_RETRIES,
+    TASK_REQUIREMENT_MAX_RETRIES_ALIAS,
+    TASK_REQUIREMENT_MEMORY,
+    TASK_REQUIREMENT_RETURN_CODES,
+    TASK_REQUIREMENT_RETURN_CODES_ALIAS,
+];
+
+/// The keys of `hints` sections in WDL v1.2.
+const HINTS_KEYS_V1_2: &[&str] = &[
+    TASK_HINT_DISKS,
+    TASK_HINT_FPGA,
+    TASK_HINT_GPU,
+    TASK_HINT_INPUTS,
+    TASK_HINT_LOCALIZATION_OPTIONAL,
+    TASK_HINT_LOCALIZATION_OPTIONAL_ALIAS,
+    TASK_HINT_MAX_CPU,
+    TASK_HINT_MAX_CPU_ALIAS,
+    TASK_HINT_MAX_MEMORY,
+    TASK_HINT_MAX_MEMORY_ALIAS,
+    TASK_HINT_OUTPUTS,
+    TASK_HINT_SHORT_TASK,
+    TASK_HINT_SHORT_TASK_ALIAS,
+];
+
+/// Creates an "unknown section key" diagnostic.
+fn unknown_section_key(section: &str, key: &Ident, suggestion: Option<&str>) -> Diagnostic {
+    let diagnostic = Diagnostic::warning(format!(
+        "unknown `{section}` key `{key}`",
+        key = key.as_str()
+    ))
+    .with_rule(ID)
+    .with_highlight(key.span());
+
+    match suggestion {
+        Some(suggestion) => diagnostic.with_fix(format!("did you mean `{suggestion}`?")),
+        None => diagnostic.with_fix(format!(
+            "remove the `{key}` key; if it is specific to an execution engine, add it to the \
+             rule's allowed keys",
+            key = key.as_str()
+        )),
+    }
+}
+
+/// Creates a "misplaced section key" diagnostic.
+fn misplaced_section_key(section: &str, key: &Ident, other: &str) -> Diagnostic {
+    Diagnostic::warning(format!(
+        "unknown `{section}` key `{key}`",
+        key = key.as_str()
+    ))
+    .with_rule(ID)
+    .with_highlight(key.span())
+    .with_fix(format!(
+        "move the `{key}` key to the `{other}` section",
+        key = key.as_str()
+    ))
+}
+
+/// Finds the known key that most closely matches the given unknown key.
+///
+/// Keys are compared case-insensitively; a suggestion is only made if the edit
+/// distance is within a third of the length of the unknown key.
+fn suggest_key(key: &str, known: &[&'static str]) -> Option<&'static str> {
+    let key = key.to_lowercase();
+    let threshold = (key.chars().count() / 3).max(1);
+    known
+        .iter()
+        .map(|k| (levenshtein(&key, &k.to_lowercase()), *k))
+        .filter(|(distance, _)| *distance <= threshold)
+        .min()
+        .map(|(_, k)| k)
+}
+
+/// Detects unknown keys in `runtime`, `requirements`, and `hints` sections.
+#[derive(Debug, Clone, Default)]
+pub struct UnknownSectionKeyRule {
+    /// The keys that are allowed in addition to those of the specification.
+    allowed_keys: Vec<String>,
+    /// The version of the document being linted.
+    version: Option<SupportedVersion>,
+}
+
+impl UnknownSectionKeyRule {
+    /// Constructs a new unknown section key rule that allows the given keys in
+    /// addition to those of the specification.
+    ///
+    /// This is intended for keys specific to an execution engine (e.g.
+    /// Cromwell's `preemptible`).
+    pub fn new(allowed_keys: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            allowed_keys: allowed_keys.into_iter().map(Into::into).collect(),
+            version: None,
+        }
+    }
+
+    /// Gets the keys that are allowed in addition to those of the
+    /// specification.
+    pub fn allowed_keys(&self) -> &[String] {
+        &self.allowed_keys
+    }
+
+    /// Checks the keys of a section against the given known keys.
+    ///
+    /// If `other` is specified, it is the name and known keys of the section
+    /// that unknown keys may have been intended for.
+    fn check<'a>(
+        &self,
+        state: &mut Diagnostics,
+        section: &str,
+        known: &[&'static str],
+        other: Option<(&str, &[&'static str])>,
+        items: impl Iterator<Item = (Ident, &'a SyntaxNode)>,
+    ) {
+        for (key, node) in items {
+            if known.contains(&key.as_str()) || self.allowed_keys.iter().any(|k| k == key.as_str())
+            {
+                continue;
+            }
+
+            let diagnostic = match other {
+                Some((other, keys)) if keys.contains(&key.as_str()) => {
+                    misplaced_section_key(section, &key, other)
+                }
+                _ => unknown_section_key(section, &key, suggest_key(key.as_str(), known)),
+            };
+
+            state.exceptable_add(
+                diagnostic,
+                SyntaxElement::from(node.clone()),
+                &self.exceptable_nodes(),
+            );
+        }
+    }
+}
+
+/// The options of the unknown section key rule.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Options {
+    /// The keys that are allowed in addition to those of the specification.
+    allowed_keys: Option<Vec<String>>,
+}
+
+impl Rule for UnknownSectionKeyRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Ensures that keys in `runtime`, `requirements`, and `hints` sections are known."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Most execution engines silently ignore keys they do not recognize, so a typo such as \
+         `dockr` in a `runtime` section means the task runs without the intended container. \
+         Keys are checked against those defined by the specification for the document's \
+         version: in WDL v1.2, `requirements` and `hints` sections each have their own set of \
+         keys and `runtime` sections accept both. When an unknown key is close to a known key, \
+         the known key is suggested; a `requirements` key in a `hints` section (or vice versa) \
+         is suggested to be moved. Keys specific to an execution engine (e.g. Cromwell's \
+         `preemptible`) may be allowed with the rule's `allowed_keys` option."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Correctness, Tag::Portability])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("unknownsectionkey"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["RuntimeSectionKeys", "MissingRequirements"]
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+        Some(&[
+            SyntaxKind::VersionStatementNode,
+            SyntaxKind::TaskDefinitionNode,
+            SyntaxKind::RuntimeSectionNode,
+            SyntaxKind::RuntimeItemNode,
+            SyntaxKind::RequirementsSectionNode,
+            SyntaxKind::RequirementsItemNode,
+            SyntaxKind::TaskHintsSectionNode,
+            SyntaxKind::TaskHintsItemNode,
+        ])
+    }
+
+    fn configure(&mut self, options: &toml::Table) -> anyhow::Result<()> {
+        let options: Options = rule_options(options)?;
+
+        if let Some(allowed_keys) = options.allowed_keys {
+            self.allowed_keys = allowed_keys;
+        }
+
+        Ok(())
+    }
+}
+
+impl Visitor for UnknownSectionKeyRule {
+    type State = Diagnostics;
+
+    fn document(
+        &mut self,
+        _: &mut Self::State,
+        reason: VisitReason,
+        _: &Document,
+        version: SupportedVersion,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        self.version = Some(version);
+    }
+
+    fn runtime_section(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        section: &RuntimeSection,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        let items: Vec<_> = section.items().collect();
+        let items = items.iter().map(|i| (i.name(), i.syntax()));
+        match self.version {
+            Some(SupportedVersion::V1(V1::Zero)) => {
+                self.check(state, "runtime", RUNTIME_KEYS_V1_0, None, items)
+            }
+            Some(SupportedVersion::V1(V1::One)) => {
+                self.check(state, "runtime", RUNTIME_KEYS_V1_1, None, items)
+            }
+            Some(_) => {
+                // In WDL v1.2, the deprecated `runtime` section accepts the keys of both
+                // the `requirements` and `hints` sections
+                let known: Vec<_> = REQUIREMENTS_KEYS_V1_2
+                    .iter()
+                    .chain(HINTS_KEYS_V1_2)
+                    .copied()
+                    .collect();
+                self.check(state, "runtime", &known, None, items)
+            }
+            None => {}
+        }
+    }
+
+    fn requirements_section(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        section: &RequirementsSection,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        let items: Vec<_> = section.items().collect();
+        self.check(
+            state,
+            "requirements",
+            REQUIREMENTS_KEYS_V1_2,
+            Some(("hints", HINTS_KEYS_V1_2)),
+            items.iter().map(|i| (i.name(), i.syntax())),
+        );
+    }
+
+    fn task_hints_section(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        section: &TaskHintsSection,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        let items: Vec<_> = section.items().collect();
+        self.check(
+            state,
+            "hints",
+            HINTS_KEYS_V1_2,
+            Some(("requirements", REQUIREMENTS_KEYS_V1_2)),
+            items.iter().map(|i| (i.name(), i.syntax())),
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn it_suggests_keys() {
+        assert_eq!(
+            suggest_key("dockr", RUNTIME_KEYS_V1_1),
+            Some(TASK_REQUIREMENT_CONTAINER_ALIAS)
+        );
+        assert_eq!(
+            suggest_key("maxretries", REQUIREMENTS_KEYS_V1_2),
+            Some(TASK_REQUIREMENT_MAX_RETRIES_ALIAS)
+        );
+        assert_eq!(
+            suggest_key("max_cpus", HINTS_KEYS_V1_2),
+            Some(TASK_HINT_MAX_CPU)
+        );
+        assert_eq!(suggest_key("preemptible", RUNTIME_KEYS_V1_1), None);
+    }
+}
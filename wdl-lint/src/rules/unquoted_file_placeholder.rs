@@ -0,0 +1,250 @@
+//! A lint rule for unquoted `File` and `Directory` placeholders in command
+//! sections.
+
+use std::collections::HashMap;
+
+use wdl_ast::AstNode;
+use wdl_ast::AstNodeExt;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+use wdl_ast::v1::CommandPart;
+use wdl_ast::v1::Decl;
+use wdl_ast::v1::Expr;
+use wdl_ast::v1::PlaceholderOption;
+use wdl_ast::v1::PrimitiveTypeKind;
+use wdl_ast::v1::TaskDefinition;
+use wdl_ast::v1::Type;
+
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+
+/// The identifier for the unquoted file placeholder rule.
+const ID: &str = "UnquotedFilePlaceholder";
+
+/// Creates an "unquoted file placeholder" diagnostic.
+fn unquoted_file_placeholder(ty: &str, name: &str, placeholder: &str, span: Span) -> Diagnostic {
+    let replacement = format!("\"{placeholder}\"");
+    Diagnostic::warning(format!(
+        "placeholder of `{ty}` declaration `{name}` is not quoted"
+    ))
+    .with_rule(ID)
+    .with_label(
+        "a path containing spaces is split into multiple arguments",
+        span,
+    )
+    .with_fix(format!(
+        "wrap the placeholder in double quotes: `{replacement}`"
+    ))
+    .with_replacement(span, replacement)
+}
+
+/// Gathers the `File` and `Directory` declarations of a task.
+///
+/// Returns a map of declaration name to the name of its type.
+fn gather_file_declarations(task: &TaskDefinition) -> HashMap<String, &'static str> {
+    task.input()
+        .into_iter()
+        .flat_map(|section| section.declarations())
+        .chain(task.declarations().map(Decl::Bound))
+        .filter_map(|decl| {
+            let Type::Primitive(ty) = decl.ty() else {
+                return None;
+            };
+
+            let ty = match ty.kind() {
+                PrimitiveTypeKind::File => "File",
+                PrimitiveTypeKind::Directory => "Directory",
+                _ => return None,
+            };
+
+            Some((decl.name().as_str().to_string(), ty))
+        })
+        .collect()
+}
+
+/// Updates the quote that is open after the given text of a command.
+///
+/// Quotes within shell comments are ignored.
+fn update_quote(text: &str, quote: &mut Option<char>) {
+    let mut chars = text.chars();
+    let mut word_start = true;
+    while let Some(c) = chars.next() {
+        match *quote {
+            Some('\'') => {
+                if c == '\'' {
+                    *quote = None;
+                }
+            }
+            Some(_) => match c {
+                '\\' => {
+                    chars.next();
+                }
+                '"' => *quote = None,
+                _ => {}
+            },
+            None => match c {
+                '\\' => {
+                    chars.next();
+                }
+                '\'' | '"' => *quote = Some(c),
+                '#' if word_start => {
+                    // Skip the remainder of the line
+                    for c in chars.by_ref() {
+                        if c == '\n' {
+                            break;
+                        }
+                    }
+
+                    word_start = true;
+                    continue;
+                }
+                _ => {}
+            },
+        }
+
+        word_start = c.is_whitespace() || matches!(c, ';' | '|' | '&' | '(');
+    }
+}
+
+/// Detects unquoted `File` and `Directory` placeholders in command sections.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct UnquotedFilePlaceholderRule;
+
+impl Rule for UnquotedFilePlaceholderRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Ensures that placeholders of `File` and `Directory` declarations in command sections \
+         are quoted."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A placeholder such as `~{bam}` is replaced with the path of the file before the command \
+         is run by the shell. If the placeholder is not quoted (e.g. `samtools view ~{bam}`), a \
+         path containing spaces or other special characters is split into multiple arguments or \
+         otherwise interpreted by the shell. Placeholders whose expression is the name of a \
+         `File` or `Directory` input or private declaration of the task should be placed \
+         within double quotes (e.g. `samtools view \"~{bam}\"`). Placeholders of other \
+         expressions, such as arrays of files joined with `sep`, are not checked. The \
+         `ShellCheck` rule does not report these placeholders, as placeholders are replaced \
+         with quoted shell variables before the command is checked."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Correctness, Tag::Portability])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("unquotedfileplaceholder"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["ShellCheck"]
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+        Some(&[
+            SyntaxKind::VersionStatementNode,
+            SyntaxKind::TaskDefinitionNode,
+            SyntaxKind::CommandSectionNode,
+        ])
+    }
+}
+
+impl Visitor for UnquotedFilePlaceholderRule {
+    type State = Diagnostics;
+
+    fn document(&mut self, _: &mut Self::State, _: VisitReason, _: &Document, _: SupportedVersion) {
+        // This is intentionally empty, as this rule has no state.
+    }
+
+    fn task_definition(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        task: &TaskDefinition,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        let Some(command) = task.command() else {
+            return;
+        };
+
+        let decls = gather_file_declarations(task);
+        if decls.is_empty() {
+            return;
+        }
+
+        let element = SyntaxElement::from(command.syntax().clone());
+        let mut quote = None;
+        for part in command.parts() {
+            let placeholder = match part {
+                CommandPart::Text(text) => {
+                    update_quote(text.as_str(), &mut quote);
+                    continue;
+                }
+                CommandPart::Placeholder(placeholder) => placeholder,
+            };
+
+            if quote.is_some() || matches!(placeholder.option(), Some(PlaceholderOption::Sep(_))) {
+                continue;
+            }
+
+            let Expr::Name(name) = placeholder.expr() else {
+                continue;
+            };
+
+            let name = name.name();
+            let Some(ty) = decls.get(name.as_str()) else {
+                continue;
+            };
+
+            state.exceptable_add(
+                unquoted_file_placeholder(
+                    ty,
+                    name.as_str(),
+                    &placeholder.syntax().text().to_string(),
+                    placeholder.span(),
+                ),
+                element.clone(),
+                &self.exceptable_nodes(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn it_tracks_quotes() {
+        let mut quote = None;
+        update_quote("samtools view ", &mut quote);
+        assert_eq!(quote, None);
+
+        update_quote("cat \"", &mut quote);
+        assert_eq!(quote, Some('"'));
+
+        update_quote("\\\" '", &mut quote);
+        assert_eq!(quote, Some('"'));
+
+        update_quote("\" # it's a comment\necho '", &mut quote);
+        assert_eq!(quote, Some('\''));
+    }
+}
@@ -0,0 +1,90 @@
+//! A lint rule for flagging lint directives that no longer suppress anything.
+
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+
+/// The identifier for the unused lint directive rule.
+pub(crate) const ID: &str = "UnusedLintDirective";
+
+/// Creates an "unused lint directive" diagnostic.
+pub(crate) fn unused_lint_directive(ids: &[&str], span: Span) -> Diagnostic {
+    let (s, list) = match ids {
+        [id] => ("", format!("`{id}`")),
+        ids => (
+            "s",
+            ids.iter()
+                .map(|id| format!("`{id}`"))
+                .collect::<Vec<_>>()
+                .join(", "),
+        ),
+    };
+
+    Diagnostic::warning(format!("unused exception for lint rule{s} {list}"))
+        .with_rule(ID)
+        .with_label("this exception no longer suppresses anything", span)
+        .with_fix(format!(
+            "remove the lint rule{s} from the `#@ except` comment"
+        ))
+}
+
+/// Detects `#@ except` comments that no longer suppress any diagnostics.
+///
+/// The detection is performed by the [`LintVisitor`](crate::LintVisitor) once
+/// every other rule has visited the document; only rules that are run by the
+/// visitor are checked.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct UnusedLintDirectiveRule;
+
+impl Rule for UnusedLintDirectiveRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags `#@ except` comments that no longer suppress any diagnostics."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Exceptions for lint rules tend to accumulate as a document changes; an exception that no \
+         longer suppresses any diagnostics hides future diagnostics that may be relevant and \
+         should be removed. Only exceptions for lint rules that were run are checked; exceptions \
+         that disable a rule for the entire document are never flagged, as the rule is not run."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Clarity])
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("unusedlintdirective"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &[
+            "UnknownRule",
+            "MalformedLintDirective",
+            "MisplacedLintDirective",
+        ]
+    }
+
+    fn exceptable_nodes(&self) -> Option<&'static [wdl_ast::SyntaxKind]> {
+        None
+    }
+}
+
+impl Visitor for UnusedLintDirectiveRule {
+    type State = Diagnostics;
+
+    fn document(&mut self, _: &mut Self::State, _: VisitReason, _: &Document, _: SupportedVersion) {
+        // This rule is implemented by the lint visitor.
+    }
+}
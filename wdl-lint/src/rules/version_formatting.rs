@@ -92,6 +92,14 @@ impl Rule for VersionFormattingRule {
         TagSet::new(&[Tag::Style])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("versionformatting"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &["PreambleFormatting", "PreambleCommentAfterVersion"]
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
         Some(&[SyntaxKind::VersionStatementNode])
     }
@@ -1,5 +1,6 @@
 //! A lint rule for whitespace.
 
+use wdl_ast::AstNode;
 use wdl_ast::AstToken;
 use wdl_ast::Diagnostic;
 use wdl_ast::Diagnostics;
@@ -12,6 +13,8 @@ use wdl_ast::VersionStatement;
 use wdl_ast::VisitReason;
 use wdl_ast::Visitor;
 use wdl_ast::Whitespace;
+use wdl_ast::v1::CommandPart;
+use wdl_ast::v1::CommandSection;
 
 use crate::Rule;
 use crate::Tag;
@@ -27,6 +30,7 @@ fn only_whitespace(span: Span) -> Diagnostic {
         .with_rule(ID)
         .with_highlight(span)
         .with_fix("remove the whitespace")
+        .with_replacement(span, "")
 }
 
 /// Creates a "trailing whitespace" diagnostic.
@@ -35,6 +39,7 @@ fn trailing_whitespace(span: Span) -> Diagnostic {
         .with_rule(ID)
         .with_highlight(span)
         .with_fix("remove the trailing whitespace")
+        .with_replacement(span, "")
 }
 
 /// Creates a "more than one blank line" diagnostic.
@@ -66,13 +71,26 @@ impl Rule for WhitespaceRule {
         "Whitespace should be used judiciously. Spurious whitespace can cause issues with parsing, \
          automation, and rendering. There should never be trailing whitespace at the end of lines \
          and blank lines should be completely empty with no whitespace characters between \
-         newlines. There should be at most one empty line in a row."
+         newlines. This includes the lines of command sections. There should be at most one \
+         empty line in a row."
     }
 
     fn tags(&self) -> TagSet {
         TagSet::new(&[Tag::Style, Tag::Spacing])
     }
 
+    fn url(&self) -> Option<&'static str> {
+        Some(rule_url!("whitespace"))
+    }
+
+    fn related_rules(&self) -> &'static [&'static str] {
+        &[
+            "BlankLinesBetweenElements",
+            "CommentWhitespace",
+            "EndingNewline",
+        ]
+    }
+
     fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
         None
     }
@@ -98,6 +116,54 @@ impl Visitor for WhitespaceRule {
         }
     }
 
+    fn command_section(
+        &mut self,
+        state: &mut Self::State,
+        reason: VisitReason,
+        section: &CommandSection,
+    ) {
+        if reason == VisitReason::Exit {
+            return;
+        }
+
+        // Each line of command text that ends with a newline is checked; the text
+        // of a line preceding a placeholder is not at the end of the line
+        for part in section.parts() {
+            let CommandPart::Text(text) = part else {
+                continue;
+            };
+
+            let span = text.span();
+            let text = text.as_str();
+            for (i, (line, start, next_start)) in lines_with_offset(text).enumerate() {
+                if next_start == text.len() && !text.ends_with('\n') {
+                    break;
+                }
+
+                let trimmed = line.trim_end_matches([' ', '\t']);
+                if trimmed.len() == line.len() {
+                    continue;
+                }
+
+                let whitespace = Span::new(
+                    span.start() + start + trimmed.len(),
+                    line.len() - trimmed.len(),
+                );
+                let diagnostic = if i > 0 && trimmed.is_empty() {
+                    only_whitespace(whitespace)
+                } else {
+                    trailing_whitespace(whitespace)
+                };
+
+                state.exceptable_add(
+                    diagnostic,
+                    SyntaxElement::from(section.syntax().clone()),
+                    &self.exceptable_nodes(),
+                );
+            }
+        }
+    }
+
     fn document(
         &mut self,
         _: &mut Self::State,
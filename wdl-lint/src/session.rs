@@ -0,0 +1,343 @@
+//! Implementation of incremental lint sessions.
+
+use std::collections::HashMap;
+use std::hash::DefaultHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
+
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+
+use crate::ImportedDocuments;
+use crate::LintVisitor;
+
+/// Represents the cached lint result of a document.
+struct CachedLint {
+    /// The hash of the content of the document and its imported documents.
+    hash: u64,
+    /// The diagnostics of the cacheable rules.
+    cached: Diagnostics,
+    /// The lint diagnostics of the document.
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// An incremental lint session.
+///
+/// A session caches the lint diagnostics of each document by URI along with a
+/// hash of the content of the document and its imported documents; linting a
+/// document whose content and imported documents have not changed since it
+/// was last linted reuses the cached diagnostics of the rules.
+///
+/// Rules that opt out of caching (see
+/// [`Rule::cacheable`](crate::Rule::cacheable)) are run every time a document
+/// is linted; if every rule may be cached, the cached diagnostics are returned
+/// without visiting the document.
+///
+/// Only lint diagnostics are returned; the documents are not otherwise
+/// validated.
+#[allow(missing_debug_implementations)]
+pub struct LintSession {
+    /// The lint visitor.
+    visitor: LintVisitor,
+    /// Whether or not the diagnostics of every rule may be cached.
+    cacheable: bool,
+    /// The cached lint results, keyed by document URI.
+    cache: HashMap<String, CachedLint>,
+}
+
+impl LintSession {
+    /// Creates a new lint session using the given lint visitor.
+    pub fn new(visitor: LintVisitor) -> Self {
+        Self {
+            cacheable: visitor.is_cacheable(),
+            visitor,
+            cache: Default::default(),
+        }
+    }
+
+    /// Lints the document with the given URI.
+    ///
+    /// The given imported documents are provided to the rules (see
+    /// [`Rule::set_imports`](crate::Rule::set_imports)).
+    ///
+    /// The cached diagnostics are reused if neither the document's content nor
+    /// the content of its imported documents has changed since it was last
+    /// linted.
+    ///
+    /// The returned diagnostics are sorted.
+    pub fn lint(
+        &mut self,
+        uri: &str,
+        document: &Document,
+        imports: ImportedDocuments,
+    ) -> &[Diagnostic] {
+        let hash = content_hash(document, &imports);
+        match self.cache.get(uri).filter(|c| c.hash == hash) {
+            Some(_) if self.cacheable => {}
+            entry => {
+                if let Some(entry) = entry {
+                    self.visitor.reuse_cached(entry.cached.clone());
+                }
+
+                self.visitor.set_imports(imports);
+                self.visit(uri, hash, document);
+            }
+        }
+
+        &self.cache[uri].diagnostics
+    }
+
+    /// Runs the lint visitor on the given document and caches the result.
+    fn visit(&mut self, uri: &str, hash: u64, document: &Document) {
+        let mut diagnostics = Diagnostics::default();
+        document.visit(&mut diagnostics, &mut self.visitor);
+        diagnostics.sort();
+        self.cache.insert(
+            uri.to_string(),
+            CachedLint {
+                hash,
+                cached: self.visitor.take_cached(),
+                diagnostics: diagnostics.diagnostics().to_vec(),
+            },
+        );
+    }
+
+    /// Removes the cached diagnostics of the document with the given URI.
+    ///
+    /// Returns `true` if the session had cached diagnostics for the document.
+    pub fn remove(&mut self, uri: &str) -> bool {
+        self.cache.remove(uri).is_some()
+    }
+
+    /// Removes the cached diagnostics of every document.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+}
+
+/// Hashes the content of a document and the documents it imports.
+fn content_hash(document: &Document, imports: &ImportedDocuments) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    document.syntax().to_string().hash(&mut hasher);
+
+    // The imports are hashed in namespace order as the collection is unordered
+    let mut imports: Vec<_> = imports.iter().collect();
+    imports.sort_by_key(|(namespace, _)| *namespace);
+    for (namespace, document) in imports {
+        namespace.hash(&mut hasher);
+        document.syntax().to_string().hash(&mut hasher);
+    }
+
+    hasher.finish()
+}
+
+impl Default for LintSession {
+    fn default() -> Self {
+        Self::new(LintVisitor::default())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    use wdl_ast::Diagnostics;
+    use wdl_ast::SupportedVersion;
+    use wdl_ast::SyntaxKind;
+    use wdl_ast::VisitReason;
+    use wdl_ast::Visitor;
+
+    use super::*;
+    use crate::Rule;
+    use crate::Tag;
+    use crate::TagSet;
+
+    /// A rule that records the documents it visits.
+    struct CountingRule {
+        /// The text of each visited document.
+        visited: Arc<Mutex<Vec<String>>>,
+        /// Whether or not the rule is cacheable.
+        cacheable: bool,
+    }
+
+    impl Rule for CountingRule {
+        fn id(&self) -> &'static str {
+            if self.cacheable {
+                "Counting"
+            } else {
+                "UncacheableCounting"
+            }
+        }
+
+        fn description(&self) -> &'static str {
+            "A rule that records the documents it visits."
+        }
+
+        fn explanation(&self) -> &'static str {
+            "A rule that records the documents it visits."
+        }
+
+        fn tags(&self) -> TagSet {
+            TagSet::new(&[Tag::Style])
+        }
+
+        fn exceptable_nodes(&self) -> Option<&'static [SyntaxKind]> {
+            None
+        }
+
+        fn cacheable(&self) -> bool {
+            self.cacheable
+        }
+    }
+
+    impl Visitor for CountingRule {
+        type State = Diagnostics;
+
+        fn document(
+            &mut self,
+            _: &mut Self::State,
+            reason: VisitReason,
+            document: &Document,
+            _: SupportedVersion,
+        ) {
+            if reason == VisitReason::Enter {
+                self.visited
+                    .lock()
+                    .unwrap()
+                    .push(document.syntax().to_string());
+            }
+        }
+    }
+
+    /// Creates a session with the default rules and a counting rule.
+    fn session(cacheable: bool) -> (LintSession, Arc<Mutex<Vec<String>>>) {
+        let visited = Arc::new(Mutex::new(Vec::new()));
+        let rule = CountingRule {
+            visited: visited.clone(),
+            cacheable,
+        };
+        let rules = crate::rules()
+            .into_iter()
+            .chain([Box::new(rule) as Box<dyn Rule>]);
+        (LintSession::new(LintVisitor::new(rules)), visited)
+    }
+
+    #[test]
+    fn it_only_lints_changed_documents() {
+        let sources = [
+            ("file:///a.wdl", "version 1.1\n\nworkflow a {}\n"),
+            ("file:///b.wdl", "version 1.1\n\nworkflow b {}\n"),
+            ("file:///c.wdl", "version 1.1\n\nworkflow c {}\n"),
+        ];
+
+        let (mut session, visited) = session(true);
+        let mut expected = Vec::new();
+        for (uri, source) in sources {
+            let (document, _) = Document::parse(source);
+            expected.push(session.lint(uri, &document, Default::default()).to_vec());
+        }
+
+        assert_eq!(visited.lock().unwrap().len(), 3);
+        assert!(expected.iter().all(|d| !d.is_empty()));
+
+        // Change the second document and lint all three again
+        let changed = "version 1.1\n\nworkflow b_changed {}\n";
+        let mut results = Vec::new();
+        for (uri, source) in sources {
+            let source = if uri == "file:///b.wdl" {
+                changed
+            } else {
+                source
+            };
+
+            let (document, _) = Document::parse(source);
+            results.push(session.lint(uri, &document, Default::default()).to_vec());
+        }
+
+        assert_eq!(*visited.lock().unwrap().last().unwrap(), changed);
+        assert_eq!(visited.lock().unwrap().len(), 4);
+        assert_eq!(results[0], expected[0]);
+        assert_eq!(results[2], expected[2]);
+
+        // Removing a document from the session causes it to be linted again
+        assert!(session.remove("file:///a.wdl"));
+        let (document, _) = Document::parse(sources[0].1);
+        assert_eq!(
+            session.lint("file:///a.wdl", &document, Default::default()),
+            expected[0]
+        );
+        assert_eq!(visited.lock().unwrap().len(), 5);
+    }
+
+    #[test]
+    fn it_always_lints_with_uncacheable_rules() {
+        let (mut session, visited) = session(false);
+        let (document, _) = Document::parse("version 1.1\n\nworkflow a {}\n");
+        session.lint("file:///a.wdl", &document, Default::default());
+        session.lint("file:///a.wdl", &document, Default::default());
+        assert_eq!(visited.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn it_only_runs_uncacheable_rules_for_unchanged_documents() {
+        let cached = Arc::new(Mutex::new(Vec::new()));
+        let uncached = Arc::new(Mutex::new(Vec::new()));
+        let rules = crate::rules().into_iter().chain([
+            Box::new(CountingRule {
+                visited: cached.clone(),
+                cacheable: true,
+            }) as Box<dyn Rule>,
+            Box::new(CountingRule {
+                visited: uncached.clone(),
+                cacheable: false,
+            }),
+        ]);
+        let mut session = LintSession::new(LintVisitor::new(rules).with_summary(true));
+
+        let source =
+            "#@ except: SnakeCase\nversion 1.1\n\n#@ except: MissingMetas\nworkflow a {}\n";
+        let (document, _) = Document::parse(source);
+        let expected = session
+            .lint("file:///a.wdl", &document, Default::default())
+            .to_vec();
+        assert!(!expected.is_empty());
+
+        for _ in 0..2 {
+            assert_eq!(
+                session.lint("file:///a.wdl", &document, Default::default()),
+                expected
+            );
+        }
+
+        assert_eq!(cached.lock().unwrap().len(), 1);
+        assert_eq!(uncached.lock().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn it_lints_again_when_imports_change() {
+        let rules = [Box::<crate::rules::UndefinedCallTargetRule>::default() as Box<dyn Rule>];
+        let mut session = LintSession::new(LintVisitor::new(rules));
+        assert!(session.cacheable);
+
+        let (document, _) = Document::parse(
+            "version 1.1\n\nimport \"tools.wdl\" as tools\n\nworkflow a {\n    call \
+             tools.sort\n}\n",
+        );
+        let imports = |source: &str| -> ImportedDocuments {
+            let (tools, _) = Document::parse(source);
+            [("tools", tools)].into_iter().collect()
+        };
+
+        // The imported document does not define the called task
+        let tools = imports("version 1.1\n\ntask align {\n    command <<<>>>\n}\n");
+        let diagnostics = session.lint("file:///a.wdl", &document, tools);
+        assert_eq!(diagnostics.len(), 1);
+
+        // The imported document now defines the called task
+        let tools = imports("version 1.1\n\ntask sort {\n    command <<<>>>\n}\n");
+        let diagnostics = session.lint("file:///a.wdl", &document, tools);
+        assert!(diagnostics.is_empty());
+    }
+}
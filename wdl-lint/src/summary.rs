@@ -0,0 +1,310 @@
+//! Summaries of the lint diagnostics of a document.
+//!
+//! A summary condenses the diagnostics of a document into counts by tag and by
+//! severity, which is useful to consumers (e.g. CI dashboards) that want a
+//! single entry per document rather than every finding.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use serde::Serialize;
+use wdl_ast::AstNode;
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Severity;
+use wdl_ast::Span;
+use wdl_ast::ToSpan;
+
+use crate::RuleSet;
+use crate::SuppressionReport;
+use crate::Tag;
+
+/// Formats a count with the singular or plural form of the given noun.
+fn plural(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("{count} {noun}")
+    } else {
+        format!("{count} {noun}s")
+    }
+}
+
+/// Represents a summary of the lint diagnostics of a document.
+///
+/// The summary is built from the diagnostics remaining after `#@ except`
+/// comments have been applied; the diagnostics suppressed by those comments
+/// are only counted in [`suppressed`](Self::suppressed). Rules disabled for
+/// the entire document are not run, so their diagnostics are not counted.
+///
+/// A summary serializes to JSON as an object with the `tags`, `errors`,
+/// `warnings`, `notes`, `suppressed`, and `rules` fields.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct LintSummary {
+    /// The number of diagnostics for each tag.
+    ///
+    /// A diagnostic is counted once for each tag of its rule.
+    tags: BTreeMap<Tag, usize>,
+    /// The number of error diagnostics.
+    errors: usize,
+    /// The number of warning diagnostics.
+    warnings: usize,
+    /// The number of note diagnostics.
+    notes: usize,
+    /// The number of diagnostics suppressed by `#@ except` comments.
+    suppressed: usize,
+    /// The identifiers of the rules that reported a diagnostic.
+    rules: BTreeSet<String>,
+    /// The span of the document's version statement.
+    #[serde(skip)]
+    span: Option<Span>,
+}
+
+impl LintSummary {
+    /// Creates a summary of the given diagnostics of a document.
+    ///
+    /// The diagnostics collection must be the result of validating the
+    /// document (see [`Validator::run`](wdl_ast::Validator::run)) so that the
+    /// suppressions recorded for `#@ except` comments are counted.
+    ///
+    /// The tags of a diagnostic are those of its rule in the given rule set;
+    /// diagnostics without a rule, or with a rule not in the set, are counted
+    /// by severity only.
+    pub fn new(document: &Document, diagnostics: &Diagnostics, rules: &RuleSet) -> Self {
+        let mut summary = Self {
+            suppressed: SuppressionReport::new(document, diagnostics)
+                .directives()
+                .iter()
+                .map(|d| d.suppressed())
+                .sum(),
+            span: document
+                .version_statement()
+                .map(|v| v.syntax().text_range().to_span()),
+            ..Default::default()
+        };
+
+        for diagnostic in diagnostics.diagnostics() {
+            match diagnostic.severity() {
+                Severity::Error => summary.errors += 1,
+                Severity::Warning => summary.warnings += 1,
+                Severity::Note => summary.notes += 1,
+            }
+
+            let Some(id) = diagnostic.rule() else {
+                continue;
+            };
+
+            summary.rules.insert(id.to_string());
+            if let Some(rule) = rules.get(id) {
+                for tag in rule.tags().iter() {
+                    *summary.tags.entry(tag).or_default() += 1;
+                }
+            }
+        }
+
+        summary
+    }
+
+    /// Gets the number of diagnostics for each tag.
+    ///
+    /// A diagnostic is counted once for each tag of its rule.
+    pub fn tags(&self) -> &BTreeMap<Tag, usize> {
+        &self.tags
+    }
+
+    /// Gets the total number of diagnostics.
+    pub fn findings(&self) -> usize {
+        self.errors + self.warnings + self.notes
+    }
+
+    /// Gets the number of error diagnostics.
+    pub fn errors(&self) -> usize {
+        self.errors
+    }
+
+    /// Gets the number of warning diagnostics.
+    pub fn warnings(&self) -> usize {
+        self.warnings
+    }
+
+    /// Gets the number of note diagnostics.
+    pub fn notes(&self) -> usize {
+        self.notes
+    }
+
+    /// Gets the number of diagnostics suppressed by `#@ except` comments.
+    pub fn suppressed(&self) -> usize {
+        self.suppressed
+    }
+
+    /// Gets the identifiers of the rules that reported a diagnostic.
+    pub fn rules(&self) -> &BTreeSet<String> {
+        &self.rules
+    }
+
+    /// Converts the summary into a note diagnostic anchored at the document's
+    /// version statement.
+    ///
+    /// The diagnostic is not associated with a rule and therefore cannot be
+    /// excepted.
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        let diagnostic = Diagnostic::note(format!(
+            "lint summary: {findings} ({errors}, {warnings}, {notes}), {suppressed} suppressed",
+            findings = plural(self.findings(), "finding"),
+            errors = plural(self.errors, "error"),
+            warnings = plural(self.warnings, "warning"),
+            notes = plural(self.notes, "note"),
+            suppressed = self.suppressed,
+        ));
+
+        let Some(span) = self.span else {
+            return diagnostic;
+        };
+
+        if self.tags.is_empty() && self.rules.is_empty() {
+            return diagnostic.with_highlight(span);
+        }
+
+        let mut label = String::new();
+        if !self.tags.is_empty() {
+            label.push_str("findings by tag: ");
+            for (i, (tag, count)) in self.tags.iter().enumerate() {
+                if i > 0 {
+                    label.push_str(", ");
+                }
+
+                write!(label, "{tag}: {count}").unwrap();
+            }
+        }
+
+        if !self.rules.is_empty() {
+            if !label.is_empty() {
+                label.push_str("; ");
+            }
+
+            label.push_str("rules: ");
+            label.push_str(&self.rules.iter().cloned().collect::<Vec<_>>().join(", "));
+        }
+
+        diagnostic.with_label(label, span)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use wdl_ast::Validator;
+
+    use super::*;
+    use crate::LintVisitor;
+    use crate::Rule;
+    use crate::rules::MissingOutputRule;
+    use crate::rules::SnakeCaseRule;
+    use crate::rules::TodoRule;
+
+    /// The source of a document with known findings.
+    const SOURCE: &str = r#"#@ except: MissingOutput
+
+version 1.1
+
+# TODO: rename the declarations
+workflow test {
+    #@ except: SnakeCase
+    Int badName = 1
+    Int otherName = 2
+}
+"#;
+
+    /// Gets the rules used to lint the test document.
+    fn rules() -> Vec<Box<dyn Rule>> {
+        vec![
+            Box::<SnakeCaseRule>::default(),
+            Box::<TodoRule>::default(),
+            Box::new(MissingOutputRule),
+        ]
+    }
+
+    #[test]
+    fn it_summarizes_findings() {
+        let (document, diagnostics) = Document::parse(SOURCE);
+        assert!(diagnostics.is_empty());
+
+        let mut validator = Validator::empty();
+        validator.add_visitor(LintVisitor::new(rules()));
+        let diagnostics = validator.run(&document);
+        assert_eq!(diagnostics.diagnostics().len(), 2);
+
+        let summary = LintSummary::new(
+            &document,
+            &diagnostics,
+            &rules().into_iter().collect::<RuleSet>(),
+        );
+        assert_eq!(summary.findings(), 2);
+        assert_eq!(summary.errors(), 0);
+        assert_eq!(summary.warnings(), 1);
+        assert_eq!(summary.notes(), 1);
+        assert_eq!(summary.suppressed(), 1);
+        assert_eq!(
+            summary.tags().iter().collect::<Vec<_>>(),
+            [
+                (&Tag::Completeness, &1),
+                (&Tag::Naming, &1),
+                (&Tag::Style, &1),
+                (&Tag::Clarity, &1),
+            ]
+        );
+        assert_eq!(
+            summary.rules().iter().collect::<Vec<_>>(),
+            ["SnakeCase", "Todo"]
+        );
+
+        assert_eq!(
+            serde_json::to_value(&summary).unwrap(),
+            serde_json::json!({
+                "tags": {
+                    "Completeness": 1,
+                    "Naming": 1,
+                    "Style": 1,
+                    "Clarity": 1,
+                },
+                "errors": 0,
+                "warnings": 1,
+                "notes": 1,
+                "suppressed": 1,
+                "rules": ["SnakeCase", "Todo"],
+            })
+        );
+    }
+
+    #[test]
+    fn it_appends_a_summary_diagnostic() {
+        let (document, diagnostics) = Document::parse(SOURCE);
+        assert!(diagnostics.is_empty());
+
+        let mut validator = Validator::empty();
+        validator.add_visitor(LintVisitor::new(rules()).with_summary(true));
+        let diagnostics = validator.validate(&document).unwrap_err();
+        assert_eq!(diagnostics.len(), 3);
+
+        let summary = diagnostics
+            .iter()
+            .find(|d| d.rule().is_none())
+            .expect("should have a summary diagnostic");
+        assert_eq!(summary.severity(), Severity::Note);
+        assert_eq!(
+            summary.message(),
+            "lint summary: 2 findings (0 errors, 1 warning, 1 note), 1 suppressed"
+        );
+
+        let label = summary.labels().next().expect("should have a label");
+        assert_eq!(
+            label.message(),
+            "findings by tag: Completeness: 1, Naming: 1, Style: 1, Clarity: 1; rules: \
+             SnakeCase, Todo"
+        );
+        assert_eq!(
+            &SOURCE[label.span().start()..label.span().end()],
+            "version 1.1"
+        );
+    }
+}
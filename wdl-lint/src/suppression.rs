@@ -0,0 +1,89 @@
+//! Reporting of the diagnostics suppressed by `#@ except` comments.
+
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::EXCEPT_COMMENT_PREFIX;
+use wdl_ast::Span;
+use wdl_ast::SyntaxKind;
+use wdl_ast::SyntaxToken;
+use wdl_ast::ToSpan;
+
+/// Represents an `#@ except` comment and the diagnostics it suppressed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExceptDirective {
+    /// The `#@ except` comment token.
+    comment: SyntaxToken,
+    /// The excepted rule identifiers and the number of diagnostics suppressed
+    /// for each rule.
+    rules: Vec<(String, usize)>,
+}
+
+impl ExceptDirective {
+    /// Gets the `#@ except` comment token of the directive.
+    pub fn comment(&self) -> &SyntaxToken {
+        &self.comment
+    }
+
+    /// Gets the span of the directive.
+    pub fn span(&self) -> Span {
+        self.comment.text_range().to_span()
+    }
+
+    /// Gets the excepted rule identifiers and the number of diagnostics
+    /// suppressed for each rule.
+    pub fn rules(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.rules.iter().map(|(id, count)| (id.as_str(), *count))
+    }
+
+    /// Gets the total number of diagnostics suppressed by the directive.
+    pub fn suppressed(&self) -> usize {
+        self.rules.iter().map(|(_, count)| count).sum()
+    }
+}
+
+/// Represents a report of the diagnostics suppressed by each `#@ except`
+/// comment of a document.
+///
+/// Suppressions are recorded in the [`Diagnostics`] collection when a
+/// diagnostic is excepted; use [`Validator::run`](wdl_ast::Validator::run) to
+/// retain the collection after validation.
+///
+/// Note that only rules that were run can have suppressed diagnostics; a rule
+/// that was not run will always have a count of zero.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SuppressionReport {
+    /// The `#@ except` directives of the document, in source order.
+    directives: Vec<ExceptDirective>,
+}
+
+impl SuppressionReport {
+    /// Creates a new suppression report for the given document from the
+    /// suppressions recorded during validation.
+    pub fn new(document: &Document, diagnostics: &Diagnostics) -> Self {
+        let directives = document
+            .syntax()
+            .descendants_with_tokens()
+            .filter_map(|e| e.into_token())
+            .filter(|t| t.kind() == SyntaxKind::Comment)
+            .filter_map(|comment| {
+                let ids = comment.text().strip_prefix(EXCEPT_COMMENT_PREFIX)?;
+                let span = comment.text_range().to_span();
+                let rules = ids
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|id| !id.is_empty())
+                    .map(|id| (id.to_string(), diagnostics.suppressed(span, id)))
+                    .collect();
+
+                Some(ExceptDirective { comment, rules })
+            })
+            .collect();
+
+        Self { directives }
+    }
+
+    /// Gets the `#@ except` directives of the document, in source order.
+    pub fn directives(&self) -> &[ExceptDirective] {
+        &self.directives
+    }
+}
@@ -30,6 +30,9 @@ pub enum Tag {
 
     /// Rules associated with the use of deprecated language constructs.
     Deprecated,
+
+    /// Rules associated with the security of a document.
+    Security,
 }
 
 impl std::fmt::Display for Tag {
@@ -44,10 +47,38 @@ impl std::fmt::Display for Tag {
             Self::Correctness => write!(f, "Correctness"),
             Self::Sorting => write!(f, "Sorting"),
             Self::Deprecated => write!(f, "Deprecated"),
+            Self::Security => write!(f, "Security"),
+        }
+    }
+}
+
+impl std::str::FromStr for Tag {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "completeness" => Ok(Self::Completeness),
+            "naming" => Ok(Self::Naming),
+            "spacing" => Ok(Self::Spacing),
+            "style" => Ok(Self::Style),
+            "clarity" => Ok(Self::Clarity),
+            "portability" => Ok(Self::Portability),
+            "correctness" => Ok(Self::Correctness),
+            "sorting" => Ok(Self::Sorting),
+            "deprecated" => Ok(Self::Deprecated),
+            "security" => Ok(Self::Security),
+            _ => anyhow::bail!("unknown lint tag `{s}`"),
         }
     }
 }
 
+/// Serializes a tag as its name (e.g. `"Spacing"`).
+impl serde::Serialize for Tag {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
 /// A set of lint tags.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct TagSet(u32);
@@ -75,11 +106,24 @@ impl TagSet {
         Self(bits)
     }
 
+    /// Constructs an empty tag set.
+    ///
+    /// An empty tag set is only useful for filtering rules by tag; the tag set
+    /// of a rule must be non-empty.
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
     /// Unions two tag sets together.
     pub const fn union(self, other: Self) -> Self {
         Self(self.0 | other.0)
     }
 
+    /// Intersects two tag sets.
+    pub const fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
     /// Checks if the tag is contained in the set.
     pub const fn contains(&self, tag: Tag) -> bool {
         self.0 & Self::mask(tag) != 0
@@ -90,6 +134,11 @@ impl TagSet {
         self.0.count_ones() as usize
     }
 
+    /// Checks if the set is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
     /// Masks the given tag to a `u32`.
     const fn mask(tag: Tag) -> u32 {
         1u32 << (tag as u8)
@@ -116,6 +165,15 @@ impl TagSet {
     }
 }
 
+/// Collects tags into a tag set.
+///
+/// Unlike [`TagSet::new`], no tags are implied by the collected tags.
+impl FromIterator<Tag> for TagSet {
+    fn from_iter<T: IntoIterator<Item = Tag>>(iter: T) -> Self {
+        Self(iter.into_iter().fold(0, |bits, tag| bits | Self::mask(tag)))
+    }
+}
+
 /// Display for a tag set.
 impl std::fmt::Display for TagSet {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
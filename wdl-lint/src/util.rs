@@ -3,9 +3,22 @@
 use std::process::Command;
 use std::process::Stdio;
 
+use wdl_ast::AstNode;
 use wdl_ast::AstToken;
 use wdl_ast::Comment;
 use wdl_ast::SyntaxKind;
+use wdl_ast::v1::LiteralHintsItem;
+use wdl_ast::v1::LiteralInputItem;
+use wdl_ast::v1::MetadataValue;
+use wdl_ast::v1::TaskDefinition;
+
+/// The words after which the next word starts a new command.
+const COMMAND_KEYWORDS: &[&str] = &[
+    "!", "{", "do", "elif", "else", "if", "then", "time", "until", "while",
+];
+
+/// The name of the hint that marks an input as not requiring localization.
+pub const LOCALIZATION_OPTIONAL: &str = "localization_optional";
 
 /// Counts the amount of leading whitespace in a string slice.
 ///
@@ -87,6 +100,44 @@ pub fn lines_with_offset(s: &str) -> impl Iterator<Item = (&str, usize, usize)>
     })
 }
 
+/// Determines if the given input of a task is marked with a
+/// `localization_optional` hint.
+///
+/// The hint may be specified in the `parameter_meta` section (e.g. `x: {
+/// localization_optional: true }`) or, in WDL 1.2, in the `inputs` hint of the
+/// `hints` section.
+pub fn is_localization_optional(task: &TaskDefinition, input: &str) -> bool {
+    let in_parameter_meta = task.parameter_metadata().is_some_and(|section| {
+        section.items().any(|item| {
+            item.name().as_str() == input
+                && matches!(
+                    item.value(),
+                    MetadataValue::Object(o)
+                        if o.items().any(|i| i.name().as_str() == LOCALIZATION_OPTIONAL)
+                )
+        })
+    });
+
+    in_parameter_meta
+        || task.hints().is_some_and(|section| {
+            section
+                .items()
+                .filter(|item| item.name().as_str() == "inputs")
+                .flat_map(|item| {
+                    item.syntax()
+                        .descendants()
+                        .filter_map(LiteralInputItem::cast)
+                })
+                .filter(|item| item.names().next().is_some_and(|n| n.as_str() == input))
+                .any(|item| {
+                    item.syntax()
+                        .descendants()
+                        .filter_map(LiteralHintsItem::cast)
+                        .any(|i| i.name().as_str() == LOCALIZATION_OPTIONAL)
+                })
+        })
+}
+
 /// Check whether or not a program exists.
 ///
 /// On unix-like OSes, uses `which`.
@@ -102,6 +153,178 @@ pub fn program_exists(exec: &str) -> bool {
         .is_ok_and(|r| r.success())
 }
 
+/// Calculates the Levenshtein edit distance between two strings.
+///
+/// The distance is the minimum number of single character insertions,
+/// deletions, or substitutions required to change one string into the other.
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut prev = (0..=b.len()).collect::<Vec<_>>();
+    let mut cur = vec![0; b.len() + 1];
+    for (i, ca) in a.chars().enumerate() {
+        cur[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = prev[j] + usize::from(ca != *cb);
+            cur[j + 1] = substitution.min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+
+        std::mem::swap(&mut prev, &mut cur);
+    }
+
+    prev[b.len()]
+}
+
+/// Splits a line of a command into words, returning the offset of each word.
+///
+/// Splitting stops at a word that starts a comment.
+pub fn words(line: &str) -> Vec<(usize, &str)> {
+    let mut words = Vec::new();
+    let mut start = None;
+    for (i, c) in line.char_indices().chain([(line.len(), ' ')]) {
+        match start {
+            Some(s) if c.is_whitespace() => {
+                words.push((s, &line[s..i]));
+                start = None;
+            }
+            None if !c.is_whitespace() => {
+                if c == '#' {
+                    break;
+                }
+
+                start = Some(i);
+            }
+            _ => {}
+        }
+    }
+
+    words
+}
+
+/// Finds the words of a line of a command that are in command position.
+///
+/// `command` is whether or not the first word of the line is in command
+/// position.
+///
+/// Returns the index of each word within `words` with the offset and text of
+/// the command name; the name of a command starting a command substitution or
+/// subshell excludes the opening characters.
+pub fn command_words<'a>(
+    words: &[(usize, &'a str)],
+    mut command: bool,
+) -> Vec<(usize, usize, &'a str)> {
+    let mut commands = Vec::new();
+    for (i, &(mut offset, mut word)) in words.iter().enumerate() {
+        // Command substitutions and subshells start a new command
+        while let Some(index) = word
+            .find("$(")
+            .map(|start| start + 2)
+            .or_else(|| word.find(['(', '`']).map(|start| start + 1))
+            .filter(|start| *start < word.len())
+        {
+            offset += index;
+            word = &word[index..];
+            command = true;
+        }
+
+        if command {
+            commands.push((i, offset, word.trim_end_matches([')', '`', ';'])));
+        }
+
+        command = COMMAND_KEYWORDS.contains(&word)
+            || word.ends_with(|c| matches!(c, ';' | '|' | '&' | '('));
+    }
+
+    commands
+}
+
+/// Replaces the quoted text of a line of a command with spaces.
+///
+/// `quote` is the quote that is open at the start of the line; it is updated
+/// to the quote that is open at the end of the line. Command substitutions in
+/// double quotes (e.g. `"$(curl ...)"`) are not replaced.
+pub fn mask_quotes(line: &str, quote: &mut Option<u8>) -> String {
+    let bytes = line.as_bytes();
+    let mut masked = Vec::with_capacity(bytes.len());
+    let mut depth = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i];
+        match *quote {
+            Some(b'\'') => {
+                if b == b'\'' {
+                    *quote = None;
+                    masked.push(b);
+                } else {
+                    masked.push(b' ');
+                }
+            }
+            Some(_) if depth > 0 => {
+                match b {
+                    b'(' => depth += 1,
+                    b')' => depth -= 1,
+                    _ => {}
+                }
+
+                masked.push(b);
+            }
+            Some(_) => {
+                if b == b'\\' && i + 1 < bytes.len() {
+                    masked.extend(b"  ");
+                    i += 2;
+                    continue;
+                }
+
+                if bytes[i..].starts_with(b"$(") {
+                    depth = 1;
+                    masked.extend(b"$(");
+                    i += 2;
+                    continue;
+                }
+
+                if b == b'"' {
+                    *quote = None;
+                    masked.push(b);
+                } else {
+                    masked.push(b' ');
+                }
+            }
+            None => {
+                if b == b'\\' && i + 1 < bytes.len() {
+                    masked.extend(&bytes[i..i + 2]);
+                    i += 2;
+                    continue;
+                }
+
+                if b == b'\'' || b == b'"' {
+                    *quote = Some(b);
+                }
+
+                masked.push(b);
+            }
+        }
+
+        i += 1;
+    }
+
+    // Only ASCII bytes and whole characters were replaced
+    String::from_utf8(masked).expect("masked line should be UTF-8")
+}
+
+/// Applies the replacements of the given diagnostics to the source.
+#[cfg(test)]
+pub fn apply_replacements(source: &str, diagnostics: &[wdl_ast::Diagnostic]) -> String {
+    let mut replacements: Vec<_> = diagnostics.iter().flat_map(|d| d.replacements()).collect();
+    replacements.sort_by_key(|r| r.span().start());
+
+    let mut fixed = source.to_string();
+    for replacement in replacements.iter().rev() {
+        let span = replacement.span();
+        fixed.replace_range(span.start()..span.end(), replacement.text());
+    }
+
+    fixed
+}
+
 /// Strips a single newline from the end of a string.
 pub fn strip_newline(s: &str) -> Option<&str> {
     s.strip_suffix("\r\n").or_else(|| s.strip_suffix('\n'))
@@ -152,6 +375,22 @@ task foo {  # an in-line comment
         assert!(!is_inline);
     }
 
+    #[test]
+    fn it_masks_quotes() {
+        let mut quote = None;
+        assert_eq!(
+            mask_quotes(r#"echo "use curl" 'or wget' it\'s"#, &mut quote),
+            r#"echo "        " '       ' it\'s"#
+        );
+        assert_eq!(quote, None);
+
+        assert_eq!(
+            mask_quotes(r#"v="$(curl -s "$url")" && echo "~"#, &mut quote),
+            r#"v="$(curl -s "$url")" && echo " "#
+        );
+        assert_eq!(quote, Some(b'"'));
+    }
+
     #[test]
     fn test_strip_newline() {
         let s = "this has no newline";
@@ -211,6 +450,17 @@ task foo {  # an in-line comment
         }
     }
 
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("", ""), 0);
+        assert_eq!(levenshtein("abc", ""), 3);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("ShellCheck", "ShellCheck"), 0);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("SnakeCas", "SnakeCase"), 1);
+        assert_eq!(levenshtein("shellcheck", "ShellCheck"), 2);
+    }
+
     #[test]
     fn test_is_properly_quoted() {
         let s = "\"this string is quoted properly.\"";
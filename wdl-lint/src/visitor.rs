@@ -2,12 +2,12 @@
 
 use std::collections::HashSet;
 
-use indexmap::IndexMap;
 use wdl_ast::AstNode;
 use wdl_ast::Comment;
 use wdl_ast::Diagnostics;
 use wdl_ast::Document;
 use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxElement;
 use wdl_ast::SyntaxNodeExt;
 use wdl_ast::VersionStatement;
 use wdl_ast::VisitReason;
@@ -15,12 +15,19 @@ use wdl_ast::Visitor;
 use wdl_ast::Whitespace;
 use wdl_ast::v1;
 
+use crate::ImportedDocuments;
+use crate::LintSummary;
 use crate::Rule;
-use crate::rules;
+use crate::RuleSet;
+use crate::SuppressionReport;
+use crate::TagSet;
+use crate::rules::unused_lint_directive;
+use crate::rules_with_tags;
 
 /// A visitor that runs linting rules.
 ///
-/// By default, the visitor runs all lint rules.
+/// By default, the visitor runs the default lint rules; use a [`RuleSet`] to
+/// run additional rules.
 ///
 /// This visitor respects `#@ except` comments that precede AST nodes.
 ///
@@ -32,42 +39,191 @@ use crate::rules;
 ///
 /// Otherwise, `#@ except` comments disable the rule for the immediately
 /// following AST node.
+///
+/// Rules superseded by another rule in the rule set (see
+/// [`Rule::superseded_by`]) are not run.
+///
+/// The visitor records the number of diagnostics suppressed by each `#@ except`
+/// comment in the diagnostics collection (see [`SuppressionReport`]). Rules
+/// disabled for the entire document are not run, so their suppressed
+/// diagnostics are not counted; such comments are always considered to be used.
+///
+/// Optionally, the visitor appends a [`LintSummary`] note for each document
+/// (see [`with_summary`](Self::with_summary)).
 #[allow(missing_debug_implementations)]
 pub struct LintVisitor {
-    /// The map of rule name to rule.
-    rules: IndexMap<&'static str, Box<dyn Rule>>,
+    /// The set of rules to run.
+    rules: RuleSet,
     /// The set of rule ids that are disabled for the current document.
     document_exceptions: HashSet<String>,
+    /// The documents imported by the document being linted.
+    imports: ImportedDocuments,
+    /// Whether or not a summary diagnostic is appended for each document.
+    summary: bool,
+    /// The diagnostics of the cacheable rules for the current document.
+    cached: Diagnostics,
+    /// Whether or not the cacheable rules are skipped in favor of the cached
+    /// diagnostics.
+    reuse_cached: bool,
 }
 
 impl LintVisitor {
     /// Creates a new linting visitor with the given rules.
     pub fn new(rules: impl IntoIterator<Item = Box<dyn Rule>>) -> Self {
-        Self {
-            rules: rules.into_iter().map(|r| (r.id(), r)).collect(),
-            document_exceptions: HashSet::default(),
-        }
+        Self::from(rules.into_iter().collect::<RuleSet>())
+    }
+
+    /// Creates a new linting visitor with the rules filtered by tag.
+    ///
+    /// See [`rules_with_tags`] for how the rules are filtered.
+    ///
+    /// Returns an error if a tag is both included and excluded.
+    pub fn with_tags(include: TagSet, exclude: TagSet) -> anyhow::Result<Self> {
+        Ok(Self::new(rules_with_tags(include, exclude)?))
+    }
+
+    /// Sets the documents imported by the document being linted.
+    ///
+    /// The imported documents are provided to each rule upon entry of the
+    /// document (see [`Rule::set_imports`]).
+    pub fn with_imports(mut self, imports: ImportedDocuments) -> Self {
+        self.imports = imports;
+        self
+    }
+
+    /// Sets the documents imported by the next document being linted.
+    pub(crate) fn set_imports(&mut self, imports: ImportedDocuments) {
+        self.imports = imports;
+    }
+
+    /// Sets whether or not a summary diagnostic is appended for each document.
+    ///
+    /// The summary is a note anchored at the document's version statement that
+    /// counts the document's diagnostics by tag and by severity, along with
+    /// the number of diagnostics suppressed by `#@ except` comments (see
+    /// [`LintSummary`]).
+    ///
+    /// Defaults to `false`.
+    pub fn with_summary(mut self, summary: bool) -> Self {
+        self.summary = summary;
+        self
+    }
+
+    /// Determines if the diagnostics of every rule may be cached for a
+    /// document whose content has not changed.
+    ///
+    /// See [`Rule::cacheable`].
+    pub(crate) fn is_cacheable(&self) -> bool {
+        self.rules.iter().all(|rule| rule.cacheable())
+    }
+
+    /// Sets the diagnostics of the cacheable rules to use for the next
+    /// document instead of running the cacheable rules.
+    ///
+    /// The diagnostics must be from a previous visitation of a document with
+    /// the same content.
+    pub(crate) fn reuse_cached(&mut self, cached: Diagnostics) {
+        self.cached = cached;
+        self.reuse_cached = true;
+    }
+
+    /// Takes the diagnostics of the cacheable rules for the last document.
+    pub(crate) fn take_cached(&mut self) -> Diagnostics {
+        self.reuse_cached = false;
+        std::mem::take(&mut self.cached)
     }
 
     /// Invokes a callback on each rule
+    ///
+    /// Rules disabled for the entire document are skipped.
+    ///
+    /// The diagnostics of cacheable rules are collected separately so that
+    /// they can be cached; cacheable rules are not invoked when their cached
+    /// diagnostics are reused.
     fn each_enabled_rule<F>(&mut self, state: &mut Diagnostics, mut cb: F)
     where
         F: FnMut(&mut Diagnostics, &mut dyn Rule),
     {
-        for (id, rule) in &mut self.rules {
-            if self.document_exceptions.contains(id.to_owned()) {
+        for (id, rule) in self.rules.iter_mut() {
+            if self.document_exceptions.contains(id) {
+                continue;
+            }
+
+            if !rule.cacheable() {
+                cb(state, rule);
+            } else if !self.reuse_cached {
+                cb(&mut self.cached, rule);
+            }
+        }
+    }
+
+    /// Reports any unused exceptions of the document.
+    ///
+    /// The exceptions that disable rules for the entire document are always
+    /// considered to be used, as the excepted rules are not run.
+    fn report_unused_exceptions(&mut self, state: &mut Diagnostics, doc: &Document) {
+        if !self.rules.contains(unused_lint_directive::ID)
+            || self.document_exceptions.contains(unused_lint_directive::ID)
+        {
+            return;
+        }
+
+        let document_comments: Vec<_> = doc
+            .version_statement()
+            .expect("document should have version statement")
+            .syntax()
+            .except_comments()
+            .collect();
+
+        for directive in SuppressionReport::new(doc, state).directives() {
+            if document_comments.contains(directive.comment()) {
                 continue;
             }
-            cb(state, rule.as_mut());
+
+            let unused: Vec<_> = directive
+                .rules()
+                .filter(|(id, count)| {
+                    *count == 0 && *id != unused_lint_directive::ID && self.rules.contains(id)
+                })
+                .map(|(id, _)| id)
+                .collect();
+
+            if !unused.is_empty() {
+                state.exceptable_add(
+                    unused_lint_directive::unused_lint_directive(&unused, directive.span()),
+                    SyntaxElement::from(directive.comment().clone()),
+                    &None,
+                );
+            }
         }
     }
 }
 
 impl Default for LintVisitor {
     fn default() -> Self {
+        Self::from(RuleSet::default())
+    }
+}
+
+impl From<RuleSet> for LintVisitor {
+    fn from(mut rules: RuleSet) -> Self {
+        // Rules superseded by another rule in the set are not run
+        let superseded: Vec<_> = rules
+            .iter()
+            .filter(|r| r.superseded_by().iter().any(|id| rules.contains(id)))
+            .map(|r| r.id())
+            .collect();
+        for id in superseded {
+            rules.remove(id);
+        }
+
         Self {
-            rules: rules().into_iter().map(|r| (r.id(), r)).collect(),
+            rules,
             document_exceptions: HashSet::default(),
+            imports: ImportedDocuments::default(),
+            summary: false,
+            cached: Diagnostics::default(),
+            reuse_cached: false,
         }
     }
 }
@@ -85,6 +241,14 @@ impl Visitor for LintVisitor {
         if reason == VisitReason::Enter {
             // Reset state for a new document
             self.document_exceptions.clear();
+            if !self.reuse_cached {
+                self.cached = Diagnostics::default();
+            }
+
+            for rule in self.rules.iter().filter(|r| r.allows_duplicates()) {
+                state.allow_duplicates(rule.id());
+                self.cached.allow_duplicates(rule.id());
+            }
         }
 
         self.document_exceptions.extend(
@@ -97,6 +261,24 @@ impl Visitor for LintVisitor {
         self.each_enabled_rule(state, |state, rule| {
             rule.document(state, reason, doc, version);
         });
+
+        if reason == VisitReason::Enter {
+            let imports = std::mem::take(&mut self.imports);
+            self.each_enabled_rule(state, |_, rule| rule.set_imports(&imports));
+            self.imports = imports;
+        }
+
+        if reason == VisitReason::Exit {
+            state.merge(self.cached.clone());
+            self.report_unused_exceptions(state, doc);
+
+            // The summary is created last so that it counts the diagnostics
+            // remaining after exceptions were applied
+            if self.summary {
+                let summary = LintSummary::new(doc, state, &self.rules);
+                state.add(summary.to_diagnostic());
+            }
+        }
     }
 
     fn whitespace(&mut self, state: &mut Self::State, whitespace: &Whitespace) {
@@ -414,4 +596,228 @@ workflow test {
             .validate(&document)
             .expect("should not have any diagnostics");
     }
+
+    #[test]
+    fn it_reports_suppressions() {
+        let source = r#"#@ except: MissingMetas, MissingOutput, LineWidth
+
+## Test source
+
+version 1.1
+
+workflow test {
+    #@ except: SnakeCase
+    Int badName = 1
+    #@ except: SnakeCase, Todo
+    Int good_name = 2
+}
+"#;
+
+        let (document, diagnostics) = wdl_ast::Document::parse(source);
+        assert!(diagnostics.is_empty());
+
+        let rules = crate::rules()
+            .into_iter()
+            .chain([Box::<crate::rules::UnusedLintDirectiveRule>::default() as Box<dyn Rule>]);
+        let mut validator = Validator::default();
+        validator.add_visitor(LintVisitor::new(rules));
+
+        let diagnostics = validator.run(&document);
+        let report = SuppressionReport::new(&document, &diagnostics);
+        let counts: Vec<Vec<_>> = report
+            .directives()
+            .iter()
+            .map(|d| d.rules().collect())
+            .collect();
+        assert_eq!(
+            counts,
+            [
+                vec![("MissingMetas", 0), ("MissingOutput", 0), ("LineWidth", 0)],
+                vec![("SnakeCase", 1)],
+                vec![("SnakeCase", 0), ("Todo", 0)],
+            ]
+        );
+
+        let unused: Vec<_> = diagnostics
+            .diagnostics()
+            .iter()
+            .filter(|d| d.rule() == Some(unused_lint_directive::ID))
+            .map(|d| d.message())
+            .collect();
+        assert_eq!(
+            unused,
+            ["unused exception for lint rules `SnakeCase`, `Todo`"]
+        );
+    }
+
+    #[test]
+    fn it_does_not_run_excepted_rules() {
+        /// A rule that records whether or not it was run.
+        struct RunRule(std::rc::Rc<std::cell::Cell<bool>>);
+
+        impl Rule for RunRule {
+            fn id(&self) -> &'static str {
+                "Run"
+            }
+
+            fn description(&self) -> &'static str {
+                "A rule that records whether or not it was run."
+            }
+
+            fn explanation(&self) -> &'static str {
+                "A rule that records whether or not it was run."
+            }
+
+            fn tags(&self) -> TagSet {
+                TagSet::new(&[crate::Tag::Style])
+            }
+
+            fn exceptable_nodes(&self) -> Option<&'static [wdl_ast::SyntaxKind]> {
+                None
+            }
+        }
+
+        impl Visitor for RunRule {
+            type State = Diagnostics;
+
+            fn document(
+                &mut self,
+                _: &mut Self::State,
+                _: VisitReason,
+                _: &wdl_ast::Document,
+                _: SupportedVersion,
+            ) {
+                self.0.set(true);
+            }
+        }
+
+        for (source, run) in [
+            ("version 1.1\n\nworkflow test {}\n", true),
+            ("#@ except: Run\nversion 1.1\n\nworkflow test {}\n", false),
+        ] {
+            let (document, diagnostics) = wdl_ast::Document::parse(source);
+            assert!(diagnostics.is_empty());
+
+            let ran = std::rc::Rc::new(std::cell::Cell::new(false));
+            let rules: [Box<dyn Rule>; 2] = [
+                Box::new(RunRule(ran.clone())),
+                Box::<crate::rules::UnusedLintDirectiveRule>::default(),
+            ];
+            let mut validator = Validator::empty();
+            validator.add_visitor(LintVisitor::new(rules));
+
+            // The document-wide exception is not reported as unused
+            let diagnostics = validator.run(&document);
+            assert!(diagnostics.diagnostics().is_empty());
+            assert_eq!(ran.get(), run);
+        }
+    }
+
+    #[test]
+    fn it_skips_superseded_rules() {
+        let (document, diagnostics) = wdl_ast::Document::parse(
+            "version 1.1\n\ntask test {\n    command <<<\n        which samtools\n    >>>\n}\n",
+        );
+        assert!(diagnostics.is_empty());
+
+        let count = |rules: Vec<Box<dyn Rule>>| {
+            let mut validator = Validator::empty();
+            validator.add_visitor(LintVisitor::new(rules));
+            validator
+                .run(&document)
+                .diagnostics()
+                .iter()
+                .filter(|d| d.rule() == Some("BasicShellPortability"))
+                .count()
+        };
+
+        let portability =
+            || Box::<crate::rules::BasicShellPortabilityRule>::default() as Box<dyn Rule>;
+        assert_eq!(count(vec![portability()]), 1);
+
+        // The rule is superseded by the `ShellCheck` rule
+        assert_eq!(
+            count(vec![
+                portability(),
+                Box::<crate::rules::ShellCheckRule>::default()
+            ]),
+            0
+        );
+    }
+
+    #[test]
+    fn it_sorts_diagnostics() {
+        /// A rule that flags the name of every workflow.
+        struct NameRule(&'static str);
+
+        impl Rule for NameRule {
+            fn id(&self) -> &'static str {
+                self.0
+            }
+
+            fn description(&self) -> &'static str {
+                "A rule that flags workflow names."
+            }
+
+            fn explanation(&self) -> &'static str {
+                "A rule that flags workflow names."
+            }
+
+            fn tags(&self) -> TagSet {
+                TagSet::new(&[crate::Tag::Style])
+            }
+
+            fn exceptable_nodes(&self) -> Option<&'static [wdl_ast::SyntaxKind]> {
+                None
+            }
+        }
+
+        impl Visitor for NameRule {
+            type State = Diagnostics;
+
+            fn document(
+                &mut self,
+                _: &mut Self::State,
+                _: VisitReason,
+                _: &wdl_ast::Document,
+                _: SupportedVersion,
+            ) {
+            }
+
+            fn workflow_definition(
+                &mut self,
+                state: &mut Self::State,
+                reason: VisitReason,
+                workflow: &v1::WorkflowDefinition,
+            ) {
+                if reason == VisitReason::Enter {
+                    state.add(
+                        wdl_ast::Diagnostic::note("a workflow name")
+                            .with_rule(self.0)
+                            .with_highlight(workflow.name().span()),
+                    );
+                }
+            }
+        }
+
+        let (document, diagnostics) = wdl_ast::Document::parse("version 1.1\n\nworkflow test {}\n");
+        assert!(diagnostics.is_empty());
+
+        // Both rules report on the same span; the order of the diagnostics must not
+        // depend on the order in which the rules run
+        for ids in [["Zulu", "Alpha"], ["Alpha", "Zulu"]] {
+            let mut validator = Validator::empty();
+            validator.add_visitor(LintVisitor::new(
+                ids.map(|id| Box::new(NameRule(id)) as Box<dyn Rule>),
+            ));
+
+            let diagnostics = validator.run(&document);
+            let rules: Vec<_> = diagnostics
+                .diagnostics()
+                .iter()
+                .map(|d| d.rule().unwrap())
+                .collect();
+            assert_eq!(rules, ["Alpha", "Zulu"]);
+        }
+    }
 }
@@ -0,0 +1,71 @@
+//! The parallel linting test.
+//!
+//! This test lints the `source.wdl` of each directory in `tests/lints` both
+//! serially and in parallel and checks that the results are identical.
+
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+use wdl_ast::Document;
+use wdl_lint::LintVisitor;
+use wdl_lint::lint_documents_with;
+use wdl_lint::optional_rules;
+use wdl_lint::rules;
+
+/// Finds the lint test sources.
+fn find_sources() -> Vec<PathBuf> {
+    let mut sources: Vec<PathBuf> = Path::new("tests/lints")
+        .read_dir()
+        .unwrap()
+        .map(|entry| entry.expect("failed to read directory").path())
+        .filter(|path| path.is_dir())
+        .map(|path| path.join("source.wdl"))
+        .filter(|path| path.is_file())
+        .collect();
+
+    sources.sort();
+    sources
+}
+
+/// Creates a lint visitor with every rule except `ShellCheck`, which depends
+/// on an external program.
+fn visitor() -> LintVisitor {
+    LintVisitor::new(
+        rules()
+            .into_iter()
+            .chain(optional_rules())
+            .filter(|r| r.id() != "ShellCheck"),
+    )
+}
+
+#[test]
+fn parallel_linting_matches_serial_linting() {
+    let sources: Vec<_> = find_sources()
+        .iter()
+        .map(|path| fs::read_to_string(path).expect("failed to read source"))
+        .collect();
+    assert!(!sources.is_empty(), "expected lint test sources");
+
+    let documents = || sources.iter().map(|s| Document::parse(s).0);
+    let serial = lint_documents_with(1, documents(), visitor);
+    let parallel = lint_documents_with(4, documents(), visitor);
+
+    assert_eq!(serial.len(), sources.len());
+    assert_eq!(parallel.len(), sources.len());
+    for (source, ((expected_document, expected), (actual_document, actual))) in
+        sources.iter().zip(serial.iter().zip(&parallel))
+    {
+        // The results must be in input order
+        assert_eq!(expected_document.syntax().to_string(), *source);
+        assert_eq!(actual_document.syntax().to_string(), *source);
+        assert_eq!(expected, actual);
+    }
+
+    assert!(
+        serial
+            .iter()
+            .any(|(_, diagnostics)| !diagnostics.is_empty()),
+        "expected lint diagnostics"
+    );
+}
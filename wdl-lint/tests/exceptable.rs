@@ -0,0 +1,86 @@
+//! The lint rule exceptable nodes test.
+//!
+//! This test runs every lint rule, including the optional rules, in isolation
+//! against the `source.wdl` of each directory in `tests/lints`.
+//!
+//! In debug builds, `Diagnostics::exceptable_add` asserts that the element a
+//! diagnostic is attached to can be excepted by one of the rule's exceptable
+//! nodes; this test records each failed assertion and fails if a rule emitted a
+//! diagnostic that an `#@ except` comment would be unable to suppress.
+//!
+//! New rules are automatically checked when added to either the default or
+//! optional rule set.
+
+#![cfg(debug_assertions)]
+
+use std::fs;
+use std::panic;
+use std::panic::AssertUnwindSafe;
+use std::path::Path;
+use std::path::PathBuf;
+
+use wdl_ast::Document;
+use wdl_ast::Validator;
+use wdl_lint::LintVisitor;
+use wdl_lint::optional_rules;
+use wdl_lint::rules;
+
+/// Finds the lint test sources.
+fn find_sources() -> Vec<PathBuf> {
+    let mut sources: Vec<PathBuf> = Path::new("tests/lints")
+        .read_dir()
+        .unwrap()
+        .map(|entry| entry.expect("failed to read directory").path())
+        .filter(|path| path.is_dir())
+        .map(|path| path.join("source.wdl"))
+        .filter(|path| path.is_file())
+        .collect();
+
+    sources.sort();
+    sources
+}
+
+#[test]
+fn rules_can_except_their_diagnostics() {
+    let mut failures = Vec::new();
+
+    // Suppress the output of the expected panics; they are reported below
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    for path in find_sources() {
+        let source = fs::read_to_string(&path).expect("failed to read source file");
+        let (document, diagnostics) = Document::parse(&source);
+        if !diagnostics.is_empty() {
+            continue;
+        }
+
+        for rule in rules().into_iter().chain(optional_rules()) {
+            let id = rule.id();
+            let mut validator = Validator::empty();
+            validator.add_visitor(LintVisitor::new([rule]));
+
+            if let Err(e) = panic::catch_unwind(AssertUnwindSafe(|| validator.validate(&document)))
+            {
+                failures.push(format!(
+                    "{path}: rule `{id}`: {e}",
+                    path = path.display(),
+                    e = e
+                        .downcast_ref::<String>()
+                        .map(|s| s.as_str())
+                        .or_else(|| e.downcast_ref::<&str>().copied())
+                        .unwrap_or("no panic message")
+                ));
+            }
+        }
+    }
+
+    panic::set_hook(hook);
+
+    assert!(
+        failures.is_empty(),
+        "{count} lint rule diagnostic(s) cannot be excepted:\n{failures}",
+        count = failures.len(),
+        failures = failures.join("\n")
+    );
+}
@@ -0,0 +1,76 @@
+//! The imported documents test.
+//!
+//! This test lints a document with the documents it imports using the
+//! `UndefinedCallTarget` rule, which relies on the imported documents provided
+//! to the lint visitor.
+
+use wdl_ast::Document;
+use wdl_ast::Validator;
+use wdl_lint::ImportedDocuments;
+use wdl_lint::LintVisitor;
+use wdl_lint::Rule;
+use wdl_lint::rules::UndefinedCallTargetRule;
+
+/// The source of the document being linted.
+const SOURCE: &str = r#"version 1.1
+
+import "tools.wdl" as tools
+import "missing.wdl" as missing
+
+workflow test {
+    call tools.align
+    call tools.sort
+    call tools.pipeline
+    call missing.anything
+}
+"#;
+
+/// The source of the imported `tools.wdl` document.
+const TOOLS: &str = r#"version 1.1
+
+task align {
+    command <<<>>>
+}
+
+workflow pipeline {
+}
+"#;
+
+/// Lints the source with the given imported documents and returns the messages
+/// of the resulting diagnostics.
+fn lint(imports: ImportedDocuments) -> Vec<String> {
+    let (document, diagnostics) = Document::parse(SOURCE);
+    assert!(diagnostics.is_empty());
+
+    let mut validator = Validator::empty();
+    validator.add_visitor(
+        LintVisitor::new([Box::<UndefinedCallTargetRule>::default() as Box<dyn Rule>])
+            .with_imports(imports),
+    );
+
+    validator
+        .validate(&document)
+        .err()
+        .unwrap_or_default()
+        .iter()
+        .map(|d| d.message().to_string())
+        .collect()
+}
+
+#[test]
+fn it_flags_undefined_call_targets() {
+    let (tools, diagnostics) = Document::parse(TOOLS);
+    assert!(diagnostics.is_empty());
+
+    // The `missing.wdl` import is not resolved, so calls into it are not checked
+    let imports = [("tools", tools)].into_iter().collect();
+    assert_eq!(
+        lint(imports),
+        ["`sort` is not a task or workflow in the document imported as `tools`"]
+    );
+}
+
+#[test]
+fn it_ignores_unresolved_imports() {
+    assert!(lint(ImportedDocuments::default()).is_empty());
+}
@@ -8,6 +8,13 @@
 //!   must be a comment with the lint rule name to run.
 //! * `source.errors` - the expected set of lint diagnostics.
 //!
+//! A directory may also contain a `wdl.toml` lint configuration file that
+//! selects and configures the lint rules to run (e.g. to enable an optional
+//! rule or to set a rule's options); otherwise, the default rules are run.
+//!
+//! Imports of `source.wdl` are resolved relative to the directory; the
+//! imported documents that exist are provided to the lint rules.
+//!
 //! The `source.errors` file may be automatically generated or updated by
 //! setting the `BLESS` environment variable when running this test.
 
@@ -28,9 +35,12 @@ use codespan_reporting::term::termcolor::Buffer;
 use colored::Colorize;
 use pretty_assertions::StrComparison;
 use rayon::prelude::*;
+use wdl_ast::Ast;
+use wdl_ast::AstToken;
 use wdl_ast::Diagnostic;
 use wdl_ast::Document;
 use wdl_ast::Validator;
+use wdl_lint::ImportedDocuments;
 use wdl_lint::LintVisitor;
 use wdl_lint::rules::ShellCheckRule;
 
@@ -118,6 +128,40 @@ fn compare_result(path: &Path, result: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// Creates a lint visitor for a test.
+///
+/// The rules are read from the test's `wdl.toml` configuration file, if there
+/// is one.
+fn lints(test: &Path) -> Result<LintVisitor, String> {
+    let path = test.join("wdl.toml");
+    if !path.exists() {
+        return Ok(LintVisitor::default());
+    }
+
+    let rules = wdl_lint::Config::from_file(&path)
+        .and_then(|config| config.lint.rules())
+        .map_err(|e| format!("{e:#}"))?;
+    Ok(LintVisitor::new(rules))
+}
+
+/// Parses the documents imported by a test's source document.
+///
+/// Imports are resolved relative to the test's directory; imports of files
+/// that do not exist are not resolved.
+fn imports(test: &Path, document: &Document) -> ImportedDocuments {
+    let Ast::V1(ast) = document.ast() else {
+        return ImportedDocuments::default();
+    };
+
+    ast.imports()
+        .filter_map(|stmt| {
+            let (namespace, _) = stmt.namespace()?;
+            let source = fs::read_to_string(test.join(stmt.uri().text()?.as_str())).ok()?;
+            Some((namespace, Document::parse(&source).0))
+        })
+        .collect()
+}
+
 /// Runs a test.
 fn run_test(test: &Path, ntests: &AtomicUsize) -> Result<(), String> {
     let path = test.join("source.wdl");
@@ -141,8 +185,9 @@ fn run_test(test: &Path, ntests: &AtomicUsize) -> Result<(), String> {
         )?;
     } else {
         let mut validator = Validator::default();
-        validator.add_visitor(LintVisitor::default());
+        validator.add_visitor(lints(test)?.with_imports(imports(test, &document)));
         validator.add_visitor(ShellCheckRule);
+
         let errors = match validator.validate(&document) {
             Ok(()) => String::new(),
             Err(diagnostics) => format_diagnostics(&diagnostics, &path, &source),
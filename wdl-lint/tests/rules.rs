@@ -0,0 +1,159 @@
+//! The lint rule documentation test.
+//!
+//! This test generates the rule listing from the `Rule` implementations of
+//! every lint rule, including the optional rules, and compares it against
+//! `RULES.md`.
+//!
+//! The `RULES.md` file may be automatically generated or updated by setting the
+//! `BLESS` environment variable when running this test.
+
+use std::env;
+use std::fmt::Write;
+use std::fs;
+
+use pretty_assertions::StrComparison;
+use wdl_lint::Rule;
+use wdl_lint::optional_rules;
+use wdl_lint::rules;
+
+/// The path to the rule listing.
+const RULES_PATH: &str = "RULES.md";
+
+/// The header of the rule listing.
+const HEADER: &str = "# Rules
+
+This document lists all `wdl` lint rules implemented on the `main` branch of
+the `stjude-rust-labs/wdl` repository. Note that the information may be out of
+sync with released packages.
+
+This document is generated from the lint rule implementations; run
+`BLESS=1 cargo test -p wdl-lint --test rules` to update it.
+";
+
+/// Gets the anchor of a rule's section in the listing.
+fn anchor(id: &str) -> String {
+    id.to_lowercase()
+}
+
+/// Gets the tags of a rule as a comma-separated list.
+fn tags(rule: &dyn Rule) -> String {
+    rule.tags()
+        .iter()
+        .map(|t| t.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Writes a row of the rule index table.
+fn write_row(output: &mut String, columns: &[&str], widths: &[usize]) {
+    for (column, width) in columns.iter().zip(widths) {
+        write!(output, "| {column:<width$} ").unwrap();
+    }
+
+    output.push_str("|\n");
+}
+
+/// Generates the rule listing.
+fn generate() -> String {
+    let optional: Vec<_> = optional_rules().iter().map(|r| r.id()).collect();
+    let mut rules: Vec<_> = rules().into_iter().chain(optional_rules()).collect();
+    rules.sort_by_key(|r| r.id());
+
+    let mut output = String::from(HEADER);
+
+    // Write the index of the rules
+    output.push_str("\n## Lint Rules\n\n");
+    let rows: Vec<_> = rules
+        .iter()
+        .map(|r| {
+            [
+                format!("[`{id}`](#{anchor})", id = r.id(), anchor = anchor(r.id())),
+                tags(r.as_ref()),
+                r.description().to_string(),
+            ]
+        })
+        .collect();
+
+    let header = ["Name", "Tags", "Description"];
+    let widths: Vec<_> = (0..header.len())
+        .map(|i| {
+            rows.iter()
+                .map(|r| r[i].chars().count())
+                .chain([header[i].len()])
+                .max()
+                .unwrap()
+        })
+        .collect();
+
+    write_row(&mut output, &header, &widths);
+    for width in &widths {
+        write!(output, "|:{dashes}", dashes = "-".repeat(width + 1)).unwrap();
+    }
+
+    output.push_str("|\n");
+    for row in &rows {
+        write_row(
+            &mut output,
+            &row.iter().map(String::as_str).collect::<Vec<_>>(),
+            &widths,
+        );
+    }
+
+    // Write the details of each rule
+    output.push_str("\n## Rule Details\n");
+    for rule in &rules {
+        write!(
+            output,
+            "\n### `{id}`\n\n{description}\n\n* **Tags:** {tags}\n",
+            id = rule.id(),
+            description = rule.description(),
+            tags = tags(rule.as_ref())
+        )
+        .unwrap();
+
+        if !rule.related_rules().is_empty() {
+            let related = rule
+                .related_rules()
+                .iter()
+                .map(|id| format!("[`{id}`](#{anchor})", anchor = anchor(id)))
+                .collect::<Vec<_>>()
+                .join(", ");
+            writeln!(output, "* **Related rules:** {related}").unwrap();
+        }
+
+        if optional.contains(&rule.id()) {
+            output.push_str("* **Optional:** only enabled by the `pedantic` profile\n");
+        }
+
+        // Strip the source indentation from the explanation
+        let explanation = rule
+            .explanation()
+            .lines()
+            .map(str::trim)
+            .collect::<Vec<_>>()
+            .join("\n");
+        writeln!(output, "\n{explanation}").unwrap();
+    }
+
+    output
+}
+
+#[test]
+fn rules_listing_is_up_to_date() {
+    let generated = generate();
+    if env::var_os("BLESS").is_some() {
+        fs::write(RULES_PATH, &generated).expect("failed to write rule listing");
+        return;
+    }
+
+    let expected = fs::read_to_string(RULES_PATH)
+        .expect("failed to read rule listing")
+        .replace("\r\n", "\n");
+
+    assert!(
+        expected == generated,
+        "`{RULES_PATH}` is out of date; run `BLESS=1 cargo test -p wdl-lint --test rules` to \
+         update it:\n{diff}",
+        diff = StrComparison::new(&expected, &generated)
+    );
+}
@@ -48,10 +48,11 @@ use wdl_engine::Engine;
 use wdl_engine::EvaluationError;
 use wdl_engine::Inputs;
 use wdl_engine::local::LocalTaskExecutionBackend;
+use wdl_engine::path_component;
 use wdl_engine::v1::TaskEvaluator;
 use wdl_format::Formatter;
 use wdl_format::element::node::AstNodeFormatExt as _;
-use wdl_lint::rules::ShellCheckRule;
+use wdl_lint::ConfigDiscovery;
 
 /// Emits the given diagnostics to the output stream.
 ///
@@ -72,13 +73,16 @@ fn emit_diagnostics(path: &str, source: &str, diagnostics: &[Diagnostic]) -> Res
             errors += 1;
         }
 
-        emit(
-            &mut stream,
-            &Config::default(),
-            &file,
-            &diagnostic.to_codespan(),
-        )
-        .context("failed to emit diagnostic")?;
+        // Point lint diagnostics at the documentation of their rule
+        let mut codespan = diagnostic.to_codespan();
+        if let Some(url) = diagnostic.rule().and_then(wdl_lint::rule_url) {
+            codespan
+                .notes
+                .push(format!("see {url} for more information"));
+        }
+
+        emit(&mut stream, &Config::default(), &file, &codespan)
+            .context("failed to emit diagnostic")?;
     }
 
     Ok(errors)
@@ -300,6 +304,29 @@ pub struct LintCommand {
     /// Enable shellcheck lints.
     #[clap(long, action)]
     pub shellcheck: bool,
+    /// The lint rule profile to use (`minimal`, `recommended`, or `pedantic`).
+    ///
+    /// Defaults to the profile of the configuration or `recommended`.
+    #[clap(long, value_name = "PROFILE")]
+    pub profile: Option<String>,
+    /// Enables a lint rule in addition to the rules of the profile.
+    #[clap(long, value_name = "RULE")]
+    pub enable: Vec<String>,
+    /// Disables a lint rule of the profile.
+    #[clap(long, value_name = "RULE")]
+    pub disable: Vec<String>,
+    /// Only runs the lint rules with the given tag.
+    #[clap(long, value_name = "TAG")]
+    pub include_tag: Vec<String>,
+    /// Skips the lint rules with the given tag.
+    #[clap(long, value_name = "TAG")]
+    pub exclude_tag: Vec<String>,
+    /// The configuration file to use instead of discovering `wdl.toml` files.
+    #[clap(long, value_name = "CONFIG")]
+    pub config: Option<PathBuf>,
+    /// Disables the discovery of `wdl.toml` configuration files.
+    #[clap(long, conflicts_with = "config")]
+    pub no_config: bool,
 }
 
 impl LintCommand {
@@ -317,12 +344,39 @@ impl LintCommand {
             );
         }
 
-        let mut validator = Validator::default();
-        validator.add_visitor(LintVisitor::default());
+        let mut discovery = if self.no_config {
+            ConfigDiscovery::disabled()
+        } else if let Some(path) = &self.config {
+            ConfigDiscovery::with_config(wdl_lint::Config::from_file(path)?)
+        } else {
+            ConfigDiscovery::new()
+        };
+
+        // Apply the command line options on top of the configuration
+        let mut config = discovery.config_for(&self.path)?.lint.clone();
+        if let Some(profile) = self.profile {
+            config.profile = Some(profile);
+        }
+
+        config.enable.extend(self.enable);
         if self.shellcheck {
-            validator.add_visitor(ShellCheckRule);
+            config.enable.push("ShellCheck".to_string());
+        }
+
+        config.disable.extend(self.disable);
+        if !self.include_tag.is_empty() {
+            config.tags.include = self.include_tag;
+        }
+
+        if !self.exclude_tag.is_empty() {
+            config.tags.exclude = self.exclude_tag;
         }
-        if let Err(diagnostics) = validator.validate(&document) {
+
+        let rules = config.rules()?;
+        let mut validator = Validator::default();
+        validator.add_visitor(LintVisitor::new(rules));
+        if let Err(mut diagnostics) = validator.validate(&document) {
+            config.apply_severities(&mut diagnostics);
             emit_diagnostics(&self.path.to_string_lossy(), &source, &diagnostics)?;
 
             bail!(
@@ -480,6 +534,16 @@ pub struct RunCommand {
     #[clap(long)]
     pub overwrite: bool,
 
+    /// Accepts floats with a zero fractional part (e.g. `3.0`) where an `Int`
+    /// is expected in inputs and in values read with `read_json`.
+    #[clap(long)]
+    pub lenient_float_to_int: bool,
+
+    /// Rejects any engine configuration that relies on extensions to the WDL
+    /// specification (e.g. `--lenient-float-to-int`).
+    #[clap(long)]
+    pub conformance: bool,
+
     /// The analysis options.
     #[clap(flatten)]
     pub options: AnalysisOptions,
@@ -509,7 +573,9 @@ impl RunCommand {
         let document = result.document();
 
         // TODO: support other backends in the future
-        let mut engine = Engine::new(LocalTaskExecutionBackend::new());
+        let mut engine = Engine::new(LocalTaskExecutionBackend::new())
+            .with_lenient_float_to_int(self.lenient_float_to_int)
+            .with_conformance_mode(self.conformance);
         let (path, name, inputs) = if let Some(path) = self.inputs {
             let abs_path = absolute(&path).with_context(|| {
                 format!(
@@ -556,7 +622,7 @@ impl RunCommand {
 
         let output_dir = self
             .output
-            .unwrap_or_else(|| Path::new(&name).to_path_buf());
+            .unwrap_or_else(|| Path::new(path_component(&name).as_ref()).to_path_buf());
 
         // Check to see if the output directory already exists and if it should be
         // removed